@@ -1,3 +1,5 @@
 pub mod basic;
+pub mod credit;
 pub mod forex;
+pub mod inflation;
 pub mod interestrate;
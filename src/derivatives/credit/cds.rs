@@ -0,0 +1,466 @@
+//! Single-name credit default swaps under the ISDA standard model
+//! conventions.
+//!
+//! * Coupons accrue ACT/360 between CDS roll dates (20 March, June,
+//!   September, December; [`IMM::next_cds_date`]), adjusted Following on
+//!   weekends. The first period starts on the roll date on or before the
+//!   step-in date (trade date + 1); the last runs through the maturity
+//!   date inclusive.
+//! * Protection and accrual-on-default cover defaults from the step-in
+//!   date through maturity.
+//! * Legs are integrated exactly on sub-intervals over which the hazard
+//!   rate and the discount forward rate are flat (hazard pillars, coupon
+//!   boundaries and monthly nodes for the discount curve).
+//!
+//! ```text
+//!   protection   (1 − R) Σ λ/(λ + f) · (P_a Q_a − P_b Q_b)
+//!   premium      Σ Δ_i P(pay_i) Q(end_i − 1d)  +  accrual on default
+//!   upfront      protection − c · (premium − accrued)        per unit notional, paid by the buyer
+//!   par spread   protection / (premium − accrued)
+//! ```
+//!
+//! `Direction::Buy` buys protection.
+//!
+//! # Papers
+//!
+//! - **ISDA (2009)** — *ISDA CDS Standard Model*, with the Big Bang /
+//!   Small Bang protocol conventions and the December 2015 semi-annual
+//!   roll.
+//! - **O'Kane, D. (2008)** — *Modelling Single-name and Multi-name Credit
+//!   Derivatives*, Wiley, ch. 6.
+
+use chrono::{Months, NaiveDate};
+use iso_currency::Currency;
+
+use crate::derivatives::basic::Direction;
+use crate::derivatives::forex::basic::CurrencyValue;
+use crate::error::Result;
+use crate::markets::termstructures::defaultcurve::HazardRateCurve;
+use crate::markets::termstructures::yieldcurve::{InterpolationMethodEnum, YieldCurve};
+use crate::time::businessdayconvention::BusinessDayConvention;
+use crate::time::calendars::{Calendar, WeekendsOnly};
+use crate::time::daycounters::DayCounters;
+use crate::time::daycounters::actual360::Actual360;
+use crate::time::daycounters::actual365fixed::Actual365Fixed;
+use crate::time::imm::IMM;
+use crate::time::period::{ONE_DAY, Period};
+
+/// One premium period. `accrual_end` of the final period is the day
+/// after maturity so the maturity date itself accrues.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct CdsPeriod {
+    pub accrual_start: NaiveDate,
+    pub accrual_end: NaiveDate,
+    pub pay_date: NaiveDate,
+}
+
+/// Standard CDS premium schedule and leg valuation, per unit notional.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CdsSchedule {
+    pub trade_date: NaiveDate,
+    pub maturity_date: NaiveDate,
+    pub periods: Vec<CdsPeriod>,
+}
+
+fn adjust(date: NaiveDate) -> NaiveDate {
+    WeekendsOnly
+        .adjust(date, BusinessDayConvention::Following)
+        .unwrap()
+}
+
+impl CdsSchedule {
+    /// Schedule for a contract traded on `trade_date` maturing on
+    /// `maturity_date`.
+    pub fn new(trade_date: NaiveDate, maturity_date: NaiveDate) -> Self {
+        let step_in = trade_date + ONE_DAY;
+        let mut dates = vec![IMM.previous_cds_date(step_in)];
+        while *dates.last().unwrap() < maturity_date {
+            let next = IMM.next_cds_date(*dates.last().unwrap());
+            dates.push(next.min(maturity_date));
+        }
+        let last = dates.len() - 2;
+        let periods = dates
+            .windows(2)
+            .enumerate()
+            .map(|(i, pair)| CdsPeriod {
+                accrual_start: adjust(pair[0]),
+                accrual_end: if i == last {
+                    pair[1] + ONE_DAY
+                } else {
+                    adjust(pair[1])
+                },
+                pay_date: adjust(pair[1]),
+            })
+            .collect();
+        Self {
+            trade_date,
+            maturity_date,
+            periods,
+        }
+    }
+
+    /// Standard contract: maturity from the semi-annual roll rule.
+    pub fn standard(trade_date: NaiveDate, tenor: Period) -> Result<Self> {
+        let maturity = IMM.cds_maturity(trade_date, tenor.months()?);
+        Ok(Self::new(trade_date, maturity))
+    }
+
+    pub fn step_in_date(&self) -> NaiveDate {
+        self.trade_date + ONE_DAY
+    }
+
+    /// End of protection: the day after maturity.
+    pub fn protection_end(&self) -> NaiveDate {
+        self.maturity_date + ONE_DAY
+    }
+
+    /// Accrued premium per unit coupon at step-in (ACT/360 from the
+    /// current period's start).
+    pub fn accrued(&self) -> Result<f64> {
+        let step_in = self.step_in_date();
+        match self
+            .periods
+            .iter()
+            .find(|p| p.accrual_start <= step_in && step_in < p.accrual_end)
+        {
+            Some(p) => Actual360.year_fraction(p.accrual_start, step_in),
+            None => Ok(0.0),
+        }
+    }
+
+    /// Integration nodes on `[start, end]`: the ends, hazard pillars and
+    /// monthly points.
+    fn nodes(&self, curve: &HazardRateCurve, start: NaiveDate, end: NaiveDate) -> Vec<NaiveDate> {
+        let mut nodes = vec![start, end];
+        nodes.extend(
+            curve
+                .pillars
+                .iter()
+                .map(|p| p.0)
+                .filter(|&d| start < d && d < end),
+        );
+        let mut month = start + Months::new(1);
+        while month < end {
+            nodes.push(month);
+            month = month + Months::new(1);
+        }
+        nodes.sort();
+        nodes.dedup();
+        nodes
+    }
+
+    /// `(t, P, Q)` at each node.
+    fn node_values(
+        &self,
+        curve: &HazardRateCurve,
+        yts: &dyn YieldCurve,
+        nodes: &[NaiveDate],
+    ) -> Result<Vec<(f64, f64, f64)>> {
+        let method = &InterpolationMethodEnum::PiecewiseLinearContinuous;
+        let dc = Actual365Fixed::default();
+        nodes
+            .iter()
+            .map(|&d| {
+                Ok((
+                    dc.year_fraction(curve.valuation_date, d)?,
+                    yts.discount(d, method)?,
+                    curve.survival_probability(d)?,
+                ))
+            })
+            .collect()
+    }
+
+    /// Protection leg per unit notional.
+    pub fn protection_leg(
+        &self,
+        recovery_rate: f64,
+        curve: &HazardRateCurve,
+        yts: &dyn YieldCurve,
+    ) -> Result<f64> {
+        let start = self.step_in_date().max(curve.valuation_date);
+        let end = self.protection_end();
+        if end <= start {
+            return Ok(0.0);
+        }
+        let values = self.node_values(curve, yts, &self.nodes(curve, start, end))?;
+        let mut pv = 0.0;
+        for pair in values.windows(2) {
+            let ((ta, pa, qa), (tb, pb, qb)) = (pair[0], pair[1]);
+            let dt = tb - ta;
+            if dt <= 0.0 {
+                continue;
+            }
+            let lambda = (qa / qb).ln() / dt;
+            let lf = lambda + (pa / pb).ln() / dt;
+            pv += if (lf * dt).abs() < 1.0e-10 {
+                lambda * pa * qa * dt
+            } else {
+                lambda / lf * (pa * qa - pb * qb)
+            };
+        }
+        Ok((1.0 - recovery_rate) * pv)
+    }
+
+    /// Premium leg per unit coupon (risky annuity including the full
+    /// current coupon and accrual on default).
+    pub fn premium_leg(&self, curve: &HazardRateCurve, yts: &dyn YieldCurve) -> Result<f64> {
+        let method = &InterpolationMethodEnum::PiecewiseLinearContinuous;
+        let dc = Actual365Fixed::default();
+        let step_in = self.step_in_date().max(curve.valuation_date);
+        let mut pv = 0.0;
+        for p in &self.periods {
+            if p.accrual_end <= step_in {
+                continue;
+            }
+            // ISDA observes survival on the last accruing day, one day
+            // before the accrual end.
+            let accrual = Actual360.year_fraction(p.accrual_start, p.accrual_end)?;
+            pv += accrual
+                * yts.discount(p.pay_date, method)?
+                * curve.survival_probability(p.accrual_end - ONE_DAY)?;
+
+            // Accrual on default: the accrued coupon to the default time.
+            let ts = dc.year_fraction(curve.valuation_date, p.accrual_start)?;
+            let nodes = self.nodes(curve, p.accrual_start.max(step_in), p.accrual_end);
+            for pair in self.node_values(curve, yts, &nodes)?.windows(2) {
+                let ((ta, pa, qa), (tb, pb, qb)) = (pair[0], pair[1]);
+                let dt = tb - ta;
+                if dt <= 0.0 {
+                    continue;
+                }
+                let lambda = (qa / qb).ln() / dt;
+                let lf = lambda + (pa / pb).ln() / dt;
+                let x = lf * dt;
+                // ∫_a^b (t − ts) e^{−lf (t − a)} dt
+                let integral = if x.abs() < 1.0e-8 {
+                    (ta - ts) * dt + 0.5 * dt * dt
+                } else {
+                    (ta - ts) * (-(-x).exp_m1()) / lf + (1.0 - (-x).exp() * (1.0 + x)) / (lf * lf)
+                };
+                pv += lambda * pa * qa * integral * 365.0 / 360.0;
+            }
+        }
+        Ok(pv)
+    }
+
+    /// Clean risky annuity: premium leg less the accrued the buyer
+    /// refunds at settlement.
+    pub fn risky_annuity(&self, curve: &HazardRateCurve, yts: &dyn YieldCurve) -> Result<f64> {
+        Ok(self.premium_leg(curve, yts)? - self.accrued()?)
+    }
+
+    /// Running spread at which the contract trades with zero upfront.
+    pub fn par_spread(
+        &self,
+        recovery_rate: f64,
+        curve: &HazardRateCurve,
+        yts: &dyn YieldCurve,
+    ) -> Result<f64> {
+        Ok(self.protection_leg(recovery_rate, curve, yts)? / self.risky_annuity(curve, yts)?)
+    }
+
+    /// Clean upfront per unit notional paid by the protection buyer for
+    /// running coupon `coupon`.
+    pub fn upfront(
+        &self,
+        coupon: f64,
+        recovery_rate: f64,
+        curve: &HazardRateCurve,
+        yts: &dyn YieldCurve,
+    ) -> Result<f64> {
+        Ok(self.protection_leg(recovery_rate, curve, yts)?
+            - coupon * self.risky_annuity(curve, yts)?)
+    }
+}
+
+#[derive(Debug)]
+pub struct CreditDefaultSwap {
+    pub direction: Direction,
+    pub notional: f64,
+    pub currency: Currency,
+    /// Running coupon (e.g. 0.01 for the 100bp standard coupon).
+    pub coupon: f64,
+    pub recovery_rate: f64,
+    pub schedule: CdsSchedule,
+}
+
+impl CreditDefaultSwap {
+    /// Standard contract traded on `trade_date` for `tenor`.
+    pub fn new(
+        direction: Direction,
+        notional: f64,
+        currency: Currency,
+        coupon: f64,
+        recovery_rate: f64,
+        trade_date: NaiveDate,
+        tenor: Period,
+    ) -> Result<Self> {
+        Ok(Self {
+            direction,
+            notional,
+            currency,
+            coupon,
+            recovery_rate,
+            schedule: CdsSchedule::standard(trade_date, tenor)?,
+        })
+    }
+
+    fn sign(&self) -> f64 {
+        self.direction as i8 as f64
+    }
+
+    /// Dirty present value: protection less the full premium leg.
+    pub fn npv(&self, curve: &HazardRateCurve, yts: &dyn YieldCurve) -> Result<CurrencyValue> {
+        let value = self
+            .schedule
+            .protection_leg(self.recovery_rate, curve, yts)?
+            - self.coupon * self.schedule.premium_leg(curve, yts)?;
+        Ok(CurrencyValue {
+            currency: self.currency,
+            value: self.sign() * self.notional * value,
+        })
+    }
+
+    pub fn par_spread(&self, curve: &HazardRateCurve, yts: &dyn YieldCurve) -> Result<f64> {
+        self.schedule.par_spread(self.recovery_rate, curve, yts)
+    }
+
+    /// Clean upfront in currency paid by the protection buyer.
+    pub fn upfront(&self, curve: &HazardRateCurve, yts: &dyn YieldCurve) -> Result<CurrencyValue> {
+        Ok(CurrencyValue {
+            currency: self.currency,
+            value: self.notional
+                * self
+                    .schedule
+                    .upfront(self.coupon, self.recovery_rate, curve, yts)?,
+        })
+    }
+
+    /// Change in PV for a 1bp parallel rise in the curve's par spreads,
+    /// rebootstrapping the hazard rates.
+    pub fn cs01(&self, curve: &HazardRateCurve, yts: &dyn YieldCurve) -> Result<CurrencyValue> {
+        let bumped = curve.bumped(1.0e-4, curve.recovery_rate, yts)?;
+        Ok(CurrencyValue {
+            currency: self.currency,
+            value: self.npv(&bumped, yts)?.value - self.npv(curve, yts)?.value,
+        })
+    }
+
+    /// Change in PV when curve and trade recovery both rise by one
+    /// percentage point with par spreads held fixed.
+    pub fn recovery01(
+        &self,
+        curve: &HazardRateCurve,
+        yts: &dyn YieldCurve,
+    ) -> Result<CurrencyValue> {
+        let bumped = curve.bumped(0.0, curve.recovery_rate + 0.01, yts)?;
+        let shifted = CreditDefaultSwap {
+            direction: self.direction,
+            notional: self.notional,
+            currency: self.currency,
+            coupon: self.coupon,
+            recovery_rate: self.recovery_rate + 0.01,
+            schedule: self.schedule.clone(),
+        };
+        Ok(CurrencyValue {
+            currency: self.currency,
+            value: shifted.npv(&bumped, yts)?.value - self.npv(curve, yts)?.value,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::markets::termstructures::defaultcurve::{CdsQuote, CdsQuoteType};
+    use crate::markets::termstructures::yieldcurve::flat_rate_curve;
+
+    fn d(y: i32, m: u32, dd: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, dd).unwrap()
+    }
+
+    /// Schedule: first accrual from the previous roll date, quarterly
+    /// weekend-adjusted boundaries, the last one running through
+    /// maturity.
+    #[test]
+    fn standard_schedule() -> Result<()> {
+        let s = CdsSchedule::standard(d(2026, 5, 6), Period::Years(5))?;
+        assert_eq!(s.maturity_date, d(2031, 6, 20));
+        assert_eq!(s.periods.len(), 21);
+        assert_eq!(s.periods[0].accrual_start, d(2026, 3, 20));
+        // 20 June 2026 is a Saturday.
+        assert_eq!(s.periods[0].pay_date, d(2026, 6, 22));
+        let last = s.periods.last().unwrap();
+        assert_eq!(last.accrual_end, d(2031, 6, 21));
+        // Accrued: 20 March to step-in 7 May, 48 days.
+        assert!((s.accrued()? - 48.0 / 360.0).abs() < 1e-15);
+        Ok(())
+    }
+
+    /// A protection buyer gains as spreads widen, by about the risky
+    /// annuity per bp, and a trade struck at par has zero value.
+    #[test]
+    fn pv_cs01_and_recovery_sensitivity() -> Result<()> {
+        let valuation_date = d(2026, 5, 6);
+        let yts = flat_rate_curve(valuation_date, 0.035);
+        let quotes: Vec<CdsQuote> = [(1, 0.0150), (3, 0.0220), (5, 0.0300), (7, 0.0320)]
+            .iter()
+            .map(|&(y, s)| CdsQuote {
+                tenor: Period::Years(y),
+                quote: CdsQuoteType::ParSpread(s),
+            })
+            .collect();
+        let curve = HazardRateCurve::bootstrap(valuation_date, &quotes, 0.4, &yts)?;
+
+        let par = CreditDefaultSwap::new(
+            Direction::Buy,
+            1.0e7,
+            Currency::USD,
+            0.03,
+            0.4,
+            valuation_date,
+            Period::Years(5),
+        )?;
+        assert!((par.par_spread(&curve, &yts)? - 0.03).abs() < 1e-12);
+        assert!(par.upfront(&curve, &yts)?.value.abs() < 1e-6);
+
+        // At par, CS01 is the risky annuity per bp.
+        let annuity = par.schedule.risky_annuity(&curve, &yts)?;
+        let par_cs01 = par.cs01(&curve, &yts)?.value;
+        assert!(
+            (par_cs01 / (1.0e7 * 1.0e-4 * annuity) - 1.0).abs() < 0.01,
+            "{par_cs01}"
+        );
+
+        let trade = CreditDefaultSwap {
+            coupon: 0.01,
+            ..par
+        };
+        let upfront = trade.upfront(&curve, &yts)?.value;
+        assert!((upfront - 1.0e7 * 0.02 * annuity).abs() < 1e-6);
+        // Off-market, widening also shortens the annuity the buyer is in
+        // the money on.
+        let cs01 = trade.cs01(&curve, &yts)?.value;
+        assert!(0.0 < cs01 && cs01 < par_cs01);
+
+        let seller = CreditDefaultSwap {
+            direction: Direction::Sell,
+            ..CreditDefaultSwap::new(
+                Direction::Buy,
+                1.0e7,
+                Currency::USD,
+                0.01,
+                0.4,
+                valuation_date,
+                Period::Years(5),
+            )?
+        };
+        assert!((seller.npv(&curve, &yts)?.value + trade.npv(&curve, &yts)?.value).abs() < 1e-6);
+
+        // With spreads held, higher recovery means higher hazard rates and
+        // a smaller annuity on which the buyer is in the money.
+        let rec01 = trade.recovery01(&curve, &yts)?.value;
+        assert!(rec01 < 0.0 && rec01.abs() < cs01, "{rec01}");
+        Ok(())
+    }
+}
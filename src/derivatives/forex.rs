@@ -1,3 +1,4 @@
+pub mod barrier;
 pub mod basic;
 #[cfg(test)]
 pub mod end_to_end_test;
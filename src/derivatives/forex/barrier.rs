@@ -0,0 +1,1130 @@
+//! FX barrier options: single and double, knock-in and knock-out, with a
+//! rebate paid at expiry.
+//!
+//! Under flat vol the knock-out is the Reiner–Rubinstein value, written by
+//! the reflection principle as the payoff restricted to the live band less
+//! its images across the barriers. With `b = ln(F/S)/T` and
+//! `μ = b/σ² − ½`:
+//!
+//! ```text
+//!     single   KO = V_band(S) − (H/S)^{2μ} · V_band(H²/S)
+//!     double   KO = Σ_n (U/L)^{2nμ} · [ V_band(S·(U/L)^{2n}) − (L/S)^{2μ} · V_band(L²/S·(U/L)^{2n}) ]
+//!              KI = vanilla − KO
+//! ```
+//!
+//! `V_band(x)` is the discounted Black–Scholes value, from spot `x` (so
+//! forward `F·x/S`), of the payoff on the band the barriers leave alive:
+//! `(H, ∞)` below a down barrier, `(0, H)` above an up barrier, `(L, U)`
+//! between two. The double series is summed over `|n| ≤ 5`. The rebate
+//! goes to a knocked-out holder, or to a knock-in holder never knocked in,
+//! and is valued by the same formulas with a unit digital payoff. A spot
+//! already through a barrier, or [`FXBarrierOption::knocked`], counts as a
+//! knock.
+//!
+//! Discretely monitored barriers take the Broadie–Glasserman continuity
+//! correction over the `m` monitoring dates left:
+//!
+//! ```text
+//!     H → H · exp(±β·σ·√(T/m)),     β = −ζ(½)/√(2π) ≈ 0.5826
+//! ```
+//!
+//! with the sign moving each barrier away from spot.
+//!
+//! [`FXBarrierOption::monte_carlo`] prices under any [`FxPathModel`] —
+//! [`FxHhwSimulator`], [`TimeDependentSabrSimulator`] or
+//! [`GeometricBrownianMotion`] — and [`FXBarrierOption::monte_carlo_slv`]
+//! under the particle-method [`TimeDependentSabrSlvSimulator`]. Paths are
+//! observed daily. Between observations of a continuous barrier a path
+//! survives with the Brownian-bridge probability
+//!
+//! ```text
+//!     1 − exp(−2 · ln(H/Sᵢ) · ln(H/Sᵢ₊₁) / (σᵢ²·Δt))
+//! ```
+//!
+//! (the two crossing probabilities summed for a double barrier), which
+//! weights the payoff in place of a hard knock. Discrete barriers are
+//! checked on their monitoring dates only. Payoffs are discounted on the
+//! domestic curve.
+//!
+//! [`FXDerivatives`] Greeks bump and reprice the flat-vol value under
+//! either [`GreekMethod`]. The option is also a [`VannaVolgaTarget`] whose
+//! survival probability is the flat-vol no-touch probability.
+//!
+//! # Papers
+//!
+//! * **Reiner, E., Rubinstein, M. (1991)** — *Breaking Down the
+//!   Barriers*, Risk 4(8): 28–35. Single-barrier closed forms.
+//! * **Ikeda, M., Kunitomo, N. (1992)** — *Pricing Options with Curved
+//!   Boundaries*, Mathematical Finance 2(4): 275–298. The double-barrier
+//!   image series.
+//! * **Broadie, M., Glasserman, P., Kou, S. (1997)** — *A Continuity
+//!   Correction for Discrete Barrier Options*, Mathematical Finance 7(4):
+//!   325–349.
+//! * **Beaglehole, D., Dybvig, P., Zhou, G. (1997)** — *Going to
+//!   Extremes: Correcting Simulation Bias in Exotic Option Valuation*,
+//!   Financial Analysts Journal 53(1): 62–68. Brownian-bridge crossing
+//!   probabilities.
+
+use crate::derivatives::basic::BasicInfo;
+use crate::derivatives::forex::basic::{
+    CurrencyValue, FXDerivatives, FXGreeks, FXUnderlying, GreekMethod,
+};
+use crate::derivatives::forex::option::{OptionType, black_scholes};
+use crate::error::{Error, Result};
+use crate::markets::forex::market_context::{FxMarketContext, FxMarketShift};
+use crate::markets::termstructures::yieldcurve::InterpolationMethodEnum;
+use crate::math::normal::cdf;
+use crate::models::common::longstaff_schwartz::LsmEstimate;
+use crate::models::common::simulation::{
+    GeometricBrownianMotion, SimulationModel, simulate_at_dates,
+};
+use crate::models::forex::fx_hhw::FxHhwSimulator;
+use crate::models::forex::sabr::SabrState;
+use crate::models::forex::sabr_slv::TimeDependentSabrSlvSimulator;
+use crate::models::forex::sabr_time_dependent::TimeDependentSabrSimulator;
+use crate::models::forex::vanna_volga::VannaVolgaTarget;
+use crate::time::daycounters::DayCounters;
+use crate::time::daycounters::actual365fixed::Actual365Fixed;
+use chrono::{Duration, NaiveDate};
+use iso_currency::Currency;
+use serde::{Deserialize, Serialize};
+
+/// Broadie–Glasserman–Kou constant `−ζ(½)/√(2π)`.
+const BGK_BETA: f64 = 0.5826;
+
+/// Image terms on each side of the double-barrier series.
+const DOUBLE_BARRIER_TERMS: i32 = 5;
+
+/// Paths per simulation batch times observation dates, bounding the
+/// memory held by daily observations.
+const BATCH_STATES: usize = 100_000;
+
+/// Spot bump relative to spot.
+const SPOT_BUMP: f64 = 1.0e-4;
+/// Absolute vol bump.
+const VOL_BUMP: f64 = 1.0e-4;
+
+/// Barrier levels on spot.
+#[derive(Deserialize, Serialize, Copy, Clone, Debug, PartialEq)]
+pub enum FXBarrier {
+    /// Touched when spot rises to the level.
+    Up(f64),
+    /// Touched when spot falls to the level.
+    Down(f64),
+    /// Touched when spot leaves `(lower, upper)`.
+    Double { lower: f64, upper: f64 },
+}
+
+impl FXBarrier {
+    /// `(lower, upper)` levels, `None` where there is no barrier.
+    fn levels(&self) -> Result<(Option<f64>, Option<f64>)> {
+        let (lower, upper) = match *self {
+            FXBarrier::Up(h) => (None, Some(h)),
+            FXBarrier::Down(h) => (Some(h), None),
+            FXBarrier::Double { lower, upper } => (Some(lower), Some(upper)),
+        };
+        let valid = lower.is_none_or(|l| l > 0.0)
+            && upper.is_none_or(|u| u > 0.0)
+            && lower.zip(upper).is_none_or(|(l, u)| l < u);
+        if !valid {
+            return Err(Error::InvalidData(format!("invalid barrier {self:?}")));
+        }
+        Ok((lower, upper))
+    }
+}
+
+/// What a barrier touch does to the option.
+#[derive(Deserialize, Serialize, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BarrierKnock {
+    /// The option comes alive on a touch.
+    KnockIn,
+    /// The option dies on a touch.
+    KnockOut,
+}
+
+/// When the barrier is observed.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub enum BarrierMonitoring {
+    /// At every instant to expiry.
+    Continuous,
+    /// At the close of the listed dates; those after expiry are ignored.
+    Discrete(Vec<NaiveDate>),
+}
+
+/// FX barrier option deal record, priced like [`FXVanillaOption`] off
+/// spot, forward points and the domestic curve.
+///
+/// [`FXVanillaOption`]: crate::derivatives::forex::option::FXVanillaOption
+#[derive(Deserialize, Serialize, Debug)]
+pub struct FXBarrierOption {
+    pub basic_info: BasicInfo,
+    pub asset: FXUnderlying,
+    pub option_type: OptionType,
+    pub notional_currency: Currency,
+    pub notional_amounts: f64,
+    pub strike: f64,
+    pub barrier: FXBarrier,
+    pub knock: BarrierKnock,
+    pub monitoring: BarrierMonitoring,
+    /// Quote-currency cash per unit of base notional, paid at expiry on
+    /// a knock-out or when a knock-in never knocks in.
+    #[serde(default)]
+    pub rebate: f64,
+    /// A touch has already been observed.
+    #[serde(default)]
+    pub knocked: bool,
+    /// Implied volatility override (annualised, decimal). `None` reads the
+    /// market's vol surface at the expiry and strike.
+    #[serde(default)]
+    pub volatility: Option<f64>,
+}
+
+/// Market quantities at one evaluation.
+struct BarrierContext {
+    spot: f64,
+    forward: f64,
+    year_fraction: f64,
+    discount: f64,
+    valuation_date: NaiveDate,
+}
+
+/// Payoff at expiry per unit of base notional.
+#[derive(Copy, Clone)]
+enum Payoff {
+    Vanilla(OptionType, f64),
+    Digital,
+}
+
+impl Payoff {
+    fn at(self, spot: f64) -> f64 {
+        match self {
+            Payoff::Vanilla(OptionType::Call, k) => (spot - k).max(0.0),
+            Payoff::Vanilla(OptionType::Put, k) => (k - spot).max(0.0),
+            Payoff::Digital => 1.0,
+        }
+    }
+}
+
+/// Discounted value of `payoff` on `lower < S_T < upper` from `forward`
+/// with total variance `variance`.
+fn band_value(
+    payoff: Payoff,
+    forward: f64,
+    variance: f64,
+    discount: f64,
+    lower: f64,
+    upper: f64,
+) -> f64 {
+    if variance <= 0.0 {
+        let inside = lower < forward && forward < upper;
+        return if inside {
+            discount * payoff.at(forward)
+        } else {
+            0.0
+        };
+    }
+    let sqrt_v = variance.sqrt();
+    // `(E[S_T·1{S_T > k}]/F, P(S_T > k))`.
+    let above = |k: f64| {
+        if k <= 0.0 {
+            (1.0, 1.0)
+        } else if k.is_infinite() {
+            (0.0, 0.0)
+        } else {
+            let d1 = ((forward / k).ln() + 0.5 * variance) / sqrt_v;
+            (cdf(d1), cdf(d1 - sqrt_v))
+        }
+    };
+    // Asset and cash digitals paying on `(a, b)`.
+    let between = |a: f64, b: f64| {
+        if b <= a {
+            return (0.0, 0.0);
+        }
+        let ((asset_a, cash_a), (asset_b, cash_b)) = (above(a), above(b));
+        (forward * (asset_a - asset_b), cash_a - cash_b)
+    };
+    discount
+        * match payoff {
+            Payoff::Digital => between(lower, upper).1,
+            Payoff::Vanilla(OptionType::Call, k) => {
+                let (asset, cash) = between(lower.max(k), upper);
+                asset - k * cash
+            }
+            Payoff::Vanilla(OptionType::Put, k) => {
+                let (asset, cash) = between(lower, upper.min(k));
+                k * cash - asset
+            }
+        }
+}
+
+/// Flat-vol value of `payoff` knocked out at continuously monitored
+/// `lower` / `upper` barriers, by the image series.
+fn knock_out_value(
+    payoff: Payoff,
+    ctx: &BarrierContext,
+    variance: f64,
+    lower: Option<f64>,
+    upper: Option<f64>,
+) -> f64 {
+    let (s, f) = (ctx.spot, ctx.forward);
+    let (l, u) = (lower.unwrap_or(0.0), upper.unwrap_or(f64::INFINITY));
+    let band = |x: f64| band_value(payoff, f * x / s, variance, ctx.discount, l, u);
+    if variance <= 0.0 {
+        return band(s);
+    }
+    let mu = (f / s).ln() / variance - 0.5;
+    match (lower, upper) {
+        (None, None) => band(s),
+        (Some(h), None) | (None, Some(h)) => band(s) - (h / s).powf(2.0 * mu) * band(h * h / s),
+        (Some(l), Some(u)) => (-DOUBLE_BARRIER_TERMS..=DOUBLE_BARRIER_TERMS)
+            .map(|n| {
+                let image = (u / l).powi(2 * n);
+                (u / l).powf(2.0 * n as f64 * mu)
+                    * (band(s * image) - (l / s).powf(2.0 * mu) * band(l * l / s * image))
+            })
+            .sum(),
+    }
+}
+
+/// An FX simulator a barrier can be monitored on. The model runs in
+/// ACT/365F years from the market's valuation date and starts from its
+/// spot.
+pub trait FxPathModel: SimulationModel {
+    /// Spot on a path at model time `t`. Models of the forward to the
+    /// option expiry `expiry` convert it with the market carry `b`,
+    /// `S = F·exp(−b·(expiry − t))`.
+    fn spot(&self, state: &Self::State, t: f64, expiry: f64, carry: f64) -> f64;
+
+    /// Instantaneous lognormal vol of spot on a path at model time `t`.
+    fn log_vol(&self, state: &Self::State, t: f64) -> f64;
+}
+
+impl FxPathModel for FxHhwSimulator {
+    fn spot(&self, state: &Self::State, _t: f64, _expiry: f64, _carry: f64) -> f64 {
+        state.fx
+    }
+
+    fn log_vol(&self, state: &Self::State, _t: f64) -> f64 {
+        state.variance.max(0.0).sqrt()
+    }
+}
+
+impl FxPathModel for TimeDependentSabrSimulator {
+    fn spot(&self, state: &Self::State, t: f64, expiry: f64, carry: f64) -> f64 {
+        state.forward * (-carry * (expiry - t)).exp()
+    }
+
+    fn log_vol(&self, state: &Self::State, _t: f64) -> f64 {
+        state.vol * state.forward.max(1.0e-12).powf(self.params.beta - 1.0)
+    }
+}
+
+impl FxPathModel for GeometricBrownianMotion {
+    fn spot(&self, state: &Self::State, _t: f64, _expiry: f64, _carry: f64) -> f64 {
+        *state
+    }
+
+    fn log_vol(&self, _state: &Self::State, _t: f64) -> f64 {
+        self.volatility
+    }
+}
+
+/// Daily observation grid and barrier state shared by every path.
+struct PathSetup {
+    dates: Vec<NaiveDate>,
+    times: Vec<f64>,
+    /// Whether the barrier is checked at each observation.
+    monitored: Vec<bool>,
+    continuous: bool,
+    expiry_time: f64,
+    carry: f64,
+    discount: f64,
+    lower: Option<f64>,
+    upper: Option<f64>,
+    knocked: bool,
+}
+
+/// Probability weight of a path not having touched a barrier so far, and
+/// its last observation.
+#[derive(Copy, Clone)]
+struct PathMonitor {
+    survival: f64,
+    time: f64,
+    spot: f64,
+    vol: f64,
+}
+
+impl PathSetup {
+    fn outside(&self, spot: f64) -> bool {
+        self.lower.is_some_and(|l| spot <= l) || self.upper.is_some_and(|u| spot >= u)
+    }
+
+    fn start(&self, spot: f64, vol: f64) -> PathMonitor {
+        PathMonitor {
+            survival: if self.knocked { 0.0 } else { 1.0 },
+            time: 0.0,
+            spot,
+            vol,
+        }
+    }
+
+    fn observe(&self, monitor: &mut PathMonitor, i: usize, spot: f64, vol: f64) {
+        let t = self.times[i];
+        if monitor.survival > 0.0 && self.monitored[i] {
+            if self.outside(spot) {
+                monitor.survival = 0.0;
+            } else if self.continuous {
+                let variance = monitor.vol * monitor.vol * (t - monitor.time);
+                let crossing: f64 = [self.lower, self.upper]
+                    .into_iter()
+                    .flatten()
+                    .filter(|_| variance > 0.0)
+                    .map(|h| (-2.0 * (h / monitor.spot).ln() * (h / spot).ln() / variance).exp())
+                    .sum();
+                monitor.survival *= (1.0 - crossing).max(0.0);
+            }
+        }
+        *monitor = PathMonitor {
+            survival: monitor.survival,
+            time: t,
+            spot,
+            vol,
+        };
+    }
+}
+
+impl FXBarrierOption {
+    fn context_under(
+        &self,
+        market: &FxMarketContext,
+        shift: &FxMarketShift,
+    ) -> Result<BarrierContext> {
+        let expiry = self.basic_info.expiry_date;
+        let forward = market
+            .forward_under(
+                expiry,
+                &self.asset.calendar(),
+                self.asset.forward_points_converter(),
+                shift,
+            )?
+            .ok_or_else(|| {
+                Error::TradeExpired(format!(
+                    "Option expiry {} outside the forward points range (valuation {})",
+                    expiry, market.valuation_date
+                ))
+            })?;
+        let valuation_date = market.valuation_date_under(shift);
+        Ok(BarrierContext {
+            spot: market.spot + shift.spot,
+            forward,
+            year_fraction: Actual365Fixed::default().year_fraction(valuation_date, expiry)?,
+            discount: market.discount_d_under(
+                expiry,
+                &InterpolationMethodEnum::StepFunctionForward,
+                shift,
+            )?,
+            valuation_date,
+        })
+    }
+
+    /// Pricing vol: the override if set, else the surface at expiry and
+    /// strike.
+    pub fn volatility(&self, market: &FxMarketContext) -> Result<f64> {
+        self.volatility_under(market, &FxMarketShift::default())
+    }
+
+    fn volatility_under(&self, market: &FxMarketContext, shift: &FxMarketShift) -> Result<f64> {
+        match self.volatility {
+            Some(vol) => {
+                Ok(market.rolled_vol(self.basic_info.expiry_date, vol, shift)? + shift.vol)
+            }
+            None => market.implied_vol_under(self.basic_info.expiry_date, self.strike, shift),
+        }
+    }
+
+    /// Monitoring dates after `valuation_date`, up to expiry.
+    fn monitoring_dates(&self, valuation_date: NaiveDate) -> Option<Vec<NaiveDate>> {
+        match &self.monitoring {
+            BarrierMonitoring::Continuous => None,
+            BarrierMonitoring::Discrete(dates) => Some(
+                dates
+                    .iter()
+                    .copied()
+                    .filter(|d| *d > valuation_date && *d <= self.basic_info.expiry_date)
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Barriers the continuous formulas see at flat `sigma`, shifted for
+    /// discrete monitoring and dropped once monitoring has ended; `None`
+    /// once knocked.
+    fn live_barriers(
+        &self,
+        ctx: &BarrierContext,
+        sigma: f64,
+    ) -> Result<Option<(Option<f64>, Option<f64>)>> {
+        let (lower, upper) = self.barrier.levels()?;
+        let through = lower.is_some_and(|l| ctx.spot <= l) || upper.is_some_and(|u| ctx.spot >= u);
+        if self.knocked || through {
+            return Ok(None);
+        }
+        Ok(Some(match self.monitoring_dates(ctx.valuation_date) {
+            None => (lower, upper),
+            Some(dates) if dates.is_empty() => (None, None),
+            Some(dates) => {
+                let shift =
+                    (BGK_BETA * sigma * (ctx.year_fraction / dates.len() as f64).sqrt()).exp();
+                (lower.map(|l| l / shift), upper.map(|u| u * shift))
+            }
+        }))
+    }
+
+    /// Holder's quote-currency value under `shift` with the vol flat at
+    /// `sigma`.
+    fn flat_value(
+        &self,
+        market: &FxMarketContext,
+        shift: &FxMarketShift,
+        sigma: f64,
+    ) -> Result<f64> {
+        let ctx = self.context_under(market, shift)?;
+        let variance = sigma * sigma * ctx.year_fraction;
+        let vanilla = black_scholes(
+            ctx.forward,
+            self.strike,
+            variance,
+            ctx.discount,
+            self.option_type,
+        );
+        let per_unit = match (self.live_barriers(&ctx, sigma)?, self.knock) {
+            (None, BarrierKnock::KnockOut) => self.rebate * ctx.discount,
+            (None, BarrierKnock::KnockIn) => vanilla,
+            (Some((lower, upper)), knock) => {
+                let payoff = Payoff::Vanilla(self.option_type, self.strike);
+                let out = knock_out_value(payoff, &ctx, variance, lower, upper);
+                let survival = knock_out_value(Payoff::Digital, &ctx, variance, lower, upper);
+                match knock {
+                    BarrierKnock::KnockOut => out + self.rebate * (ctx.discount - survival),
+                    BarrierKnock::KnockIn => vanilla - out + self.rebate * survival,
+                }
+            }
+        };
+        Ok(self.base_amount() * per_unit)
+    }
+
+    fn value_under(&self, market: &FxMarketContext, shift: &FxMarketShift) -> Result<f64> {
+        self.flat_value(market, shift, self.volatility_under(market, shift)?)
+    }
+
+    /// Holder's value in the quote currency: Reiner–Rubinstein under the
+    /// pricing vol, continuity-corrected for discrete monitoring.
+    pub fn value(&self, market: &FxMarketContext) -> Result<f64> {
+        self.value_under(market, &FxMarketShift::default())
+    }
+
+    fn path_setup(&self, market: &FxMarketContext) -> Result<PathSetup> {
+        let ctx = self.context_under(market, &FxMarketShift::default())?;
+        let expiry = self.basic_info.expiry_date;
+        let n_days = (expiry - market.valuation_date).num_days();
+        if n_days <= 0 {
+            return Err(Error::TradeExpired(format!(
+                "Option expiry {} is not after valuation {}",
+                expiry, market.valuation_date
+            )));
+        }
+        let dates: Vec<NaiveDate> = (1..=n_days)
+            .map(|n| market.valuation_date + Duration::days(n))
+            .collect();
+        let dc = Actual365Fixed::default();
+        let times = dates
+            .iter()
+            .map(|d| dc.year_fraction(market.valuation_date, *d))
+            .collect::<Result<Vec<_>>>()?;
+        let monitoring = self.monitoring_dates(market.valuation_date);
+        let monitored = dates
+            .iter()
+            .map(|d| monitoring.as_ref().is_none_or(|m| m.contains(d)))
+            .collect();
+        let (lower, upper) = self.barrier.levels()?;
+        Ok(PathSetup {
+            dates,
+            times,
+            monitored,
+            continuous: monitoring.is_none(),
+            expiry_time: ctx.year_fraction,
+            carry: (ctx.forward / ctx.spot).ln() / ctx.year_fraction,
+            discount: ctx.discount,
+            lower,
+            upper,
+            knocked: self.knocked
+                || lower.is_some_and(|l| ctx.spot <= l)
+                || upper.is_some_and(|u| ctx.spot >= u),
+        })
+    }
+
+    /// Discounted payoff per unit of a path ending at `monitor`.
+    fn path_value(&self, setup: &PathSetup, monitor: &PathMonitor) -> f64 {
+        let payoff = Payoff::Vanilla(self.option_type, self.strike).at(monitor.spot);
+        let alive = monitor.survival;
+        setup.discount
+            * match self.knock {
+                BarrierKnock::KnockOut => payoff * alive + self.rebate * (1.0 - alive),
+                BarrierKnock::KnockIn => payoff * (1.0 - alive) + self.rebate * alive,
+            }
+    }
+
+    fn estimate(&self, samples: &[f64]) -> LsmEstimate {
+        let n = samples.len() as f64;
+        let mean = samples.iter().sum::<f64>() / n;
+        let var = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0).max(1.0);
+        let amount = self.base_amount();
+        LsmEstimate {
+            value: amount * mean,
+            std_error: amount.abs() * (var / n).sqrt(),
+        }
+    }
+
+    /// Monte Carlo value in the quote currency over `n_paths` daily paths
+    /// of `model`, with the Brownian-bridge correction on continuous
+    /// barriers.
+    pub fn monte_carlo<M: FxPathModel>(
+        &self,
+        model: &mut M,
+        market: &FxMarketContext,
+        n_paths: usize,
+    ) -> Result<LsmEstimate> {
+        let setup = self.path_setup(market)?;
+        let (t_end, carry) = (setup.expiry_time, setup.carry);
+        let initial = model.initial_state();
+        let start = setup.start(
+            model.spot(&initial, 0.0, t_end, carry),
+            model.log_vol(&initial, 0.0),
+        );
+        let dc = Actual365Fixed::default();
+        let batch = (BATCH_STATES / setup.dates.len()).clamp(1, n_paths);
+        let mut samples = Vec::with_capacity(n_paths);
+        while samples.len() < n_paths {
+            let n = batch.min(n_paths - samples.len());
+            let paths = simulate_at_dates(model, market.valuation_date, &setup.dates, n, 1, &dc);
+            for path in &paths.paths {
+                let mut monitor = start;
+                for (i, state) in path.iter().enumerate() {
+                    let t = setup.times[i];
+                    let spot = model.spot(state, t, t_end, carry);
+                    setup.observe(&mut monitor, i, spot, model.log_vol(state, t));
+                }
+                samples.push(self.path_value(&setup, &monitor));
+            }
+        }
+        Ok(self.estimate(&samples))
+    }
+
+    /// Monte Carlo value in the quote currency over `n_paths` daily paths
+    /// of the SABR-SLV particle method, whose forward is to the option
+    /// expiry. The bridge uses the Dupire local vol the model mimics.
+    pub fn monte_carlo_slv(
+        &self,
+        sim: &mut TimeDependentSabrSlvSimulator,
+        market: &FxMarketContext,
+        n_paths: usize,
+    ) -> Result<LsmEstimate> {
+        let setup = self.path_setup(market)?;
+        let (t_end, carry) = (setup.expiry_time, setup.carry);
+        let dupire = sim.dupire.clone();
+        let spot = |s: &SabrState, t: f64| s.forward * (-carry * (t_end - t)).exp();
+        let forward_0 = sim.params.forward_0;
+        let start = setup.start(
+            forward_0 * (-carry * t_end).exp(),
+            dupire.local_vol(0.0, forward_0),
+        );
+        let mut monitors = vec![start; n_paths];
+        sim.simulate_on_grid(&setup.times, n_paths, |i, states| {
+            let t = setup.times[i];
+            for (monitor, state) in monitors.iter_mut().zip(states) {
+                let vol = dupire.local_vol(t, state.forward.max(1.0e-12));
+                setup.observe(monitor, i, spot(state, t), vol);
+            }
+        });
+        let samples: Vec<f64> = monitors
+            .iter()
+            .map(|m| self.path_value(&setup, m))
+            .collect();
+        Ok(self.estimate(&samples))
+    }
+
+    /// Signed base-currency notional held.
+    fn base_amount(&self) -> f64 {
+        let notional = if self.notional_currency == self.asset.frn_currency() {
+            self.notional_amounts
+        } else {
+            self.notional_amounts / self.strike
+        };
+        self.direction_sign() * notional
+    }
+
+    fn direction_sign(&self) -> f64 {
+        self.basic_info.direction as i8 as f64
+    }
+
+    /// `(∂V/∂S, ∂²V/∂S²)` by central differences.
+    fn spot_sensitivities(&self, market: &FxMarketContext) -> Result<(f64, f64)> {
+        let h = SPOT_BUMP * market.spot;
+        let at = |ds: f64| {
+            self.value_under(
+                market,
+                &FxMarketShift {
+                    spot: ds,
+                    ..Default::default()
+                },
+            )
+        };
+        let (up, mid, down) = (at(h)?, at(0.0)?, at(-h)?);
+        Ok(((up - down) / (2.0 * h), (up - 2.0 * mid + down) / (h * h)))
+    }
+}
+
+impl VannaVolgaTarget for FXBarrierOption {
+    fn expiry(&self) -> NaiveDate {
+        self.basic_info.expiry_date
+    }
+
+    fn flat_vol_value(
+        &self,
+        market: &FxMarketContext,
+        shift: &FxMarketShift,
+        sigma: f64,
+    ) -> Result<f64> {
+        self.flat_value(market, shift, sigma)
+    }
+
+    fn survival_probability(&self, market: &FxMarketContext, sigma: f64) -> Result<f64> {
+        let ctx = self.context_under(market, &FxMarketShift::default())?;
+        Ok(match self.live_barriers(&ctx, sigma)? {
+            None => 0.0,
+            Some((lower, upper)) => {
+                let variance = sigma * sigma * ctx.year_fraction;
+                knock_out_value(Payoff::Digital, &ctx, variance, lower, upper) / ctx.discount
+            }
+        })
+    }
+}
+
+impl FXDerivatives for FXBarrierOption {
+    /// Premium in the notional currency, signed as for
+    /// [`FXVanillaOption`](crate::derivatives::forex::option::FXVanillaOption):
+    /// a buyer sees a negative PV.
+    fn mtm(&self, market: &FxMarketContext) -> Result<CurrencyValue> {
+        let value = self.value(market)?;
+        let premium = if self.notional_currency == self.asset.frn_currency() {
+            value / market.spot
+        } else {
+            value
+        };
+        Ok(CurrencyValue {
+            currency: self.notional_currency,
+            value: -premium,
+        })
+    }
+
+    /// Forward delta `∂V/∂F` in the base currency. The spot bump moves
+    /// the forward one-for-one with the points held, so the bumped delta
+    /// is `DF_d·∂V/∂F`.
+    fn delta(&self, market: &FxMarketContext) -> Result<CurrencyValue> {
+        let ctx = self.context_under(market, &FxMarketShift::default())?;
+        let (delta, _) = self.spot_sensitivities(market)?;
+        Ok(CurrencyValue {
+            currency: self.asset.frn_currency(),
+            value: delta / ctx.discount,
+        })
+    }
+
+    /// Change in spot delta per unit of spot.
+    fn gamma(&self, market: &FxMarketContext) -> Result<f64> {
+        Ok(self.spot_sensitivities(market)?.1)
+    }
+
+    /// Value change per 1 % vol, in the notional currency.
+    fn vega(&self, market: &FxMarketContext) -> Result<f64> {
+        let at = |vol: f64| {
+            self.value_under(
+                market,
+                &FxMarketShift {
+                    vol,
+                    ..Default::default()
+                },
+            )
+        };
+        let vega = (at(VOL_BUMP)? - at(-VOL_BUMP)?) / (2.0 * VOL_BUMP) / 100.0;
+        Ok(if self.notional_currency == self.asset.frn_currency() {
+            vega / market.spot
+        } else {
+            vega
+        })
+    }
+
+    /// Bump-and-reprice report; there are no closed-form barrier Greeks,
+    /// so `Analytic` is an error.
+    fn greeks(&self, market: &FxMarketContext, method: GreekMethod) -> Result<FXGreeks> {
+        match method {
+            GreekMethod::Analytic => Err(Error::InvalidData(
+                "barrier options have no analytic Greeks; use BumpAndReprice".to_string(),
+            )),
+            GreekMethod::BumpAndReprice => {
+                FXGreeks::bumped(&self.asset, market, |shift| self.value_under(market, shift))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BarrierKnock, BarrierMonitoring, FXBarrier, FXBarrierOption};
+    use crate::derivatives::basic::{BasicInfo, Direction, Style};
+    use crate::derivatives::forex::basic::{FXDerivatives, FXUnderlying, GreekMethod};
+    use crate::derivatives::forex::option::{
+        FXVanillaOption, OptionType, SmileDynamics, black_scholes,
+    };
+    use crate::error::Result;
+    use crate::markets::forex::market_context::{FxMarketContext, FxMarketShift};
+    use crate::markets::forex::quotes::forwardpoints::{FXForwardHelper, FXForwardQuote};
+    use crate::markets::forex::quotes::volsurface::{FXDeltaVolPillar, FXVolQuote, FXVolSurface};
+    use crate::markets::termstructures::yieldcurve::{InterpolationMethodEnum, zero_rate_curve};
+    use crate::math::normal::cdf;
+    use crate::models::common::simulation::GeometricBrownianMotion;
+    use crate::models::forex::dupire_local_vol::build as dupire_build;
+    use crate::models::forex::sabr_effective::PiecewiseConstant;
+    use crate::models::forex::sabr_slv::TimeDependentSabrSlvSimulator;
+    use crate::models::forex::sabr_time_dependent::TimeDependentSabrParams;
+    use crate::models::forex::vanna_volga::VannaVolgaTarget;
+    use crate::time::daycounters::DayCounters;
+    use crate::time::daycounters::actual365fixed::Actual365Fixed;
+    use crate::time::period::Period;
+    use chrono::{Duration, NaiveDate};
+    use iso_currency::Currency;
+
+    const SIGMA: f64 = 0.10;
+
+    fn expiry() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, 21).unwrap()
+    }
+
+    /// EURUSD at 1.17 with a flat 3.5% domestic curve and 150 points a
+    /// year.
+    fn market() -> Result<FxMarketContext> {
+        let valuation_date = NaiveDate::from_ymd_opt(2026, 4, 21).unwrap();
+        let curve = || {
+            zero_rate_curve(
+                valuation_date,
+                &[
+                    (NaiveDate::from_ymd_opt(2027, 4, 21).unwrap(), 0.035),
+                    (NaiveDate::from_ymd_opt(2028, 4, 21).unwrap(), 0.035),
+                ],
+            )
+        };
+        let forwards = FXForwardHelper::new(
+            valuation_date,
+            1.17,
+            vec![
+                FXForwardQuote {
+                    tenor: Period::SPOT,
+                    value: 0.0,
+                },
+                FXForwardQuote {
+                    tenor: Period::Years(1),
+                    value: 150.0,
+                },
+            ],
+        );
+        let surface = FXVolSurface::new(
+            valuation_date,
+            vec![FXDeltaVolPillar::new(
+                expiry().into(),
+                1.18,
+                vec![
+                    FXVolQuote::Atm(SIGMA),
+                    FXVolQuote::Put {
+                        delta: 0.25,
+                        vol: SIGMA,
+                    },
+                    FXVolQuote::Call {
+                        delta: 0.25,
+                        vol: SIGMA,
+                    },
+                ],
+            )],
+        )?;
+        Ok(FxMarketContext::new(
+            valuation_date,
+            1.17,
+            (Currency::USD, Currency::EUR),
+            curve(),
+            curve(),
+            forwards,
+            surface,
+        ))
+    }
+
+    fn barrier_option(
+        option_type: OptionType,
+        strike: f64,
+        barrier: FXBarrier,
+        knock: BarrierKnock,
+        monitoring: BarrierMonitoring,
+    ) -> FXBarrierOption {
+        FXBarrierOption {
+            basic_info: BasicInfo {
+                trade_date: NaiveDate::from_ymd_opt(2026, 4, 21).unwrap(),
+                style: Style::FXCall,
+                direction: Direction::Buy,
+                expiry_date: expiry(),
+                delivery_date: expiry(),
+            },
+            asset: FXUnderlying::EURUSD,
+            option_type,
+            notional_currency: Currency::EUR,
+            notional_amounts: 1_000_000.0,
+            strike,
+            barrier,
+            knock,
+            monitoring,
+            rebate: 0.0,
+            knocked: false,
+            volatility: Some(SIGMA),
+        }
+    }
+
+    /// `(F, DF, T)` to expiry.
+    fn forward_discount_time(market: &FxMarketContext) -> Result<(f64, f64, f64)> {
+        let asset = FXUnderlying::EURUSD;
+        let unshifted = FxMarketShift::default();
+        let forward = market
+            .forward_under(
+                expiry(),
+                &asset.calendar(),
+                asset.forward_points_converter(),
+                &unshifted,
+            )?
+            .unwrap();
+        let discount = market.discount_d_under(
+            expiry(),
+            &InterpolationMethodEnum::StepFunctionForward,
+            &unshifted,
+        )?;
+        let t = Actual365Fixed::default().year_fraction(market.valuation_date, expiry())?;
+        Ok((forward, discount, t))
+    }
+
+    /// In-out parity with rebates, the no-touch probability of an up
+    /// barrier against the running-maximum law, the far-barrier limits and
+    /// the continuity correction's ordering.
+    #[test]
+    fn analytic_parity_survival_and_limits() -> Result<()> {
+        let ctx = market()?;
+        let (f, df, t) = forward_discount_time(&ctx)?;
+        let weekly: Vec<NaiveDate> = (1..=26)
+            .map(|w| ctx.valuation_date + Duration::weeks(w))
+            .collect();
+        for option_type in [OptionType::Call, OptionType::Put] {
+            let vanilla = 1.0e6 * black_scholes(f, 1.17, SIGMA * SIGMA * t, df, option_type);
+            for barrier in [
+                FXBarrier::Up(1.25),
+                FXBarrier::Down(1.10),
+                FXBarrier::Double {
+                    lower: 1.10,
+                    upper: 1.25,
+                },
+            ] {
+                for monitoring in [
+                    BarrierMonitoring::Continuous,
+                    BarrierMonitoring::Discrete(weekly.clone()),
+                ] {
+                    let priced = |knock| {
+                        let mut option =
+                            barrier_option(option_type, 1.17, barrier, knock, monitoring.clone());
+                        option.rebate = 0.01;
+                        option.value(&ctx)
+                    };
+                    let (out, into) = (
+                        priced(BarrierKnock::KnockOut)?,
+                        priced(BarrierKnock::KnockIn)?,
+                    );
+                    assert!(out > 0.0 && into > 0.0, "{out} {into}");
+                    let parity = vanilla + 1.0e6 * 0.01 * df;
+                    assert!((out + into - parity).abs() < 1e-8 * parity, "{barrier:?}");
+                }
+            }
+
+            // Far barriers leave the vanilla; a far lower barrier leaves
+            // the single up barrier.
+            let out = |barrier, monitoring| {
+                barrier_option(
+                    option_type,
+                    1.17,
+                    barrier,
+                    BarrierKnock::KnockOut,
+                    monitoring,
+                )
+                .value(&ctx)
+            };
+            let continuous = BarrierMonitoring::Continuous;
+            let wide = FXBarrier::Double {
+                lower: 0.5,
+                upper: 3.0,
+            };
+            assert!((out(wide, continuous.clone())? - vanilla).abs() < 1e-8 * vanilla);
+            let up = out(FXBarrier::Up(1.25), continuous.clone())?;
+            let double = out(
+                FXBarrier::Double {
+                    lower: 0.5,
+                    upper: 1.25,
+                },
+                continuous.clone(),
+            )?;
+            assert!((double - up).abs() < 1e-8 * vanilla);
+            // Weekly monitoring knocks out less often than continuous.
+            let discrete = out(
+                FXBarrier::Up(1.25),
+                BarrierMonitoring::Discrete(weekly.clone()),
+            )?;
+            assert!(up < discrete && discrete < vanilla);
+        }
+
+        // P(max S < U) = N((ln(U/S) − νT)/σ√T) − (U/S)^{2ν/σ²} · N((−ln(U/S) − νT)/σ√T).
+        let u = 1.25;
+        let option = barrier_option(
+            OptionType::Call,
+            1.17,
+            FXBarrier::Up(u),
+            BarrierKnock::KnockOut,
+            BarrierMonitoring::Continuous,
+        );
+        let (x, nu, sd) = (
+            (u / 1.17_f64).ln(),
+            (f / 1.17).ln() / t - 0.5 * SIGMA * SIGMA,
+            SIGMA * t.sqrt(),
+        );
+        let expected = cdf((x - nu * t) / sd)
+            - (u / 1.17_f64).powf(2.0 * nu / (SIGMA * SIGMA)) * cdf((-x - nu * t) / sd);
+        let survival = option.survival_probability(&ctx, SIGMA)?;
+        assert!((survival - expected).abs() < 1e-12, "{survival} {expected}");
+
+        // Close to the barrier an up-and-out call loses value with vol;
+        // there are no analytic barrier Greeks.
+        let near = barrier_option(
+            OptionType::Call,
+            1.10,
+            FXBarrier::Up(1.20),
+            BarrierKnock::KnockOut,
+            BarrierMonitoring::Continuous,
+        );
+        let greeks = near.greeks(&ctx, GreekMethod::BumpAndReprice)?;
+        assert!(greeks.vega.value < 0.0 && near.vega(&ctx)? < 0.0);
+        assert!(near.greeks(&ctx, GreekMethod::Analytic).is_err());
+
+        // With far barriers the forward delta is the vanilla's.
+        let far = barrier_option(
+            OptionType::Call,
+            1.17,
+            FXBarrier::Double {
+                lower: 0.5,
+                upper: 3.0,
+            },
+            BarrierKnock::KnockOut,
+            BarrierMonitoring::Continuous,
+        );
+        let vanilla = FXVanillaOption {
+            basic_info: BasicInfo {
+                trade_date: ctx.valuation_date,
+                style: Style::FXCall,
+                direction: Direction::Buy,
+                expiry_date: expiry(),
+                delivery_date: expiry(),
+            },
+            asset: FXUnderlying::EURUSD,
+            option_type: OptionType::Call,
+            notional_currency: Currency::EUR,
+            notional_amounts: 1_000_000.0,
+            strike: 1.17,
+            volatility: Some(SIGMA),
+            smile_dynamics: SmileDynamics::default(),
+        };
+        let (barrier_delta, vanilla_delta) = (far.delta(&ctx)?, vanilla.delta(&ctx)?);
+        assert_eq!(barrier_delta.currency, Currency::EUR);
+        assert!(
+            (barrier_delta.value - vanilla_delta.value).abs() < 1e-6 * vanilla_delta.value,
+            "{barrier_delta:?} {vanilla_delta:?}"
+        );
+        Ok(())
+    }
+
+    /// Bridge-corrected Monte Carlo under GBM and SABR-SLV reproduces the
+    /// continuous closed forms, and discrete monitoring the continuity
+    /// correction, within four standard errors.
+    #[test]
+    fn monte_carlo_matches_analytic() -> Result<()> {
+        let ctx = market()?;
+        let (f, _, t) = forward_discount_time(&ctx)?;
+        let carry = (f / ctx.spot).ln() / t;
+        let n_paths = 4_000;
+        let weekly: Vec<NaiveDate> = (1..=26)
+            .map(|w| ctx.valuation_date + Duration::weeks(w))
+            .collect();
+        let cases = [
+            barrier_option(
+                OptionType::Call,
+                1.17,
+                FXBarrier::Up(1.25),
+                BarrierKnock::KnockOut,
+                BarrierMonitoring::Continuous,
+            ),
+            barrier_option(
+                OptionType::Call,
+                1.15,
+                FXBarrier::Double {
+                    lower: 1.10,
+                    upper: 1.25,
+                },
+                BarrierKnock::KnockOut,
+                BarrierMonitoring::Continuous,
+            ),
+            barrier_option(
+                OptionType::Put,
+                1.15,
+                FXBarrier::Down(1.12),
+                BarrierKnock::KnockIn,
+                BarrierMonitoring::Discrete(weekly),
+            ),
+        ];
+        for (seed, option) in cases.iter().enumerate() {
+            let mut gbm = GeometricBrownianMotion::new(ctx.spot, carry, SIGMA, seed as u64);
+            let mc = option.monte_carlo(&mut gbm, &ctx, n_paths)?;
+            let analytic = option.value(&ctx)?;
+            assert!(
+                (mc.value - analytic).abs() < 4.0 * mc.std_error,
+                "{option:?}: {mc:?} vs {analytic}"
+            );
+        }
+
+        // A flat local vol makes the SLV forward lognormal.
+        let expiries = vec![0.25, 0.5, 1.0];
+        let strikes: Vec<f64> = (0..7).map(|i| f * (0.7 + 0.1 * i as f64)).collect();
+        let vols = vec![vec![SIGMA; strikes.len()]; expiries.len()];
+        let dupire = dupire_build(&expiries, &strikes, &vols, f, 0.0, 0.0);
+        let params = TimeDependentSabrParams::new(
+            PiecewiseConstant::constant(1.0, SIGMA),
+            PiecewiseConstant::constant(1.0, -0.3),
+            PiecewiseConstant::constant(1.0, 0.5),
+            1.0,
+            f,
+        );
+        let mut slv = TimeDependentSabrSlvSimulator::new(params, dupire, 7).with_bins(20);
+        let mc = cases[0].monte_carlo_slv(&mut slv, &ctx, n_paths)?;
+        let analytic = cases[0].value(&ctx)?;
+        assert!(
+            (mc.value - analytic).abs() < 4.0 * mc.std_error,
+            "{mc:?} vs {analytic}"
+        );
+        Ok(())
+    }
+}
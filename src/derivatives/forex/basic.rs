@@ -1,6 +1,8 @@
-use crate::error::Result;
-use crate::markets::forex::market_context::FxMarketContext;
+use crate::error::{Error, Result};
+use crate::markets::forex::market_context::{FxMarketContext, FxMarketShift};
 use crate::markets::forex::quotes::forwardpoints::{FXForwardHelper, FXForwardQuote};
+use crate::markets::forex::quotes::volsurface::FXDeltaConvention;
+use crate::time::businessdayconvention::BusinessDayConvention;
 use crate::time::calendars::{
     Australia, Brazil, Calendar, Canada, China, CzechRepublic, Denmark, HongKong, Hungary, India,
     Indonesia, Israel, Japan, JointCalendar, Mexico, NewZealand, Norway, Poland, Romania, Russia,
@@ -220,6 +222,103 @@ impl FXUnderlying {
         period.near_date_with_lag(valuation_date, &cal, self.settles() as i64)
     }
 
+    /// Spot date for a trade on `trade_date`: `settles()` business days on
+    /// the pair's calendar, rolled forward to a day that is also good in
+    /// USD.
+    ///
+    /// Unlike the spot leg of [`Self::near_date`], which adds the lag in
+    /// calendar days and rolls once, each day of the lag is counted as a
+    /// business day: a T+2 trade on a Friday spots on Tuesday, not Monday.
+    pub fn spot_date(&self, trade_date: NaiveDate) -> NaiveDate {
+        let calendar = self.calendar();
+        let mut spot = trade_date;
+        for _ in 0..self.settles() {
+            spot += Duration::days(1);
+            while !calendar.is_business_day(spot) {
+                spot += Duration::days(1);
+            }
+        }
+        following(&self.delivery_calendar(), spot)
+    }
+
+    /// Expiry, delivery and cut of an option struck for `tenor` on
+    /// `trade_date`, under the FX option date rules:
+    ///
+    /// * `ON`, day and week tenors count calendar days to the expiry,
+    ///   rolled forward to a pair business day; delivery is the expiry's
+    ///   spot date.
+    /// * Month and year tenors add the tenor to the spot date for the
+    ///   delivery — the last good USD day of the month if spot is the last
+    ///   one of its month, else modified following — and expire on the
+    ///   latest pair business day whose spot date is not after delivery.
+    ///
+    /// Delivery dates are good days on the pair's and the USD calendars.
+    /// Quotes taken after [`Self::cutoff_utc`] should pass
+    /// [`Self::effective_valuation_date`] as `trade_date`; the returned
+    /// `cut` is that same cut-off.
+    pub fn option_dates(&self, tenor: Period, trade_date: NaiveDate) -> Result<FXOptionDates> {
+        let calendar = self.calendar();
+        let (expiry, delivery) = match tenor {
+            Period::ON | Period::Days(_) | Period::Weeks(_) => {
+                let days = match tenor {
+                    Period::Days(n) => n,
+                    Period::Weeks(n) => 7 * n,
+                    _ => 1,
+                };
+                if days <= 0 {
+                    return Err(Error::InvalidData(format!(
+                        "option tenor {tenor:?} must be positive"
+                    )));
+                }
+                let expiry = following(&calendar, trade_date + Duration::days(days));
+                (expiry, self.spot_date(expiry))
+            }
+            Period::Months(_) | Period::Years(_) => {
+                let delivery_calendar = self.delivery_calendar();
+                let spot = self.spot_date(trade_date);
+                let unadjusted = (spot + tenor)?;
+                let delivery = if spot == delivery_calendar.end_of_month(spot) {
+                    delivery_calendar.end_of_month(unadjusted)
+                } else {
+                    delivery_calendar
+                        .adjust(unadjusted, BusinessDayConvention::ModifiedFollowing)
+                        .unwrap_or(unadjusted)
+                };
+                let mut expiry = delivery;
+                while expiry > trade_date
+                    && (!calendar.is_business_day(expiry) || self.spot_date(expiry) > delivery)
+                {
+                    expiry -= Duration::days(1);
+                }
+                if expiry <= trade_date {
+                    return Err(Error::InvalidData(format!(
+                        "no {tenor:?} option expiry after {trade_date}"
+                    )));
+                }
+                (expiry, delivery)
+            }
+            Period::TN | Period::SPOT | Period::SN => {
+                return Err(Error::InvalidData(format!(
+                    "{tenor:?} is not an option tenor"
+                )));
+            }
+        };
+        Ok(FXOptionDates {
+            expiry,
+            cut: self.cutoff_utc(),
+            delivery,
+        })
+    }
+
+    /// The pair's calendar joined with USD, on which deliveries settle.
+    fn delivery_calendar(&self) -> JointCalendar {
+        JointCalendar::new(vec![
+            self.currency_to_country(self.dom_currency()),
+            self.currency_to_country(self.frn_currency()),
+            self.currency_to_country(Currency::USD),
+        ])
+    }
+
     /// Build an [`FXForwardHelper`] using this pair's spot-lag convention.
     ///
     /// Prefer this over constructing the helper directly so the correct spot lag
@@ -246,6 +345,66 @@ impl FXUnderlying {
         let frn_calendar = self.currency_to_country(self.frn_currency());
         JointCalendar::new(vec![dom_calendar, frn_calendar])
     }
+
+    /// Currency the option premium is paid in: USD when the pair has a USD
+    /// leg, otherwise the base currency.
+    pub fn premium_currency(&self) -> Currency {
+        if self.dom_currency() == Currency::USD || self.frn_currency() == Currency::USD {
+            Currency::USD
+        } else {
+            self.frn_currency()
+        }
+    }
+
+    /// Market delta convention for an expiry `year_fraction` away. Deltas
+    /// are premium-adjusted when the premium is paid in the base currency
+    /// (USDJPY, EURGBP); spot deltas apply to G10 pairs out to one year,
+    /// forward deltas beyond that and for EM pairs at every tenor.
+    pub fn delta_convention(&self, year_fraction: f64) -> FXDeltaConvention {
+        let premium_adjusted = self.premium_currency() == self.frn_currency();
+        let g10 = |c: Currency| {
+            matches!(
+                c,
+                Currency::USD
+                    | Currency::EUR
+                    | Currency::JPY
+                    | Currency::GBP
+                    | Currency::CHF
+                    | Currency::AUD
+                    | Currency::NZD
+                    | Currency::CAD
+                    | Currency::NOK
+                    | Currency::SEK
+                    | Currency::DKK
+            )
+        };
+        let spot = year_fraction <= 1.0 && g10(self.dom_currency()) && g10(self.frn_currency());
+        match (spot, premium_adjusted) {
+            (true, false) => FXDeltaConvention::Spot,
+            (false, false) => FXDeltaConvention::Forward,
+            (true, true) => FXDeltaConvention::SpotPremiumAdjusted,
+            (false, true) => FXDeltaConvention::ForwardPremiumAdjusted,
+        }
+    }
+}
+
+/// Dates of an FX option struck for a tenor; see
+/// [`FXUnderlying::option_dates`].
+#[derive(Deserialize, Serialize, Copy, Clone, PartialEq, Debug)]
+pub struct FXOptionDates {
+    pub expiry: NaiveDate,
+    /// Expiry cut, UTC.
+    pub cut: NaiveTime,
+    pub delivery: NaiveDate,
+}
+
+/// First business day on or after `date`.
+fn following(calendar: &impl Calendar, date: NaiveDate) -> NaiveDate {
+    let mut date = date;
+    while !calendar.is_business_day(date) {
+        date += Duration::days(1);
+    }
+    date
 }
 
 #[derive(Deserialize, Serialize, PartialEq, Debug)]
@@ -271,12 +430,141 @@ pub trait FXDerivatives {
     fn delta(&self, market: &FxMarketContext) -> Result<CurrencyValue>;
     fn gamma(&self, market: &FxMarketContext) -> Result<f64>;
     fn vega(&self, market: &FxMarketContext) -> Result<f64>;
+    fn greeks(&self, market: &FxMarketContext, method: GreekMethod) -> Result<FXGreeks>;
+}
+
+/// How [`FXDerivatives::greeks`] computes the report.
+#[derive(Deserialize, Serialize, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum GreekMethod {
+    /// Closed-form Garman–Kohlhagen sensitivities.
+    Analytic,
+    /// Finite differences of the trade's value under [`FxMarketShift`]s.
+    BumpAndReprice,
+}
+
+/// Full Greek report for one FX trade, signed for the holder. Spot
+/// sensitivities are in the base currency, the rest in the quote
+/// currency:
+///
+/// * `delta` — spot delta `∂V/∂S`, base amount (discounted, unlike
+///   [`FXDerivatives::delta`], which is the forward delta);
+/// * `gamma` — change in `delta` per unit of spot;
+/// * `vega` — value change per 1 % vol;
+/// * `vanna` — change in `delta` per 1 % vol;
+/// * `volga` — change in `vega` per 1 % vol;
+/// * `theta_calendar` / `theta_business` — value change as the date
+///   rolls one calendar day / to the next business day;
+/// * `charm` — change in `delta` over one calendar day;
+/// * `rho_domestic` / `rho_foreign` — value change per 1bp;
+/// * `forward_points` — value change per forward point.
+#[derive(Deserialize, Serialize, PartialEq, Debug)]
+pub struct FXGreeks {
+    pub delta: CurrencyValue,
+    pub gamma: CurrencyValue,
+    pub vega: CurrencyValue,
+    pub vanna: CurrencyValue,
+    pub volga: CurrencyValue,
+    pub theta_calendar: CurrencyValue,
+    pub theta_business: CurrencyValue,
+    pub charm: CurrencyValue,
+    pub rho_domestic: CurrencyValue,
+    pub rho_foreign: CurrencyValue,
+    pub forward_points: CurrencyValue,
+}
+
+/// Spot bump relative to spot.
+const SPOT_BUMP: f64 = 1.0e-4;
+/// Absolute vol bump.
+const VOL_BUMP: f64 = 1.0e-4;
+
+impl FXGreeks {
+    /// Bump-and-reprice report from the holder's quote-currency value
+    /// under a market shift. Central differences throughout except for
+    /// the date roll, which only goes forward.
+    pub(crate) fn bumped(
+        asset: &FXUnderlying,
+        market: &FxMarketContext,
+        value: impl Fn(&FxMarketShift) -> Result<f64>,
+    ) -> Result<Self> {
+        let (base, quote) = (asset.frn_currency(), asset.dom_currency());
+        let at = |shift: FxMarketShift| value(&shift);
+        let spot = |ds: f64, vol: f64, days: i64| {
+            at(FxMarketShift {
+                spot: ds,
+                vol,
+                days,
+                ..Default::default()
+            })
+        };
+        let h = SPOT_BUMP * market.spot;
+        let delta = |vol: f64, days: i64| -> Result<f64> {
+            Ok((spot(h, vol, days)? - spot(-h, vol, days)?) / (2.0 * h))
+        };
+        let base_value = at(FxMarketShift::default())?;
+        let delta_0 = delta(0.0, 0)?;
+        let roll = |days: i64| -> Result<f64> { Ok(spot(0.0, 0.0, days)? - base_value) };
+        let business_days = business_days_to_next(asset, market)?;
+        let rate = |d_bp: f64, f_bp: f64| {
+            at(FxMarketShift {
+                rate_d_bp: d_bp,
+                rate_f_bp: f_bp,
+                ..Default::default()
+            })
+        };
+        let points = |p: f64| {
+            at(FxMarketShift {
+                forward_points: p,
+                ..Default::default()
+            })
+        };
+        let vol_up = spot(0.0, VOL_BUMP, 0)?;
+        let vol_down = spot(0.0, -VOL_BUMP, 0)?;
+        let in_base = |value: f64| CurrencyValue {
+            currency: base,
+            value,
+        };
+        let in_quote = |value: f64| CurrencyValue {
+            currency: quote,
+            value,
+        };
+        Ok(Self {
+            delta: in_base(delta_0),
+            gamma: in_base((spot(h, 0.0, 0)? - 2.0 * base_value + spot(-h, 0.0, 0)?) / (h * h)),
+            vega: in_quote((vol_up - vol_down) / (2.0 * VOL_BUMP) * 0.01),
+            vanna: in_base((delta(VOL_BUMP, 0)? - delta(-VOL_BUMP, 0)?) / (2.0 * VOL_BUMP) * 0.01),
+            volga: in_quote(
+                (vol_up - 2.0 * base_value + vol_down) / (VOL_BUMP * VOL_BUMP) * 1.0e-4,
+            ),
+            theta_calendar: in_quote(roll(1)?),
+            theta_business: in_quote(roll(business_days)?),
+            charm: in_base(delta(0.0, 1)? - delta_0),
+            rho_domestic: in_quote((rate(1.0, 0.0)? - rate(-1.0, 0.0)?) / 2.0),
+            rho_foreign: in_quote((rate(0.0, 1.0)? - rate(0.0, -1.0)?) / 2.0),
+            forward_points: in_quote((points(1.0)? - points(-1.0)?) / 2.0),
+        })
+    }
+}
+
+/// Calendar days from the valuation date to the pair's next business
+/// day.
+pub(crate) fn business_days_to_next(asset: &FXUnderlying, market: &FxMarketContext) -> Result<i64> {
+    let next = asset
+        .calendar()
+        .advance(
+            market.valuation_date,
+            Period::Days(1),
+            BusinessDayConvention::Following,
+            None,
+        )?
+        .unwrap_or(market.valuation_date + Duration::days(1));
+    Ok((next - market.valuation_date).num_days())
 }
 
 #[cfg(test)]
 mod tests {
     use super::FXUnderlying;
     use crate::error::Result;
+    use crate::markets::forex::quotes::volsurface::FXDeltaConvention;
     use crate::time::daycounters::DayCounters;
     use crate::time::daycounters::actual360::Actual360;
     use chrono::{NaiveDate, NaiveTime};
@@ -295,6 +583,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn delta_convention_follows_premium_currency_and_tenor() {
+        use FXDeltaConvention::*;
+        assert_eq!(FXUnderlying::EURUSD.delta_convention(0.5), Spot);
+        assert_eq!(FXUnderlying::EURUSD.delta_convention(2.0), Forward);
+        assert_eq!(FXUnderlying::USDJPY.premium_currency(), Currency::USD);
+        assert_eq!(
+            FXUnderlying::USDJPY.delta_convention(1.0),
+            SpotPremiumAdjusted
+        );
+        assert_eq!(FXUnderlying::EURGBP.premium_currency(), Currency::EUR);
+        assert_eq!(
+            FXUnderlying::EURGBP.delta_convention(5.0),
+            ForwardPremiumAdjusted
+        );
+        assert_eq!(
+            FXUnderlying::USDBRL.delta_convention(0.25),
+            ForwardPremiumAdjusted
+        );
+        assert_eq!(
+            FXUnderlying::AUDSGD.delta_convention(0.25),
+            ForwardPremiumAdjusted
+        );
+        assert_eq!(
+            FXUnderlying::USDZAR.delta_convention(0.25),
+            ForwardPremiumAdjusted
+        );
+    }
+
     #[test]
     fn test_settles() {
         assert_eq!(FXUnderlying::USDCAD.settles(), 1);
@@ -519,6 +836,34 @@ mod tests {
         );
     }
 
+    /// EURUSD option dates traded Tuesday 2026-04-21 (spot Thursday
+    /// 04-23): the 1M delivery rolls over US Memorial Day (05-25), and the
+    /// expiry backs off so its own spot still delivers on time.
+    #[test]
+    fn test_option_dates_eurusd() -> Result<()> {
+        use crate::time::period::Period;
+
+        let date = |m, d| NaiveDate::from_ymd_opt(2026, m, d).unwrap();
+        let pair = FXUnderlying::EURUSD;
+        let dates = |tenor| -> Result<(NaiveDate, NaiveDate)> {
+            let dates = pair.option_dates(tenor, date(4, 21))?;
+            assert_eq!(dates.cut, pair.cutoff_utc());
+            Ok((dates.expiry, dates.delivery))
+        };
+        assert_eq!(dates(Period::ON)?, (date(4, 22), date(4, 24)));
+        assert_eq!(dates(Period::Weeks(1))?, (date(4, 28), date(4, 30)));
+        assert_eq!(dates(Period::Months(1))?, (date(5, 21), date(5, 26)));
+
+        // Spot 04-30 is April's last good day, so 1M delivers on May's.
+        let month_end = pair.option_dates(Period::Months(1), date(4, 28))?;
+        assert_eq!(
+            (month_end.expiry, month_end.delivery),
+            (date(5, 27), date(5, 29))
+        );
+        assert!(pair.option_dates(Period::TN, date(4, 21)).is_err());
+        Ok(())
+    }
+
     #[test]
     fn test_forward_points_converter_eurjpy() {
         assert_eq!(FXUnderlying::EURJPY.forward_points_converter(), 100f64);
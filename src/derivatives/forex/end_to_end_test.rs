@@ -26,7 +26,7 @@
 mod tests {
     use crate::derivatives::basic::{BasicInfo, Direction, Style};
     use crate::derivatives::forex::basic::{FXDerivatives, FXUnderlying};
-    use crate::derivatives::forex::option::{FXVanillaOption, OptionType};
+    use crate::derivatives::forex::option::{FXVanillaOption, OptionType, SmileDynamics};
     use crate::error::Result;
     use crate::markets::forex::market_context::FxMarketContext;
     use crate::markets::forex::quotes::forwardpoints::FXForwardQuote;
@@ -129,10 +129,10 @@ mod tests {
         ];
 
         // FX vol pillars (raw — delta-quoted smile).
-        let vol_pillars = vec![FXDeltaVolPillar {
-            expiry: exp_1y,
+        let vol_pillars = vec![FXDeltaVolPillar::new(
+            exp_1y.into(),
             forward,
-            quotes: vec![
+            vec![
                 FXVolQuote::Atm(0.0663),
                 FXVolQuote::Put {
                     delta: 0.25,
@@ -151,7 +151,7 @@ mod tests {
                     vol: 0.082775,
                 },
             ],
-        }];
+        )];
 
         // NB: skipping full from_raw_quotes pipeline on the IR side
         // because `YieldTermMarketData::get_stripped_curve` needs full
@@ -204,7 +204,8 @@ mod tests {
             notional_currency: Currency::from_code("EUR").unwrap(),
             notional_amounts: 1_000_000.0,
             strike: 1.20,
-            volatility: ctx.implied_vol(expiry, 1.20)?,
+            volatility: None,
+            smile_dynamics: SmileDynamics::StickyStrike,
         };
         let pv = option.mtm(&ctx)?;
         // Buyer: negative PV (paid premium). Sign + magnitude sanity.
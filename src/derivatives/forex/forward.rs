@@ -1,8 +1,12 @@
 use crate::derivatives::basic::BasicInfo;
-use crate::derivatives::forex::basic::{CurrencyValue, FXDerivatives, FXUnderlying};
+use crate::derivatives::forex::basic::{
+    CurrencyValue, FXDerivatives, FXGreeks, FXUnderlying, GreekMethod, business_days_to_next,
+};
 use crate::error::{Error, Result};
-use crate::markets::forex::market_context::FxMarketContext;
+use crate::markets::forex::market_context::{FxMarketContext, FxMarketShift};
 use crate::markets::termstructures::yieldcurve::InterpolationMethodEnum;
+use crate::time::daycounters::DayCounters;
+use crate::time::daycounters::actual365fixed::Actual365Fixed;
 use iso_currency::Currency;
 use serde::{Deserialize, Serialize};
 
@@ -15,51 +19,110 @@ pub struct FXForward {
     pub strike: f64,
 }
 
-impl FXDerivatives for FXForward {
-    fn mtm(&self, market: &FxMarketContext) -> Result<CurrencyValue> {
+impl FXForward {
+    /// Signed base-currency amount the holder buys forward.
+    fn base_amount(&self) -> f64 {
+        let direction = self.basic_info.direction as i8 as f64;
+        if self.notional_currency == self.asset.frn_currency() {
+            self.notional_amounts * direction
+        } else {
+            -self.notional_amounts / self.strike * direction
+        }
+    }
+
+    /// Holder's value in the quote currency under a market shift.
+    fn value_under(&self, market: &FxMarketContext, shift: &FxMarketShift) -> Result<f64> {
         let calendar = self.asset.calendar();
-        let forward_points = market
-            .forwards
-            .get_forward(self.basic_info.expiry_date, &calendar)?
+        // Market convention: forward points are quoted as raw pips
+        // (e.g. EURUSD 48.12 pts → 0.004812). JPY crosses use a 100 divisor.
+        let outright_forward = market
+            .forward_under(
+                self.basic_info.expiry_date,
+                &calendar,
+                self.asset.forward_points_converter(),
+                shift,
+            )?
             .ok_or_else(|| {
                 Error::TradeExpired(format!(
                     "Trade expired at {} before {}",
                     self.basic_info.expiry_date, market.valuation_date
                 ))
             })?;
-        // Market convention: forward points are quoted as raw pips
-        // (e.g. EURUSD 48.12 pts → 0.004812). JPY crosses use a 100 divisor.
-        let outright_forward = market.spot + forward_points / self.asset.forward_points_converter();
-        let discount_factor = market.domestic_curve.discount(
+        let discount_factor = market.discount_d_under(
             self.basic_info.expiry_date,
             &InterpolationMethodEnum::PiecewiseLinearContinuous,
+            shift,
         )?;
-        let payoff = if self.notional_currency == self.asset.frn_currency() {
-            self.notional_amounts
-                * self.basic_info.direction as i8 as f64
-                * (outright_forward - self.strike)
-        } else {
-            -self.notional_amounts / self.strike
-                * self.basic_info.direction as i8 as f64
-                * (outright_forward - self.strike)
+        Ok(self.base_amount() * (outright_forward - self.strike) * discount_factor)
+    }
+
+    /// Closed-form report. The forward only carries delta, rate and
+    /// time risk; theta and charm use the flat rate `−ln(DF)/T`.
+    fn analytic_greeks(&self, market: &FxMarketContext) -> Result<FXGreeks> {
+        let no_shift = FxMarketShift::default();
+        let expiry = self.basic_info.expiry_date;
+        let forward = market
+            .forward_under(
+                expiry,
+                &self.asset.calendar(),
+                self.asset.forward_points_converter(),
+                &no_shift,
+            )?
+            .ok_or_else(|| {
+                Error::TradeExpired(format!(
+                    "Trade expired at {} before {}",
+                    expiry, market.valuation_date
+                ))
+            })?;
+        let discount_factor = market.discount_d_under(
+            expiry,
+            &InterpolationMethodEnum::PiecewiseLinearContinuous,
+            &no_shift,
+        )?;
+        let t = Actual365Fixed::default().year_fraction(market.valuation_date, expiry)?;
+        let rate = -discount_factor.ln() / t;
+        let value = self.value_under(market, &no_shift)?;
+        let delta = self.base_amount() * discount_factor;
+        let business_days = business_days_to_next(&self.asset, market)? as f64;
+        let (base, quote) = (self.asset.frn_currency(), self.asset.dom_currency());
+        let in_base = |value: f64| CurrencyValue {
+            currency: base,
+            value,
+        };
+        let in_quote = |value: f64| CurrencyValue {
+            currency: quote,
+            value,
         };
+        Ok(FXGreeks {
+            delta: in_base(delta),
+            gamma: in_base(0.0),
+            vega: in_quote(0.0),
+            vanna: in_base(0.0),
+            volga: in_quote(0.0),
+            theta_calendar: in_quote(rate * value / 365.0),
+            theta_business: in_quote(rate * value * business_days / 365.0),
+            charm: in_base(rate * delta / 365.0),
+            rho_domestic: in_quote((delta * forward - value) * t * 1.0e-4),
+            rho_foreign: in_quote(-delta * forward * t * 1.0e-4),
+            forward_points: in_quote(delta / self.asset.forward_points_converter()),
+        })
+    }
+}
+
+impl FXDerivatives for FXForward {
+    fn mtm(&self, market: &FxMarketContext) -> Result<CurrencyValue> {
         Ok(CurrencyValue {
             currency: self.asset.dom_currency(),
-            value: payoff * discount_factor,
+            value: self.value_under(market, &FxMarketShift::default())?,
         })
     }
 
     /// FX forward has a linear payoff, so delta is just the signed notional —
     /// independent of market data.
     fn delta(&self, _market: &FxMarketContext) -> Result<CurrencyValue> {
-        let delta = if self.notional_currency == self.asset.frn_currency() {
-            self.notional_amounts * self.basic_info.direction as i8 as f64
-        } else {
-            -self.notional_amounts / self.strike * self.basic_info.direction as i8 as f64
-        };
         Ok(CurrencyValue {
             currency: self.asset.frn_currency(),
-            value: delta,
+            value: self.base_amount(),
         })
     }
 
@@ -70,13 +133,24 @@ impl FXDerivatives for FXForward {
     fn vega(&self, _market: &FxMarketContext) -> Result<f64> {
         Ok(0f64)
     }
+
+    fn greeks(&self, market: &FxMarketContext, method: GreekMethod) -> Result<FXGreeks> {
+        match method {
+            GreekMethod::Analytic => self.analytic_greeks(market),
+            GreekMethod::BumpAndReprice => {
+                FXGreeks::bumped(&self.asset, market, |shift| self.value_under(market, shift))
+            }
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::FXForward;
     use crate::derivatives::basic::{BasicInfo, Direction, Style};
-    use crate::derivatives::forex::basic::{CurrencyValue, FXDerivatives, FXUnderlying};
+    use crate::derivatives::forex::basic::{
+        CurrencyValue, FXDerivatives, FXUnderlying, GreekMethod,
+    };
     use crate::error::Result;
     use crate::markets::forex::market_context::FxMarketContext;
     use crate::markets::forex::quotes::forwardpoints::{FXForwardHelper, FXForwardQuote};
@@ -331,4 +405,94 @@ mod tests {
         );
         Ok(())
     }
+
+    #[test]
+    fn greek_report_agrees_across_methods() -> Result<()> {
+        // A Friday: the business-day roll spans the weekend.
+        let valuation_date = NaiveDate::from_ymd_opt(2026, 4, 24).unwrap();
+        let delivery_date = NaiveDate::from_ymd_opt(2026, 7, 28).unwrap();
+        let pillar = |date: NaiveDate| StrippedCurve {
+            first_settle_date: valuation_date,
+            date,
+            market_rate: 0.037,
+            zero_rate: 0.037,
+            discount: 1.0,
+            source: InterestRateQuoteEnum::OIS,
+            hidden_pillar: false,
+        };
+        let yts = YieldTermStructure::new(
+            Box::new(UnitedStates::default()),
+            Box::new(Actual365Fixed::default()),
+            valuation_date,
+            vec![
+                pillar(delivery_date),
+                pillar(NaiveDate::from_ymd_opt(2026, 10, 28).unwrap()),
+            ],
+        );
+        let fx_forward_helper = FXForwardHelper::new(
+            valuation_date,
+            1.1736,
+            vec![
+                FXForwardQuote {
+                    tenor: Period::SPOT,
+                    value: 0.0,
+                },
+                FXForwardQuote {
+                    tenor: Period::Months(3),
+                    value: 48.12,
+                },
+                FXForwardQuote {
+                    tenor: Period::Months(6),
+                    value: 87.38,
+                },
+            ],
+        );
+        let ctx = FxMarketContext::for_linear(
+            valuation_date,
+            1.1736,
+            (Currency::USD, Currency::EUR),
+            clone_yts(&yts),
+            clone_yts(&yts),
+            fx_forward_helper,
+        )?;
+        let fx_forward = FXForward {
+            basic_info: BasicInfo {
+                trade_date: valuation_date,
+                style: Style::FXForward,
+                direction: Direction::Sell,
+                expiry_date: delivery_date,
+                delivery_date,
+            },
+            asset: FXUnderlying::EURUSD,
+            notional_currency: Currency::USD,
+            notional_amounts: 1_150_000.0,
+            strike: 1.15,
+        };
+        let analytic = fx_forward.greeks(&ctx, GreekMethod::Analytic)?;
+        let bumped = fx_forward.greeks(&ctx, GreekMethod::BumpAndReprice)?;
+
+        // Selling USD forward is buying EUR 1m.
+        let df = (-0.037 * 95.0 / 365.0_f64).exp();
+        assert!((analytic.delta.value - 1.0e6 * df).abs() < 1e-6);
+        assert_eq!(analytic.gamma.value, 0.0);
+        assert_eq!(analytic.vega.value, 0.0);
+        assert!((analytic.theta_business.value - 3.0 * analytic.theta_calendar.value).abs() < 1e-9);
+        for (a, b) in [
+            (&analytic.delta, &bumped.delta),
+            (&analytic.theta_calendar, &bumped.theta_calendar),
+            (&analytic.theta_business, &bumped.theta_business),
+            (&analytic.charm, &bumped.charm),
+            (&analytic.rho_domestic, &bumped.rho_domestic),
+            (&analytic.rho_foreign, &bumped.rho_foreign),
+            (&analytic.forward_points, &bumped.forward_points),
+        ] {
+            assert_eq!(a.currency, b.currency);
+            assert!(
+                (a.value - b.value).abs() < 1e-3 * b.value.abs(),
+                "{a:?} {b:?}"
+            );
+        }
+        assert!(bumped.gamma.value.abs() < 1e-3);
+        Ok(())
+    }
 }
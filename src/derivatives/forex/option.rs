@@ -12,17 +12,61 @@
 //! currency per unit of base (foreign) notional. Convert to EUR premium by
 //! dividing by spot.
 //!
-//! This module does not handle business-time adjustments, premium-included
-//! delta conventions, or smile construction — those belong elsewhere.
+//! The vol is read off [`FxMarketContext::implied_vol`] at the expiry and
+//! strike unless the deal carries an override. Delta and gamma follow the
+//! deal's [`SmileDynamics`]: under sticky strike `σ(K)` stays put as the
+//! forward moves; under sticky delta the smile slides with the forward,
+//! `σ(F) = σ₀(K·F₀/F)`, adding the smile-slope terms
+//!
+//! ```text
+//!     Δ = ω·N(ω·d1) + ν·σ_F
+//!     Γ = DF · [ φ(d1)/(F·σ·√T) + 2·vanna·σ_F + volga·σ_F² + ν·σ_FF ]
+//!     σ_F  = −σ₀'(K)·K/F,    σ_FF = σ₀''(K)·K²/F² + 2·σ₀'(K)·K/F²
+//!     ν = F·φ(d1)·√T,   vanna = −φ(d1)·d2/σ,   volga = ν·d1·d2/σ
+//! ```
+//!
+//! with the strike derivatives of the smile taken by central differences.
+//!
+//! [`FXDerivatives::greeks`] reports the rest of the Garman–Kohlhagen set,
+//! with rates entering through `F = S·exp((r_d − r_f)·T)` and `r = −ln(DF)/T`:
+//!
+//! ```text
+//!     Θ     = r·V − DF·F·φ(d1)·σ / (2·√T)
+//!     charm = r·Δ + DF·[ φ(d1)·d2/(2T) − σ_F·F·φ(d1)·(1 + d1·d2)/(2·√T) ]
+//!     ρ_d   = (Δ·F − V)·T,   ρ_f = −Δ·F·T,   ∂V/∂pts = Δ / conv
+//! ```
+//!
+//! The bump-and-reprice fallback revalues under an
+//! [`FxMarketShift`](crate::markets::forex::market_context::FxMarketShift).
+//!
+//! [`FXVanillaOption::delta_in`] reports delta under any
+//! [`FXDeltaConvention`] and [`FXVanillaOption::premium_in`] quotes the
+//! premium in pips or percent of either currency. The option is also a
+//! [`VannaVolgaTarget`](crate::models::forex::vanna_volga::VannaVolgaTarget),
+//! which Vanna–Volga prices back onto the smile at the pillar strikes.
+//!
+//! Time decay runs on the surface's vol clock: rolling the valuation date
+//! consumes `τ(roll)/τ(expiry)` of the variance to expiry (see
+//! [`FxMarketContext::rolled_vol`]), so the vol terms of theta and charm
+//! scale by the vol time of the roll in place of `days/365`. Without a
+//! clock this is calendar time.
+//!
+//! This module does not handle smile construction — that belongs
+//! elsewhere.
 
 use crate::derivatives::basic::BasicInfo;
-use crate::derivatives::forex::basic::{CurrencyValue, FXDerivatives, FXUnderlying};
+use crate::derivatives::forex::basic::{
+    CurrencyValue, FXDerivatives, FXGreeks, FXUnderlying, GreekMethod, business_days_to_next,
+};
 use crate::error::{Error, Result};
-use crate::markets::forex::market_context::FxMarketContext;
+use crate::markets::forex::market_context::{FxMarketContext, FxMarketShift};
+use crate::markets::forex::quotes::volsurface::FXDeltaConvention;
 use crate::markets::termstructures::yieldcurve::InterpolationMethodEnum;
 use crate::math::normal::{cdf, pdf};
+use crate::models::forex::vanna_volga::VannaVolgaTarget;
 use crate::time::daycounters::DayCounters;
 use crate::time::daycounters::actual365fixed::Actual365Fixed;
+use chrono::{Duration, NaiveDate};
 use iso_currency::Currency;
 use serde::{Deserialize, Serialize};
 
@@ -75,6 +119,36 @@ pub fn black_scholes(
     discount * (omega * forward * cdf(omega * d1) - omega * strike * cdf(omega * d2))
 }
 
+/// How the implied vol at the option's strike responds to a move in the
+/// forward when computing delta and gamma.
+#[derive(Deserialize, Serialize, Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum SmileDynamics {
+    /// The smile is fixed in strike: `σ(K)` does not move.
+    #[default]
+    StickyStrike,
+    /// The smile is fixed in moneyness `K/F`, hence in delta, and slides
+    /// with the forward.
+    StickyDelta,
+}
+
+/// Quotation of an option premium. With `P` the quote-currency premium per
+/// unit of base notional: domestic pips `P·conv`, foreign pips
+/// `P/(S·K)·10⁴`, domestic % `100·P/K`, foreign % `100·P/S`.
+#[derive(Deserialize, Serialize, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FXPremiumQuote {
+    /// Quote currency per unit of base, in pips.
+    DomesticPips,
+    /// Base currency per unit of quote, in 10⁻⁴ units.
+    ForeignPips,
+    /// Quote currency as a percentage of the quote notional.
+    DomesticPercent,
+    /// Base currency as a percentage of the base notional.
+    ForeignPercent,
+}
+
+/// Relative strike step for the smile's central differences.
+const SMILE_BUMP: f64 = 1.0e-4;
+
 /// FX vanilla option deal record. The premium is computed with spot + forward
 /// points (producing F via covered parity, identical to the FX forward pricer)
 /// and the domestic discount curve.
@@ -86,10 +160,14 @@ pub struct FXVanillaOption {
     pub notional_currency: Currency,
     pub notional_amounts: f64,
     pub strike: f64,
-    /// Implied volatility (annualised, decimal). Surface-interpolated vol is
-    /// passed in by the caller for this pass; wiring up an `FXVolSurface` is
-    /// a follow-up.
-    pub volatility: f64,
+    /// Implied volatility override (annualised, decimal). `None` reads the
+    /// market's vol surface at the expiry and strike.
+    #[serde(default)]
+    pub volatility: Option<f64>,
+    /// Smile dynamics for delta and gamma; irrelevant with a flat
+    /// override vol.
+    #[serde(default)]
+    pub smile_dynamics: SmileDynamics,
 }
 
 /// Intermediate quantities needed by all Black-Scholes formulas at a single
@@ -101,48 +179,278 @@ struct BsContext {
     strike: f64,
     year_fraction: f64,
     discount: f64,
+    sigma: f64,
     sqrt_v: f64,
     d1: f64,
 }
 
 impl FXVanillaOption {
     fn bs_context(&self, market: &FxMarketContext) -> Result<BsContext> {
+        self.bs_context_under(market, &FxMarketShift::default())
+    }
+
+    fn bs_context_under(
+        &self,
+        market: &FxMarketContext,
+        shift: &FxMarketShift,
+    ) -> Result<BsContext> {
         let calendar = self.asset.calendar();
-        let forward_points = market
-            .forwards
-            .get_forward(self.basic_info.expiry_date, &calendar)?
+        let forward = market
+            .forward_under(
+                self.basic_info.expiry_date,
+                &calendar,
+                self.asset.forward_points_converter(),
+                shift,
+            )?
             .ok_or_else(|| {
                 Error::TradeExpired(format!(
                     "Option expiry {} outside the forward points range (valuation {})",
                     self.basic_info.expiry_date, market.valuation_date
                 ))
             })?;
-        let forward = market.spot + forward_points / self.asset.forward_points_converter();
-        let year_fraction = Actual365Fixed::default()
-            .year_fraction(market.valuation_date, self.basic_info.expiry_date)?;
-        let variance = self.volatility * self.volatility * year_fraction;
+        let year_fraction = Actual365Fixed::default().year_fraction(
+            market.valuation_date_under(shift),
+            self.basic_info.expiry_date,
+        )?;
+        let sigma = self.volatility_under(market, shift)?;
+        let variance = sigma * sigma * year_fraction;
         let sqrt_v = variance.sqrt();
         let d1 = ((forward / self.strike).ln() + 0.5 * variance) / sqrt_v;
-        let discount = market.domestic_curve.discount(
+        let discount = market.discount_d_under(
             self.basic_info.expiry_date,
             &InterpolationMethodEnum::StepFunctionForward,
+            shift,
         )?;
         Ok(BsContext {
             forward,
-            spot: market.spot,
+            spot: market.spot + shift.spot,
             strike: self.strike,
             year_fraction,
             discount,
+            sigma,
             sqrt_v,
             d1,
         })
     }
 
+    /// Pricing vol: the override if set, else the surface at expiry and
+    /// strike.
+    pub fn volatility(&self, market: &FxMarketContext) -> Result<f64> {
+        self.volatility_under(market, &FxMarketShift::default())
+    }
+
+    fn volatility_under(&self, market: &FxMarketContext, shift: &FxMarketShift) -> Result<f64> {
+        match self.volatility {
+            Some(vol) => {
+                Ok(market.rolled_vol(self.basic_info.expiry_date, vol, shift)? + shift.vol)
+            }
+            None => market.implied_vol_under(self.basic_info.expiry_date, self.strike, shift),
+        }
+    }
+
+    /// `(∂σ/∂F, ∂²σ/∂F²)` at the strike under the deal's smile dynamics;
+    /// zero under sticky strike or with an override vol.
+    fn smile_sensitivities(&self, market: &FxMarketContext, ctx: &BsContext) -> Result<(f64, f64)> {
+        if self.volatility.is_some() || self.smile_dynamics == SmileDynamics::StickyStrike {
+            return Ok((0.0, 0.0));
+        }
+        let expiry = self.basic_info.expiry_date;
+        let (k, f) = (ctx.strike, ctx.forward);
+        let h = SMILE_BUMP * k;
+        let up = market.implied_vol(expiry, k + h)?;
+        let down = market.implied_vol(expiry, k - h)?;
+        let slope = (up - down) / (2.0 * h);
+        let curvature = (up - 2.0 * ctx.sigma + down) / (h * h);
+        Ok((
+            -slope * k / f,
+            curvature * k * k / (f * f) + 2.0 * slope * k / (f * f),
+        ))
+    }
+
+    /// `ω·N(ω·d1) + ν·σ_F`: forward, premium-excluded delta per unit.
+    fn forward_delta_per_unit(&self, market: &FxMarketContext, ctx: &BsContext) -> Result<f64> {
+        let omega = self.option_type.omega();
+        let (sigma_f, _) = self.smile_sensitivities(market, ctx)?;
+        let nu = ctx.forward * pdf(ctx.d1) * ctx.year_fraction.sqrt();
+        Ok(omega * cdf(omega * ctx.d1) + nu * sigma_f)
+    }
+
+    /// Base-currency delta of the held notional under `convention`, e.g.
+    /// `self.asset.delta_convention(t)` for the pair's market quote. The
+    /// premium adjustment subtracts the forward premium in base units,
+    /// `B/F`; spot deltas scale by the parity foreign discount
+    /// `DF_f = F·DF_d/S`.
+    pub fn delta_in(
+        &self,
+        market: &FxMarketContext,
+        convention: FXDeltaConvention,
+    ) -> Result<CurrencyValue> {
+        let ctx = self.bs_context(market)?;
+        let mut delta = self.forward_delta_per_unit(market, &ctx)?;
+        if convention.is_premium_adjusted() {
+            let variance = ctx.sqrt_v * ctx.sqrt_v;
+            delta -= black_scholes(ctx.forward, ctx.strike, variance, 1.0, self.option_type)
+                / ctx.forward;
+        }
+        if convention.is_spot() {
+            delta *= ctx.forward * ctx.discount / ctx.spot;
+        }
+        Ok(CurrencyValue {
+            currency: self.asset.frn_currency(),
+            value: self.base_amount() * delta,
+        })
+    }
+
+    /// Premium per unit of notional in the requested quotation, unsigned.
+    pub fn premium_in(&self, market: &FxMarketContext, quote: FXPremiumQuote) -> Result<f64> {
+        let ctx = self.bs_context(market)?;
+        let variance = ctx.sqrt_v * ctx.sqrt_v;
+        let premium_dom_per_unit = black_scholes(
+            ctx.forward,
+            ctx.strike,
+            variance,
+            ctx.discount,
+            self.option_type,
+        );
+        Ok(match quote {
+            FXPremiumQuote::DomesticPips => {
+                premium_dom_per_unit * self.asset.forward_points_converter()
+            }
+            FXPremiumQuote::ForeignPips => premium_dom_per_unit / (ctx.spot * ctx.strike) * 1.0e4,
+            FXPremiumQuote::DomesticPercent => 100.0 * premium_dom_per_unit / ctx.strike,
+            FXPremiumQuote::ForeignPercent => 100.0 * premium_dom_per_unit / ctx.spot,
+        })
+    }
+
+    /// Signed base-currency notional held.
+    fn base_amount(&self) -> f64 {
+        let notional = if self.notional_currency == self.asset.frn_currency() {
+            self.notional_amounts
+        } else {
+            self.notional_amounts / self.strike
+        };
+        self.direction_sign() * notional
+    }
+
+    /// Holder's value in the quote currency under a market shift, with the
+    /// smile moving per the deal's dynamics.
+    fn value_under(&self, market: &FxMarketContext, shift: &FxMarketShift) -> Result<f64> {
+        let mut ctx = self.bs_context_under(market, shift)?;
+        if self.volatility.is_none() && self.smile_dynamics == SmileDynamics::StickyDelta {
+            // The smile slides with the forward: read it at `K·F₀/F`.
+            let forward_0 = self.bs_context(market)?.forward;
+            ctx.sigma = market.implied_vol_under(
+                self.basic_info.expiry_date,
+                self.strike * forward_0 / ctx.forward,
+                shift,
+            )?;
+        }
+        let premium = black_scholes(
+            ctx.forward,
+            ctx.strike,
+            ctx.sigma * ctx.sigma * ctx.year_fraction,
+            ctx.discount,
+            self.option_type,
+        );
+        Ok(self.base_amount() * premium)
+    }
+
+    /// Closed-form report. Delta, gamma, vanna and charm carry the
+    /// smile-slope terms of the deal's dynamics; theta and charm hold the
+    /// forward and the smile fixed and use the flat rate `−ln(DF)/T`.
+    fn analytic_greeks(&self, market: &FxMarketContext) -> Result<FXGreeks> {
+        let ctx = self.bs_context(market)?;
+        let (sigma_f, sigma_ff) = self.smile_sensitivities(market, &ctx)?;
+        let (f, t, df, sigma, d1) = (
+            ctx.forward,
+            ctx.year_fraction,
+            ctx.discount,
+            ctx.sigma,
+            ctx.d1,
+        );
+        let d2 = d1 - ctx.sqrt_v;
+        let omega = self.option_type.omega();
+        let rate = -df.ln() / t;
+        let phi = pdf(d1);
+        let nu = f * phi * t.sqrt();
+        let vanna = -phi * d2 / sigma;
+        let volga = nu * d1 * d2 / sigma;
+
+        let value = black_scholes(f, ctx.strike, ctx.sqrt_v * ctx.sqrt_v, df, self.option_type);
+        let delta = df * (omega * cdf(omega * d1) + nu * sigma_f);
+        let gamma = df
+            * (phi / (f * ctx.sqrt_v)
+                + 2.0 * vanna * sigma_f
+                + volga * sigma_f * sigma_f
+                + nu * sigma_ff);
+        let decay = df * f * phi * sigma / (2.0 * t.sqrt());
+        let charm_decay =
+            df * (phi * d2 / (2.0 * t) - sigma_f * f * phi * (1.0 + d1 * d2) / (2.0 * t.sqrt()));
+
+        let amount = self.base_amount();
+        let business_days = business_days_to_next(&self.asset, market)?;
+        // Act/365 time whose variance the next `days` consume on the vol
+        // clock; `days / 365` without one.
+        let (valuation, expiry) = (market.valuation_date, self.basic_info.expiry_date);
+        let to_expiry = market.vol_time(valuation, expiry)?;
+        let elapsed = |days: i64| -> Result<f64> {
+            Ok(t * market.vol_time(valuation, valuation + Duration::days(days))? / to_expiry)
+        };
+        let (day, business) = (elapsed(1)?, elapsed(business_days)?);
+        let (base, quote) = (self.asset.frn_currency(), self.asset.dom_currency());
+        let in_base = |value: f64| CurrencyValue {
+            currency: base,
+            value: amount * value,
+        };
+        let in_quote = |value: f64| CurrencyValue {
+            currency: quote,
+            value: amount * value,
+        };
+        Ok(FXGreeks {
+            delta: in_base(delta),
+            gamma: in_base(gamma),
+            vega: in_quote(df * nu * 0.01),
+            vanna: in_base(df * (vanna + volga * sigma_f) * 0.01),
+            volga: in_quote(df * volga * 1.0e-4),
+            theta_calendar: in_quote(rate * value / 365.0 - decay * day),
+            theta_business: in_quote(
+                rate * value * business_days as f64 / 365.0 - decay * business,
+            ),
+            charm: in_base(rate * delta / 365.0 + charm_decay * day),
+            rho_domestic: in_quote((delta * f - value) * t * 1.0e-4),
+            rho_foreign: in_quote(-delta * f * t * 1.0e-4),
+            forward_points: in_quote(delta / self.asset.forward_points_converter()),
+        })
+    }
+
     fn direction_sign(&self) -> f64 {
         self.basic_info.direction as i8 as f64
     }
 }
 
+impl VannaVolgaTarget for FXVanillaOption {
+    fn expiry(&self) -> NaiveDate {
+        self.basic_info.expiry_date
+    }
+
+    fn flat_vol_value(
+        &self,
+        market: &FxMarketContext,
+        shift: &FxMarketShift,
+        sigma: f64,
+    ) -> Result<f64> {
+        let ctx = self.bs_context_under(market, shift)?;
+        let premium = black_scholes(
+            ctx.forward,
+            ctx.strike,
+            sigma * sigma * ctx.year_fraction,
+            ctx.discount,
+            self.option_type,
+        );
+        Ok(self.base_amount() * premium)
+    }
+}
+
 impl FXDerivatives for FXVanillaOption {
     /// Premium in the notional currency. Sign is adjusted for Buy / Sell —
     /// a buyer sees a negative PV (they owe premium), a seller positive.
@@ -177,29 +485,32 @@ impl FXDerivatives for FXVanillaOption {
         })
     }
 
-    /// Forward delta scaled by notional and direction, in the foreign
-    /// (base) currency. For a call: `Δ_fwd = N(d₁)`; for a put:
-    /// `Δ_fwd = N(d₁) − 1`. Multiply by signed notional to get the effective
-    /// base-currency exposure.
+    /// Forward delta of the held base-currency notional, in the foreign
+    /// (base) currency: [`FXVanillaOption::delta_in`] under
+    /// [`FXDeltaConvention::Forward`]. For a call: `Δ_fwd = N(d₁)`; for a
+    /// put: `Δ_fwd = N(d₁) − 1`, plus `ν·σ_F` under sticky delta.
     fn delta(&self, market: &FxMarketContext) -> Result<CurrencyValue> {
-        let ctx = self.bs_context(market)?;
-        let omega = self.option_type.omega();
-        let fwd_delta_per_unit = omega * cdf(omega * ctx.d1);
-        let delta = self.notional_amounts * self.direction_sign() * fwd_delta_per_unit;
-        Ok(CurrencyValue {
-            currency: self.asset.frn_currency(),
-            value: delta,
-        })
+        self.delta_in(market, FXDeltaConvention::Forward)
     }
 
     /// Black-Scholes gamma per 1 unit of spot, scaled by notional:
-    /// `Γ = DF_d · φ(d₁) / (F · σ · √T) × notional × direction_sign`.
+    /// `Γ = DF_d · φ(d₁) / (F · σ · √T) × notional × direction_sign`, plus
+    /// the vanna, volga and smile-curvature terms under sticky delta.
     fn gamma(&self, market: &FxMarketContext) -> Result<f64> {
         let ctx = self.bs_context(market)?;
         if ctx.sqrt_v <= 0.0 {
             return Ok(0.0);
         }
-        let gamma_per_unit = ctx.discount * pdf(ctx.d1) / (ctx.forward * ctx.sqrt_v);
+        let (sigma_f, sigma_ff) = self.smile_sensitivities(market, &ctx)?;
+        let d2 = ctx.d1 - ctx.sqrt_v;
+        let nu = ctx.forward * pdf(ctx.d1) * ctx.year_fraction.sqrt();
+        let vanna = -pdf(ctx.d1) * d2 / ctx.sigma;
+        let volga = nu * ctx.d1 * d2 / ctx.sigma;
+        let gamma_per_unit = ctx.discount
+            * (pdf(ctx.d1) / (ctx.forward * ctx.sqrt_v)
+                + 2.0 * vanna * sigma_f
+                + volga * sigma_f * sigma_f
+                + nu * sigma_ff);
         Ok(self.notional_amounts * self.direction_sign() * gamma_per_unit)
     }
 
@@ -222,24 +533,39 @@ impl FXDerivatives for FXVanillaOption {
         };
         Ok(self.direction_sign() * scale * vega_per_unit_base_dom / 100.0)
     }
+
+    fn greeks(&self, market: &FxMarketContext, method: GreekMethod) -> Result<FXGreeks> {
+        match method {
+            GreekMethod::Analytic => self.analytic_greeks(market),
+            GreekMethod::BumpAndReprice => {
+                FXGreeks::bumped(&self.asset, market, |shift| self.value_under(market, shift))
+            }
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{FXVanillaOption, OptionType, black_scholes};
+    use super::{FXPremiumQuote, FXVanillaOption, OptionType, SmileDynamics, black_scholes};
     use crate::derivatives::basic::{BasicInfo, Direction, Style};
-    use crate::derivatives::forex::basic::{FXDerivatives, FXUnderlying};
+    use crate::derivatives::forex::basic::{FXDerivatives, FXUnderlying, GreekMethod};
     use crate::error::Result;
-    use crate::markets::forex::market_context::FxMarketContext;
+    use crate::markets::forex::market_context::{FxMarketContext, FxMarketShift};
     use crate::markets::forex::quotes::forwardpoints::{FXForwardHelper, FXForwardQuote};
-    use crate::markets::forex::quotes::volsurface::{FXDeltaVolPillar, FXVolQuote, FXVolSurface};
+    use crate::markets::forex::quotes::volsurface::{
+        FXDeltaConvention, FXDeltaVolPillar, FXVolQuote, FXVolSurface,
+    };
     use crate::markets::termstructures::yieldcurve::{
         InterestRateQuoteEnum, StrippedCurve, YieldTermStructure,
     };
+    use crate::math::normal::cdf;
+    use crate::models::forex::vanna_volga::{VannaVolga, VannaVolgaTarget, VannaVolgaWeighting};
     use crate::time::calendars::Target;
     use crate::time::calendars::UnitedStates;
+    use crate::time::daycounters::DayCounters;
     use crate::time::daycounters::actual365fixed::Actual365Fixed;
     use crate::time::period::Period;
+    use crate::time::volclock::{VolClock, VolEvent};
     use chrono::NaiveDate;
     use iso_currency::Currency;
 
@@ -388,7 +714,8 @@ mod tests {
             notional_currency: Currency::from_code("EUR").unwrap(),
             notional_amounts: 1_000_000.0,
             strike: 1.2995,
-            volatility: 0.07748, // Expected mid vol
+            volatility: Some(0.07748), // Expected mid vol
+            smile_dynamics: SmileDynamics::StickyStrike,
         };
 
         let ctx = FxMarketContext::for_linear(
@@ -480,10 +807,10 @@ mod tests {
         // --- Vol surface: single 5Y pillar with Expected vol mids ---
         let surface = FXVolSurface::new(
             valuation_date,
-            vec![FXDeltaVolPillar {
-                expiry: expiry_date,
-                forward: 1.2376,
-                quotes: vec![
+            vec![FXDeltaVolPillar::new(
+                expiry_date.into(),
+                1.2376,
+                vec![
                     FXVolQuote::Atm(0.0769),
                     FXVolQuote::Put {
                         delta: 0.10,
@@ -502,10 +829,10 @@ mod tests {
                         vol: 0.093325,
                     },
                 ],
-            }],
+            )],
         )?;
 
-        // Interpolate vol at the deal strike from the surface.
+        // The option reads its vol at the deal strike off the surface.
         let sigma = surface.volatility(expiry_date, 1.2995)?;
 
         let option = FXVanillaOption {
@@ -521,7 +848,8 @@ mod tests {
             notional_currency: Currency::from_code("EUR").unwrap(),
             notional_amounts: 1_000_000.0,
             strike: 1.2995,
-            volatility: sigma,
+            volatility: None,
+            smile_dynamics: SmileDynamics::StickyStrike,
         };
 
         let ctx = FxMarketContext::new(
@@ -613,7 +941,8 @@ mod tests {
             notional_currency: Currency::from_code("EUR").unwrap(),
             notional_amounts: 1_000_000.0,
             strike: 1.2995,
-            volatility: 0.07748,
+            volatility: Some(0.07748),
+            smile_dynamics: SmileDynamics::StickyStrike,
         };
 
         let buy_call = make(OptionType::Call, Direction::Buy);
@@ -681,4 +1010,388 @@ mod tests {
         );
         Ok(())
     }
+
+    /// EURUSD with a flat 3.5% curve and a one-year call-skewed smile.
+    fn smile_market() -> Result<FxMarketContext> {
+        let valuation_date = NaiveDate::from_ymd_opt(2026, 4, 21).unwrap();
+        let expiry_date = NaiveDate::from_ymd_opt(2027, 4, 21).unwrap();
+        let pillar = |date: NaiveDate| StrippedCurve {
+            first_settle_date: valuation_date,
+            date,
+            market_rate: 0.035,
+            zero_rate: 0.035,
+            discount: 1.0,
+            source: InterestRateQuoteEnum::Swap,
+            hidden_pillar: false,
+        };
+        let yts = YieldTermStructure::new(
+            Box::new(Target),
+            Box::new(Actual365Fixed::default()),
+            valuation_date,
+            vec![
+                pillar(expiry_date),
+                pillar(NaiveDate::from_ymd_opt(2028, 4, 21).unwrap()),
+            ],
+        );
+        let forwards = FXForwardHelper::new(
+            valuation_date,
+            1.17,
+            vec![
+                FXForwardQuote {
+                    tenor: Period::SPOT,
+                    value: 0.0,
+                },
+                FXForwardQuote {
+                    tenor: Period::Years(1),
+                    value: 150.0,
+                },
+                FXForwardQuote {
+                    tenor: Period::Years(2),
+                    value: 300.0,
+                },
+            ],
+        );
+        let surface = FXVolSurface::new(
+            valuation_date,
+            vec![FXDeltaVolPillar::new(
+                expiry_date.into(),
+                1.185,
+                vec![
+                    FXVolQuote::Atm(0.075),
+                    FXVolQuote::Put {
+                        delta: 0.25,
+                        vol: 0.071,
+                    },
+                    FXVolQuote::Call {
+                        delta: 0.25,
+                        vol: 0.084,
+                    },
+                ],
+            )],
+        )?;
+        Ok(FxMarketContext::new(
+            valuation_date,
+            1.17,
+            (Currency::USD, Currency::EUR),
+            clone_yts(&yts),
+            clone_yts(&yts),
+            forwards,
+            surface,
+        ))
+    }
+
+    /// The closed-form report, smile terms included under both
+    /// dynamics, agrees with bump-and-reprice on every Greek.
+    #[test]
+    fn analytic_greeks_match_bump_and_reprice() -> Result<()> {
+        let ctx = smile_market()?;
+        let expiry_date = NaiveDate::from_ymd_opt(2027, 4, 21).unwrap();
+        for (option_type, smile_dynamics) in [
+            (OptionType::Call, SmileDynamics::StickyStrike),
+            (OptionType::Put, SmileDynamics::StickyDelta),
+        ] {
+            let option = FXVanillaOption {
+                basic_info: BasicInfo {
+                    trade_date: ctx.valuation_date,
+                    style: Style::FXCall,
+                    direction: Direction::Buy,
+                    expiry_date,
+                    delivery_date: expiry_date,
+                },
+                asset: FXUnderlying::EURUSD,
+                option_type,
+                notional_currency: Currency::EUR,
+                notional_amounts: 1_000_000.0,
+                strike: 1.2,
+                volatility: None,
+                smile_dynamics,
+            };
+            let analytic = option.greeks(&ctx, GreekMethod::Analytic)?;
+            let bumped = option.greeks(&ctx, GreekMethod::BumpAndReprice)?;
+            for (a, b) in [
+                (&analytic.delta, &bumped.delta),
+                (&analytic.gamma, &bumped.gamma),
+                (&analytic.vega, &bumped.vega),
+                (&analytic.vanna, &bumped.vanna),
+                (&analytic.volga, &bumped.volga),
+                (&analytic.theta_calendar, &bumped.theta_calendar),
+                (&analytic.theta_business, &bumped.theta_business),
+                (&analytic.charm, &bumped.charm),
+                (&analytic.rho_domestic, &bumped.rho_domestic),
+                (&analytic.rho_foreign, &bumped.rho_foreign),
+                (&analytic.forward_points, &bumped.forward_points),
+            ] {
+                assert_eq!(a.currency, b.currency);
+                // Theta and charm compare a one-day roll with the time
+                // derivative.
+                assert!(
+                    (a.value - b.value).abs() < 5e-3 * b.value.abs(),
+                    "{a:?} {b:?}"
+                );
+            }
+            assert_eq!(analytic.delta.currency, Currency::EUR);
+            assert_eq!(analytic.rho_domestic.currency, Currency::USD);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn vol_clock_theta_matches_roll() -> Result<()> {
+        let calendar_time = smile_market()?;
+        let mut ctx = smile_market()?;
+        let event = VolEvent {
+            name: "ECB".to_string(),
+            date: NaiveDate::from_ymd_opt(2026, 4, 22).unwrap(),
+            weight: 2.0,
+        };
+        let clock = VolClock::new(
+            Box::new(FXUnderlying::EURUSD.calendar()),
+            0.2,
+            0.5,
+            vec![event],
+        )?;
+        ctx.vol_surface = ctx.vol_surface.with_vol_clock(clock)?;
+        let expiry_date = NaiveDate::from_ymd_opt(2027, 4, 21).unwrap();
+        let option = FXVanillaOption {
+            basic_info: BasicInfo {
+                trade_date: ctx.valuation_date,
+                style: Style::FXCall,
+                direction: Direction::Buy,
+                expiry_date,
+                delivery_date: expiry_date,
+            },
+            asset: FXUnderlying::EURUSD,
+            option_type: OptionType::Call,
+            notional_currency: Currency::EUR,
+            notional_amounts: 1_000_000.0,
+            strike: 1.2,
+            volatility: None,
+            smile_dynamics: SmileDynamics::StickyStrike,
+        };
+        // Same pillar, same price; the event day decays faster.
+        let premium = option.mtm(&ctx)?.value;
+        assert!((premium - option.mtm(&calendar_time)?.value).abs() < 1e-8);
+        let analytic = option.greeks(&ctx, GreekMethod::Analytic)?;
+        let bumped = option.greeks(&ctx, GreekMethod::BumpAndReprice)?;
+        for (a, b) in [
+            (&analytic.theta_calendar, &bumped.theta_calendar),
+            (&analytic.theta_business, &bumped.theta_business),
+            (&analytic.charm, &bumped.charm),
+        ] {
+            // The event roll consumes three days of variance, so the
+            // one-day difference strays further from the derivative.
+            assert!(
+                (a.value - b.value).abs() < 1e-2 * b.value.abs(),
+                "{a:?} {b:?}"
+            );
+        }
+        let flat = option.greeks(&calendar_time, GreekMethod::Analytic)?;
+        assert!(analytic.theta_calendar.value < 2.0 * flat.theta_calendar.value);
+        Ok(())
+    }
+
+    /// Vanna–Volga hedges a pillar vanilla with itself, so it reprices the
+    /// smile there exactly, and lands near the smile in between.
+    #[test]
+    fn vanna_volga_reprices_pillar_vanillas() -> Result<()> {
+        let ctx = smile_market()?;
+        let expiry_date = NaiveDate::from_ymd_opt(2027, 4, 21).unwrap();
+        let asset = FXUnderlying::EURUSD;
+        let forward = ctx.forward_at(expiry_date, &asset.calendar())? / 1.0e4 + ctx.spot;
+        let discount = ctx.discount_d(expiry_date)?;
+        let pillars = ctx.vol_surface.delta_strikes(
+            expiry_date,
+            forward,
+            forward * discount / ctx.spot,
+            asset.delta_convention(1.0),
+            0.25,
+        )?;
+        let call = |strike: f64| FXVanillaOption {
+            basic_info: BasicInfo {
+                trade_date: ctx.valuation_date,
+                style: Style::FXCall,
+                direction: Direction::Buy,
+                expiry_date,
+                delivery_date: expiry_date,
+            },
+            asset: FXUnderlying::EURUSD,
+            option_type: OptionType::Call,
+            notional_currency: Currency::EUR,
+            notional_amounts: 1.0,
+            strike,
+            volatility: None,
+            smile_dynamics: SmileDynamics::StickyStrike,
+        };
+        let smile_value = |option: &FXVanillaOption| -> Result<f64> {
+            let sigma = option.volatility(&ctx)?;
+            option.flat_vol_value(&ctx, &FxMarketShift::default(), sigma)
+        };
+        for weighting in [
+            VannaVolgaWeighting::Full,
+            VannaVolgaWeighting::SurvivalProbability,
+        ] {
+            let engine = VannaVolga::new(weighting);
+            for (i, &(strike, _)) in pillars.iter().enumerate() {
+                let option = call(strike);
+                let price = engine.price(&option, &asset, &ctx)?;
+                assert_eq!(price.weight, 1.0);
+                assert!((price.hedge[i] - 1.0).abs() < 1e-4, "{price:?}");
+                assert!(
+                    (price.value - smile_value(&option)?).abs() < 1e-7,
+                    "{price:?}"
+                );
+            }
+            let between = call(0.5 * (pillars[1].0 + pillars[2].0));
+            let price = engine.price(&between, &asset, &ctx)?;
+            assert!(
+                (price.value - smile_value(&between)?).abs() < 1e-4,
+                "{price:?}"
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn delta_conventions_and_premium_quotes() -> Result<()> {
+        let ctx = smile_market()?;
+        let expiry_date = NaiveDate::from_ymd_opt(2027, 4, 21).unwrap();
+        let option = FXVanillaOption {
+            basic_info: BasicInfo {
+                trade_date: ctx.valuation_date,
+                style: Style::FXCall,
+                direction: Direction::Sell,
+                expiry_date,
+                delivery_date: expiry_date,
+            },
+            asset: FXUnderlying::EURUSD,
+            option_type: OptionType::Call,
+            notional_currency: Currency::USD,
+            notional_amounts: 1_230_000.0,
+            strike: 1.23,
+            volatility: Some(0.08),
+            smile_dynamics: SmileDynamics::StickyStrike,
+        };
+        let delta = |convention| -> Result<f64> { Ok(option.delta_in(&ctx, convention)?.value) };
+        let forward = delta(FXDeltaConvention::Forward)?;
+        // USD 1.23m at 1.23 is EUR 1m: the trait delta is the forward
+        // delta of the EUR amount, whichever currency the notional is in.
+        assert_eq!(option.delta(&ctx)?.value, forward);
+        let in_base = FXVanillaOption {
+            basic_info: BasicInfo {
+                trade_date: ctx.valuation_date,
+                style: Style::FXCall,
+                direction: Direction::Sell,
+                expiry_date,
+                delivery_date: expiry_date,
+            },
+            asset: FXUnderlying::EURUSD,
+            option_type: OptionType::Call,
+            notional_currency: Currency::EUR,
+            notional_amounts: 1.0e6,
+            strike: 1.23,
+            volatility: Some(0.08),
+            smile_dynamics: SmileDynamics::StickyStrike,
+        };
+        assert!((in_base.delta(&ctx)?.value - forward).abs() < 1e-6);
+        let forward_pa = delta(FXDeltaConvention::ForwardPremiumAdjusted)?;
+        // Short EUR 1m of calls: premium adjustment shrinks the short.
+        assert!(forward < 0.0 && forward_pa > forward);
+
+        // Premium-adjusted forward delta is (K/F)·N(d2) in Black–Scholes.
+        let asset = FXUnderlying::EURUSD;
+        let f = ctx.spot
+            + ctx.forward_at(expiry_date, &asset.calendar())? / asset.forward_points_converter();
+        let sqrt_v = 0.08;
+        let d2 = (f / 1.23).ln() / sqrt_v - 0.5 * sqrt_v;
+        assert!((forward_pa + 1.0e6 * 1.23 / f * cdf(d2)).abs() < 1e-6);
+
+        // Spot deltas carry the parity foreign discount DF_d·F/S.
+        let df_f = ctx.discount_d(expiry_date)? * f / ctx.spot;
+        assert!((delta(FXDeltaConvention::Spot)? - df_f * forward).abs() < 1e-6);
+        assert!((delta(FXDeltaConvention::SpotPremiumAdjusted)? - df_f * forward_pa).abs() < 1e-6);
+
+        let pips = option.premium_in(&ctx, FXPremiumQuote::DomesticPips)?;
+        let premium = pips / 1.0e4;
+        let dom_pct = option.premium_in(&ctx, FXPremiumQuote::DomesticPercent)?;
+        let frn_pct = option.premium_in(&ctx, FXPremiumQuote::ForeignPercent)?;
+        let frn_pips = option.premium_in(&ctx, FXPremiumQuote::ForeignPips)?;
+        assert!((dom_pct - 100.0 * premium / 1.23).abs() < 1e-12);
+        assert!((frn_pct - 100.0 * premium / ctx.spot).abs() < 1e-12);
+        assert!((frn_pips - frn_pct / 1.23 * 100.0).abs() < 1e-9);
+        // The adjustment is the forward premium in base units.
+        let mtm = option.mtm(&ctx)?.value;
+        assert!(
+            ((forward_pa - forward) * f * ctx.discount_d(expiry_date)? - mtm).abs() < 1e-6 * mtm
+        );
+        Ok(())
+    }
+
+    /// Without an override the option prices at the surface vol. Under
+    /// sticky delta, delta and gamma match central differences of the
+    /// forward premium with the smile sliding as `σ₀(K·F₀/F)`; under
+    /// sticky strike they are the flat-vol Black–Scholes Greeks.
+    #[test]
+    fn surface_vol_and_sticky_delta_greeks() -> Result<()> {
+        let ctx = smile_market()?;
+        let valuation_date = ctx.valuation_date;
+        let expiry_date = NaiveDate::from_ymd_opt(2027, 4, 21).unwrap();
+        let strike = 1.23;
+        let make = |volatility: Option<f64>, smile_dynamics| FXVanillaOption {
+            basic_info: BasicInfo {
+                trade_date: valuation_date,
+                style: Style::FXCall,
+                direction: Direction::Buy,
+                expiry_date,
+                delivery_date: expiry_date,
+            },
+            asset: FXUnderlying::EURUSD,
+            option_type: OptionType::Call,
+            notional_currency: Currency::EUR,
+            notional_amounts: 1_000_000.0,
+            strike,
+            volatility,
+            smile_dynamics,
+        };
+        let sigma = ctx.implied_vol(expiry_date, strike)?;
+        let sticky_strike = make(None, SmileDynamics::StickyStrike);
+        let flat = make(Some(sigma), SmileDynamics::StickyDelta);
+        assert_eq!(sticky_strike.mtm(&ctx)?.value, flat.mtm(&ctx)?.value);
+        assert_eq!(sticky_strike.delta(&ctx)?.value, flat.delta(&ctx)?.value);
+        assert_eq!(sticky_strike.gamma(&ctx)?, flat.gamma(&ctx)?);
+
+        // Undiscounted premium as the forward moves with the smile.
+        let asset = FXUnderlying::EURUSD;
+        let forward = ctx.spot
+            + ctx.forward_at(expiry_date, &asset.calendar())? / asset.forward_points_converter();
+        let t = Actual365Fixed::default().year_fraction(valuation_date, expiry_date)?;
+        let premium = |f: f64| -> Result<f64> {
+            let vol = ctx.implied_vol(expiry_date, strike * forward / f)?;
+            Ok(black_scholes(
+                f,
+                strike,
+                vol * vol * t,
+                1.0,
+                OptionType::Call,
+            ))
+        };
+        let h = 1.0e-3;
+        let (up, mid, down) = (
+            premium(forward + h)?,
+            premium(forward)?,
+            premium(forward - h)?,
+        );
+        let df = ctx.discount_d(expiry_date)?;
+        let sticky_delta = make(None, SmileDynamics::StickyDelta);
+        let delta = sticky_delta.delta(&ctx)?.value / 1.0e6;
+        let gamma = sticky_delta.gamma(&ctx)? / 1.0e6;
+        assert!((delta - (up - down) / (2.0 * h)).abs() < 1e-5, "{delta}");
+        assert!(
+            (gamma - df * (up - 2.0 * mid + down) / (h * h)).abs() < 1e-3 * gamma.abs(),
+            "{gamma}"
+        );
+        // Call skew: vols rise with strike, so sliding the smile up with
+        // the forward lowers the vol at K and the delta.
+        assert!(delta < sticky_strike.delta(&ctx)?.value / 1.0e6);
+        Ok(())
+    }
 }
@@ -0,0 +1,299 @@
+//! Zero-coupon and year-on-year inflation swaps, projected off a
+//! [`ZeroInflationTermStructure`] and discounted on a nominal
+//! [`YieldCurve`].
+//!
+//! `Direction::Buy` receives the inflation leg (buys inflation
+//! protection); `Sell` pays it. `I(·)` is the index's lagged,
+//! interpolated reference value, published where available and projected
+//! otherwise.
+//!
+//! ```text
+//!   ZC:    N · [I(T)/I(S) − 1]             vs  N · [(1 + K)^n − 1]     paid at T
+//!   YoY:   N · [I(T_i)/I(T_{i−1}) − 1]     vs  N · K                   paid at each T_i
+//! ```
+//!
+//! Year-on-year ratios are taken as ratios of forward index levels,
+//! without the convexity adjustment a stochastic inflation model would
+//! add.
+
+use chrono::NaiveDate;
+use iso_currency::Currency;
+
+use crate::derivatives::basic::Direction;
+use crate::derivatives::forex::basic::CurrencyValue;
+use crate::error::{Error, Result};
+use crate::markets::inflation::inflationindex::InflationIndex;
+use crate::markets::termstructures::inflationcurve::ZeroInflationTermStructure;
+use crate::markets::termstructures::yieldcurve::{InterpolationMethodEnum, YieldCurve};
+use crate::time::period::Period;
+
+#[derive(Debug)]
+pub struct ZeroCouponInflationSwap {
+    pub direction: Direction,
+    pub notional: f64,
+    pub currency: Currency,
+    pub fixed_rate: f64,
+    pub start_date: NaiveDate,
+    pub maturity_date: NaiveDate,
+    /// Fixed-leg compounding period in years.
+    pub years: f64,
+}
+
+impl ZeroCouponInflationSwap {
+    /// Swap starting on `start_date` running for `tenor` (whole months or
+    /// years).
+    pub fn new(
+        direction: Direction,
+        notional: f64,
+        currency: Currency,
+        fixed_rate: f64,
+        start_date: NaiveDate,
+        tenor: Period,
+    ) -> Result<Self> {
+        let years = tenor.years()?;
+        Ok(Self {
+            direction,
+            notional,
+            currency,
+            fixed_rate,
+            start_date,
+            maturity_date: (start_date + tenor)?,
+            years,
+        })
+    }
+
+    /// Forward index ratio `I(T)/I(S)`.
+    pub fn index_ratio(
+        &self,
+        index: &InflationIndex,
+        inflation: &ZeroInflationTermStructure,
+    ) -> Result<f64> {
+        Ok(index.reference_value(self.maturity_date, Some(inflation))?
+            / index.reference_value(self.start_date, Some(inflation))?)
+    }
+
+    /// Fixed rate at which the swap is worth zero.
+    pub fn fair_rate(
+        &self,
+        index: &InflationIndex,
+        inflation: &ZeroInflationTermStructure,
+    ) -> Result<f64> {
+        Ok(self.index_ratio(index, inflation)?.powf(1.0 / self.years) - 1.0)
+    }
+
+    pub fn npv(
+        &self,
+        index: &InflationIndex,
+        inflation: &ZeroInflationTermStructure,
+        nominal: &dyn YieldCurve,
+    ) -> Result<CurrencyValue> {
+        if self.maturity_date <= nominal.valuation_date() {
+            return Ok(CurrencyValue {
+                currency: self.currency,
+                value: 0.0,
+            });
+        }
+        let inflation_leg = self.index_ratio(index, inflation)? - 1.0;
+        let fixed_leg = (1.0 + self.fixed_rate).powf(self.years) - 1.0;
+        let df = nominal.discount(
+            self.maturity_date,
+            &InterpolationMethodEnum::PiecewiseLinearContinuous,
+        )?;
+        Ok(CurrencyValue {
+            currency: self.currency,
+            value: self.direction as i8 as f64 * self.notional * (inflation_leg - fixed_leg) * df,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct YearOnYearInflationSwap {
+    pub direction: Direction,
+    pub notional: f64,
+    pub currency: Currency,
+    pub fixed_rate: f64,
+    pub start_date: NaiveDate,
+    /// Annual period end (and pay) dates.
+    pub payment_dates: Vec<NaiveDate>,
+}
+
+impl YearOnYearInflationSwap {
+    pub fn new(
+        direction: Direction,
+        notional: f64,
+        currency: Currency,
+        fixed_rate: f64,
+        start_date: NaiveDate,
+        years: u32,
+    ) -> Result<Self> {
+        let payment_dates = (1..=years)
+            .map(|y| start_date + Period::Years(y))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self {
+            direction,
+            notional,
+            currency,
+            fixed_rate,
+            start_date,
+            payment_dates,
+        })
+    }
+
+    /// `(pay date, forward YoY rate, nominal discount factor)` for each
+    /// period still to pay.
+    fn live_periods(
+        &self,
+        index: &InflationIndex,
+        inflation: &ZeroInflationTermStructure,
+        nominal: &dyn YieldCurve,
+    ) -> Result<Vec<(NaiveDate, f64, f64)>> {
+        let method = &InterpolationMethodEnum::PiecewiseLinearContinuous;
+        let mut period_start = self.start_date;
+        let mut periods = Vec::new();
+        for &pay in &self.payment_dates {
+            if pay > nominal.valuation_date() {
+                let rate = index.reference_value(pay, Some(inflation))?
+                    / index.reference_value(period_start, Some(inflation))?
+                    - 1.0;
+                periods.push((pay, rate, nominal.discount(pay, method)?));
+            }
+            period_start = pay;
+        }
+        Ok(periods)
+    }
+
+    /// Fixed rate at which the remaining periods are worth zero.
+    pub fn fair_rate(
+        &self,
+        index: &InflationIndex,
+        inflation: &ZeroInflationTermStructure,
+        nominal: &dyn YieldCurve,
+    ) -> Result<f64> {
+        let periods = self.live_periods(index, inflation, nominal)?;
+        let annuity: f64 = periods.iter().map(|p| p.2).sum();
+        if annuity == 0.0 {
+            return Err(Error::InvalidData(
+                "year-on-year swap has no remaining periods".to_string(),
+            ));
+        }
+        Ok(periods.iter().map(|p| p.1 * p.2).sum::<f64>() / annuity)
+    }
+
+    pub fn npv(
+        &self,
+        index: &InflationIndex,
+        inflation: &ZeroInflationTermStructure,
+        nominal: &dyn YieldCurve,
+    ) -> Result<CurrencyValue> {
+        let value: f64 = self
+            .live_periods(index, inflation, nominal)?
+            .iter()
+            .map(|(_, rate, df)| (rate - self.fixed_rate) * df)
+            .sum();
+        Ok(CurrencyValue {
+            currency: self.currency,
+            value: self.direction as i8 as f64 * self.notional * value,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::markets::inflation::inflationindex::InflationIndexEnum;
+    use crate::markets::termstructures::inflationcurve::ZeroCouponInflationSwapHelper;
+    use crate::markets::termstructures::yieldcurve::{YieldTermStructure, zero_rate_curve};
+
+    fn d(y: i32, m: u32, dd: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, dd).unwrap()
+    }
+
+    fn nominal(valuation_date: NaiveDate) -> YieldTermStructure {
+        zero_rate_curve(
+            valuation_date,
+            &[(d(2027, 6, 1), 0.04), (d(2056, 6, 1), 0.045)],
+        )
+    }
+
+    fn rpi() -> InflationIndex {
+        let mut index = InflationIndex::from_enum(InflationIndexEnum::UKRPI);
+        index.add_fixing(d(2026, 3, 1), 402.5);
+        index.add_fixing(d(2026, 4, 1), 404.1);
+        index
+    }
+
+    /// A ZC swap struck at its bootstrap quote is worth zero; off-market
+    /// strikes are worth the discounted fixed-leg difference.
+    #[test]
+    fn zero_coupon_swap_at_market_is_flat() -> Result<()> {
+        let valuation_date = d(2026, 6, 15);
+        let index = rpi();
+        let quotes = [(2, 0.034), (5, 0.032), (10, 0.031)];
+        let helpers: Vec<_> = quotes
+            .iter()
+            .map(|&(y, rate)| ZeroCouponInflationSwapHelper {
+                tenor: Period::Years(y),
+                rate,
+            })
+            .collect();
+        let curve = ZeroInflationTermStructure::bootstrap(valuation_date, &index, &helpers, None)?;
+        let yts = nominal(valuation_date);
+        for &(y, rate) in &quotes {
+            let swap = ZeroCouponInflationSwap::new(
+                Direction::Buy,
+                1.0e7,
+                Currency::GBP,
+                rate,
+                valuation_date,
+                Period::Years(y),
+            )?;
+            assert!(swap.npv(&index, &curve, &yts)?.value.abs() < 1e-6);
+            assert!((swap.fair_rate(&index, &curve)? - rate).abs() < 1e-12);
+
+            let cheap = ZeroCouponInflationSwap {
+                fixed_rate: rate - 0.001,
+                ..swap
+            };
+            let df = yts.discount(
+                cheap.maturity_date,
+                &InterpolationMethodEnum::PiecewiseLinearContinuous,
+            )?;
+            let expected =
+                1.0e7 * ((1.0 + rate).powf(y as f64) - (1.0 + rate - 0.001).powf(y as f64)) * df;
+            assert!((cheap.npv(&index, &curve, &yts)?.value - expected).abs() < 1e-4);
+        }
+        Ok(())
+    }
+
+    /// On a flat curve with flat index reads every YoY period accrues the
+    /// zero rate, so the YoY fair rate equals it; paying and receiving
+    /// offset.
+    #[test]
+    fn year_on_year_on_flat_curve() -> Result<()> {
+        let valuation_date = d(2026, 6, 15);
+        let index = rpi();
+        let curve = ZeroInflationTermStructure::new(
+            d(2026, 4, 1),
+            404.1,
+            vec![(d(2027, 4, 1), 0.03)],
+            None,
+        );
+        let yts = nominal(valuation_date);
+        // Starts forward so no period references published fixings.
+        let start = d(2027, 6, 15);
+        let receive =
+            YearOnYearInflationSwap::new(Direction::Buy, 1.0e7, Currency::GBP, 0.025, start, 5)?;
+        assert!((receive.fair_rate(&index, &curve, &yts)? - 0.03).abs() < 1e-12);
+        let pay = YearOnYearInflationSwap {
+            direction: Direction::Sell,
+            ..YearOnYearInflationSwap::new(Direction::Buy, 1.0e7, Currency::GBP, 0.025, start, 5)?
+        };
+        let (r, p) = (
+            receive.npv(&index, &curve, &yts)?.value,
+            pay.npv(&index, &curve, &yts)?.value,
+        );
+        assert!(r > 0.0);
+        assert!((r + p).abs() < 1e-8);
+        Ok(())
+    }
+}
@@ -1,3 +1,8 @@
 pub mod basic;
+pub mod bond;
+pub mod bond_futures;
 pub mod cap;
+pub mod cms;
+pub mod futures;
+pub mod structured;
 pub mod swap;
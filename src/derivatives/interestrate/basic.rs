@@ -1,10 +1,13 @@
 //! Shared IR-derivatives types: the `IRDerivatives` trait (the IR analogue of
-//! `FXDerivatives`), plus the two small enums that classify interest-rate
-//! option contracts (cap vs floor, forward-looking vs backward-looking RFR).
+//! `FXDerivatives`), the cash-flow rows it reports, plus the two small enums
+//! that classify interest-rate option contracts (cap vs floor,
+//! forward-looking vs backward-looking RFR).
 
 use crate::derivatives::forex::basic::CurrencyValue;
-use crate::error::Result;
+use crate::derivatives::interestrate::swap::InterestRateSchedulePeriod;
+use crate::error::{Error, Result};
 use crate::markets::interestrate::market_context::IrMarketContext;
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 
 /// Whether the option is a cap (call on rate) or a floor (put on rate).
@@ -86,6 +89,139 @@ pub const DEFAULT_RATE_SHIFT_BP: f64 = 10.0;
 /// convention for normal-vol Greeks.
 pub const DEFAULT_VOL_SHIFT_BP: f64 = 1.0;
 
+/// Whether a cash flow's amount is contractually known or projected off
+/// the market.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum CashflowKind {
+    Fixed,
+    Projected,
+}
+
+impl CashflowKind {
+    /// Classification of a flow fixing on `period`'s reset date: `Fixed`
+    /// once the reset is on or before `valuation_date`, else `Projected`.
+    /// Returns the reset date as the fixing date.
+    pub fn of_fixing(
+        period: &InterestRateSchedulePeriod,
+        valuation_date: NaiveDate,
+    ) -> (Self, Option<NaiveDate>) {
+        let kind = if period.reset_date <= valuation_date {
+            CashflowKind::Fixed
+        } else {
+            CashflowKind::Projected
+        };
+        (kind, Some(period.reset_date))
+    }
+}
+
+/// One dated flow of an IR trade on the valuation date's market. Amounts
+/// are signed from the holder's side (received positive) and
+/// `present_value = amount · discount`. Option-like flows carry their
+/// expected payoff at the pay date, with `rate` the projected fixing.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct InterestRateCashflow {
+    pub kind: CashflowKind,
+    /// Index fixing the amount depends on; `None` for fixed-rate coupons
+    /// and principal.
+    pub fixing_date: Option<NaiveDate>,
+    pub accrual_start_date: Option<NaiveDate>,
+    pub accrual_end_date: Option<NaiveDate>,
+    pub pay_date: NaiveDate,
+    pub rate: Option<f64>,
+    pub notional: f64,
+    pub amount: f64,
+    pub discount: f64,
+    pub present_value: f64,
+}
+
+impl InterestRateCashflow {
+    /// Coupon accruing over `period`.
+    pub(crate) fn coupon(
+        kind: CashflowKind,
+        fixing_date: Option<NaiveDate>,
+        period: &InterestRateSchedulePeriod,
+        rate: f64,
+        notional: f64,
+        amount: f64,
+        discount: f64,
+    ) -> Self {
+        Self {
+            kind,
+            fixing_date,
+            accrual_start_date: Some(period.accrual_start_date),
+            accrual_end_date: Some(period.accrual_end_date),
+            pay_date: period.pay_date,
+            rate: Some(rate),
+            notional,
+            amount,
+            discount,
+            present_value: amount * discount,
+        }
+    }
+
+    /// Flow with no accrual period: principal, margin or premium.
+    pub(crate) fn single(
+        kind: CashflowKind,
+        pay_date: NaiveDate,
+        notional: f64,
+        amount: f64,
+        discount: f64,
+    ) -> Self {
+        Self {
+            kind,
+            fixing_date: None,
+            accrual_start_date: None,
+            accrual_end_date: None,
+            pay_date,
+            rate: None,
+            notional,
+            amount,
+            discount,
+            present_value: amount * discount,
+        }
+    }
+}
+
+/// Column order of [`cashflows_to_csv`].
+pub const CASHFLOW_CSV_HEADER: &str = "kind,fixing_date,accrual_start_date,accrual_end_date,\
+pay_date,rate,notional,amount,discount,present_value";
+
+/// Cash flows as CSV with a [`CASHFLOW_CSV_HEADER`] line: ISO dates,
+/// shortest round-trip numbers, empty cells for missing values.
+pub fn cashflows_to_csv(flows: &[InterestRateCashflow]) -> String {
+    fn cell<T: std::fmt::Display>(value: Option<T>) -> String {
+        value.map_or_else(String::new, |v| v.to_string())
+    }
+    let mut csv = String::from(CASHFLOW_CSV_HEADER);
+    csv.push('\n');
+    for cf in flows {
+        let kind = match cf.kind {
+            CashflowKind::Fixed => "Fixed",
+            CashflowKind::Projected => "Projected",
+        };
+        csv.push_str(&format!(
+            "{},{},{},{},{},{},{},{},{},{}\n",
+            kind,
+            cell(cf.fixing_date),
+            cell(cf.accrual_start_date),
+            cell(cf.accrual_end_date),
+            cf.pay_date,
+            cell(cf.rate),
+            cf.notional,
+            cf.amount,
+            cf.discount,
+            cf.present_value,
+        ));
+    }
+    csv
+}
+
+/// Cash flows as a JSON array of [`InterestRateCashflow`] objects.
+pub fn cashflows_to_json(flows: &[InterestRateCashflow]) -> Result<String> {
+    serde_json::to_string(flows)
+        .map_err(|e| Error::InvalidData(format!("cash flows do not serialise: {}", e)))
+}
+
 /// Market-aware trait for IR-option pricing with bump-and-reprice
 /// Greeks. Inputs come through a single [`IrMarketContext`] bundling
 /// the discount curve and (where needed) the caplet vol surface —
@@ -124,6 +260,15 @@ pub trait IRDerivatives {
     /// [`DEFAULT_VOL_SHIFT_BP`] (1bp) for the conventional per-1bp number.
     fn vega(&self, market: &IrMarketContext, vol_shift_bp: f64) -> Result<f64>;
 
+    /// Dated flows behind [`IRDerivatives::mtm`]: their present values
+    /// sum to the PV. Defaults to an error so implementors outside the
+    /// crate keep compiling until they report a ladder.
+    fn cashflows(&self, _market: &IrMarketContext) -> Result<Vec<InterestRateCashflow>> {
+        Err(Error::InvalidData(
+            "cash-flow ladder not implemented for this trade".to_string(),
+        ))
+    }
+
     /// Modified duration = −DV01 · 1e4 / PV. Returns `0.0` if PV is
     /// effectively zero. Always derived from the per-1bp DV01 regardless of
     /// the bump sizes chosen for gamma or vega.
@@ -139,7 +284,11 @@ pub trait IRDerivatives {
 
 #[cfg(test)]
 mod tests {
-    use super::{CapStyle, caplet_total_variance};
+    use super::{
+        CASHFLOW_CSV_HEADER, CapStyle, CashflowKind, InterestRateCashflow, caplet_total_variance,
+        cashflows_to_csv,
+    };
+    use chrono::NaiveDate;
 
     #[test]
     fn forward_variance_is_linear_in_time() {
@@ -168,6 +317,21 @@ mod tests {
         assert!((v - expected).abs() < 1e-15);
     }
 
+    #[test]
+    fn cashflow_csv_leaves_missing_cells_empty() {
+        let pay_date = NaiveDate::from_ymd_opt(2030, 1, 15).unwrap();
+        let principal =
+            InterestRateCashflow::single(CashflowKind::Fixed, pay_date, 1.0e6, -1.0e6, 0.5);
+        let csv = cashflows_to_csv(&[principal]);
+        let mut lines = csv.lines();
+        assert_eq!(lines.next(), Some(CASHFLOW_CSV_HEADER));
+        assert_eq!(
+            lines.next(),
+            Some("Fixed,,,,2030-01-15,,1000000,-1000000,0.5,-500000")
+        );
+        assert_eq!(lines.next(), None);
+    }
+
     #[test]
     fn expired_variance_is_zero() {
         assert_eq!(
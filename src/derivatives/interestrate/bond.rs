@@ -0,0 +1,858 @@
+//! Fixed-coupon bonds, floating-rate notes and zero-coupon bonds built
+//! from an accrual schedule.
+//!
+//! Prices are quoted **per 100 of notional**; `dirty = clean + accrued`.
+//! All price functions take a settlement date and only count cash flows
+//! the buyer at that date receives:
+//!
+//! * flows paying on or before settlement are gone;
+//! * inside the ex-coupon window (`ex_coupon_days` calendar days before a
+//!   coupon's pay date) the running coupon belongs to the seller, and
+//!   accrued interest turns negative — the buyer is compensated for the
+//!   days from settlement to the period end.
+//!
+//! Coupon accruals use either a plain [`DayCounters`] or
+//! ACT/ACT ICMA, which measures each coupon period against its notional
+//! regular periods (rolled back from the period end), so a regular coupon
+//! is exactly `rate / frequency` and short or long stubs are prorated.
+//!
+//! Floating-rate notes project each coupon as `(P(s)/P(e) − 1)/τ +
+//! spread` off a supplied curve; the running coupon can be pinned with
+//! `current_fixing`. Curve-based measures (Z-spread, asset-swap spread,
+//! [`IRDerivatives`]) project and discount on the same curve, which may
+//! be any [`YieldCurve`] — bootstrapped or parametric.
+//!
+//! ```text
+//!   yield:      P_dirty = 100/N · Σ CF_i · DF_y(t_i),   t_i = dc(settle, pay_i)
+//!   Z-spread:   P_dirty = 100/N · Σ CF_i · P(pay_i)·e^{−z·t_i} / (P(settle)·e^{−z·t_s})
+//!   par-par ASW s:       P_curve − P_market = 100 · s · Σ τ_i · P(pay_i)/P(settle)
+//! ```
+
+use crate::derivatives::forex::basic::CurrencyValue;
+use crate::derivatives::interestrate::basic::{
+    CashflowKind, IRDerivatives, InterestRateCashflow, RateShiftMode,
+};
+use crate::derivatives::interestrate::swap::InterestRateSchedulePeriod;
+use crate::error::{Error, Result};
+use crate::markets::interestrate::market_context::IrMarketContext;
+use crate::markets::termstructures::yieldcurve::{InterpolationMethodEnum, YieldCurve};
+use crate::time::daycounters::DayCounters;
+use crate::time::frequency::Frequency;
+use chrono::{Months, NaiveDate};
+use iso_currency::Currency;
+use roots::{SimpleConvergency, find_root_brent};
+
+/// Coupon type.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum BondType {
+    Fixed {
+        coupon: f64,
+    },
+    /// Floating-rate note paying projected index + `spread`. If
+    /// `current_fixing` is set it replaces the projection for the period
+    /// running at settlement.
+    Floating {
+        spread: f64,
+        current_fixing: Option<f64>,
+    },
+    ZeroCoupon,
+}
+
+/// How coupon accrual fractions are measured.
+#[derive(Debug)]
+pub enum AccrualBasis {
+    DayCounter(Box<dyn DayCounters>),
+    /// ACT/ACT ICMA: days over days in the notional regular period, over
+    /// the coupon frequency.
+    ActualActualIcma,
+}
+
+/// Yield compounding convention.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Compounding {
+    /// `DF = 1 / (1 + y·t)`.
+    Simple,
+    /// `DF = (1 + y/n)^(−n·t)`.
+    Compounded { per_year: u32 },
+    /// `DF = e^(−y·t)`.
+    Continuous,
+}
+
+impl Compounding {
+    fn discount(&self, y: f64, t: f64) -> f64 {
+        match *self {
+            Compounding::Simple => 1.0 / (1.0 + y * t),
+            Compounding::Compounded { per_year } => {
+                let n = per_year as f64;
+                (1.0 + y / n).powf(-n * t)
+            }
+            Compounding::Continuous => (-y * t).exp(),
+        }
+    }
+
+    /// `(∂DF/∂y, ∂²DF/∂y²)`.
+    fn discount_derivatives(&self, y: f64, t: f64) -> (f64, f64) {
+        let df = self.discount(y, t);
+        match *self {
+            Compounding::Simple => (-t * df * df, 2.0 * t * t * df * df * df),
+            Compounding::Compounded { per_year } => {
+                let n = per_year as f64;
+                let g = 1.0 + y / n;
+                (-t * df / g, t * (t + 1.0 / n) * df / (g * g))
+            }
+            Compounding::Continuous => (-t * df, t * t * df),
+        }
+    }
+}
+
+/// Day count and compounding used to turn a yield into discount factors.
+#[derive(Debug)]
+pub struct YieldConvention {
+    pub day_counter: Box<dyn DayCounters>,
+    pub compounding: Compounding,
+}
+
+/// One cash flow received by the holder.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct BondCashflow {
+    pub pay_date: NaiveDate,
+    /// Coupon rate (zero for principal).
+    pub rate: f64,
+    /// Amount in currency.
+    pub amount: f64,
+    pub is_principal: bool,
+}
+
+/// Yield-based risk measures. Durations and convexity are in years
+/// (years²); `dv01` is the currency gain for a 1bp *fall* in yield.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct BondRisk {
+    pub macaulay_duration: f64,
+    pub modified_duration: f64,
+    pub convexity: f64,
+    pub dv01: f64,
+}
+
+#[derive(Debug)]
+pub struct Bond {
+    pub bond_type: BondType,
+    pub notional: f64,
+    /// Principal repaid at maturity as a fraction of notional.
+    pub redemption: f64,
+    pub currency: Currency,
+    pub frequency: Frequency,
+    pub schedule: Vec<InterestRateSchedulePeriod>,
+    pub accrual: AccrualBasis,
+    /// Calendar days before a pay date from which the coupon trades ex.
+    pub ex_coupon_days: i64,
+}
+
+impl Bond {
+    pub fn new(
+        bond_type: BondType,
+        notional: f64,
+        currency: Currency,
+        frequency: Frequency,
+        schedule: Vec<InterestRateSchedulePeriod>,
+        accrual: AccrualBasis,
+    ) -> Self {
+        Self {
+            bond_type,
+            notional,
+            redemption: 1.0,
+            currency,
+            frequency,
+            schedule,
+            accrual,
+            ex_coupon_days: 0,
+        }
+    }
+
+    /// Zero-coupon bond: a single accrual period from `issue` to
+    /// `maturity`, principal paid at maturity.
+    pub fn zero_coupon(
+        notional: f64,
+        currency: Currency,
+        issue: NaiveDate,
+        maturity: NaiveDate,
+        accrual: AccrualBasis,
+    ) -> Self {
+        let period =
+            InterestRateSchedulePeriod::new(issue, maturity, maturity, issue, 0.0, notional);
+        Self::new(
+            BondType::ZeroCoupon,
+            notional,
+            currency,
+            Frequency::Once,
+            vec![period],
+            accrual,
+        )
+    }
+
+    pub fn with_ex_coupon_days(mut self, days: i64) -> Self {
+        self.ex_coupon_days = days;
+        self
+    }
+
+    pub fn with_redemption(mut self, redemption: f64) -> Self {
+        self.redemption = redemption;
+        self
+    }
+
+    pub fn maturity_date(&self) -> Result<NaiveDate> {
+        self.schedule
+            .last()
+            .map(|p| p.pay_date)
+            .ok_or_else(|| Error::InvalidData("bond has an empty schedule".to_string()))
+    }
+
+    /// Accrual fraction for `[from, to]` inside `period`.
+    fn accrual_fraction(
+        &self,
+        period: &InterestRateSchedulePeriod,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<f64> {
+        match &self.accrual {
+            AccrualBasis::DayCounter(dc) => dc.year_fraction(from, to),
+            AccrualBasis::ActualActualIcma => {
+                let n = periods_per_year(&self.frequency)?;
+                let months = Months::new(12 / n);
+                let mut fraction = 0.0;
+                let mut b = period.accrual_end_date;
+                while b > from {
+                    let a = b.checked_sub_months(months).ok_or_else(|| {
+                        Error::InvalidData("ICMA reference period out of range".to_string())
+                    })?;
+                    let lo = from.max(a);
+                    let hi = to.min(b);
+                    if hi > lo {
+                        fraction += (hi - lo).num_days() as f64 / (b - a).num_days() as f64;
+                    }
+                    b = a;
+                }
+                Ok(fraction / n as f64)
+            }
+        }
+    }
+
+    /// Coupon rate for `period`. Floating rates need `projection` unless
+    /// the period is the running one and `current_fixing` is set; the
+    /// projection curve is read under a parallel `shift_bp` zero shift.
+    fn period_rate(
+        &self,
+        period: &InterestRateSchedulePeriod,
+        settle: NaiveDate,
+        projection: Option<&dyn YieldCurve>,
+        shift_bp: f64,
+    ) -> Result<f64> {
+        match self.bond_type {
+            BondType::Fixed { coupon } => Ok(coupon),
+            BondType::ZeroCoupon => Ok(0.0),
+            BondType::Floating {
+                spread,
+                current_fixing,
+            } => {
+                if period.accrual_start_date <= settle
+                    && let Some(fixing) = current_fixing
+                {
+                    return Ok(fixing + spread);
+                }
+                let yts = projection.ok_or_else(|| {
+                    Error::InvalidData("floating-rate note needs a projection curve".to_string())
+                })?;
+                let method = &InterpolationMethodEnum::PiecewiseLinearContinuous;
+                let tau = self.accrual_fraction(
+                    period,
+                    period.accrual_start_date,
+                    period.accrual_end_date,
+                )?;
+                let df_start = yts.shifted_discount(period.accrual_start_date, method, shift_bp)?;
+                let df_end = yts.shifted_discount(period.accrual_end_date, method, shift_bp)?;
+                Ok((df_start / df_end - 1.0) / tau + spread)
+            }
+        }
+    }
+
+    fn is_ex_coupon(&self, period: &InterestRateSchedulePeriod, settle: NaiveDate) -> bool {
+        self.ex_coupon_days > 0
+            && settle < period.pay_date
+            && (period.pay_date - settle).num_days() <= self.ex_coupon_days
+    }
+
+    /// Cash flows received by a buyer settling on `settle`.
+    pub fn cashflows(
+        &self,
+        settle: NaiveDate,
+        projection: Option<&dyn YieldCurve>,
+    ) -> Result<Vec<BondCashflow>> {
+        self.shifted_cashflows(settle, projection, 0.0)
+    }
+
+    fn shifted_cashflows(
+        &self,
+        settle: NaiveDate,
+        projection: Option<&dyn YieldCurve>,
+        shift_bp: f64,
+    ) -> Result<Vec<BondCashflow>> {
+        let mut flows = Vec::new();
+        for period in &self.schedule {
+            if period.pay_date <= settle || self.bond_type == BondType::ZeroCoupon {
+                continue;
+            }
+            if self.is_ex_coupon(period, settle) {
+                continue;
+            }
+            let rate = self.period_rate(period, settle, projection, shift_bp)?;
+            let fraction =
+                self.accrual_fraction(period, period.accrual_start_date, period.accrual_end_date)?;
+            flows.push(BondCashflow {
+                pay_date: period.pay_date,
+                rate,
+                amount: rate * fraction * self.notional,
+                is_principal: false,
+            });
+        }
+        let maturity = self.maturity_date()?;
+        if maturity > settle {
+            flows.push(BondCashflow {
+                pay_date: maturity,
+                rate: 0.0,
+                amount: self.redemption * self.notional,
+                is_principal: true,
+            });
+        }
+        Ok(flows)
+    }
+
+    /// Accrued interest per 100 at `settle`; negative inside the
+    /// ex-coupon window.
+    pub fn accrued_interest(
+        &self,
+        settle: NaiveDate,
+        projection: Option<&dyn YieldCurve>,
+    ) -> Result<f64> {
+        if self.bond_type == BondType::ZeroCoupon {
+            return Ok(0.0);
+        }
+        let Some(period) = self
+            .schedule
+            .iter()
+            .find(|p| p.accrual_start_date <= settle && settle < p.accrual_end_date)
+        else {
+            return Ok(0.0);
+        };
+        let rate = self.period_rate(period, settle, projection, 0.0)?;
+        let accrued = if self.is_ex_coupon(period, settle) {
+            -rate * self.accrual_fraction(period, settle, period.accrual_end_date)?
+        } else {
+            rate * self.accrual_fraction(period, period.accrual_start_date, settle)?
+        };
+        Ok(100.0 * accrued)
+    }
+
+    /// Dirty price per 100 from a yield.
+    pub fn dirty_price_from_yield(
+        &self,
+        settle: NaiveDate,
+        yield_rate: f64,
+        convention: &YieldConvention,
+        projection: Option<&dyn YieldCurve>,
+    ) -> Result<f64> {
+        let mut pv = 0.0;
+        for cf in self.cashflows(settle, projection)? {
+            let t = convention.day_counter.year_fraction(settle, cf.pay_date)?;
+            pv += cf.amount * convention.compounding.discount(yield_rate, t);
+        }
+        Ok(100.0 * pv / self.notional)
+    }
+
+    /// Clean price per 100 from a yield.
+    pub fn clean_price_from_yield(
+        &self,
+        settle: NaiveDate,
+        yield_rate: f64,
+        convention: &YieldConvention,
+        projection: Option<&dyn YieldCurve>,
+    ) -> Result<f64> {
+        Ok(
+            self.dirty_price_from_yield(settle, yield_rate, convention, projection)?
+                - self.accrued_interest(settle, projection)?,
+        )
+    }
+
+    /// Yield to maturity reproducing `clean_price` (per 100).
+    pub fn yield_to_maturity(
+        &self,
+        settle: NaiveDate,
+        clean_price: f64,
+        convention: &YieldConvention,
+        projection: Option<&dyn YieldCurve>,
+    ) -> Result<f64> {
+        let dirty = clean_price + self.accrued_interest(settle, projection)?;
+        let flows = self.cashflows(settle, projection)?;
+        let times = flows
+            .iter()
+            .map(|cf| convention.day_counter.year_fraction(settle, cf.pay_date))
+            .collect::<Result<Vec<f64>>>()?;
+        let mut residual = |y: f64| -> f64 {
+            let pv: f64 = flows
+                .iter()
+                .zip(times.iter())
+                .map(|(cf, &t)| cf.amount * convention.compounding.discount(y, t))
+                .sum();
+            100.0 * pv / self.notional - dirty
+        };
+        let mut convergency = SimpleConvergency {
+            eps: 1e-12_f64,
+            max_iter: 200,
+        };
+        find_root_brent(-0.5_f64, 1.0_f64, &mut residual, &mut convergency)
+            .map_err(|e| Error::InvalidData(format!("yield solve failed: {:?}", e)))
+    }
+
+    /// Macaulay / modified duration, convexity and DV01 at `yield_rate`.
+    pub fn risk(
+        &self,
+        settle: NaiveDate,
+        yield_rate: f64,
+        convention: &YieldConvention,
+        projection: Option<&dyn YieldCurve>,
+    ) -> Result<BondRisk> {
+        let mut pv = 0.0;
+        let mut time_weighted = 0.0;
+        let mut d1 = 0.0;
+        let mut d2 = 0.0;
+        for cf in self.cashflows(settle, projection)? {
+            let t = convention.day_counter.year_fraction(settle, cf.pay_date)?;
+            let df = convention.compounding.discount(yield_rate, t);
+            let (ddf, d2df) = convention.compounding.discount_derivatives(yield_rate, t);
+            pv += cf.amount * df;
+            time_weighted += cf.amount * df * t;
+            d1 += cf.amount * ddf;
+            d2 += cf.amount * d2df;
+        }
+        if pv.abs() < 1.0e-12 {
+            return Err(Error::InvalidData(
+                "bond has no remaining value at settlement".to_string(),
+            ));
+        }
+        Ok(BondRisk {
+            macaulay_duration: time_weighted / pv,
+            modified_duration: -d1 / pv,
+            convexity: d2 / pv,
+            dv01: -d1 * 1.0e-4,
+        })
+    }
+
+    /// Dirty price per 100 off `yts`, with a continuously-compounded
+    /// spread `z_spread` over its zero rates.
+    pub fn dirty_price_from_curve(
+        &self,
+        settle: NaiveDate,
+        yts: &dyn YieldCurve,
+        z_spread: f64,
+    ) -> Result<f64> {
+        let method = &InterpolationMethodEnum::PiecewiseLinearContinuous;
+        let df_settle = yts.shifted_discount(settle, method, z_spread * 1.0e4)?;
+        let mut pv = 0.0;
+        for cf in self.cashflows(settle, Some(yts))? {
+            pv += cf.amount * yts.shifted_discount(cf.pay_date, method, z_spread * 1.0e4)?;
+        }
+        Ok(100.0 * pv / (self.notional * df_settle))
+    }
+
+    /// Z-spread reproducing `clean_price` (per 100) off `yts`.
+    pub fn z_spread(
+        &self,
+        settle: NaiveDate,
+        clean_price: f64,
+        yts: &dyn YieldCurve,
+    ) -> Result<f64> {
+        let dirty = clean_price + self.accrued_interest(settle, Some(yts))?;
+        let mut residual = |z: f64| -> f64 {
+            match self.dirty_price_from_curve(settle, yts, z) {
+                Ok(p) => p - dirty,
+                Err(_) => f64::NAN,
+            }
+        };
+        let mut convergency = SimpleConvergency {
+            eps: 1e-12_f64,
+            max_iter: 200,
+        };
+        find_root_brent(-0.5_f64, 0.5_f64, &mut residual, &mut convergency)
+            .map_err(|e| Error::InvalidData(format!("Z-spread solve failed: {:?}", e)))
+    }
+
+    /// Par-par asset-swap spread for a bond bought at `clean_price`: the
+    /// running spread over the floating leg (on the bond's own accrual
+    /// periods) that makes a par package fair against `yts`.
+    pub fn asset_swap_spread(
+        &self,
+        settle: NaiveDate,
+        clean_price: f64,
+        yts: &dyn YieldCurve,
+    ) -> Result<f64> {
+        let method = &InterpolationMethodEnum::PiecewiseLinearContinuous;
+        let market_dirty = clean_price + self.accrued_interest(settle, Some(yts))?;
+        let model_dirty = self.dirty_price_from_curve(settle, yts, 0.0)?;
+        let df_settle = yts.discount(settle, method)?;
+        let mut annuity = 0.0;
+        for period in self.schedule.iter().filter(|p| p.pay_date > settle) {
+            let tau =
+                self.accrual_fraction(period, period.accrual_start_date, period.accrual_end_date)?;
+            annuity += tau * yts.discount(period.pay_date, method)? / df_settle;
+        }
+        if annuity <= 0.0 {
+            return Err(Error::InvalidData(
+                "asset swap needs at least one remaining period".to_string(),
+            ));
+        }
+        Ok((model_dirty - market_dirty) / (100.0 * annuity))
+    }
+
+    /// PV at the curve's valuation date under a parallel zero-rate shift.
+    fn pv_under_shift(&self, market: &IrMarketContext, rate_shift_bp: f64) -> Result<f64> {
+        let method = &InterpolationMethodEnum::PiecewiseLinearContinuous;
+        let mut pv = 0.0;
+        let flows =
+            self.shifted_cashflows(market.valuation_date, Some(&market.curve), rate_shift_bp)?;
+        for cf in flows {
+            pv += cf.amount
+                * market
+                    .curve
+                    .shifted_discount(cf.pay_date, method, rate_shift_bp)?;
+        }
+        Ok(pv)
+    }
+}
+
+impl IRDerivatives for Bond {
+    fn mtm(&self, market: &IrMarketContext) -> Result<CurrencyValue> {
+        Ok(CurrencyValue {
+            currency: self.currency,
+            value: self.pv_under_shift(market, 0.0)?,
+        })
+    }
+
+    /// Curve DV01 = PV(y + 1bp) − PV(y): negative for a long bond.
+    /// Projected FRN coupons move with the shifted curve, so an FRN only
+    /// carries the rate risk of its running period.
+    fn dv01(&self, market: &IrMarketContext) -> Result<f64> {
+        Ok(self.pv_under_shift(market, 1.0)? - self.pv_under_shift(market, 0.0)?)
+    }
+
+    fn gamma(
+        &self,
+        market: &IrMarketContext,
+        rate_shift_bp: f64,
+        mode: RateShiftMode,
+    ) -> Result<f64> {
+        match mode {
+            RateShiftMode::Zeros => {}
+            other => {
+                return Err(Error::InvalidData(format!(
+                    "rate shift mode {:?} is not yet implemented; only Zeros is supported",
+                    other
+                )));
+            }
+        }
+        let base = self.pv_under_shift(market, 0.0)?;
+        let up = self.pv_under_shift(market, rate_shift_bp)?;
+        let down = self.pv_under_shift(market, -rate_shift_bp)?;
+        Ok(up + down - 2.0 * base)
+    }
+
+    /// Bonds carry no optionality: vega is identically zero.
+    fn vega(&self, _market: &IrMarketContext, _vol_shift_bp: f64) -> Result<f64> {
+        Ok(0.0)
+    }
+
+    /// Coupons a buyer settling on the valuation date receives, then the
+    /// redemption. FRN coupons are projected except a running one whose
+    /// `current_fixing` is set.
+    fn cashflows(&self, market: &IrMarketContext) -> Result<Vec<InterestRateCashflow>> {
+        let settle = market.valuation_date;
+        let method = &InterpolationMethodEnum::PiecewiseLinearContinuous;
+        let mut periods = self
+            .schedule
+            .iter()
+            .filter(|p| p.pay_date > settle && !self.is_ex_coupon(p, settle));
+        let mut rows = Vec::new();
+        for flow in Bond::cashflows(self, settle, Some(&market.curve))? {
+            let discount = market.curve.discount(flow.pay_date, method)?;
+            if flow.is_principal {
+                rows.push(InterestRateCashflow::single(
+                    CashflowKind::Fixed,
+                    flow.pay_date,
+                    self.notional,
+                    flow.amount,
+                    discount,
+                ));
+                continue;
+            }
+            let period = periods
+                .next()
+                .ok_or_else(|| Error::InvalidData("coupon without a period".to_string()))?;
+            let (kind, fixing_date) = match self.bond_type {
+                BondType::Floating { current_fixing, .. } => {
+                    let fixed = period.accrual_start_date <= settle && current_fixing.is_some();
+                    let kind = if fixed {
+                        CashflowKind::Fixed
+                    } else {
+                        CashflowKind::Projected
+                    };
+                    (kind, Some(period.reset_date))
+                }
+                _ => (CashflowKind::Fixed, None),
+            };
+            rows.push(InterestRateCashflow::coupon(
+                kind,
+                fixing_date,
+                period,
+                flow.rate,
+                self.notional,
+                flow.amount,
+                discount,
+            ));
+        }
+        Ok(rows)
+    }
+}
+
+/// Coupons per year for the frequencies bonds are issued with.
+pub(crate) fn periods_per_year(frequency: &Frequency) -> Result<u32> {
+    match frequency {
+        Frequency::Annual => Ok(1),
+        Frequency::Semiannual => Ok(2),
+        Frequency::EveryFourthMonth => Ok(3),
+        Frequency::Quarterly => Ok(4),
+        Frequency::Bimonthly => Ok(6),
+        Frequency::Monthly => Ok(12),
+        other => Err(Error::InvalidData(format!(
+            "ICMA accrual needs a monthly-based coupon frequency, got {}",
+            other.name()
+        ))),
+    }
+}
+
+/// Unadjusted coupon schedule rolled back from `maturity` in steps of
+/// `frequency`, with a short front stub if `issue` is off-cycle. Pay
+/// dates equal accrual end dates.
+pub fn bond_schedule(
+    issue: NaiveDate,
+    maturity: NaiveDate,
+    frequency: &Frequency,
+    notional: f64,
+) -> Result<Vec<InterestRateSchedulePeriod>> {
+    let months = Months::new(12 / periods_per_year(frequency)?);
+    let mut ends = vec![maturity];
+    let mut d = maturity;
+    loop {
+        d = d
+            .checked_sub_months(months)
+            .ok_or_else(|| Error::InvalidData("bond schedule date out of range".to_string()))?;
+        if d <= issue {
+            break;
+        }
+        ends.push(d);
+    }
+    ends.reverse();
+    let mut start = issue;
+    Ok(ends
+        .into_iter()
+        .map(|end| {
+            let p = InterestRateSchedulePeriod::new(start, end, end, start, 0.0, notional);
+            start = end;
+            p
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::markets::termstructures::yieldcurve::flat_rate_curve;
+    use crate::time::daycounters::actual365fixed::Actual365Fixed;
+    use crate::time::daycounters::thirty360::{Thirty360, Thirty360Market};
+
+    fn d(y: i32, m: u32, dd: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, dd).unwrap()
+    }
+
+    fn fixed_bond(coupon: f64) -> Bond {
+        let schedule =
+            bond_schedule(d(2025, 1, 15), d(2030, 1, 15), &Frequency::Semiannual, 1e6).unwrap();
+        Bond::new(
+            BondType::Fixed { coupon },
+            1.0e6,
+            Currency::USD,
+            Frequency::Semiannual,
+            schedule,
+            AccrualBasis::ActualActualIcma,
+        )
+    }
+
+    fn bond_basis() -> YieldConvention {
+        YieldConvention {
+            day_counter: Box::new(Thirty360::new(Thirty360Market::European)),
+            compounding: Compounding::Compounded { per_year: 2 },
+        }
+    }
+
+    /// On a coupon date a bond yielding its coupon prices at par; a
+    /// round trip through the yield solver is exact.
+    #[test]
+    fn par_bond_and_yield_round_trip() -> Result<()> {
+        let bond = fixed_bond(0.05);
+        let conv = bond_basis();
+        let settle = d(2026, 1, 15);
+        let par = bond.clean_price_from_yield(settle, 0.05, &conv, None)?;
+        assert!((par - 100.0).abs() < 1e-10, "{par}");
+
+        let settle = d(2026, 3, 3);
+        let clean = bond.clean_price_from_yield(settle, 0.0437, &conv, None)?;
+        let y = bond.yield_to_maturity(settle, clean, &conv, None)?;
+        assert!((y - 0.0437).abs() < 1e-10);
+        Ok(())
+    }
+
+    /// ICMA accrual: 45 of 181 days of a 5 % semi-annual coupon.
+    #[test]
+    fn icma_accrued_and_ex_coupon() -> Result<()> {
+        let settle = d(2025, 3, 1);
+        let accrued = fixed_bond(0.05).accrued_interest(settle, None)?;
+        assert!((accrued - 2.5 * 45.0 / 181.0).abs() < 1e-12);
+
+        // Seven days ex: the buyer gets no July coupon and pays negative
+        // accrued for the remaining days; clean price is continuous across
+        // the ex-date.
+        let bond = fixed_bond(0.05).with_ex_coupon_days(7);
+        let conv = bond_basis();
+        let ex_settle = d(2025, 7, 10);
+        let accrued = bond.accrued_interest(ex_settle, None)?;
+        assert!((accrued + 2.5 * 5.0 / 181.0).abs() < 1e-12);
+        assert_eq!(bond.cashflows(ex_settle, None)?[0].pay_date, d(2026, 1, 15));
+        let cum = fixed_bond(0.05).clean_price_from_yield(ex_settle, 0.05, &conv, None)?;
+        let ex = bond.clean_price_from_yield(ex_settle, 0.05, &conv, None)?;
+        assert!((cum - ex).abs() < 5e-3, "cum {cum} ex {ex}");
+        Ok(())
+    }
+
+    /// Short front stub under ICMA accrues against the notional regular
+    /// period, and a long stub spans two of them.
+    #[test]
+    fn icma_stubs_are_prorated() -> Result<()> {
+        let schedule = bond_schedule(d(2025, 3, 1), d(2027, 1, 15), &Frequency::Semiannual, 100.0)?;
+        assert_eq!(schedule[0].accrual_start_date, d(2025, 3, 1));
+        assert_eq!(schedule[0].accrual_end_date, d(2025, 7, 15));
+        let bond = Bond::new(
+            BondType::Fixed { coupon: 0.04 },
+            100.0,
+            Currency::EUR,
+            Frequency::Semiannual,
+            schedule,
+            AccrualBasis::ActualActualIcma,
+        );
+        let flows = bond.cashflows(d(2025, 3, 1), None)?;
+        assert!((flows[0].amount - 2.0 * 136.0 / 181.0).abs() < 1e-12);
+        assert!((flows[1].amount - 2.0).abs() < 1e-12);
+
+        let long = InterestRateSchedulePeriod::new(
+            d(2024, 11, 1),
+            d(2025, 7, 15),
+            d(2025, 7, 15),
+            d(2024, 11, 1),
+            0.0,
+            100.0,
+        );
+        let f = bond.accrual_fraction(&long, long.accrual_start_date, long.accrual_end_date)?;
+        assert!((f - 0.5 * (1.0 + 75.0 / 184.0)).abs() < 1e-12);
+        Ok(())
+    }
+
+    /// Zero-coupon: Macaulay duration is the time to maturity; analytic
+    /// modified duration, convexity and DV01 match finite differences.
+    #[test]
+    fn duration_convexity_and_dv01() -> Result<()> {
+        let conv = bond_basis();
+        let settle = d(2026, 1, 15);
+        let zero = Bond::zero_coupon(
+            100.0,
+            Currency::USD,
+            d(2025, 1, 15),
+            d(2031, 1, 15),
+            AccrualBasis::ActualActualIcma,
+        );
+        let risk = zero.risk(settle, 0.04, &conv, None)?;
+        assert!((risk.macaulay_duration - 5.0).abs() < 1e-12);
+        assert!((risk.modified_duration - 5.0 / 1.02).abs() < 1e-12);
+        assert_eq!(zero.accrued_interest(settle, None)?, 0.0);
+
+        let bond = fixed_bond(0.045);
+        let settle = d(2026, 5, 20);
+        let y = 0.05;
+        let h = 1e-5;
+        let p = |y| bond.dirty_price_from_yield(settle, y, &conv, None).unwrap();
+        let risk = bond.risk(settle, y, &conv, None)?;
+        let fd_dur = -(p(y + h) - p(y - h)) / (2.0 * h) / p(y);
+        let fd_cvx = (p(y + h) - 2.0 * p(y) + p(y - h)) / (h * h) / p(y);
+        assert!((risk.modified_duration - fd_dur).abs() < 1e-6);
+        assert!((risk.convexity - fd_cvx).abs() < 1e-3);
+        let fd_dv01 = (p(y - 1e-4) - p(y + 1e-4)) / 2.0 * bond.notional / 100.0;
+        assert!((risk.dv01 - fd_dv01).abs() / fd_dv01 < 1e-6);
+        Ok(())
+    }
+
+    /// A bond priced off the curve has zero Z-spread and zero asset-swap
+    /// spread; a cheaper bond has positive spreads of similar size.
+    #[test]
+    fn z_spread_and_asset_swap_spread() -> Result<()> {
+        let settle = d(2026, 3, 3);
+        let yts = flat_rate_curve(settle, 0.035);
+        let bond = fixed_bond(0.05);
+        let fair = bond.dirty_price_from_curve(settle, &yts, 0.0)?
+            - bond.accrued_interest(settle, Some(&yts))?;
+        assert!(bond.z_spread(settle, fair, &yts)?.abs() < 1e-10);
+        assert!(bond.asset_swap_spread(settle, fair, &yts)?.abs() < 1e-12);
+
+        let cheap = fair - 2.0;
+        let z = bond.z_spread(settle, cheap, &yts)?;
+        let asw = bond.asset_swap_spread(settle, cheap, &yts)?;
+        let repriced = bond.dirty_price_from_curve(settle, &yts, z)?
+            - bond.accrued_interest(settle, Some(&yts))?;
+        assert!((repriced - cheap).abs() < 1e-8);
+        assert!(z > 0.0 && asw > 0.0);
+        assert!((z - asw).abs() < 0.001, "z {z} asw {asw}");
+        Ok(())
+    }
+
+    /// Single-curve FRN with no spread is worth par on a reset date, and
+    /// its curve DV01 is only the running period's.
+    #[test]
+    fn frn_prices_at_par_on_reset() -> Result<()> {
+        let settle = d(2026, 1, 15);
+        let yts = flat_rate_curve(settle, 0.03);
+        let schedule = bond_schedule(d(2025, 1, 15), d(2030, 1, 15), &Frequency::Quarterly, 1e6)?;
+        let frn = Bond::new(
+            BondType::Floating {
+                spread: 0.0,
+                current_fixing: None,
+            },
+            1.0e6,
+            Currency::USD,
+            Frequency::Quarterly,
+            schedule,
+            AccrualBasis::DayCounter(Box::new(Actual365Fixed::default())),
+        );
+        let dirty = frn.dirty_price_from_curve(settle, &yts, 0.0)?;
+        assert!((dirty - 100.0).abs() < 1e-9, "{dirty}");
+        assert!(frn.cashflows(settle, None).is_err());
+
+        let market = IrMarketContext::new(settle, Currency::USD, yts, None);
+        let pv = frn.mtm(&market)?.value;
+        assert!((pv - 1.0e6).abs() < 1e-3);
+        assert!(frn.dv01(&market)?.abs() < 1.0);
+        Ok(())
+    }
+}
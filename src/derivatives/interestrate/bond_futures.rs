@@ -0,0 +1,556 @@
+//! Government bond futures (UST on CBOT, Bund on Eurex, Gilt on ICE)
+//! with a deliverable basket, exchange conversion factors and
+//! cheapest-to-deliver analytics.
+//!
+//! The short delivers any basket bond and is paid the invoice amount
+//! `N · (F · CF + AI_del) / 100`. For a bond bought at clean `P` for
+//! spot settlement and financed at repo `r` to delivery, with coupons
+//! `C_j` received at `t_j` in between (`d`, `d_j` days to delivery, `B`
+//! the money-market day basis):
+//!
+//! ```text
+//!   gross basis   = P − F · CF
+//!   forward       = (P + AI_s)(1 + r d/B) − Σ C_j (1 + r d_j/B) − AI_del
+//!   carry         = P − forward,        net basis = gross basis − carry
+//!   implied repo  = (F · CF + AI_del + Σ C_j − P − AI_s) · B / ((P + AI_s) d − Σ C_j d_j)
+//! ```
+//!
+//! The cheapest to deliver has the highest implied repo. Off a curve the
+//! futures price is `min_i forward_i / CF_i` with forwards read off the
+//! curve; the delivery option's time value is ignored, so DV01 and gamma
+//! are the CTD's forward sensitivities over its conversion factor.
+//!
+//! Conversion factors follow each exchange's rule: the clean price per
+//! 1 at the notional coupon (6 % CBOT and Eurex, 4 % ICE) on the first
+//! delivery day (CBOT, ICE) or the delivery day (Eurex). CBOT measures
+//! maturity in whole months from the first day of the delivery month,
+//! rounded down to quarters for bond and 10-year contracts, and rounds
+//! to 4 decimals; Eurex discounts on ACT/ACT fractions of the running
+//! coupon period and rounds to 6; ICE does the same semi-annually,
+//! without the ex-dividend adjustment, and rounds to 7.
+
+use chrono::{Datelike, NaiveDate};
+use iso_currency::Currency;
+
+use crate::derivatives::basic::BasicInfo;
+use crate::derivatives::forex::basic::CurrencyValue;
+use crate::derivatives::interestrate::basic::{
+    CashflowKind, IRDerivatives, InterestRateCashflow, RateShiftMode,
+};
+use crate::derivatives::interestrate::bond::{Bond, BondType, periods_per_year};
+use crate::derivatives::interestrate::cap::ensure_supported_mode;
+use crate::error::{Error, Result};
+use crate::markets::interestrate::market_context::IrMarketContext;
+use crate::markets::termstructures::yieldcurve::YieldCurve;
+use crate::time::businessdayconvention::BusinessDayConvention;
+use crate::time::calendars::germany::GermanyMarket;
+use crate::time::calendars::unitedkingdom::UnitedKingdomMarket;
+use crate::time::calendars::unitedstates::UnitedStatesMarket;
+use crate::time::calendars::{Calendar, Germany, UnitedKingdom, UnitedStates};
+
+/// Listing exchange, which fixes delivery days, conversion factors and
+/// the repo day basis.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BondFutureExchange {
+    /// CBOT Treasury futures; `quarter_rounding` for the bond and
+    /// 10-year note contracts.
+    Cbot {
+        quarter_rounding: bool,
+    },
+    Eurex,
+    Ice,
+}
+
+impl BondFutureExchange {
+    pub fn calendar(&self) -> Box<dyn Calendar> {
+        match self {
+            BondFutureExchange::Cbot { .. } => Box::new(UnitedStates {
+                market: Some(UnitedStatesMarket::GovernmentBond),
+            }),
+            BondFutureExchange::Eurex => Box::new(Germany {
+                market: Some(GermanyMarket::Eurex),
+            }),
+            BondFutureExchange::Ice => Box::new(UnitedKingdom {
+                market: Some(UnitedKingdomMarket::Exchange),
+            }),
+        }
+    }
+
+    pub fn notional_coupon(&self) -> f64 {
+        match self {
+            BondFutureExchange::Ice => 0.04,
+            _ => 0.06,
+        }
+    }
+
+    /// Money-market day basis for repo.
+    pub fn repo_day_basis(&self) -> f64 {
+        match self {
+            BondFutureExchange::Ice => 365.0,
+            _ => 360.0,
+        }
+    }
+}
+
+/// Basis analytics for one deliverable, per 100 notional.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct DeliverableAnalytics {
+    /// Position in [`BondFuture::basket`].
+    pub index: usize,
+    pub conversion_factor: f64,
+    pub gross_basis: f64,
+    pub carry: f64,
+    pub net_basis: f64,
+    pub implied_repo: f64,
+}
+
+/// Position in a bond future. `Buy` is long the futures price.
+#[derive(Debug)]
+pub struct BondFuture {
+    pub basic_info: BasicInfo,
+    pub currency: Currency,
+    pub exchange: BondFutureExchange,
+    pub contracts: f64,
+    /// Face value per contract.
+    pub contract_size: f64,
+    pub delivery_year: i32,
+    pub delivery_month: u32,
+    /// Price the position was traded (or last settled) at.
+    pub trade_price: f64,
+    /// Fixed-coupon deliverable bonds.
+    pub basket: Vec<Bond>,
+}
+
+impl BondFuture {
+    fn direction_sign(&self) -> f64 {
+        self.basic_info.direction as i8 as f64
+    }
+
+    fn month_start(&self) -> Result<NaiveDate> {
+        NaiveDate::from_ymd_opt(self.delivery_year, self.delivery_month, 1).ok_or_else(|| {
+            Error::InvalidData(format!(
+                "invalid delivery month {}-{}",
+                self.delivery_year, self.delivery_month
+            ))
+        })
+    }
+
+    /// First business day of the delivery month.
+    pub fn first_delivery_date(&self) -> Result<NaiveDate> {
+        let calendar = self.exchange.calendar();
+        calendar
+            .adjust(self.month_start()?, BusinessDayConvention::Following)
+            .ok_or_else(|| Error::InvalidData("no first delivery day".to_string()))
+    }
+
+    /// Delivery day the basis is computed to: the 10th (or next business
+    /// day) on Eurex, the last business day of the month on CBOT and ICE
+    /// (where positive carry pushes delivery late).
+    pub fn delivery_date(&self) -> Result<NaiveDate> {
+        let calendar = self.exchange.calendar();
+        match self.exchange {
+            BondFutureExchange::Eurex => calendar
+                .adjust(
+                    self.month_start()? + chrono::Days::new(9),
+                    BusinessDayConvention::Following,
+                )
+                .ok_or_else(|| Error::InvalidData("no Eurex delivery day".to_string())),
+            _ => Ok(calendar.end_of_month(self.month_start()?)),
+        }
+    }
+
+    /// Exchange conversion factor of `bond`.
+    pub fn conversion_factor(&self, bond: &Bond) -> Result<f64> {
+        let BondType::Fixed { coupon } = bond.bond_type else {
+            return Err(Error::InvalidData(
+                "bond futures deliver fixed-coupon bonds only".to_string(),
+            ));
+        };
+        let y = self.exchange.notional_coupon();
+        let (factor, decimals) = match self.exchange {
+            BondFutureExchange::Cbot { quarter_rounding } => {
+                let start = self.month_start()?;
+                let maturity = bond.maturity_date()?;
+                let mut months = (maturity.year() - start.year()) * 12 + maturity.month() as i32
+                    - start.month() as i32;
+                if maturity.day() < start.day() {
+                    months -= 1;
+                }
+                let n = months / 12;
+                let mut z = months % 12;
+                if quarter_rounding {
+                    z -= z % 3;
+                }
+                let v = if z < 7 {
+                    z
+                } else if quarter_rounding {
+                    3
+                } else {
+                    z - 6
+                };
+                let h = 1.0 + y / 2.0;
+                let a = h.powf(-(v as f64) / 6.0);
+                let b = coupon / 2.0 * (6 - v) as f64 / 6.0;
+                let c = if z < 7 {
+                    h.powi(-2 * n)
+                } else {
+                    h.powi(-2 * n - 1)
+                };
+                let d = coupon / y * (1.0 - c);
+                (a * (coupon / 2.0 + c + d) - b, 4)
+            }
+            BondFutureExchange::Eurex => (
+                notional_clean_price(bond, coupon, self.delivery_date()?, y)?,
+                6,
+            ),
+            BondFutureExchange::Ice => (
+                notional_clean_price(bond, coupon, self.first_delivery_date()?, y)?,
+                7,
+            ),
+        };
+        let scale = 10f64.powi(decimals);
+        Ok((factor * scale).round() / scale)
+    }
+
+    /// Invoice amount for delivering one contract of basket bond `index`
+    /// at futures price `futures_price`.
+    pub fn invoice_amount(&self, index: usize, futures_price: f64) -> Result<f64> {
+        let bond = self.bond(index)?;
+        let accrued = bond.accrued_interest(self.delivery_date()?, None)?;
+        Ok(self.contract_size * (futures_price * self.conversion_factor(bond)? + accrued) / 100.0)
+    }
+
+    fn bond(&self, index: usize) -> Result<&Bond> {
+        self.basket
+            .get(index)
+            .ok_or_else(|| Error::InvalidData(format!("no deliverable at index {index}")))
+    }
+
+    /// Gross and net basis, carry and implied repo of every deliverable
+    /// bought at `clean_prices` for settlement on `settle` and financed
+    /// at `repo_rate`.
+    pub fn analytics(
+        &self,
+        futures_price: f64,
+        settle: NaiveDate,
+        clean_prices: &[f64],
+        repo_rate: f64,
+    ) -> Result<Vec<DeliverableAnalytics>> {
+        if clean_prices.len() != self.basket.len() {
+            return Err(Error::InvalidData(format!(
+                "{} prices for {} deliverables",
+                clean_prices.len(),
+                self.basket.len()
+            )));
+        }
+        let delivery = self.delivery_date()?;
+        if delivery <= settle {
+            return Err(Error::InvalidData(format!(
+                "delivery {} is not after settlement {}",
+                delivery, settle
+            )));
+        }
+        let basis = self.exchange.repo_day_basis();
+        let d = (delivery - settle).num_days() as f64;
+        self.basket
+            .iter()
+            .zip(clean_prices)
+            .enumerate()
+            .map(|(index, (bond, &price))| {
+                let cf = self.conversion_factor(bond)?;
+                let dirty = price + bond.accrued_interest(settle, None)?;
+                let accrued_delivery = bond.accrued_interest(delivery, None)?;
+                let (mut coupons, mut weighted) = (0.0, 0.0);
+                for flow in bond.cashflows(settle, None)? {
+                    if flow.pay_date <= delivery && !flow.is_principal {
+                        let c = 100.0 * flow.amount / bond.notional;
+                        coupons += c;
+                        weighted += c * (delivery - flow.pay_date).num_days() as f64;
+                    }
+                }
+                let forward = dirty * (1.0 + repo_rate * d / basis)
+                    - coupons
+                    - repo_rate * weighted / basis
+                    - accrued_delivery;
+                let gross_basis = price - futures_price * cf;
+                let carry = price - forward;
+                let implied_repo = (futures_price * cf + accrued_delivery + coupons - dirty)
+                    * basis
+                    / (dirty * d - weighted);
+                Ok(DeliverableAnalytics {
+                    index,
+                    conversion_factor: cf,
+                    gross_basis,
+                    carry,
+                    net_basis: gross_basis - carry,
+                    implied_repo,
+                })
+            })
+            .collect()
+    }
+
+    /// Deliverable with the highest implied repo.
+    pub fn cheapest_to_deliver(
+        &self,
+        futures_price: f64,
+        settle: NaiveDate,
+        clean_prices: &[f64],
+        repo_rate: f64,
+    ) -> Result<DeliverableAnalytics> {
+        self.analytics(futures_price, settle, clean_prices, repo_rate)?
+            .into_iter()
+            .max_by(|a, b| a.implied_repo.total_cmp(&b.implied_repo))
+            .ok_or_else(|| Error::InvalidData("empty deliverable basket".to_string()))
+    }
+
+    /// Clean forward price per 100 of basket bond `index` for delivery,
+    /// off `yts` under a parallel zero shift.
+    fn curve_forward(&self, index: usize, yts: &dyn YieldCurve, rate_shift_bp: f64) -> Result<f64> {
+        let bond = self.bond(index)?;
+        let delivery = self.delivery_date()?;
+        Ok(
+            bond.dirty_price_from_curve(delivery, yts, rate_shift_bp * 1.0e-4)?
+                - bond.accrued_interest(delivery, None)?,
+        )
+    }
+
+    /// Curve-implied CTD and futures price `min_i forward_i / CF_i`.
+    pub fn theoretical_price(&self, yts: &dyn YieldCurve) -> Result<(usize, f64)> {
+        let mut best: Option<(usize, f64)> = None;
+        for (index, bond) in self.basket.iter().enumerate() {
+            let price = self.curve_forward(index, yts, 0.0)? / self.conversion_factor(bond)?;
+            if best.is_none_or(|b| price < b.1) {
+                best = Some((index, price));
+            }
+        }
+        best.ok_or_else(|| Error::InvalidData("empty deliverable basket".to_string()))
+    }
+
+    /// PV with the CTD held fixed under a parallel zero shift.
+    fn pv_under_shift(&self, yts: &dyn YieldCurve, ctd: usize, rate_shift_bp: f64) -> Result<f64> {
+        let price = self.curve_forward(ctd, yts, rate_shift_bp)?
+            / self.conversion_factor(self.bond(ctd)?)?;
+        Ok(
+            self.direction_sign()
+                * self.contracts
+                * self.contract_size
+                * (price - self.trade_price)
+                / 100.0,
+        )
+    }
+}
+
+/// Clean price per 1 of `bond` on `date` at yield `y`, compounded at the
+/// coupon frequency, discounting over ACT/ACT fractions of the running
+/// coupon period with regular coupons.
+fn notional_clean_price(bond: &Bond, coupon: f64, date: NaiveDate, y: f64) -> Result<f64> {
+    let k = periods_per_year(&bond.frequency)? as f64;
+    let running = bond
+        .schedule
+        .iter()
+        .position(|p| p.accrual_start_date <= date && date < p.accrual_end_date)
+        .ok_or_else(|| Error::InvalidData(format!("bond does not accrue on {}", date)))?;
+    let period = &bond.schedule[running];
+    let f = (period.accrual_end_date - date).num_days() as f64
+        / (period.accrual_end_date - period.accrual_start_date).num_days() as f64;
+    let remaining = bond.schedule.len() - running;
+    let v = 1.0 / (1.0 + y / k);
+    let coupons: f64 = (0..remaining)
+        .map(|j| coupon / k * v.powf(f + j as f64))
+        .sum();
+    let dirty = coupons + bond.redemption * v.powf(f + (remaining - 1) as f64);
+    Ok(dirty - coupon / k * (1.0 - f))
+}
+
+impl IRDerivatives for BondFuture {
+    /// Variation margin to the curve-implied futures price.
+    fn mtm(&self, market: &IrMarketContext) -> Result<CurrencyValue> {
+        let (ctd, _) = self.theoretical_price(&market.curve)?;
+        Ok(CurrencyValue {
+            currency: self.currency,
+            value: self.pv_under_shift(&market.curve, ctd, 0.0)?,
+        })
+    }
+
+    /// CTD forward DV01 over its conversion factor.
+    fn dv01(&self, market: &IrMarketContext) -> Result<f64> {
+        let (ctd, _) = self.theoretical_price(&market.curve)?;
+        Ok(self.pv_under_shift(&market.curve, ctd, 1.0)?
+            - self.pv_under_shift(&market.curve, ctd, 0.0)?)
+    }
+
+    fn gamma(
+        &self,
+        market: &IrMarketContext,
+        rate_shift_bp: f64,
+        mode: RateShiftMode,
+    ) -> Result<f64> {
+        ensure_supported_mode(mode)?;
+        let (ctd, _) = self.theoretical_price(&market.curve)?;
+        let base = self.pv_under_shift(&market.curve, ctd, 0.0)?;
+        let up = self.pv_under_shift(&market.curve, ctd, rate_shift_bp)?;
+        let down = self.pv_under_shift(&market.curve, ctd, -rate_shift_bp)?;
+        Ok(up + down - 2.0 * base)
+    }
+
+    /// The delivery option is not valued, so there is no vol risk.
+    fn vega(&self, _market: &IrMarketContext, _vol_shift_bp: f64) -> Result<f64> {
+        Ok(0.0)
+    }
+
+    /// The variation margin to date, settled on the valuation date.
+    fn cashflows(&self, market: &IrMarketContext) -> Result<Vec<InterestRateCashflow>> {
+        Ok(vec![InterestRateCashflow::single(
+            CashflowKind::Projected,
+            market.valuation_date,
+            self.contracts * self.contract_size,
+            self.mtm(market)?.value,
+            1.0,
+        )])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::derivatives::basic::{Direction, Style};
+    use crate::derivatives::interestrate::bond::{AccrualBasis, bond_schedule};
+    use crate::markets::termstructures::yieldcurve::zero_rate_curve;
+    use crate::time::frequency::Frequency;
+
+    fn d(y: i32, m: u32, dd: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, dd).unwrap()
+    }
+
+    fn bond(coupon: f64, issue: NaiveDate, maturity: NaiveDate, frequency: Frequency) -> Bond {
+        let schedule = bond_schedule(issue, maturity, &frequency, 100.0).unwrap();
+        Bond::new(
+            BondType::Fixed { coupon },
+            100.0,
+            Currency::EUR,
+            frequency,
+            schedule,
+            AccrualBasis::ActualActualIcma,
+        )
+    }
+
+    fn future(exchange: BondFutureExchange, basket: Vec<Bond>) -> BondFuture {
+        BondFuture {
+            basic_info: BasicInfo {
+                trade_date: d(2026, 8, 12),
+                style: Style::IRSwap,
+                direction: Direction::Buy,
+                expiry_date: d(2026, 12, 8),
+                delivery_date: d(2026, 12, 10),
+            },
+            currency: Currency::EUR,
+            exchange,
+            contracts: 10.0,
+            contract_size: 100_000.0,
+            delivery_year: 2026,
+            delivery_month: 12,
+            trade_price: 130.0,
+            basket,
+        }
+    }
+
+    /// Bonds paying the notional coupon convert at par. December 2026
+    /// factors of real deliverables: the 10-year note contract rounds the
+    /// 7y 11m left on the 4 1/4 % Nov-34 down to 7y 9m and the 7y 5m on
+    /// the 4 3/8 % May-34 down to 7y 3m; the Bund contract discounts the
+    /// 2.60 % Aug-35 over 248/365 of its running coupon period and 8
+    /// whole years. Eurex delivers on the 10th, CBOT at month end.
+    #[test]
+    fn conversion_factors_follow_exchange_rules() -> Result<()> {
+        let cbot = future(
+            BondFutureExchange::Cbot {
+                quarter_rounding: true,
+            },
+            vec![],
+        );
+        let six = bond(
+            0.06,
+            d(2021, 12, 15),
+            d(2036, 12, 15),
+            Frequency::Semiannual,
+        );
+        assert_eq!(cbot.conversion_factor(&six)?, 1.0);
+        let nov34 = bond(
+            0.0425,
+            d(2024, 11, 15),
+            d(2034, 11, 15),
+            Frequency::Semiannual,
+        );
+        let may34 = bond(
+            0.04375,
+            d(2024, 5, 15),
+            d(2034, 5, 15),
+            Frequency::Semiannual,
+        );
+        assert_eq!(cbot.conversion_factor(&nov34)?, 0.8927);
+        assert_eq!(cbot.conversion_factor(&may34)?, 0.9055);
+        assert_eq!(cbot.delivery_date()?, d(2026, 12, 31));
+
+        let eurex = future(BondFutureExchange::Eurex, vec![]);
+        assert_eq!(eurex.delivery_date()?, d(2026, 12, 10));
+        let bund = bond(0.06, d(2025, 12, 10), d(2036, 12, 10), Frequency::Annual);
+        assert!((eurex.conversion_factor(&bund)? - 1.0).abs() < 1e-12);
+        let aug35 = bond(0.026, d(2025, 8, 15), d(2035, 8, 15), Frequency::Annual);
+        assert_eq!(eurex.conversion_factor(&aug35)?, 0.774902);
+        Ok(())
+    }
+
+    /// A futures price at a bond's repo forward over its CF gives that
+    /// bond zero net basis and an implied repo equal to the repo rate,
+    /// making it CTD against a bond whose futures-implied carry is worse.
+    #[test]
+    fn basis_implied_repo_and_ctd() -> Result<()> {
+        let basket = vec![
+            bond(0.025, d(2025, 2, 15), d(2035, 2, 15), Frequency::Annual),
+            bond(0.02, d(2025, 8, 15), d(2035, 8, 15), Frequency::Annual),
+        ];
+        let fut = future(BondFutureExchange::Eurex, basket);
+        let settle = d(2026, 8, 14);
+        let prices = [96.0, 91.5];
+        let repo = 0.02;
+        let cf0 = fut.conversion_factor(&fut.basket[0])?;
+        let probe = fut.analytics(100.0, settle, &prices, repo)?;
+        let forward = prices[0] - probe[0].carry;
+        let futures_price = forward / cf0;
+        let analytics = fut.analytics(futures_price, settle, &prices, repo)?;
+        assert!(analytics[0].net_basis.abs() < 1e-10);
+        assert!((analytics[0].implied_repo - repo).abs() < 1e-10);
+        // By hand: CFs 0.778654 and 0.735245 at the 6% notional coupon, so
+        // the futures price is 95.829196 / 0.778654 = 123.070319. The 2%
+        // bond pays its 2.0 coupon on 15 Aug and forwards to 91.453333,
+        // a net basis of 0.966496 and an implied repo of −1.2221%.
+        assert_eq!(cf0, 0.778654);
+        assert_eq!(fut.conversion_factor(&fut.basket[1])?, 0.735245);
+        assert!((futures_price - 123.070319).abs() < 1e-6);
+        assert!((analytics[1].net_basis - 0.966496).abs() < 1e-6);
+        assert!((analytics[1].implied_repo + 0.012221).abs() < 1e-6);
+        let ctd = fut.cheapest_to_deliver(futures_price, settle, &prices, repo)?;
+        assert_eq!(ctd.index, 0);
+        let invoice = fut.invoice_amount(0, futures_price)?;
+        let accrued = fut.basket[0].accrued_interest(fut.delivery_date()?, None)?;
+        assert!((invoice - 1000.0 * (futures_price * cf0 + accrued)).abs() < 1e-8);
+
+        // Off a curve the future follows its CTD's forward DV01.
+        let valuation_date = d(2026, 8, 12);
+        let curve = zero_rate_curve(
+            valuation_date,
+            &[(d(2027, 1, 1), 0.028), (d(2040, 1, 1), 0.028)],
+        );
+        let market = IrMarketContext::new(valuation_date, Currency::EUR, curve, None);
+        let (ctd, price) = fut.theoretical_price(&market.curve)?;
+        let cf = fut.conversion_factor(&fut.basket[ctd])?;
+        let forward_dv01 = fut.curve_forward(ctd, &market.curve, 1.0)?
+            - fut.curve_forward(ctd, &market.curve, 0.0)?;
+        let dv01 = fut.dv01(&market)?;
+        assert!((dv01 - 10.0 * 1000.0 * forward_dv01 / cf).abs() < 1e-8);
+        assert!(dv01 < 0.0);
+        let mtm = fut.mtm(&market)?.value;
+        assert!((mtm - 10.0 * 1000.0 * (price - 130.0)).abs() < 1e-6);
+        Ok(())
+    }
+}
@@ -15,11 +15,16 @@
 //!     V_k = σ² · (T_s^k − t)                                      (forward-looking)
 //!         = σ² · [ (T_s^k − t) + (T_e^k − T_s^k) / 3 ]            (backward-compounded, t ≤ T_s)
 //! ```
+//!
+//! The same caplet machinery prices a [`Collar`] (long cap, short floor,
+//! one trade) and [`DigitalCapFloor`] strips, which replicate each
+//! cash-or-nothing caplet with a tight call spread on the smile.
 
 use crate::derivatives::basic::BasicInfo;
 use crate::derivatives::forex::basic::CurrencyValue;
 use crate::derivatives::interestrate::basic::{
-    CapFloorKind, CapStyle, IRDerivatives, RateShiftMode, caplet_total_variance,
+    CapFloorKind, CapStyle, CashflowKind, IRDerivatives, InterestRateCashflow, RateShiftMode,
+    caplet_total_variance,
 };
 use crate::derivatives::interestrate::swap::InterestRateSchedulePeriod;
 use crate::error::Error;
@@ -66,28 +71,18 @@ impl CapFloor {
         rate_shift_bp: f64,
         vol_shift_bp: f64,
     ) -> Result<CapletMarket> {
-        let method = &InterpolationMethodEnum::StepFunctionForward;
-        let tau = self
-            .accrual_day_counter
-            .year_fraction(period.accrual_start_date, period.accrual_end_date)?;
-        let df_start = yts.shifted_discount(period.accrual_start_date, method, rate_shift_bp)?;
-        let df_end = yts.shifted_discount(period.accrual_end_date, method, rate_shift_bp)?;
-        let df_pay = yts.shifted_discount(period.pay_date, method, rate_shift_bp)?;
-        let forward = (df_start / df_end - 1.0) / tau;
-
-        let vol_time = Actual365Fixed::default();
-        let yf_start = vol_time.year_fraction(self.valuation_date, period.accrual_start_date)?;
-        let yf_end = vol_time.year_fraction(self.valuation_date, period.accrual_end_date)?;
-        let sigma_base = vs.caplet_volatility(period.accrual_start_date, self.strike)?;
-        let sigma = sigma_base + vol_shift_bp * 1.0e-4;
-        let variance = caplet_total_variance(self.style, sigma, yf_start, yf_end);
-
-        Ok(CapletMarket {
-            tau,
-            df_pay,
-            forward,
-            variance,
-        })
+        caplet_market(
+            &CapletStrip {
+                style: self.style,
+                valuation_date: self.valuation_date,
+                accrual_day_counter: self.accrual_day_counter.as_ref(),
+            },
+            period,
+            self.strike,
+            yts,
+            vs,
+            (rate_shift_bp, vol_shift_bp),
+        )
     }
 
     /// Present value under an optional (rate, vol) parallel shift. The
@@ -99,24 +94,121 @@ impl CapFloor {
         rate_shift_bp: f64,
         vol_shift_bp: f64,
     ) -> Result<f64> {
-        let mut pv = 0.0_f64;
-        for period in &self.schedule {
-            let m = self.caplet_market(period, yts, vs, rate_shift_bp, vol_shift_bp)?;
-            let opt = match self.kind {
-                CapFloorKind::Cap => bachelier_call(m.forward, self.strike, m.variance),
-                CapFloorKind::Floor => bachelier_put(m.forward, self.strike, m.variance),
-            };
-            pv += self.direction_sign() * self.notional * m.tau * m.df_pay * opt;
-        }
-        Ok(pv)
+        Ok(present_value(&self.cashflows_under_shift(
+            yts,
+            vs,
+            rate_shift_bp,
+            vol_shift_bp,
+        )?))
+    }
+
+    /// Expected caplet payoffs at their pay dates under the same shifts.
+    fn cashflows_under_shift(
+        &self,
+        yts: &YieldTermStructure,
+        vs: &IRNormalVolSurface,
+        rate_shift_bp: f64,
+        vol_shift_bp: f64,
+    ) -> Result<Vec<InterestRateCashflow>> {
+        self.schedule
+            .iter()
+            .map(|period| {
+                let m = self.caplet_market(period, yts, vs, rate_shift_bp, vol_shift_bp)?;
+                let opt = match self.kind {
+                    CapFloorKind::Cap => bachelier_call(m.forward, self.strike, m.variance),
+                    CapFloorKind::Floor => bachelier_put(m.forward, self.strike, m.variance),
+                };
+                Ok(caplet_cashflow(
+                    period,
+                    self.valuation_date,
+                    &m,
+                    self.notional,
+                    self.direction_sign() * self.notional * m.tau * opt,
+                ))
+            })
+            .collect()
     }
 }
 
-struct CapletMarket {
-    tau: f64,
-    df_pay: f64,
-    forward: f64,
-    variance: f64,
+/// Sum of the flows' present values.
+pub(crate) fn present_value(flows: &[InterestRateCashflow]) -> f64 {
+    flows.iter().map(|cf| cf.present_value).sum()
+}
+
+/// Flow of an option on the fixing of `period`, paying `amount` in
+/// expectation; classified by [`CashflowKind::of_fixing`].
+fn caplet_cashflow(
+    period: &InterestRateSchedulePeriod,
+    valuation_date: chrono::NaiveDate,
+    m: &CapletMarket,
+    notional: f64,
+    amount: f64,
+) -> InterestRateCashflow {
+    let (kind, fixing_date) = CashflowKind::of_fixing(period, valuation_date);
+    InterestRateCashflow::coupon(
+        kind,
+        fixing_date,
+        period,
+        m.forward,
+        notional,
+        amount,
+        m.df_pay,
+    )
+}
+
+/// Conventions shared by every caplet of a strip.
+pub(crate) struct CapletStrip<'a> {
+    pub(crate) style: CapStyle,
+    pub(crate) valuation_date: chrono::NaiveDate,
+    pub(crate) accrual_day_counter: &'a dyn DayCounters,
+}
+
+pub(crate) struct CapletMarket {
+    pub(crate) tau: f64,
+    pub(crate) df_pay: f64,
+    pub(crate) forward: f64,
+    pub(crate) variance: f64,
+}
+
+/// Total variance of the caplet on `period` struck at `strike`, with the
+/// surface vol moved by `vol_shift_bp`.
+pub(crate) fn caplet_variance(
+    strip: &CapletStrip,
+    period: &InterestRateSchedulePeriod,
+    strike: f64,
+    vs: &IRNormalVolSurface,
+    vol_shift_bp: f64,
+) -> Result<f64> {
+    let vol_time = Actual365Fixed::default();
+    let yf_start = vol_time.year_fraction(strip.valuation_date, period.accrual_start_date)?;
+    let yf_end = vol_time.year_fraction(strip.valuation_date, period.accrual_end_date)?;
+    let sigma = vs.caplet_volatility(period.accrual_start_date, strike)? + vol_shift_bp * 1.0e-4;
+    Ok(caplet_total_variance(strip.style, sigma, yf_start, yf_end))
+}
+
+/// Per-caplet market inputs at `strike` under a parallel
+/// `(rate, vol)` shift in basis points.
+pub(crate) fn caplet_market(
+    strip: &CapletStrip,
+    period: &InterestRateSchedulePeriod,
+    strike: f64,
+    yts: &YieldTermStructure,
+    vs: &IRNormalVolSurface,
+    (rate_shift_bp, vol_shift_bp): (f64, f64),
+) -> Result<CapletMarket> {
+    let method = &InterpolationMethodEnum::StepFunctionForward;
+    let tau = strip
+        .accrual_day_counter
+        .year_fraction(period.accrual_start_date, period.accrual_end_date)?;
+    let df_start = yts.shifted_discount(period.accrual_start_date, method, rate_shift_bp)?;
+    let df_end = yts.shifted_discount(period.accrual_end_date, method, rate_shift_bp)?;
+    let df_pay = yts.shifted_discount(period.pay_date, method, rate_shift_bp)?;
+    Ok(CapletMarket {
+        tau,
+        df_pay,
+        forward: (df_start / df_end - 1.0) / tau,
+        variance: caplet_variance(strip, period, strike, vs, vol_shift_bp)?,
+    })
 }
 
 /// Unpack the IR context into (curve, vol surface), erroring cleanly
@@ -177,9 +269,255 @@ impl IRDerivatives for CapFloor {
         let up = self.pv_under_shift(yts, vs, 0.0, vol_shift_bp)?;
         Ok(up - base)
     }
+
+    /// One projected flow per caplet: its expected payoff, with the
+    /// forward as rate.
+    fn cashflows(&self, market: &IrMarketContext) -> Result<Vec<InterestRateCashflow>> {
+        let (yts, vs) = unpack(market)?;
+        self.cashflows_under_shift(yts, vs, 0.0, 0.0)
+    }
+}
+
+/// Long cap at `cap_strike` and short floor at `floor_strike` on one
+/// schedule, booked as a single trade; `Direction::Sell` is the reverse
+/// collar. Each leg reads its own strike off the smile.
+#[derive(Deserialize, Serialize, Debug)]
+pub struct Collar {
+    pub basic_info: BasicInfo,
+    pub style: CapStyle,
+    pub currency: Currency,
+    pub notional: f64,
+    pub cap_strike: f64,
+    pub floor_strike: f64,
+    pub valuation_date: chrono::NaiveDate,
+    pub schedule: Vec<InterestRateSchedulePeriod>,
+    pub accrual_day_counter: Box<dyn DayCounters>,
+}
+
+impl Collar {
+    fn pv_under_shift(
+        &self,
+        yts: &YieldTermStructure,
+        vs: &IRNormalVolSurface,
+        rate_shift_bp: f64,
+        vol_shift_bp: f64,
+    ) -> Result<f64> {
+        Ok(present_value(&self.cashflows_under_shift(
+            yts,
+            vs,
+            rate_shift_bp,
+            vol_shift_bp,
+        )?))
+    }
+
+    fn cashflows_under_shift(
+        &self,
+        yts: &YieldTermStructure,
+        vs: &IRNormalVolSurface,
+        rate_shift_bp: f64,
+        vol_shift_bp: f64,
+    ) -> Result<Vec<InterestRateCashflow>> {
+        let strip = CapletStrip {
+            style: self.style,
+            valuation_date: self.valuation_date,
+            accrual_day_counter: self.accrual_day_counter.as_ref(),
+        };
+        let shifts = (rate_shift_bp, vol_shift_bp);
+        let sign = self.basic_info.direction as i8 as f64;
+        self.schedule
+            .iter()
+            .map(|period| {
+                let cap = caplet_market(&strip, period, self.cap_strike, yts, vs, shifts)?;
+                let floor = caplet_market(&strip, period, self.floor_strike, yts, vs, shifts)?;
+                let opt = bachelier_call(cap.forward, self.cap_strike, cap.variance)
+                    - bachelier_put(floor.forward, self.floor_strike, floor.variance);
+                Ok(caplet_cashflow(
+                    period,
+                    self.valuation_date,
+                    &cap,
+                    self.notional,
+                    sign * self.notional * cap.tau * opt,
+                ))
+            })
+            .collect()
+    }
+}
+
+impl IRDerivatives for Collar {
+    fn mtm(&self, market: &IrMarketContext) -> Result<CurrencyValue> {
+        let (yts, vs) = unpack(market)?;
+        Ok(CurrencyValue {
+            currency: self.currency,
+            value: self.pv_under_shift(yts, vs, 0.0, 0.0)?,
+        })
+    }
+
+    fn dv01(&self, market: &IrMarketContext) -> Result<f64> {
+        let (yts, vs) = unpack(market)?;
+        Ok(self.pv_under_shift(yts, vs, 1.0, 0.0)? - self.pv_under_shift(yts, vs, 0.0, 0.0)?)
+    }
+
+    fn gamma(
+        &self,
+        market: &IrMarketContext,
+        rate_shift_bp: f64,
+        mode: RateShiftMode,
+    ) -> Result<f64> {
+        ensure_supported_mode(mode)?;
+        let (yts, vs) = unpack(market)?;
+        let base = self.pv_under_shift(yts, vs, 0.0, 0.0)?;
+        let up = self.pv_under_shift(yts, vs, rate_shift_bp, 0.0)?;
+        let down = self.pv_under_shift(yts, vs, -rate_shift_bp, 0.0)?;
+        Ok(up + down - 2.0 * base)
+    }
+
+    /// Parallel vol bump on both legs; the long cap and short floor
+    /// offset, so a near-zero-cost collar has small vega.
+    fn vega(&self, market: &IrMarketContext, vol_shift_bp: f64) -> Result<f64> {
+        let (yts, vs) = unpack(market)?;
+        Ok(self.pv_under_shift(yts, vs, 0.0, vol_shift_bp)?
+            - self.pv_under_shift(yts, vs, 0.0, 0.0)?)
+    }
+
+    /// One projected flow per period: the caplet less the floorlet.
+    fn cashflows(&self, market: &IrMarketContext) -> Result<Vec<InterestRateCashflow>> {
+        let (yts, vs) = unpack(market)?;
+        self.cashflows_under_shift(yts, vs, 0.0, 0.0)
+    }
+}
+
+/// Half-width of the call spread that replicates a digital caplet.
+pub const DIGITAL_SPREAD: f64 = 1.0e-4;
+
+/// Cash-or-nothing caplets / floorlets: each period pays
+/// `payout · τ · N` when the fixing ends above (cap) or below (floor)
+/// `strike`. Replicated with a tight call (put) spread around the
+/// strike, each option at its own smile vol, so the skew enters:
+///
+/// ```text
+///     D_cap(K)   ≈ [C(K − ε) − C(K + ε)] / 2ε
+///     D_floor(K) ≈ [P(K + ε) − P(K − ε)] / 2ε,     ε = DIGITAL_SPREAD
+/// ```
+#[derive(Deserialize, Serialize, Debug)]
+pub struct DigitalCapFloor {
+    pub basic_info: BasicInfo,
+    pub kind: CapFloorKind,
+    pub style: CapStyle,
+    pub currency: Currency,
+    pub notional: f64,
+    pub strike: f64,
+    /// Rate paid over the accrual period when the digital is in the
+    /// money.
+    pub payout: f64,
+    pub valuation_date: chrono::NaiveDate,
+    pub schedule: Vec<InterestRateSchedulePeriod>,
+    pub accrual_day_counter: Box<dyn DayCounters>,
+}
+
+impl DigitalCapFloor {
+    fn pv_under_shift(
+        &self,
+        yts: &YieldTermStructure,
+        vs: &IRNormalVolSurface,
+        rate_shift_bp: f64,
+        vol_shift_bp: f64,
+    ) -> Result<f64> {
+        Ok(present_value(&self.cashflows_under_shift(
+            yts,
+            vs,
+            rate_shift_bp,
+            vol_shift_bp,
+        )?))
+    }
+
+    fn cashflows_under_shift(
+        &self,
+        yts: &YieldTermStructure,
+        vs: &IRNormalVolSurface,
+        rate_shift_bp: f64,
+        vol_shift_bp: f64,
+    ) -> Result<Vec<InterestRateCashflow>> {
+        let strip = CapletStrip {
+            style: self.style,
+            valuation_date: self.valuation_date,
+            accrual_day_counter: self.accrual_day_counter.as_ref(),
+        };
+        let shifts = (rate_shift_bp, vol_shift_bp);
+        let (low, high) = (self.strike - DIGITAL_SPREAD, self.strike + DIGITAL_SPREAD);
+        let scale = self.basic_info.direction as i8 as f64 * self.notional * self.payout;
+        self.schedule
+            .iter()
+            .map(|period| {
+                let lo = caplet_market(&strip, period, low, yts, vs, shifts)?;
+                let hi = caplet_market(&strip, period, high, yts, vs, shifts)?;
+                let spread = match self.kind {
+                    CapFloorKind::Cap => {
+                        bachelier_call(lo.forward, low, lo.variance)
+                            - bachelier_call(hi.forward, high, hi.variance)
+                    }
+                    CapFloorKind::Floor => {
+                        bachelier_put(hi.forward, high, hi.variance)
+                            - bachelier_put(lo.forward, low, lo.variance)
+                    }
+                };
+                let amount = scale * lo.tau * spread / (2.0 * DIGITAL_SPREAD);
+                Ok(caplet_cashflow(
+                    period,
+                    self.valuation_date,
+                    &lo,
+                    self.notional,
+                    amount,
+                ))
+            })
+            .collect()
+    }
 }
 
-fn ensure_supported_mode(mode: RateShiftMode) -> Result<()> {
+impl IRDerivatives for DigitalCapFloor {
+    fn mtm(&self, market: &IrMarketContext) -> Result<CurrencyValue> {
+        let (yts, vs) = unpack(market)?;
+        Ok(CurrencyValue {
+            currency: self.currency,
+            value: self.pv_under_shift(yts, vs, 0.0, 0.0)?,
+        })
+    }
+
+    fn dv01(&self, market: &IrMarketContext) -> Result<f64> {
+        let (yts, vs) = unpack(market)?;
+        Ok(self.pv_under_shift(yts, vs, 1.0, 0.0)? - self.pv_under_shift(yts, vs, 0.0, 0.0)?)
+    }
+
+    fn gamma(
+        &self,
+        market: &IrMarketContext,
+        rate_shift_bp: f64,
+        mode: RateShiftMode,
+    ) -> Result<f64> {
+        ensure_supported_mode(mode)?;
+        let (yts, vs) = unpack(market)?;
+        let base = self.pv_under_shift(yts, vs, 0.0, 0.0)?;
+        let up = self.pv_under_shift(yts, vs, rate_shift_bp, 0.0)?;
+        let down = self.pv_under_shift(yts, vs, -rate_shift_bp, 0.0)?;
+        Ok(up + down - 2.0 * base)
+    }
+
+    /// Parallel vol bump. Out-of-the-money digitals gain from vol,
+    /// in-the-money ones lose.
+    fn vega(&self, market: &IrMarketContext, vol_shift_bp: f64) -> Result<f64> {
+        let (yts, vs) = unpack(market)?;
+        Ok(self.pv_under_shift(yts, vs, 0.0, vol_shift_bp)?
+            - self.pv_under_shift(yts, vs, 0.0, 0.0)?)
+    }
+
+    /// One projected flow per period: the payout times the replicated
+    /// probability of finishing in the money.
+    fn cashflows(&self, market: &IrMarketContext) -> Result<Vec<InterestRateCashflow>> {
+        let (yts, vs) = unpack(market)?;
+        self.cashflows_under_shift(yts, vs, 0.0, 0.0)
+    }
+}
+
+pub(crate) fn ensure_supported_mode(mode: RateShiftMode) -> Result<()> {
     match mode {
         RateShiftMode::Zeros => Ok(()),
         other => Err(Error::InvalidData(format!(
@@ -241,23 +579,34 @@ fn analytic_vega_1bp(
 
 #[cfg(test)]
 mod tests {
-    use super::{CapFloor, CapFloorKind, CapStyle, IRDerivatives};
+    use super::{CapFloor, CapFloorKind, CapStyle, Collar, DigitalCapFloor, IRDerivatives};
     use crate::derivatives::basic::{BasicInfo, Direction, Style};
     use crate::derivatives::interestrate::basic::{
         DEFAULT_RATE_SHIFT_BP, DEFAULT_VOL_SHIFT_BP, RateShiftMode,
     };
     use crate::derivatives::interestrate::swap::InterestRateSchedulePeriod;
+    use crate::derivatives::interestrate::swap::{
+        InterestRateSwap, InterestRateSwapLeg, InterestRateSwapLegType, ScheduleDetail,
+    };
     use crate::error::Result;
+    use crate::markets::interestrate::interestrateindex::{
+        InterestRateIndex, InterestRateIndexEnum,
+    };
     use crate::markets::interestrate::market_context::IrMarketContext;
     use crate::markets::interestrate::volsurface::{
         CapQuote, CapletVolPillar, IRCapMarketData, IRNormalVolSurface,
     };
     use crate::markets::termstructures::yieldcurve::{
-        InterestRateQuoteEnum, StrippedCurve, YieldTermStructure,
+        InterestRateQuoteEnum, InterpolationMethodEnum, StrippedCurve, YieldTermStructure,
     };
+    use crate::models::common::vol_conversion::VolQuoteType;
+    use crate::time::businessdayconvention::BusinessDayConvention;
     use crate::time::calendars::UnitedStates;
+    use crate::time::daycounters::DayCounters;
     use crate::time::daycounters::actual360::Actual360;
     use crate::time::daycounters::actual365fixed::Actual365Fixed;
+    use crate::time::frequency::Frequency;
+    use crate::time::period::Period;
     use chrono::NaiveDate;
     use iso_currency::Currency;
 
@@ -410,6 +759,44 @@ mod tests {
         Ok(())
     }
 
+    /// Flat-vol quote conversion on the reference cap: the flat normal
+    /// vol equals the single stripped σ, and lognormal / shifted flat vols
+    /// implied from the same NPV reprice it exactly.
+    #[test]
+    fn flat_cap_vol_converts_between_quote_conventions() -> Result<()> {
+        let curve_date = NaiveDate::from_ymd_opt(2026, 4, 22).unwrap();
+        let yts = build_expected_usd_sofr_curve(curve_date, curve_date);
+        let make_quote = || CapQuote {
+            strike: 0.03543236,
+            notional: 10_000_000.0,
+            direction: Direction::Buy,
+            kind: CapFloorKind::Cap,
+            style: CapStyle::BackwardCompounded,
+            currency: Currency::USD,
+            schedule: expected_sofr_5y_schedule(),
+            accrual_day_counter: Box::new(Actual360),
+            market_npv: 237_665.49,
+        };
+        let quote = make_quote();
+        let normal = quote.flat_vol(&yts, curve_date, VolQuoteType::Normal)?;
+        let mut vs = IRNormalVolSurface::new(curve_date);
+        vs.rebuild(&yts, &IRCapMarketData::new(curve_date, vec![make_quote()]))?;
+        assert!((normal - vs.pillars[0].nodes[0].1).abs() < 1e-8);
+
+        for quote_type in [
+            VolQuoteType::Lognormal,
+            VolQuoteType::ShiftedLognormal { shift: 0.02 },
+        ] {
+            let vol = quote.flat_vol(&yts, curve_date, quote_type)?;
+            let npv = quote.npv_from_flat_vol(&yts, curve_date, vol, quote_type)?;
+            assert!((npv - quote.market_npv).abs() < 1e-4, "{quote_type:?}");
+            // ATM: σ_N ≈ σ_SLN · (F + s) to within a few percent.
+            let s = quote_type.shift();
+            assert!((vol * (quote.strike + s) / normal - 1.0).abs() < 0.05);
+        }
+        Ok(())
+    }
+
     /// Unsupported shift modes must return an error rather than silently
     /// falling back to `Zeros`.
     #[test]
@@ -587,6 +974,137 @@ mod tests {
         Ok(())
     }
 
+    /// Collars and digitals decompose into vanilla caplets on the same
+    /// smile, and a float leg capped and floored far from the money is
+    /// the plain float leg while a live floor adds value and vega.
+    #[test]
+    fn collar_digital_and_capped_leg_decompose() -> Result<()> {
+        let valuation_date = NaiveDate::from_ymd_opt(2026, 4, 22).unwrap();
+        let d = |y, m, dd| NaiveDate::from_ymd_opt(y, m, dd).unwrap();
+        let mut vs = IRNormalVolSurface::new(valuation_date);
+        vs.pillars = vec![CapletVolPillar {
+            expiry: d(2031, 1, 24),
+            nodes: vec![(0.025, 0.0098), (0.035, 0.0085), (0.045, 0.0091)],
+        }];
+        let ctx = IrMarketContext::new(
+            valuation_date,
+            Currency::USD,
+            build_expected_usd_sofr_curve(valuation_date, valuation_date),
+            Some(vs),
+        );
+        let basic_info = || BasicInfo {
+            trade_date: valuation_date,
+            style: Style::IRSwap,
+            direction: Direction::Buy,
+            expiry_date: d(2031, 4, 24),
+            delivery_date: d(2031, 4, 28),
+        };
+        let vanilla = |kind, strike| CapFloor {
+            basic_info: basic_info(),
+            kind,
+            style: CapStyle::BackwardCompounded,
+            currency: Currency::USD,
+            notional: 1.0e7,
+            strike,
+            valuation_date,
+            schedule: expected_sofr_5y_schedule(),
+            accrual_day_counter: Box::new(Actual360),
+        };
+        let collar = Collar {
+            basic_info: basic_info(),
+            style: CapStyle::BackwardCompounded,
+            currency: Currency::USD,
+            notional: 1.0e7,
+            cap_strike: 0.042,
+            floor_strike: 0.03,
+            valuation_date,
+            schedule: expected_sofr_5y_schedule(),
+            accrual_day_counter: Box::new(Actual360),
+        };
+        let legs = vanilla(CapFloorKind::Cap, 0.042).mtm(&ctx)?.value
+            - vanilla(CapFloorKind::Floor, 0.03).mtm(&ctx)?.value;
+        assert!((collar.mtm(&ctx)?.value - legs).abs() < 1e-6);
+
+        let digital = |kind| DigitalCapFloor {
+            basic_info: basic_info(),
+            kind,
+            style: CapStyle::BackwardCompounded,
+            currency: Currency::USD,
+            notional: 1.0e7,
+            strike: 0.036,
+            payout: 0.01,
+            valuation_date,
+            schedule: expected_sofr_5y_schedule(),
+            accrual_day_counter: Box::new(Actual360),
+        };
+        let (up, down) = (
+            digital(CapFloorKind::Cap).mtm(&ctx)?.value,
+            digital(CapFloorKind::Floor).mtm(&ctx)?.value,
+        );
+        let mut annuity = 0.0;
+        for period in &expected_sofr_5y_schedule() {
+            let tau =
+                Actual360.year_fraction(period.accrual_start_date, period.accrual_end_date)?;
+            annuity += tau
+                * ctx.curve.discount(
+                    period.pay_date,
+                    &InterpolationMethodEnum::StepFunctionForward,
+                )?;
+        }
+        assert!(up > 0.0 && down > 0.0);
+        assert!((up + down - 0.01 * 1.0e7 * annuity).abs() < 1e-4);
+
+        let leg = |swap_type, direction| {
+            InterestRateSwapLeg::new(
+                swap_type,
+                direction,
+                InterestRateIndex::from_enum(InterestRateIndexEnum::SOFR).unwrap(),
+                1.0e7,
+                ScheduleDetail::new(
+                    Frequency::Quarterly,
+                    Period::Months(3),
+                    Period::Years(5),
+                    Box::new(Actual360),
+                    Box::new(UnitedStates::default()),
+                    BusinessDayConvention::ModifiedFollowing,
+                    2,
+                    2,
+                    0,
+                ),
+                expected_sofr_5y_schedule(),
+            )
+        };
+        let against_float = |cap, floor| {
+            InterestRateSwap::new(vec![
+                leg(
+                    InterestRateSwapLegType::CappedFloored {
+                        spread: 0.001,
+                        cap,
+                        floor,
+                        style: CapStyle::BackwardCompounded,
+                    },
+                    Direction::Buy,
+                ),
+                leg(
+                    InterestRateSwapLegType::Float { spread: 0.001 },
+                    Direction::Sell,
+                ),
+            ])
+        };
+        let wide = against_float(Some(0.5), Some(-0.5));
+        assert!(wide.mtm(&ctx)?.value.abs() < 1e-6);
+        let floored = against_float(None, Some(0.035));
+        let floor_value = floored.mtm(&ctx)?.value;
+        let floor_cap = vanilla(CapFloorKind::Floor, 0.034).mtm(&ctx)?.value;
+        assert!(
+            (floor_value / floor_cap - 1.0).abs() < 0.05,
+            "{floor_value} vs {floor_cap}"
+        );
+        assert!(floored.vega(&ctx, DEFAULT_VOL_SHIFT_BP)? > 0.0);
+        assert!(against_float(Some(0.04), None).mtm(&ctx)?.value < 0.0);
+        Ok(())
+    }
+
     /// Helper: a StrippedCurve pillar from (first_settle, date, market, zero, discount).
     fn pillar(
         first_settle: NaiveDate,
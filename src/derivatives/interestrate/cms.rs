@@ -0,0 +1,406 @@
+//! CMS caps / floors and CMS spread options, replicated over the swaption
+//! cube carried by [`IrMarketContext::cms`]; see
+//! [`crate::models::interestrate::cms`] for the convexity machinery.
+//!
+//! Each period fixes the swap rate of `tenor` (or the spread between two
+//! tenors) at its reset date on a swap starting at accrual start, and
+//! pays at the pay date:
+//!
+//! ```text
+//!     PV = direction · Σ_k τ_k · N · DF(T_pay^k) · E^{T_pay}[(±(X_k − K))⁺]
+//!     X_k = S_tenor(T_reset^k)                                  (CMS cap / floor)
+//!         = S_long(T_reset^k) − S_short(T_reset^k)              (CMS spread option)
+//! ```
+//!
+//! The swaption smile for each fixing is read off the cube at the fixing
+//! time and swap tenor around the curve's forward swap rate. Vega bumps
+//! every cube vol in parallel.
+
+use chrono::NaiveDate;
+use iso_currency::Currency;
+use serde::{Deserialize, Serialize};
+
+use crate::derivatives::basic::BasicInfo;
+use crate::derivatives::forex::basic::CurrencyValue;
+use crate::derivatives::interestrate::basic::{
+    CapFloorKind, CashflowKind, IRDerivatives, InterestRateCashflow, RateShiftMode,
+};
+use crate::derivatives::interestrate::cap::present_value;
+use crate::derivatives::interestrate::swap::InterestRateSchedulePeriod;
+use crate::error::{Error, Result};
+use crate::markets::interestrate::market_context::IrMarketContext;
+use crate::markets::termstructures::yieldcurve::{InterpolationMethodEnum, YieldTermStructure};
+use crate::models::interestrate::cms::{CmsCoupon, CmsMarket, NormalSmile, cms_spread_option};
+use crate::time::daycounters::DayCounters;
+use crate::time::daycounters::actual365fixed::Actual365Fixed;
+use crate::time::frequency::Frequency;
+use crate::time::period::Period;
+
+/// Swap-rate fixing for `period` off `yts` under a parallel zero-rate
+/// shift, with its smile under a parallel normal-vol shift.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn cms_fixing(
+    valuation_date: NaiveDate,
+    period: &InterestRateSchedulePeriod,
+    tenor: Period,
+    fixed_frequency: &Frequency,
+    yts: &YieldTermStructure,
+    cms: &CmsMarket,
+    rate_shift_bp: f64,
+    vol_shift_bp: f64,
+) -> Result<(CmsCoupon, NormalSmile)> {
+    let method = &InterpolationMethodEnum::PiecewiseLinearContinuous;
+    let time = |date: NaiveDate| Actual365Fixed::default().year_fraction(valuation_date, date);
+    let point = |date: NaiveDate| -> Result<(f64, f64)> {
+        Ok((
+            time(date)?,
+            yts.shifted_discount(date, method, rate_shift_bp)?,
+        ))
+    };
+    let step = fixed_frequency
+        .period()
+        .ok_or_else(|| Error::InvalidData(format!("no fixed-leg period for {}", fixed_frequency)))?
+        .months()?;
+    let total = tenor.months()?;
+    if step == 0 || total % step != 0 {
+        return Err(Error::InvalidData(format!(
+            "{:?} swap does not split into {} periods",
+            tenor, fixed_frequency
+        )));
+    }
+    let start = period.accrual_start_date;
+    let fixed_leg = (1..=total / step)
+        .map(|i| point((start + Period::Months(i * step))?))
+        .collect::<Result<Vec<_>>>()?;
+    let coupon = CmsCoupon {
+        expiry: time(period.reset_date)?,
+        start: point(start)?,
+        fixed_leg,
+        payment: point(period.pay_date)?,
+    };
+    let smile = cms
+        .swaptions
+        .normal_smile(
+            coupon.expiry,
+            total as f64 / 12.0,
+            coupon.forward_swap_rate(),
+        )
+        .ok_or_else(|| {
+            Error::InvalidData("CMS pricing needs a non-empty swaption cube".to_string())
+        })?
+        .shifted(vol_shift_bp * 1.0e-4);
+    Ok((coupon, smile))
+}
+
+/// What each period of a [`CmsCapFloor`] is struck on.
+#[derive(Deserialize, Serialize, Copy, Clone, Debug, PartialEq)]
+pub enum CmsUnderlying {
+    /// Swap rate of `tenor`.
+    Rate { tenor: Period },
+    /// `S_long − S_short`, with the two rates' normal correlation.
+    Spread {
+        long_tenor: Period,
+        short_tenor: Period,
+        correlation: f64,
+    },
+}
+
+/// Strip of CMS caplets / floorlets, or of CMS spread options (a cap on
+/// the spread is a strip of calls).
+#[derive(Deserialize, Serialize, Debug)]
+pub struct CmsCapFloor {
+    pub basic_info: BasicInfo,
+    pub kind: CapFloorKind,
+    pub underlying: CmsUnderlying,
+    /// Fixed-leg frequency of the underlying swaps.
+    pub fixed_frequency: Frequency,
+    pub currency: Currency,
+    pub notional: f64,
+    pub strike: f64,
+    pub valuation_date: NaiveDate,
+    pub schedule: Vec<InterestRateSchedulePeriod>,
+    pub accrual_day_counter: Box<dyn DayCounters>,
+}
+
+impl CmsCapFloor {
+    fn direction_sign(&self) -> f64 {
+        self.basic_info.direction as i8 as f64
+    }
+
+    /// Present value under a parallel (rate, vol) shift.
+    fn pv_under_shift(
+        &self,
+        market: &IrMarketContext,
+        rate_shift_bp: f64,
+        vol_shift_bp: f64,
+    ) -> Result<f64> {
+        Ok(present_value(&self.cashflows_under_shift(
+            market,
+            rate_shift_bp,
+            vol_shift_bp,
+        )?))
+    }
+
+    /// Expected payoff of each unpaid period under the same shifts, with
+    /// the forward swap rate (or spread of forwards) as rate.
+    fn cashflows_under_shift(
+        &self,
+        market: &IrMarketContext,
+        rate_shift_bp: f64,
+        vol_shift_bp: f64,
+    ) -> Result<Vec<InterestRateCashflow>> {
+        let cms = market.cms.as_ref().ok_or_else(|| {
+            Error::InvalidData("CmsCapFloor: IrMarketContext.cms must be present".to_string())
+        })?;
+        let is_call = self.kind == CapFloorKind::Cap;
+        let fixing = |period: &InterestRateSchedulePeriod, tenor: Period| {
+            cms_fixing(
+                self.valuation_date,
+                period,
+                tenor,
+                &self.fixed_frequency,
+                &market.curve,
+                cms,
+                rate_shift_bp,
+                vol_shift_bp,
+            )
+        };
+        let mut flows = Vec::new();
+        for period in &self.schedule {
+            if period.pay_date <= self.valuation_date {
+                continue;
+            }
+            let tau = self
+                .accrual_day_counter
+                .year_fraction(period.accrual_start_date, period.accrual_end_date)?;
+            let (payment, forward, option) = match self.underlying {
+                CmsUnderlying::Rate { tenor } => {
+                    let (coupon, smile) = fixing(period, tenor)?;
+                    let option = coupon.option_price(&smile, cms.model, self.strike, is_call)?;
+                    (coupon.payment.1, coupon.forward_swap_rate(), option)
+                }
+                CmsUnderlying::Spread {
+                    long_tenor,
+                    short_tenor,
+                    correlation,
+                } => {
+                    let (long, long_smile) = fixing(period, long_tenor)?;
+                    let (short, short_smile) = fixing(period, short_tenor)?;
+                    let option = cms_spread_option(
+                        (&long, &long_smile),
+                        (&short, &short_smile),
+                        cms.model,
+                        correlation,
+                        self.strike,
+                        is_call,
+                    )?;
+                    let forward = long.forward_swap_rate() - short.forward_swap_rate();
+                    (long.payment.1, forward, option)
+                }
+            };
+            flows.push(InterestRateCashflow::coupon(
+                CashflowKind::Projected,
+                Some(period.reset_date),
+                period,
+                forward,
+                self.notional,
+                self.direction_sign() * self.notional * tau * option,
+                payment,
+            ));
+        }
+        Ok(flows)
+    }
+}
+
+impl IRDerivatives for CmsCapFloor {
+    fn mtm(&self, market: &IrMarketContext) -> Result<CurrencyValue> {
+        Ok(CurrencyValue {
+            currency: self.currency,
+            value: self.pv_under_shift(market, 0.0, 0.0)?,
+        })
+    }
+
+    /// DV01 = PV(y + 1bp) − PV(y), cube vols held fixed against the
+    /// moving forward swap rates.
+    fn dv01(&self, market: &IrMarketContext) -> Result<f64> {
+        Ok(self.pv_under_shift(market, 1.0, 0.0)? - self.pv_under_shift(market, 0.0, 0.0)?)
+    }
+
+    fn gamma(
+        &self,
+        market: &IrMarketContext,
+        rate_shift_bp: f64,
+        mode: RateShiftMode,
+    ) -> Result<f64> {
+        if mode != RateShiftMode::Zeros {
+            return Err(Error::InvalidData(format!(
+                "rate shift mode {:?} is not yet implemented; only Zeros is supported",
+                mode
+            )));
+        }
+        let base = self.pv_under_shift(market, 0.0, 0.0)?;
+        let up = self.pv_under_shift(market, rate_shift_bp, 0.0)?;
+        let down = self.pv_under_shift(market, -rate_shift_bp, 0.0)?;
+        Ok(up + down - 2.0 * base)
+    }
+
+    /// `PV(σ + δ) − PV(σ)` with every swaption-cube vol bumped by `δ`.
+    fn vega(&self, market: &IrMarketContext, vol_shift_bp: f64) -> Result<f64> {
+        Ok(self.pv_under_shift(market, 0.0, vol_shift_bp)?
+            - self.pv_under_shift(market, 0.0, 0.0)?)
+    }
+
+    fn cashflows(&self, market: &IrMarketContext) -> Result<Vec<InterestRateCashflow>> {
+        self.cashflows_under_shift(market, 0.0, 0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::derivatives::basic::{Direction, Style};
+    use crate::derivatives::interestrate::basic::DEFAULT_VOL_SHIFT_BP;
+    use crate::derivatives::interestrate::swap::{
+        InterestRateSwap, InterestRateSwapLeg, InterestRateSwapLegType, ScheduleDetail,
+    };
+    use crate::markets::interestrate::interestrateindex::{
+        InterestRateIndex, InterestRateIndexEnum,
+    };
+    use crate::markets::termstructures::yieldcurve::zero_rate_curve;
+    use crate::models::interestrate::cms::CmsModel;
+    use crate::models::interestrate::market_data::{SwaptionCube, SwaptionQuote};
+    use crate::time::businessdayconvention::BusinessDayConvention;
+    use crate::time::calendars::Target;
+
+    fn d(y: i32, m: u32, dd: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, dd).unwrap()
+    }
+
+    fn market(valuation_date: NaiveDate) -> IrMarketContext {
+        let curve = zero_rate_curve(
+            valuation_date,
+            &[(d(2027, 8, 12), 0.025), (d(2046, 8, 12), 0.035)],
+        );
+        let mut quotes = Vec::new();
+        for (expiry, tenor, atm) in [
+            (1.0, 2.0, 0.0105),
+            (1.0, 10.0, 0.0095),
+            (5.0, 2.0, 0.0098),
+            (5.0, 10.0, 0.0088),
+        ] {
+            for (offset, skew) in [(-0.01, 0.0008), (0.0, 0.0), (0.01, 0.0006)] {
+                quotes.push(SwaptionQuote {
+                    expiry,
+                    tenor,
+                    strike_offset: offset,
+                    normal_vol: atm + skew,
+                });
+            }
+        }
+        IrMarketContext::new(valuation_date, Currency::EUR, curve, None).with_cms(CmsMarket {
+            swaptions: SwaptionCube::new(1, quotes),
+            model: CmsModel::StaticReplication {
+                mean_reversion: 0.02,
+            },
+        })
+    }
+
+    fn schedule() -> Vec<InterestRateSchedulePeriod> {
+        (2..12)
+            .map(|q| {
+                let start = (d(2026, 8, 12) + Period::Months(3 * q)).unwrap();
+                let end = (start + Period::Months(3)).unwrap();
+                InterestRateSchedulePeriod::new(start, end, end, start, 1.0e7, 1.0e7)
+            })
+            .collect()
+    }
+
+    fn leg(swap_type: InterestRateSwapLegType, direction: Direction) -> InterestRateSwapLeg {
+        InterestRateSwapLeg::new(
+            swap_type,
+            direction,
+            InterestRateIndex::from_enum(InterestRateIndexEnum::EUIBOR(Period::Months(3))).unwrap(),
+            1.0e7,
+            ScheduleDetail::new(
+                Frequency::Quarterly,
+                Period::Months(3),
+                Period::Months(30),
+                Box::new(Actual365Fixed::default()),
+                Box::<Target>::default(),
+                BusinessDayConvention::ModifiedFollowing,
+                2,
+                0,
+                0,
+            ),
+            schedule(),
+        )
+    }
+
+    fn option(kind: CapFloorKind, underlying: CmsUnderlying, strike: f64) -> CmsCapFloor {
+        CmsCapFloor {
+            basic_info: BasicInfo {
+                trade_date: d(2026, 8, 12),
+                style: Style::IRSwap,
+                direction: Direction::Buy,
+                expiry_date: d(2029, 2, 12),
+                delivery_date: d(2029, 2, 12),
+            },
+            kind,
+            underlying,
+            fixed_frequency: Frequency::Annual,
+            currency: Currency::EUR,
+            notional: 1.0e7,
+            strike,
+            valuation_date: d(2026, 8, 12),
+            schedule: schedule(),
+            accrual_day_counter: Box::new(Actual365Fixed::default()),
+        }
+    }
+
+    /// Receiving a 10y CMS leg against a fixed leg at `K` is worth the
+    /// CMS cap minus the CMS floor at `K`; CMS legs, caps and spread
+    /// options all gain from higher vol.
+    #[test]
+    fn cms_swap_cap_floor_parity() -> Result<()> {
+        let market = market(d(2026, 8, 12));
+        let strike = 0.033;
+        let ten = CmsUnderlying::Rate {
+            tenor: Period::Years(10),
+        };
+        let cap = option(CapFloorKind::Cap, ten, strike);
+        let floor = option(CapFloorKind::Floor, ten, strike);
+        let swap = InterestRateSwap::new(vec![
+            leg(
+                InterestRateSwapLegType::Cms {
+                    tenor: Period::Years(10),
+                    fixed_frequency: Frequency::Annual,
+                    spread: 0.0,
+                },
+                Direction::Buy,
+            ),
+            leg(
+                InterestRateSwapLegType::Fixed { coupon: strike },
+                Direction::Sell,
+            ),
+        ]);
+        let parity = cap.mtm(&market)?.value - floor.mtm(&market)?.value;
+        let value = swap.mtm(&market)?.value;
+        assert!((value - parity).abs() < 0.1, "{value} vs {parity}");
+        assert!(swap.vega(&market, DEFAULT_VOL_SHIFT_BP)? > 0.0);
+        assert!(cap.vega(&market, DEFAULT_VOL_SHIFT_BP)? > 0.0);
+        assert!(cap.dv01(&market)? > 0.0);
+
+        let spread = option(
+            CapFloorKind::Cap,
+            CmsUnderlying::Spread {
+                long_tenor: Period::Years(10),
+                short_tenor: Period::Years(2),
+                correlation: 0.8,
+            },
+            0.0,
+        );
+        assert!(spread.mtm(&market)?.value > 0.0);
+        assert!(spread.vega(&market, DEFAULT_VOL_SHIFT_BP)? > 0.0);
+        Ok(())
+    }
+}
@@ -0,0 +1,488 @@
+//! Short-term interest-rate futures (SOFR, EURIBOR, …) and quarterly
+//! options on them, held as trades rather than curve inputs (compare
+//! [`crate::markets::termstructures::yieldcurve::ratehelper::FuturesRate`]).
+//!
+//! A future on the rate over `[S, E]` settles daily against its price
+//! `P = 100 · (1 − R)`, where the futures rate is the curve's forward
+//! plus the contract's convexity adjustment. Being margined, its value
+//! is the undiscounted variation margin accumulated since the trade:
+//!
+//! ```text
+//!   R        = (DF(S)/DF(E) − 1)/τ + ca
+//!   tick     = N · α · tick_size / 100          (α = contract accrual, e.g. 0.25)
+//!   PV       = direction · n · (P − P_trade) / tick_size · tick
+//! ```
+//!
+//! An option on the future is Bachelier on the futures rate, a call on
+//! the price being a put on the rate at `K_R = 1 − K/100`. The normal
+//! vol comes from the caplet surface at the option expiry or from the
+//! option's own strike smile. Futures-style margined options (ICE
+//! EURIBOR) are undiscounted; premium-paid ones (CME SOFR) are
+//! discounted from expiry.
+
+use chrono::NaiveDate;
+use iso_currency::Currency;
+use serde::{Deserialize, Serialize};
+
+use crate::derivatives::basic::BasicInfo;
+use crate::derivatives::forex::basic::CurrencyValue;
+use crate::derivatives::interestrate::basic::{
+    CashflowKind, IRDerivatives, InterestRateCashflow, RateShiftMode,
+};
+use crate::derivatives::interestrate::cap::ensure_supported_mode;
+use crate::error::{Error, Result};
+use crate::markets::interestrate::futures::InterestRateFutures;
+use crate::markets::interestrate::market_context::IrMarketContext;
+use crate::markets::termstructures::yieldcurve::{InterpolationMethodEnum, YieldCurve};
+use crate::math::interpolation::interpolate_flat;
+use crate::models::common::bachelier::{bachelier_call, bachelier_put};
+use crate::time::daycounters::DayCounters;
+use crate::time::daycounters::actual365fixed::Actual365Fixed;
+use crate::time::imm::IMM;
+use crate::time::period::Period;
+
+/// Position in a STIR future. `Buy` is long the price (short the rate).
+#[derive(Deserialize, Serialize, Debug)]
+pub struct StirFuture {
+    pub basic_info: BasicInfo,
+    pub currency: Currency,
+    /// Number of contracts.
+    pub contracts: f64,
+    /// Face value per contract.
+    pub notional: f64,
+    /// Nominal accrual the tick value is quoted on: the spec's period in
+    /// years (0.25 for 3M contracts, 1/12 for 1M).
+    pub contract_accrual: f64,
+    /// Minimum price move, in price points.
+    pub tick_size: f64,
+    /// Price the position was traded (or last settled) at.
+    pub trade_price: f64,
+    /// Futures rate minus forward rate.
+    pub convexity_adjustment: f64,
+    pub reference_start: NaiveDate,
+    pub reference_end: NaiveDate,
+    pub index_day_counter: Box<dyn DayCounters>,
+}
+
+impl StirFuture {
+    /// Reference period `[IMM date of imm_code, spec maturity]` seen
+    /// from `valuation_date`, with the spec's day count.
+    #[allow(clippy::too_many_arguments)]
+    pub fn from_imm_code(
+        basic_info: BasicInfo,
+        currency: Currency,
+        contracts: f64,
+        notional: f64,
+        tick_size: f64,
+        trade_price: f64,
+        imm_code: &str,
+        spec: InterestRateFutures,
+        valuation_date: NaiveDate,
+    ) -> Result<Self> {
+        let start = IMM
+            .date(imm_code.to_string(), Some(valuation_date))
+            .ok_or_else(|| Error::InvalidData(format!("invalid IMM code {imm_code}")))?;
+        let end = spec.maturity_date(start)?;
+        let contract_accrual = match spec.period.years() {
+            Ok(years) => years,
+            Err(_) => Actual365Fixed::default().year_fraction(start, end)?,
+        };
+        Ok(Self {
+            basic_info,
+            currency,
+            contracts,
+            notional,
+            contract_accrual,
+            tick_size,
+            trade_price,
+            convexity_adjustment: 0.0,
+            reference_start: start,
+            reference_end: end,
+            index_day_counter: spec.day_counter,
+        })
+    }
+
+    fn direction_sign(&self) -> f64 {
+        self.basic_info.direction as i8 as f64
+    }
+
+    /// Currency value of one tick on one contract.
+    pub fn tick_value(&self) -> f64 {
+        self.notional * self.contract_accrual * self.tick_size / 100.0
+    }
+
+    /// Futures rate off `yts` under a parallel zero-rate shift.
+    pub fn futures_rate(&self, yts: &dyn YieldCurve, rate_shift_bp: f64) -> Result<f64> {
+        let method = &InterpolationMethodEnum::PiecewiseLinearContinuous;
+        let start = self.reference_start.max(yts.valuation_date());
+        let tau = self
+            .index_day_counter
+            .year_fraction(start, self.reference_end)?;
+        if tau <= 0.0 {
+            return Err(Error::InvalidData(format!(
+                "future referencing {} has expired",
+                self.reference_end
+            )));
+        }
+        let forward = (yts.shifted_discount(start, method, rate_shift_bp)?
+            / yts.shifted_discount(self.reference_end, method, rate_shift_bp)?
+            - 1.0)
+            / tau;
+        Ok(forward + self.convexity_adjustment)
+    }
+
+    /// Model price `100 · (1 − R)`.
+    pub fn price(&self, yts: &dyn YieldCurve) -> Result<f64> {
+        Ok(100.0 * (1.0 - self.futures_rate(yts, 0.0)?))
+    }
+
+    /// Variation margin paid to the position when the settlement price
+    /// moves from `previous` to `current`.
+    pub fn variation_margin(&self, previous: f64, current: f64) -> f64 {
+        self.direction_sign() * self.contracts * (current - previous) / self.tick_size
+            * self.tick_value()
+    }
+
+    fn pv_under_shift(&self, yts: &dyn YieldCurve, rate_shift_bp: f64) -> Result<f64> {
+        let price = 100.0 * (1.0 - self.futures_rate(yts, rate_shift_bp)?);
+        Ok(self.variation_margin(self.trade_price, price))
+    }
+}
+
+impl IRDerivatives for StirFuture {
+    fn mtm(&self, market: &IrMarketContext) -> Result<CurrencyValue> {
+        Ok(CurrencyValue {
+            currency: self.currency,
+            value: self.pv_under_shift(&market.curve, 0.0)?,
+        })
+    }
+
+    fn dv01(&self, market: &IrMarketContext) -> Result<f64> {
+        Ok(self.pv_under_shift(&market.curve, 1.0)? - self.pv_under_shift(&market.curve, 0.0)?)
+    }
+
+    fn gamma(
+        &self,
+        market: &IrMarketContext,
+        rate_shift_bp: f64,
+        mode: RateShiftMode,
+    ) -> Result<f64> {
+        ensure_supported_mode(mode)?;
+        let base = self.pv_under_shift(&market.curve, 0.0)?;
+        let up = self.pv_under_shift(&market.curve, rate_shift_bp)?;
+        let down = self.pv_under_shift(&market.curve, -rate_shift_bp)?;
+        Ok(up + down - 2.0 * base)
+    }
+
+    /// Futures carry no vol risk.
+    fn vega(&self, _market: &IrMarketContext, _vol_shift_bp: f64) -> Result<f64> {
+        Ok(0.0)
+    }
+
+    /// The variation margin to date, settled on the valuation date, with
+    /// the futures rate over the reference period.
+    fn cashflows(&self, market: &IrMarketContext) -> Result<Vec<InterestRateCashflow>> {
+        let rate = self.futures_rate(&market.curve, 0.0)?;
+        Ok(vec![InterestRateCashflow {
+            kind: CashflowKind::Projected,
+            fixing_date: Some(self.basic_info.expiry_date),
+            accrual_start_date: Some(self.reference_start),
+            accrual_end_date: Some(self.reference_end),
+            pay_date: market.valuation_date,
+            rate: Some(rate),
+            notional: self.contracts * self.notional,
+            amount: self.variation_margin(self.trade_price, 100.0 * (1.0 - rate)),
+            discount: 1.0,
+            present_value: self.pv_under_shift(&market.curve, 0.0)?,
+        }])
+    }
+}
+
+/// Call or put on the futures price.
+#[derive(Deserialize, Serialize, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FutureOptionKind {
+    Call,
+    Put,
+}
+
+/// Where an option on a future reads its normal vol.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub enum FutureOptionVol {
+    /// [`IrMarketContext::cap_surface`] at the option expiry and rate
+    /// strike.
+    CapletSurface,
+    /// `(price strike, normal rate vol)` nodes, interpolated linearly
+    /// in strike and flat outside; sticky strike.
+    Smile(Vec<(f64, f64)>),
+}
+
+/// Option on `future.contracts` contracts of `future`; the option's
+/// direction is `basic_info.direction` and the future's own
+/// `trade_price` and direction are ignored.
+#[derive(Deserialize, Serialize, Debug)]
+pub struct StirFutureOption {
+    pub basic_info: BasicInfo,
+    pub kind: FutureOptionKind,
+    pub future: StirFuture,
+    /// Strike as a futures price.
+    pub strike: f64,
+    pub expiry_date: NaiveDate,
+    pub vol: FutureOptionVol,
+    /// Futures-style margined (undiscounted) rather than premium-paid.
+    pub futures_style: bool,
+}
+
+impl StirFutureOption {
+    fn direction_sign(&self) -> f64 {
+        self.basic_info.direction as i8 as f64
+    }
+
+    /// Strike on the futures rate.
+    pub fn rate_strike(&self) -> f64 {
+        1.0 - self.strike / 100.0
+    }
+
+    fn normal_vol(&self, market: &IrMarketContext) -> Result<f64> {
+        match &self.vol {
+            FutureOptionVol::CapletSurface => {
+                market.caplet_vol(self.expiry_date, self.rate_strike())
+            }
+            FutureOptionVol::Smile(nodes) => {
+                if nodes.is_empty() {
+                    return Err(Error::InvalidData(
+                        "futures option smile has no nodes".to_string(),
+                    ));
+                }
+                let mut nodes = nodes.clone();
+                nodes.sort_by(|a, b| a.0.total_cmp(&b.0));
+                Ok(interpolate_flat(&nodes, self.strike))
+            }
+        }
+    }
+
+    /// Present value under a parallel (rate, vol) shift.
+    fn pv_under_shift(
+        &self,
+        market: &IrMarketContext,
+        rate_shift_bp: f64,
+        vol_shift_bp: f64,
+    ) -> Result<f64> {
+        let yts = &market.curve;
+        let t = Actual365Fixed::default().year_fraction(market.valuation_date, self.expiry_date)?;
+        if t < 0.0 {
+            return Ok(0.0);
+        }
+        let rate = self.future.futures_rate(yts, rate_shift_bp)?;
+        let sigma = self.normal_vol(market)? + vol_shift_bp * 1.0e-4;
+        let variance = sigma * sigma * t;
+        let strike = self.rate_strike();
+        // A call on the price is a put on the rate.
+        let per_unit = match self.kind {
+            FutureOptionKind::Call => bachelier_put(rate, strike, variance),
+            FutureOptionKind::Put => bachelier_call(rate, strike, variance),
+        };
+        let df = if self.futures_style {
+            1.0
+        } else {
+            yts.shifted_discount(
+                self.expiry_date,
+                &InterpolationMethodEnum::PiecewiseLinearContinuous,
+                rate_shift_bp,
+            )?
+        };
+        Ok(self.direction_sign()
+            * self.future.contracts
+            * self.future.notional
+            * self.future.contract_accrual
+            * per_unit
+            * df)
+    }
+}
+
+impl IRDerivatives for StirFutureOption {
+    fn mtm(&self, market: &IrMarketContext) -> Result<CurrencyValue> {
+        Ok(CurrencyValue {
+            currency: self.future.currency,
+            value: self.pv_under_shift(market, 0.0, 0.0)?,
+        })
+    }
+
+    fn dv01(&self, market: &IrMarketContext) -> Result<f64> {
+        Ok(self.pv_under_shift(market, 1.0, 0.0)? - self.pv_under_shift(market, 0.0, 0.0)?)
+    }
+
+    fn gamma(
+        &self,
+        market: &IrMarketContext,
+        rate_shift_bp: f64,
+        mode: RateShiftMode,
+    ) -> Result<f64> {
+        ensure_supported_mode(mode)?;
+        let base = self.pv_under_shift(market, 0.0, 0.0)?;
+        let up = self.pv_under_shift(market, rate_shift_bp, 0.0)?;
+        let down = self.pv_under_shift(market, -rate_shift_bp, 0.0)?;
+        Ok(up + down - 2.0 * base)
+    }
+
+    fn vega(&self, market: &IrMarketContext, vol_shift_bp: f64) -> Result<f64> {
+        Ok(self.pv_under_shift(market, 0.0, vol_shift_bp)?
+            - self.pv_under_shift(market, 0.0, 0.0)?)
+    }
+
+    /// The expected payoff at expiry, or margined to the valuation date
+    /// for futures-style options; no flow once expired.
+    fn cashflows(&self, market: &IrMarketContext) -> Result<Vec<InterestRateCashflow>> {
+        if self.expiry_date < market.valuation_date {
+            return Ok(Vec::new());
+        }
+        let (pay_date, discount) = if self.futures_style {
+            (market.valuation_date, 1.0)
+        } else {
+            let method = &InterpolationMethodEnum::PiecewiseLinearContinuous;
+            (
+                self.expiry_date,
+                market.curve.discount(self.expiry_date, method)?,
+            )
+        };
+        let pv = self.pv_under_shift(market, 0.0, 0.0)?;
+        Ok(vec![InterestRateCashflow {
+            kind: CashflowKind::Projected,
+            fixing_date: Some(self.expiry_date),
+            accrual_start_date: Some(self.future.reference_start),
+            accrual_end_date: Some(self.future.reference_end),
+            pay_date,
+            rate: Some(self.future.futures_rate(&market.curve, 0.0)?),
+            notional: self.future.contracts * self.future.notional,
+            amount: pv / discount,
+            discount,
+            present_value: pv,
+        }])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::derivatives::basic::{Direction, Style};
+    use crate::markets::termstructures::yieldcurve::zero_rate_curve;
+    use crate::time::daycounters::actual360::Actual360;
+
+    fn d(y: i32, m: u32, dd: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, dd).unwrap()
+    }
+
+    fn market(valuation_date: NaiveDate) -> IrMarketContext {
+        let curve = zero_rate_curve(
+            valuation_date,
+            &[(d(2027, 1, 1), 0.03), (d(2031, 1, 1), 0.032)],
+        );
+        IrMarketContext::new(valuation_date, Currency::EUR, curve, None)
+    }
+
+    fn info(direction: Direction, expiry: NaiveDate) -> BasicInfo {
+        BasicInfo {
+            trade_date: d(2026, 8, 12),
+            style: Style::IRSwap,
+            direction,
+            expiry_date: expiry,
+            delivery_date: expiry,
+        }
+    }
+
+    fn euribor_dec26(direction: Direction, trade_price: f64) -> Result<StirFuture> {
+        let mut spec = InterestRateFutures::new(Period::Months(3));
+        spec.day_counter = Box::new(Actual360);
+        StirFuture::from_imm_code(
+            info(direction, d(2026, 12, 16)),
+            Currency::EUR,
+            10.0,
+            1.0e6,
+            0.005,
+            trade_price,
+            "Z6",
+            spec,
+            d(2026, 8, 12),
+        )
+    }
+
+    /// Tick value and DV01 are the textbook €12.50 and ≈ −€25 per
+    /// contract; variation margin and PV agree with the price move.
+    #[test]
+    fn future_margin_tick_and_dv01() -> Result<()> {
+        let market = market(d(2026, 8, 12));
+        let long = euribor_dec26(Direction::Buy, 96.90)?;
+        assert_eq!(long.reference_start, d(2026, 12, 16));
+        assert!((long.tick_value() - 12.5).abs() < 1e-12);
+        let price = long.price(&market.curve)?;
+        assert!(96.5 < price && price < 97.5, "{price}");
+        let pv = long.mtm(&market)?.value;
+        assert!((pv - 10.0 * (price - 96.90) / 0.005 * 12.5).abs() < 1e-9);
+        assert!((long.variation_margin(96.90, 96.91) - 250.0).abs() < 1e-9);
+        let dv01 = long.dv01(&market)?;
+        assert!((dv01 / 10.0 + 25.0).abs() < 0.5, "{dv01}");
+        let short = euribor_dec26(Direction::Sell, 96.90)?;
+        assert!((short.mtm(&market)?.value + pv).abs() < 1e-9);
+        assert_eq!(long.vega(&market, 1.0)?, 0.0);
+
+        // A 1M SOFR-style contract ticks $20.83 on $5m and still carries
+        // rate risk.
+        let one_month = StirFuture::from_imm_code(
+            info(Direction::Buy, d(2026, 12, 16)),
+            Currency::USD,
+            10.0,
+            5.0e6,
+            0.005,
+            96.90,
+            "Z6",
+            InterestRateFutures::new(Period::Months(1)),
+            d(2026, 8, 12),
+        )?;
+        assert!((one_month.contract_accrual - 1.0 / 12.0).abs() < 1e-15);
+        assert!((one_month.tick_value() - 5.0e6 / 12.0 * 0.005 / 100.0).abs() < 1e-9);
+        assert!(one_month.mtm(&market)?.value != 0.0);
+        let dv01 = one_month.dv01(&market)?;
+        assert!((dv01 / 10.0 + 5.0e6 / 12.0 * 1.0e-4).abs() < 1.0, "{dv01}");
+        Ok(())
+    }
+
+    /// Call − put on the price is the (discounted) price minus strike,
+    /// calls on the price lose as rates rise, and reading the caplet
+    /// surface needs one in the market.
+    #[test]
+    fn option_parity_and_smile() -> Result<()> {
+        let market = market(d(2026, 8, 12));
+        let future = || euribor_dec26(Direction::Buy, 0.0);
+        let smile = FutureOptionVol::Smile(vec![(96.5, 0.0085), (97.0, 0.0080), (97.5, 0.0090)]);
+        let option = |kind: FutureOptionKind, futures_style: bool| -> Result<StirFutureOption> {
+            Ok(StirFutureOption {
+                basic_info: info(Direction::Buy, d(2026, 12, 14)),
+                kind,
+                future: future()?,
+                strike: 97.0,
+                expiry_date: d(2026, 12, 14),
+                vol: smile.clone(),
+                futures_style,
+            })
+        };
+        let price = future()?.price(&market.curve)?;
+        let df = market.curve.discount(
+            d(2026, 12, 14),
+            &InterpolationMethodEnum::PiecewiseLinearContinuous,
+        )?;
+        let point = 10.0 * 1.0e6 * 0.25 / 100.0;
+        for (style, discount) in [(true, 1.0), (false, df)] {
+            let call = option(FutureOptionKind::Call, style)?;
+            let put = option(FutureOptionKind::Put, style)?;
+            let parity = call.mtm(&market)?.value - put.mtm(&market)?.value;
+            assert!((parity - (price - 97.0) * point * discount).abs() < 1e-6);
+            assert!(call.vega(&market, 1.0)? > 0.0);
+            assert!(call.dv01(&market)? < 0.0 && put.dv01(&market)? > 0.0);
+        }
+        let surface = StirFutureOption {
+            vol: FutureOptionVol::CapletSurface,
+            ..option(FutureOptionKind::Call, true)?
+        };
+        assert!(surface.mtm(&market).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,1183 @@
+//! Structured-note hedges priced by Monte Carlo under a term-structure
+//! model fitted to the discount curve: a cancellable swap and a daily
+//! range-accrual coupon leg.
+//!
+//! Both run on [`HullWhiteSimulator`] (with `θ(t)` fitted to the curve)
+//! or [`FmmSimulator`] (with initial term rates set to the curve's
+//! forwards), deflating by the model's bank account. Model time is
+//! ACT/365F from the valuation date.
+//!
+//! A cancellable swap is split into the vanilla swap, valued on the
+//! curve, plus the right to enter the reverse of the remaining swap on
+//! each cancellation date, a Bermudan valued by
+//! [`LsmEngine`](crate::models::common::longstaff_schwartz::LsmEngine):
+//!
+//! ```text
+//!   V = direction · Swap(0) ± Bermudan(−ω · Swap_rem(t_k))      + if we hold the right
+//!   Swap_rem(t) = N Σ_{S_i ≥ t} [(c − s) τ_i − (P(t,S_i)/P(t,E_i) − 1)] · P(t,T_pay,i)
+//! ```
+//!
+//! with `ω` the sign of the owner's position. A range-accrual leg pays,
+//! per period, `N · c · τ_i` times the fraction of calendar days in
+//! `[S_i, E_i)` on which the reference rate fixes inside
+//! `[lower, upper]`:
+//!
+//! ```text
+//!   V = direction · N Σ_i c τ_i / n_i Σ_{d ∈ [S_i, E_i)} E[ 1{L ≤ x(d) ≤ U} · P(d, T_pay,i) / B(d) ]
+//! ```
+//!
+//! Days on or before the valuation date fix off today's curve. Greeks
+//! are bump-and-reprice with common random numbers: the same seed is
+//! reused so the bumped and base prices share paths.
+//!
+//! [`HullWhiteSimulator`]: crate::models::interestrate::hull_white::HullWhiteSimulator
+//! [`FmmSimulator`]: crate::models::interestrate::fmm::FmmSimulator
+//!
+//! # Papers
+//!
+//! * **Longstaff, F. A., Schwartz, E. S. (2001)** — *Valuing American
+//!   Options by Simulation: A Simple Least-Squares Approach*, Review of
+//!   Financial Studies 14(1): 113–147. Regression-based cancellation.
+//! * **Brigo, D., Mercurio, F. (2006)** — *Interest Rate Models: Theory
+//!   and Practice*, 2nd ed., Springer. §3.3 for the curve-fitted
+//!   Hull–White drift; §13.9 for range accruals.
+
+use chrono::{Days, NaiveDate};
+use iso_currency::Currency;
+
+use crate::derivatives::basic::BasicInfo;
+use crate::derivatives::forex::basic::CurrencyValue;
+use crate::derivatives::interestrate::basic::{
+    CashflowKind, IRDerivatives, InterestRateCashflow, RateShiftMode,
+};
+use crate::derivatives::interestrate::cap::ensure_supported_mode;
+use crate::derivatives::interestrate::swap::InterestRateSchedulePeriod;
+use crate::error::{Error, Result};
+use crate::markets::interestrate::market_context::IrMarketContext;
+use crate::markets::termstructures::yieldcurve::{InterpolationMethodEnum, YieldCurve};
+use crate::models::common::longstaff_schwartz::{LsmEngine, LsmEstimate, LsmProduct, LsmResult};
+use crate::models::common::simulation::{SimulationModel, simulate_at_dates};
+use crate::models::interestrate::fmm::{
+    Fmm, FmmPath, FmmSimulator, InitialDiscountCurve, bank_account, bond_price,
+};
+use crate::models::interestrate::hull_white::{HullWhite1F, HullWhiteSimulator};
+use crate::time::daycounters::DayCounters;
+use crate::time::daycounters::actual365fixed::Actual365Fixed;
+use crate::time::frequency::Frequency;
+use crate::time::period::Period;
+
+/// Term-structure model the Monte Carlo runs under.
+#[derive(Clone, Debug)]
+pub enum RateModel {
+    /// Hull–White with `θ(t)` fitted to the curve; `mean_reversion`
+    /// must be positive.
+    HullWhite(HullWhite1F),
+    /// FMM whose tenor dates are ACT/365F year fractions from the
+    /// valuation date, starting at `T_0 = 0` and covering every date the
+    /// product observes. The tenor's initial rates are replaced by the
+    /// curve's forwards at each pricing.
+    Fmm(Fmm),
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct MonteCarloSettings {
+    /// Paths the exercise rule is fitted on (unused without exercise).
+    pub n_training_paths: usize,
+    /// Paths the price is averaged over.
+    pub n_paths: usize,
+    /// Integration step cap in calendar days.
+    pub max_step_days: u32,
+    pub seed: u64,
+}
+
+/// Who may cancel.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CancellationRight {
+    /// We hold the right.
+    Holder,
+    /// Our counterparty (typically the note issuer) holds it.
+    Counterparty,
+}
+
+/// Reference rate a range accrual observes.
+#[derive(Debug, PartialEq)]
+pub enum RangeAccrualIndex {
+    /// Overnight RFR, simple ACT/365F over one day.
+    Overnight,
+    /// Par rate of a spot-starting swap of `tenor` paying at
+    /// `fixed_frequency`.
+    SwapRate {
+        tenor: Period,
+        fixed_frequency: Frequency,
+    },
+}
+
+const ONE_DAY: f64 = 1.0 / 365.0;
+
+/// Paths per simulation batch times observation dates, bounding the
+/// memory held by daily observations.
+const BATCH_STATES: usize = 100_000;
+
+/// Daily ACT/365F log discount factors off a shifted curve, linearly
+/// interpolated in between and extrapolated flat-forward, so the models
+/// can read `P(0, t)` at any model time.
+#[derive(Clone, Debug)]
+struct CurveGrid {
+    log_df: Vec<f64>,
+}
+
+impl CurveGrid {
+    fn new(yts: &dyn YieldCurve, horizon: NaiveDate, shift_bp: f64) -> Result<Self> {
+        let valuation_date = yts.valuation_date();
+        let days = (horizon - valuation_date).num_days().max(1) as u64 + 2;
+        let method = &InterpolationMethodEnum::PiecewiseLinearContinuous;
+        let log_df = (0..=days)
+            .map(|i| {
+                let date = valuation_date + Days::new(i);
+                Ok(yts.shifted_discount(date, method, shift_bp)?.ln())
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self { log_df })
+    }
+
+    fn log_p(&self, t: f64) -> f64 {
+        let x = (t * 365.0).max(0.0);
+        let last = self.log_df.len() - 1;
+        let i = (x.floor() as usize).min(last - 1);
+        let w = x - i as f64;
+        self.log_df[i] + w * (self.log_df[i + 1] - self.log_df[i])
+    }
+
+    /// Instantaneous forward `f(0, t)`, read over one day.
+    fn forward(&self, t: f64) -> f64 {
+        (self.log_p(t) - self.log_p(t + ONE_DAY)) / ONE_DAY
+    }
+}
+
+impl InitialDiscountCurve for CurveGrid {
+    fn p0(&self, big_t: f64) -> f64 {
+        self.log_p(big_t).exp()
+    }
+}
+
+/// Bond prices and numeraire read off one simulated state at model time
+/// `t`.
+trait PathPricer<S> {
+    fn bond(&self, t: f64, state: &S, maturity: f64) -> f64;
+    fn numeraire(&self, t: f64, state: &S) -> f64;
+}
+
+/// [`HullWhiteSimulator`] carrying `∫₀ᵗ r(u) du` (trapezoid rule)
+/// alongside `r` so the bank account is available.
+struct HullWhiteAccount(HullWhiteSimulator);
+
+impl SimulationModel for HullWhiteAccount {
+    type State = (f64, f64);
+
+    fn initial_state(&self) -> Self::State {
+        (self.0.initial_state(), 0.0)
+    }
+
+    fn step(&mut self, state: &Self::State, t: f64, dt: f64) -> Self::State {
+        let r = self.0.step(&state.0, t, dt);
+        (r, state.1 + 0.5 * (state.0 + r) * dt)
+    }
+}
+
+struct HullWhitePricer {
+    model: HullWhite1F,
+    curve: CurveGrid,
+}
+
+impl PathPricer<(f64, f64)> for HullWhitePricer {
+    fn bond(&self, t: f64, state: &(f64, f64), maturity: f64) -> f64 {
+        if maturity <= t {
+            return 1.0;
+        }
+        self.model.discount_affine(
+            t,
+            maturity,
+            state.0,
+            self.curve.p0(t),
+            self.curve.p0(maturity),
+            self.curve.forward(t),
+        )
+    }
+
+    fn numeraire(&self, _t: f64, state: &(f64, f64)) -> f64 {
+        state.1.exp()
+    }
+}
+
+struct FmmPricer {
+    model: Fmm,
+    curve: CurveGrid,
+}
+
+impl PathPricer<FmmPath> for FmmPricer {
+    fn bond(&self, _t: f64, state: &FmmPath, maturity: f64) -> f64 {
+        bond_price(&self.model, state, maturity.max(state.t), &self.curve)
+    }
+
+    fn numeraire(&self, _t: f64, state: &FmmPath) -> f64 {
+        bank_account(&self.model, state, &self.curve)
+    }
+}
+
+/// Hull–White simulator whose drift reprices `curve`:
+/// `θ(t) = f(0,t) + ∂_t f(0,t)/λ + η²/(2λ²)·(1 − e^{−2λt})`, with the
+/// slope taken over one integration step so the Euler drift telescopes.
+fn hull_white_setup(
+    model: HullWhite1F,
+    curve: CurveGrid,
+    settings: &MonteCarloSettings,
+    vol_shift_bp: f64,
+) -> Result<(HullWhiteAccount, HullWhitePricer)> {
+    let model = HullWhite1F {
+        sigma: model.sigma + vol_shift_bp * 1.0e-4,
+        ..model
+    };
+    let (lambda, eta) = (model.mean_reversion, model.sigma);
+    if lambda <= 0.0 {
+        return Err(Error::InvalidData(format!(
+            "Hull-White mean reversion {} must be positive",
+            lambda
+        )));
+    }
+    let h = settings.max_step_days as f64 / 365.0;
+    let grid = curve.clone();
+    let theta = move |t: f64| {
+        let f = grid.forward(t);
+        f + (grid.forward(t + h) - f) / (lambda * h)
+            + eta * eta / (2.0 * lambda * lambda) * (1.0 - (-2.0 * lambda * t).exp())
+    };
+    let simulator =
+        HullWhiteSimulator::new_constant_theta(model, curve.forward(0.0), 0.0, settings.seed)
+            .with_theta_fn(theta);
+    Ok((
+        HullWhiteAccount(simulator),
+        HullWhitePricer { model, curve },
+    ))
+}
+
+/// FMM simulator started on `curve`'s forwards; the vol bump lifts
+/// each rate's normal vol `σ_j · level_j(R_j(0))` by `vol_shift_bp`.
+fn fmm_setup(
+    model: &Fmm,
+    curve: CurveGrid,
+    horizon: f64,
+    settings: &MonteCarloSettings,
+    vol_shift_bp: f64,
+) -> Result<(FmmSimulator, FmmPricer)> {
+    let dates = &model.tenor.dates;
+    if dates[0].abs() > 1e-12 || *dates.last().unwrap() < horizon - 1e-9 {
+        return Err(Error::InvalidData(format!(
+            "FMM tenor [{}, {}] does not cover [0, {}]",
+            dates[0],
+            dates.last().unwrap(),
+            horizon
+        )));
+    }
+    let mut model = model.clone();
+    model.tenor.initial_rates = dates
+        .windows(2)
+        .map(|w| (curve.p0(w[0]) / curve.p0(w[1]) - 1.0) / (w[1] - w[0]))
+        .collect();
+    if vol_shift_bp != 0.0 {
+        if model.vol_schedule.is_some() {
+            return Err(Error::InvalidData(
+                "FMM vega needs constant sigmas, not a vol schedule".to_string(),
+            ));
+        }
+        for j in 1..=model.tenor.m() {
+            let level = model.displacement_level(j, model.tenor.initial_rates[j - 1]);
+            model.sigmas[j - 1] += vol_shift_bp * 1.0e-4 / level;
+        }
+    }
+    let simulator = FmmSimulator::new(model.clone(), settings.seed)
+        .map_err(|e| Error::InvalidData(e.to_string()))?;
+    Ok((simulator, FmmPricer { model, curve }))
+}
+
+/// Accrual period in model time.
+#[derive(Copy, Clone, Debug)]
+struct PeriodTimes {
+    start: f64,
+    end: f64,
+    pay: f64,
+    accrual: f64,
+}
+
+fn period_times(
+    valuation_date: NaiveDate,
+    period: &InterestRateSchedulePeriod,
+    accrual_day_counter: &dyn DayCounters,
+) -> Result<PeriodTimes> {
+    let time = |date: NaiveDate| Actual365Fixed::default().year_fraction(valuation_date, date);
+    Ok(PeriodTimes {
+        start: time(period.accrual_start_date)?,
+        end: time(period.accrual_end_date)?,
+        pay: time(period.pay_date)?,
+        accrual: accrual_day_counter
+            .year_fraction(period.accrual_start_date, period.accrual_end_date)?,
+    })
+}
+
+/// Receiver swap (fixed `rate` against float + `spread`, single curve)
+/// over `periods` at time `t`, given `bond(T) = P(t, T)`.
+fn receiver_value(
+    periods: &[PeriodTimes],
+    notional: f64,
+    rate: f64,
+    spread: f64,
+    t: f64,
+    bond: impl Fn(f64) -> f64,
+) -> f64 {
+    periods
+        .iter()
+        .map(|p| {
+            let forward = bond(p.start.max(t)) / bond(p.end) - 1.0;
+            notional * ((rate - spread) * p.accrual - forward) * bond(p.pay)
+        })
+        .sum()
+}
+
+/// Swap whose fixed receiver or payer (per `basic_info.direction`:
+/// `Buy` receives fixed) can be cancelled on `cancellation_dates`: on
+/// each, every period whose accrual starts on or after it is torn up.
+/// The float leg pays the single-curve forward plus `float_spread` on
+/// the same schedule and day count as the fixed leg.
+#[derive(Debug)]
+pub struct CancellableSwap {
+    pub basic_info: BasicInfo,
+    pub currency: Currency,
+    pub notional: f64,
+    pub fixed_rate: f64,
+    pub float_spread: f64,
+    pub right: CancellationRight,
+    pub cancellation_dates: Vec<NaiveDate>,
+    pub valuation_date: NaiveDate,
+    pub schedule: Vec<InterestRateSchedulePeriod>,
+    pub accrual_day_counter: Box<dyn DayCounters>,
+    pub model: RateModel,
+    pub settings: MonteCarloSettings,
+}
+
+/// Right to enter the reverse of the owner's remaining swap.
+struct CancellationOption<'a, P> {
+    pricer: &'a P,
+    times: Vec<f64>,
+    /// Periods each exercise date tears up.
+    remaining: Vec<Vec<PeriodTimes>>,
+    notional: f64,
+    rate: f64,
+    spread: f64,
+    owner_sign: f64,
+}
+
+impl<P> CancellationOption<'_, P> {
+    fn remaining_value<S>(&self, k: usize, state: &S) -> f64
+    where
+        P: PathPricer<S>,
+    {
+        let t = self.times[k];
+        receiver_value(
+            &self.remaining[k],
+            self.notional,
+            self.rate,
+            self.spread,
+            t,
+            |big_t| self.pricer.bond(t, state, big_t),
+        )
+    }
+}
+
+impl<S, P: PathPricer<S>> LsmProduct<S> for CancellationOption<'_, P> {
+    fn basis(&self, k: usize, state: &S) -> Vec<f64> {
+        let v = self.remaining_value(k, state) / self.notional;
+        let last = self.remaining[k].last().map_or(self.times[k], |p| p.pay);
+        vec![1.0, v, v * v, self.pricer.bond(self.times[k], state, last)]
+    }
+
+    fn exercise_value(&self, k: usize, state: &S) -> f64 {
+        -self.owner_sign * self.remaining_value(k, state)
+    }
+
+    fn numeraire(&self, k: usize, state: &S) -> f64 {
+        self.pricer.numeraire(self.times[k], state)
+    }
+}
+
+impl CancellableSwap {
+    fn direction_sign(&self) -> f64 {
+        self.basic_info.direction as i8 as f64
+    }
+
+    fn owner_sign(&self) -> f64 {
+        match self.right {
+            CancellationRight::Holder => self.direction_sign(),
+            CancellationRight::Counterparty => -self.direction_sign(),
+        }
+    }
+
+    fn live_periods(&self) -> Result<Vec<PeriodTimes>> {
+        self.schedule
+            .iter()
+            .filter(|p| p.pay_date > self.valuation_date)
+            .map(|p| period_times(self.valuation_date, p, self.accrual_day_counter.as_ref()))
+            .collect()
+    }
+
+    fn horizon(&self) -> NaiveDate {
+        self.schedule
+            .iter()
+            .map(|p| p.pay_date.max(p.accrual_end_date))
+            .max()
+            .unwrap_or(self.valuation_date)
+    }
+
+    /// Value of the vanilla swap without the cancellation right.
+    fn swap_value(&self, curve: &CurveGrid) -> Result<f64> {
+        Ok(self.direction_sign()
+            * receiver_value(
+                &self.live_periods()?,
+                self.notional,
+                self.fixed_rate,
+                self.float_spread,
+                0.0,
+                |t| curve.p0(t),
+            ))
+    }
+
+    /// Longstaff–Schwartz valuation of the cancellation right, seen from
+    /// whoever holds it, under a parallel (rate, vol) shift. `None` when
+    /// no cancellation date is left.
+    fn option_under_shift(
+        &self,
+        market: &IrMarketContext,
+        rate_shift_bp: f64,
+        vol_shift_bp: f64,
+    ) -> Result<Option<LsmResult>> {
+        let curve = CurveGrid::new(&market.curve, self.horizon(), rate_shift_bp)?;
+        let mut dates: Vec<NaiveDate> = self
+            .cancellation_dates
+            .iter()
+            .copied()
+            .filter(|d| *d > self.valuation_date)
+            .collect();
+        dates.sort();
+        dates.dedup();
+        if dates.is_empty() {
+            return Ok(None);
+        }
+        let dc = Actual365Fixed::default();
+        let times = dates
+            .iter()
+            .map(|d| dc.year_fraction(self.valuation_date, *d))
+            .collect::<Result<Vec<_>>>()?;
+        let remaining = dates
+            .iter()
+            .map(|d| {
+                self.schedule
+                    .iter()
+                    .filter(|p| p.accrual_start_date >= *d)
+                    .map(|p| {
+                        period_times(self.valuation_date, p, self.accrual_day_counter.as_ref())
+                    })
+                    .collect::<Result<Vec<_>>>()
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let horizon = dc.year_fraction(self.valuation_date, self.horizon())?;
+        let s = &self.settings;
+        let engine = LsmEngine::new();
+        let result = match &self.model {
+            RateModel::HullWhite(hw) => {
+                let (mut sim, pricer) = hull_white_setup(*hw, curve, s, vol_shift_bp)?;
+                let option = self.option(&pricer, times, remaining);
+                let v = self.valuation_date;
+                let training = simulate_at_dates(
+                    &mut sim,
+                    v,
+                    &dates,
+                    s.n_training_paths,
+                    s.max_step_days,
+                    &dc,
+                );
+                let pricing =
+                    simulate_at_dates(&mut sim, v, &dates, s.n_paths, s.max_step_days, &dc);
+                engine.price(&mut sim, &option, &training, &pricing, &dc)?
+            }
+            RateModel::Fmm(fmm) => {
+                let (mut sim, pricer) = fmm_setup(fmm, curve, horizon, s, vol_shift_bp)?;
+                let option = self.option(&pricer, times, remaining);
+                let v = self.valuation_date;
+                let training = simulate_at_dates(
+                    &mut sim,
+                    v,
+                    &dates,
+                    s.n_training_paths,
+                    s.max_step_days,
+                    &dc,
+                );
+                let pricing =
+                    simulate_at_dates(&mut sim, v, &dates, s.n_paths, s.max_step_days, &dc);
+                engine.price(&mut sim, &option, &training, &pricing, &dc)?
+            }
+        };
+        Ok(Some(result))
+    }
+
+    fn option<'a, P>(
+        &self,
+        pricer: &'a P,
+        times: Vec<f64>,
+        remaining: Vec<Vec<PeriodTimes>>,
+    ) -> CancellationOption<'a, P> {
+        CancellationOption {
+            pricer,
+            times,
+            remaining,
+            notional: self.notional,
+            rate: self.fixed_rate,
+            spread: self.float_spread,
+            owner_sign: self.owner_sign(),
+        }
+    }
+
+    /// Regression result for the cancellation right on today's market,
+    /// including its Monte Carlo error and per-date diagnostics.
+    pub fn cancellation_option(&self, market: &IrMarketContext) -> Result<Option<LsmResult>> {
+        self.option_under_shift(market, 0.0, 0.0)
+    }
+
+    fn pv_under_shift(
+        &self,
+        market: &IrMarketContext,
+        rate_shift_bp: f64,
+        vol_shift_bp: f64,
+    ) -> Result<f64> {
+        let curve = CurveGrid::new(&market.curve, self.horizon(), rate_shift_bp)?;
+        let option = self
+            .option_under_shift(market, rate_shift_bp, vol_shift_bp)?
+            .map_or(0.0, |r| r.lower_bound.value);
+        let sign = match self.right {
+            CancellationRight::Holder => 1.0,
+            CancellationRight::Counterparty => -1.0,
+        };
+        Ok(self.swap_value(&curve)? + sign * option)
+    }
+}
+
+impl IRDerivatives for CancellableSwap {
+    fn mtm(&self, market: &IrMarketContext) -> Result<CurrencyValue> {
+        Ok(CurrencyValue {
+            currency: self.currency,
+            value: self.pv_under_shift(market, 0.0, 0.0)?,
+        })
+    }
+
+    fn dv01(&self, market: &IrMarketContext) -> Result<f64> {
+        Ok(self.pv_under_shift(market, 1.0, 0.0)? - self.pv_under_shift(market, 0.0, 0.0)?)
+    }
+
+    fn gamma(
+        &self,
+        market: &IrMarketContext,
+        rate_shift_bp: f64,
+        mode: RateShiftMode,
+    ) -> Result<f64> {
+        ensure_supported_mode(mode)?;
+        let base = self.pv_under_shift(market, 0.0, 0.0)?;
+        let up = self.pv_under_shift(market, rate_shift_bp, 0.0)?;
+        let down = self.pv_under_shift(market, -rate_shift_bp, 0.0)?;
+        Ok(up + down - 2.0 * base)
+    }
+
+    /// Bumps the model's normal rate vol: `η` for Hull–White, each
+    /// term rate's normal vol for the FMM.
+    fn vega(&self, market: &IrMarketContext, vol_shift_bp: f64) -> Result<f64> {
+        Ok(self.pv_under_shift(market, 0.0, vol_shift_bp)?
+            - self.pv_under_shift(market, 0.0, 0.0)?)
+    }
+
+    /// The vanilla swap's fixed and float coupons of every unpaid period,
+    /// then the cancellation right's value settled on the valuation date.
+    fn cashflows(&self, market: &IrMarketContext) -> Result<Vec<InterestRateCashflow>> {
+        let curve = CurveGrid::new(&market.curve, self.horizon(), 0.0)?;
+        let sign = self.direction_sign();
+        let mut flows = Vec::new();
+        for period in self
+            .schedule
+            .iter()
+            .filter(|p| p.pay_date > self.valuation_date)
+        {
+            let p = period_times(
+                self.valuation_date,
+                period,
+                self.accrual_day_counter.as_ref(),
+            )?;
+            let discount = curve.p0(p.pay);
+            let float_rate = (curve.p0(p.start.max(0.0)) / curve.p0(p.end) - 1.0) / p.accrual
+                + self.float_spread;
+            flows.push(InterestRateCashflow::coupon(
+                CashflowKind::Fixed,
+                None,
+                period,
+                self.fixed_rate,
+                self.notional,
+                sign * self.notional * self.fixed_rate * p.accrual,
+                discount,
+            ));
+            flows.push(InterestRateCashflow::coupon(
+                CashflowKind::Projected,
+                Some(period.reset_date),
+                period,
+                float_rate,
+                self.notional,
+                -sign * self.notional * float_rate * p.accrual,
+                discount,
+            ));
+        }
+        if let Some(option) = self.cancellation_option(market)? {
+            let holder = match self.right {
+                CancellationRight::Holder => 1.0,
+                CancellationRight::Counterparty => -1.0,
+            };
+            flows.push(InterestRateCashflow::single(
+                CashflowKind::Projected,
+                self.valuation_date,
+                self.notional,
+                holder * option.lower_bound.value,
+                1.0,
+            ));
+        }
+        Ok(flows)
+    }
+}
+
+/// Range-accrual coupon leg: each period pays `coupon · τ · N` scaled by
+/// the fraction of calendar days in `[accrual start, accrual end)` on
+/// which `index` fixes within `[lower, upper]`. `Buy` receives.
+#[derive(Debug)]
+pub struct RangeAccrual {
+    pub basic_info: BasicInfo,
+    pub currency: Currency,
+    pub notional: f64,
+    pub coupon: f64,
+    pub index: RangeAccrualIndex,
+    pub lower: f64,
+    pub upper: f64,
+    pub valuation_date: NaiveDate,
+    pub schedule: Vec<InterestRateSchedulePeriod>,
+    pub accrual_day_counter: Box<dyn DayCounters>,
+    pub model: RateModel,
+    pub settings: MonteCarloSettings,
+}
+
+/// One daily observation: its model time and the live period it counts
+/// towards, with that period's pay time and per-day amount.
+#[derive(Copy, Clone, Debug)]
+struct Observation {
+    time: f64,
+    period: usize,
+    pay: f64,
+    amount: f64,
+}
+
+/// Range-accrual coupons on one market: per live period, the value
+/// already locked in and, per path, the deflated coupons still to fix.
+struct SimulatedCoupons {
+    curve: CurveGrid,
+    fixed: Vec<f64>,
+    paths: Vec<Vec<f64>>,
+}
+
+impl RangeAccrual {
+    fn direction_sign(&self) -> f64 {
+        self.basic_info.direction as i8 as f64
+    }
+
+    /// Swap-rate fixed leg as `(accrual, number of payments)`.
+    fn swap_leg(tenor: Period, fixed_frequency: &Frequency) -> Result<(f64, u32)> {
+        let step = fixed_frequency
+            .period()
+            .ok_or_else(|| {
+                Error::InvalidData(format!("no fixed-leg period for {}", fixed_frequency))
+            })?
+            .months()?;
+        let total = tenor.months()?;
+        if step == 0 || total % step != 0 {
+            return Err(Error::InvalidData(format!(
+                "{:?} swap does not split into {} periods",
+                tenor, fixed_frequency
+            )));
+        }
+        Ok((step as f64 / 12.0, total / step))
+    }
+
+    /// Reference fixing at `t` given `bond(T) = P(t, T)`.
+    fn fixing(&self, t: f64, bond: impl Fn(f64) -> f64) -> Result<f64> {
+        Ok(match &self.index {
+            RangeAccrualIndex::Overnight => (1.0 / bond(t + ONE_DAY) - 1.0) / ONE_DAY,
+            RangeAccrualIndex::SwapRate {
+                tenor,
+                fixed_frequency,
+            } => {
+                let (tau, n) = Self::swap_leg(*tenor, fixed_frequency)?;
+                let annuity: f64 = (1..=n).map(|i| tau * bond(t + tau * i as f64)).sum();
+                (1.0 - bond(t + tau * n as f64)) / annuity
+            }
+        })
+    }
+
+    fn in_range(&self, x: f64) -> bool {
+        self.lower <= x && x <= self.upper
+    }
+
+    /// Time needed past the last observation to read its fixing.
+    fn index_horizon(&self) -> Result<f64> {
+        Ok(match &self.index {
+            RangeAccrualIndex::Overnight => ONE_DAY,
+            RangeAccrualIndex::SwapRate {
+                tenor,
+                fixed_frequency,
+            } => {
+                let (tau, n) = Self::swap_leg(*tenor, fixed_frequency)?;
+                tau * n as f64
+            }
+        })
+    }
+
+    fn live_periods(&self) -> impl Iterator<Item = &InterestRateSchedulePeriod> {
+        self.schedule
+            .iter()
+            .filter(|p| p.pay_date > self.valuation_date)
+    }
+
+    /// Value each live period has locked in by days on or before the
+    /// valuation date, and the daily observations still to simulate.
+    #[allow(clippy::type_complexity)]
+    fn observations(&self, curve: &CurveGrid) -> Result<(Vec<f64>, Vec<(NaiveDate, Observation)>)> {
+        let dc = Actual365Fixed::default();
+        let today_in_range = self.in_range(self.fixing(0.0, |t| curve.p0(t))?);
+        let mut fixed = vec![0.0; self.live_periods().count()];
+        let mut observations = Vec::new();
+        for (k, period) in self.live_periods().enumerate() {
+            let n_days = (period.accrual_end_date - period.accrual_start_date).num_days();
+            if n_days <= 0 {
+                continue;
+            }
+            let accrual = self
+                .accrual_day_counter
+                .year_fraction(period.accrual_start_date, period.accrual_end_date)?;
+            let amount = self.notional * self.coupon * accrual / n_days as f64;
+            let pay = dc.year_fraction(self.valuation_date, period.pay_date)?;
+            for i in 0..n_days as u64 {
+                let date = period.accrual_start_date + Days::new(i);
+                if date <= self.valuation_date {
+                    if today_in_range {
+                        fixed[k] += amount * curve.p0(pay);
+                    }
+                    continue;
+                }
+                let time = dc.year_fraction(self.valuation_date, date)?;
+                observations.push((
+                    date,
+                    Observation {
+                        time,
+                        period: k,
+                        pay,
+                        amount,
+                    },
+                ));
+            }
+        }
+        Ok((fixed, observations))
+    }
+
+    fn horizon(&self) -> Result<NaiveDate> {
+        let last = self
+            .schedule
+            .iter()
+            .map(|p| p.pay_date.max(p.accrual_end_date))
+            .max()
+            .unwrap_or(self.valuation_date);
+        let extra = (self.index_horizon()? * 365.0).ceil() as u64 + 1;
+        Ok(last + Days::new(extra))
+    }
+
+    /// Deflated coupons of each of `n_periods` live periods over
+    /// `n_paths` paths, simulated in batches so daily observations stay
+    /// within [`BATCH_STATES`].
+    fn simulate<M, P>(
+        &self,
+        sim: &mut M,
+        pricer: &P,
+        observations: &[(NaiveDate, Observation)],
+        n_periods: usize,
+    ) -> Result<Vec<Vec<f64>>>
+    where
+        M: SimulationModel,
+        P: PathPricer<M::State>,
+    {
+        let dc = Actual365Fixed::default();
+        let dates: Vec<NaiveDate> = observations.iter().map(|o| o.0).collect();
+        let n_paths = self.settings.n_paths;
+        let batch = (BATCH_STATES / dates.len()).clamp(1, n_paths);
+        let mut samples = Vec::with_capacity(n_paths);
+        while samples.len() < n_paths {
+            let n = batch.min(n_paths - samples.len());
+            let paths = simulate_at_dates(
+                sim,
+                self.valuation_date,
+                &dates,
+                n,
+                self.settings.max_step_days,
+                &dc,
+            );
+            for path in &paths.paths {
+                let mut coupons = vec![0.0; n_periods];
+                for (state, (_, o)) in path.iter().zip(observations) {
+                    let bond = |big_t: f64| pricer.bond(o.time, state, big_t);
+                    if self.in_range(self.fixing(o.time, bond)?) {
+                        coupons[o.period] +=
+                            o.amount * bond(o.pay) / pricer.numeraire(o.time, state);
+                    }
+                }
+                samples.push(coupons);
+            }
+        }
+        Ok(samples)
+    }
+
+    fn coupons_under_shift(
+        &self,
+        market: &IrMarketContext,
+        rate_shift_bp: f64,
+        vol_shift_bp: f64,
+    ) -> Result<SimulatedCoupons> {
+        let horizon = self.horizon()?;
+        let curve = CurveGrid::new(&market.curve, horizon, rate_shift_bp)?;
+        let (fixed, observations) = self.observations(&curve)?;
+        if observations.is_empty() {
+            return Ok(SimulatedCoupons {
+                curve,
+                fixed,
+                paths: Vec::new(),
+            });
+        }
+        let n_periods = fixed.len();
+        let last_pay = observations.iter().map(|o| o.1.pay).fold(0.0, f64::max);
+        let last_fixing = observations.last().map_or(0.0, |o| o.1.time);
+        let t_horizon = last_pay.max(last_fixing + self.index_horizon()?);
+        let s = &self.settings;
+        let paths = match &self.model {
+            RateModel::HullWhite(hw) => {
+                let (mut sim, pricer) = hull_white_setup(*hw, curve.clone(), s, vol_shift_bp)?;
+                self.simulate(&mut sim, &pricer, &observations, n_periods)?
+            }
+            RateModel::Fmm(fmm) => {
+                let (mut sim, pricer) = fmm_setup(fmm, curve.clone(), t_horizon, s, vol_shift_bp)?;
+                self.simulate(&mut sim, &pricer, &observations, n_periods)?
+            }
+        };
+        Ok(SimulatedCoupons {
+            curve,
+            fixed,
+            paths,
+        })
+    }
+
+    fn estimate_under_shift(
+        &self,
+        market: &IrMarketContext,
+        rate_shift_bp: f64,
+        vol_shift_bp: f64,
+    ) -> Result<LsmEstimate> {
+        let coupons = self.coupons_under_shift(market, rate_shift_bp, vol_shift_bp)?;
+        let sign = self.direction_sign();
+        let fixed: f64 = coupons.fixed.iter().sum();
+        if coupons.paths.is_empty() {
+            return Ok(LsmEstimate {
+                value: sign * fixed,
+                std_error: 0.0,
+            });
+        }
+        let samples: Vec<f64> = coupons.paths.iter().map(|p| p.iter().sum()).collect();
+        let n = samples.len() as f64;
+        let mean = samples.iter().sum::<f64>() / n;
+        let var = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0).max(1.0);
+        Ok(LsmEstimate {
+            value: sign * (fixed + mean),
+            std_error: (var / n).sqrt(),
+        })
+    }
+
+    /// Monte Carlo value with its standard error on today's market.
+    pub fn estimate(&self, market: &IrMarketContext) -> Result<LsmEstimate> {
+        self.estimate_under_shift(market, 0.0, 0.0)
+    }
+
+    fn pv_under_shift(
+        &self,
+        market: &IrMarketContext,
+        rate_shift_bp: f64,
+        vol_shift_bp: f64,
+    ) -> Result<f64> {
+        Ok(self
+            .estimate_under_shift(market, rate_shift_bp, vol_shift_bp)?
+            .value)
+    }
+}
+
+impl IRDerivatives for RangeAccrual {
+    fn mtm(&self, market: &IrMarketContext) -> Result<CurrencyValue> {
+        Ok(CurrencyValue {
+            currency: self.currency,
+            value: self.pv_under_shift(market, 0.0, 0.0)?,
+        })
+    }
+
+    fn dv01(&self, market: &IrMarketContext) -> Result<f64> {
+        Ok(self.pv_under_shift(market, 1.0, 0.0)? - self.pv_under_shift(market, 0.0, 0.0)?)
+    }
+
+    fn gamma(
+        &self,
+        market: &IrMarketContext,
+        rate_shift_bp: f64,
+        mode: RateShiftMode,
+    ) -> Result<f64> {
+        ensure_supported_mode(mode)?;
+        let base = self.pv_under_shift(market, 0.0, 0.0)?;
+        let up = self.pv_under_shift(market, rate_shift_bp, 0.0)?;
+        let down = self.pv_under_shift(market, -rate_shift_bp, 0.0)?;
+        Ok(up + down - 2.0 * base)
+    }
+
+    fn vega(&self, market: &IrMarketContext, vol_shift_bp: f64) -> Result<f64> {
+        Ok(self.pv_under_shift(market, 0.0, vol_shift_bp)?
+            - self.pv_under_shift(market, 0.0, 0.0)?)
+    }
+
+    /// One flow per unpaid period: its Monte Carlo expected coupon, with
+    /// `coupon` times the expected in-range fraction as rate. Periods
+    /// whose last observation is on or before the valuation date are
+    /// fixed.
+    fn cashflows(&self, market: &IrMarketContext) -> Result<Vec<InterestRateCashflow>> {
+        let coupons = self.coupons_under_shift(market, 0.0, 0.0)?;
+        let n = coupons.paths.len().max(1) as f64;
+        let dc = Actual365Fixed::default();
+        let sign = self.direction_sign();
+        self.live_periods()
+            .enumerate()
+            .map(|(k, period)| {
+                let simulated: f64 = coupons.paths.iter().map(|p| p[k]).sum::<f64>() / n;
+                let discount = coupons
+                    .curve
+                    .p0(dc.year_fraction(self.valuation_date, period.pay_date)?);
+                let amount = sign * (coupons.fixed[k] + simulated) / discount;
+                let accrual = self
+                    .accrual_day_counter
+                    .year_fraction(period.accrual_start_date, period.accrual_end_date)?;
+                let rate = if accrual > 0.0 {
+                    sign * amount / (self.notional * accrual)
+                } else {
+                    0.0
+                };
+                let kind = if period.accrual_end_date <= self.valuation_date + Days::new(1) {
+                    CashflowKind::Fixed
+                } else {
+                    CashflowKind::Projected
+                };
+                Ok(InterestRateCashflow::coupon(
+                    kind,
+                    None,
+                    period,
+                    rate,
+                    self.notional,
+                    amount,
+                    discount,
+                ))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::derivatives::basic::{Direction, Style};
+    use crate::markets::termstructures::yieldcurve::zero_rate_curve;
+    use crate::models::interestrate::fmm::{FmmTenor, LinearDecay};
+
+    fn d(y: i32, m: u32, dd: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, dd).unwrap()
+    }
+
+    fn market(valuation_date: NaiveDate) -> IrMarketContext {
+        let curve = zero_rate_curve(
+            valuation_date,
+            &[(d(2027, 8, 12), 0.025), (d(2036, 8, 12), 0.035)],
+        );
+        IrMarketContext::new(valuation_date, Currency::EUR, curve, None)
+    }
+
+    fn quarterly(start: NaiveDate, n: u32) -> Vec<InterestRateSchedulePeriod> {
+        (0..n)
+            .map(|q| {
+                let s = (start + Period::Months(3 * q)).unwrap();
+                let e = (start + Period::Months(3 * (q + 1))).unwrap();
+                InterestRateSchedulePeriod::new(s, e, e, s, 0.0, 1.0e7)
+            })
+            .collect()
+    }
+
+    fn basic_info(valuation_date: NaiveDate, end: NaiveDate, direction: Direction) -> BasicInfo {
+        BasicInfo {
+            trade_date: valuation_date,
+            style: Style::IRSwap,
+            direction,
+            expiry_date: end,
+            delivery_date: end,
+        }
+    }
+
+    fn settings(seed: u64) -> MonteCarloSettings {
+        MonteCarloSettings {
+            n_training_paths: 2_000,
+            n_paths: 2_000,
+            max_step_days: 7,
+            seed,
+        }
+    }
+
+    fn fmm(years: u32) -> Fmm {
+        // One extra quarter: ACT/365F maturities run past whole years.
+        let m = 4 * years as usize + 1;
+        let dates = (0..=m).map(|j| j as f64 * 0.25).collect();
+        let correlation = (0..m)
+            .map(|i| {
+                (0..m)
+                    .map(|j| (-0.1 * (i as f64 - j as f64).abs()).exp())
+                    .collect()
+            })
+            .collect();
+        Fmm::new(
+            FmmTenor::new(dates, vec![0.0; m]),
+            vec![0.008; m],
+            correlation,
+            LinearDecay,
+        )
+    }
+
+    /// A range wide enough to always accrue reprices the fixed coupon
+    /// leg off the curve under both models; a narrow range is worth
+    /// less and has vega of the expected sign.
+    #[test]
+    fn range_accrual_reprices_fixed_leg_when_always_in_range() -> Result<()> {
+        let valuation_date = d(2026, 8, 12);
+        let market = market(valuation_date);
+        let schedule = quarterly(valuation_date, 4);
+        let fixed_leg: f64 = schedule
+            .iter()
+            .map(|p| {
+                let tau = Actual365Fixed::default()
+                    .year_fraction(p.accrual_start_date, p.accrual_end_date)
+                    .unwrap();
+                1.0e7
+                    * 0.04
+                    * tau
+                    * market
+                        .curve
+                        .discount(
+                            p.pay_date,
+                            &InterpolationMethodEnum::PiecewiseLinearContinuous,
+                        )
+                        .unwrap()
+            })
+            .sum();
+        let range = |model: RateModel, lower: f64, upper: f64| RangeAccrual {
+            basic_info: basic_info(valuation_date, d(2027, 8, 12), Direction::Buy),
+            currency: Currency::EUR,
+            notional: 1.0e7,
+            coupon: 0.04,
+            index: RangeAccrualIndex::Overnight,
+            lower,
+            upper,
+            valuation_date,
+            schedule: schedule.clone(),
+            accrual_day_counter: Box::new(Actual365Fixed::default()),
+            model,
+            settings: settings(5),
+        };
+        let hw = RateModel::HullWhite(HullWhite1F {
+            mean_reversion: 0.05,
+            sigma: 0.01,
+        });
+        for model in [hw.clone(), RateModel::Fmm(fmm(1))] {
+            let wide = range(model, -1.0, 1.0).estimate(&market)?;
+            assert!(
+                (wide.value - fixed_leg).abs() < 4.0 * wide.std_error + 1.0e-4 * fixed_leg,
+                "{wide:?} vs {fixed_leg}"
+            );
+        }
+        let narrow = range(hw, 0.015, 0.035);
+        let estimate = narrow.estimate(&market)?;
+        assert!(0.3 * fixed_leg < estimate.value && estimate.value < fixed_leg);
+        assert!(narrow.vega(&market, 10.0)? < 0.0);
+        Ok(())
+    }
+
+    /// Under either model the right to cancel lifts the holder above
+    /// the vanilla swap and drags the writer below it; the holder's
+    /// option has positive vega and the receiver keeps a negative DV01.
+    #[test]
+    fn cancellable_swap_brackets_vanilla_swap() -> Result<()> {
+        let valuation_date = d(2026, 8, 12);
+        let market = market(valuation_date);
+        let schedule = quarterly(valuation_date, 12);
+        let cancellation_dates: Vec<NaiveDate> = schedule
+            .iter()
+            .skip(4)
+            .map(|p| p.accrual_start_date)
+            .collect();
+        let swap = |right: CancellationRight, model: RateModel| CancellableSwap {
+            basic_info: basic_info(valuation_date, d(2029, 8, 12), Direction::Buy),
+            currency: Currency::EUR,
+            notional: 1.0e7,
+            fixed_rate: 0.03,
+            float_spread: 0.0,
+            right,
+            cancellation_dates: cancellation_dates.clone(),
+            valuation_date,
+            schedule: quarterly(valuation_date, 12),
+            accrual_day_counter: Box::new(Actual365Fixed::default()),
+            model,
+            settings: settings(11),
+        };
+        let hw = RateModel::HullWhite(HullWhite1F {
+            mean_reversion: 0.05,
+            sigma: 0.01,
+        });
+        let vanilla = CancellableSwap {
+            cancellation_dates: Vec::new(),
+            ..swap(CancellationRight::Holder, hw.clone())
+        }
+        .mtm(&market)?
+        .value;
+        for model in [hw.clone(), RateModel::Fmm(fmm(3))] {
+            let holder = swap(CancellationRight::Holder, model.clone());
+            let issuer = swap(CancellationRight::Counterparty, model);
+            let (h, i) = (holder.mtm(&market)?.value, issuer.mtm(&market)?.value);
+            assert!(h > vanilla && i < vanilla, "{h} {vanilla} {i}");
+            let result = holder.cancellation_option(&market)?.unwrap();
+            assert_eq!(result.diagnostics.len(), cancellation_dates.len() - 1);
+            assert!(result.lower_bound.value > 5.0 * result.lower_bound.std_error);
+        }
+        let holder = swap(CancellationRight::Holder, hw);
+        assert!(holder.vega(&market, 1.0)? > 0.0);
+        assert!(holder.dv01(&market)? < 0.0);
+        Ok(())
+    }
+}
@@ -3,7 +3,14 @@ use serde::{Deserialize, Serialize};
 
 use crate::derivatives::basic::Direction;
 use crate::derivatives::forex::basic::CurrencyValue;
-use crate::derivatives::interestrate::basic::{IRDerivatives, RateShiftMode};
+/// Re-exported from [`crate::derivatives::interestrate::basic`], where the
+/// ladder row now lives, so the old `swap::InterestRateCashflow` path resolves.
+pub use crate::derivatives::interestrate::basic::InterestRateCashflow;
+use crate::derivatives::interestrate::basic::{
+    CapStyle, CashflowKind, IRDerivatives, RateShiftMode,
+};
+use crate::derivatives::interestrate::cap::{CapletStrip, caplet_variance};
+use crate::derivatives::interestrate::cms::cms_fixing;
 use crate::error::{Error, Result};
 use crate::markets::interestrate::interestrateindex::InterestRateIndex;
 use crate::markets::interestrate::market_context::IrMarketContext;
@@ -11,6 +18,7 @@ use crate::markets::termstructures::yieldcurve::{
     InterestRateQuote, InterestRateQuoteEnum, InterpolationMethodEnum, StrippedCurve,
     YieldTermStructure,
 };
+use crate::models::common::bachelier::{bachelier_call, bachelier_put};
 use crate::time::businessdayconvention::BusinessDayConvention;
 use crate::time::calendars::{Calendar, Target};
 use crate::time::daycounters::DayCounters;
@@ -22,8 +30,29 @@ use roots::{SimpleConvergency, find_root_brent};
 
 #[derive(Deserialize, Serialize, Debug)]
 pub enum InterestRateSwapLegType {
-    Float { spread: f64 },
-    Fixed { coupon: f64 },
+    Float {
+        spread: f64,
+    },
+    Fixed {
+        coupon: f64,
+    },
+    /// Swap rate of `tenor` fixed at each reset plus `spread`, convexity
+    /// adjusted over [`IrMarketContext::cms`]; the underlying swap's
+    /// fixed leg pays at `fixed_frequency`.
+    Cms {
+        tenor: Period,
+        fixed_frequency: Frequency,
+        spread: f64,
+    },
+    /// Float coupon `min(max(r + spread, floor), cap)`; the embedded
+    /// caplets and floorlets are priced off
+    /// [`IrMarketContext::cap_surface`] under `style`.
+    CappedFloored {
+        spread: f64,
+        cap: Option<f64>,
+        floor: Option<f64>,
+        style: CapStyle,
+    },
 }
 
 #[derive(Deserialize, Serialize, Debug)]
@@ -110,6 +139,21 @@ impl InterestRateSwapLeg {
         match self.swap_type {
             InterestRateSwapLegType::Fixed { coupon: _ } => true,
             InterestRateSwapLegType::Float { spread: _ } => false,
+            InterestRateSwapLegType::Cms { .. } => false,
+            InterestRateSwapLegType::CappedFloored { .. } => false,
+        }
+    }
+
+    /// Ladder classification of one coupon: fixed-rate coupons are
+    /// `Fixed`, floating ones follow [`CashflowKind::of_fixing`].
+    fn coupon_kind(
+        &self,
+        period: &InterestRateSchedulePeriod,
+        valuation_date: NaiveDate,
+    ) -> (CashflowKind, Option<NaiveDate>) {
+        match self.swap_type {
+            InterestRateSwapLegType::Fixed { .. } => (CashflowKind::Fixed, None),
+            _ => CashflowKind::of_fixing(period, valuation_date),
         }
     }
 
@@ -117,6 +161,8 @@ impl InterestRateSwapLeg {
         match self.swap_type {
             InterestRateSwapLegType::Fixed { coupon } => coupon,
             InterestRateSwapLegType::Float { spread } => spread,
+            InterestRateSwapLegType::Cms { spread, .. } => spread,
+            InterestRateSwapLegType::CappedFloored { spread, .. } => spread,
         }
     }
 
@@ -293,8 +339,13 @@ impl InterestRateSwap {
                 Direction::Sell => -1f64,
             };
             for period in &schedule {
-                let cashflow = self.calculate_period_cashflow(period, leg, yield_term_structure)?;
-                total_npv += cashflow.present_value.unwrap();
+                let cashflow = self.calculate_period_cashflow(
+                    period,
+                    leg,
+                    valuation_date,
+                    yield_term_structure,
+                )?;
+                total_npv += cashflow.present_value;
             }
             // Bond-style valuation: return of notional on the final pay date.
             // For matched-notional IRS the principals cancel in the net NPV,
@@ -314,19 +365,28 @@ impl InterestRateSwap {
         &self,
         period: &InterestRateSchedulePeriod,
         leg: &InterestRateSwapLeg,
+        valuation_date: NaiveDate,
         yield_term_structure: &mut YieldTermStructure,
     ) -> Result<InterestRateCashflow> {
-        let day_count = leg
-            .schedule_detail
-            .day_counter
-            .day_count(period.accrual_start_date, period.accrual_end_date)?;
         let year_fraction = leg
             .schedule_detail
             .day_counter
             .year_fraction(period.accrual_start_date, period.accrual_end_date)?;
 
+        let (kind, fixing_date) = leg.coupon_kind(period, valuation_date);
         let reset_rate = match leg.swap_type {
             InterestRateSwapLegType::Fixed { coupon } => coupon,
+            InterestRateSwapLegType::Cms { .. } => {
+                return Err(Error::InvalidData(
+                    "CMS coupons need a swaption cube; price through IRDerivatives".to_string(),
+                ));
+            }
+            InterestRateSwapLegType::CappedFloored { .. } => {
+                return Err(Error::InvalidData(
+                    "capped / floored coupons need a caplet surface; price through IRDerivatives"
+                        .to_string(),
+                ));
+            }
             InterestRateSwapLegType::Float { spread } => {
                 // Daily-compounded overnight (SOFR/SONIA/ESTR) or IBOR-style rate
                 // implied by the curve over the accrual period:
@@ -355,15 +415,15 @@ impl InterestRateSwap {
         };
         let payment = reset_rate * year_fraction * period.balance;
 
-        Ok(InterestRateCashflow {
-            day_counts: Some(day_count),
-            notional: Some(period.balance),
-            principal: Some(period.balance),
-            reset_rate: Some(reset_rate),
-            payment: Some(payment),
-            discount: Some(discount),
-            present_value: Some(payment * discount * direction_sign),
-        })
+        Ok(InterestRateCashflow::coupon(
+            kind,
+            fixing_date,
+            period,
+            reset_rate,
+            period.balance,
+            payment * direction_sign,
+            discount,
+        ))
     }
 
     /// Deal currency — inferred from the first leg's rate-index
@@ -384,15 +444,33 @@ impl InterestRateSwap {
     /// Float-leg coupons are implied by the *shifted* discount
     /// factors via `r · τ = DF(start)/DF(end) − 1`, so the shift
     /// propagates to both the projection and the discounting step —
-    /// the standard "bumped-curve" DV01 definition.
+    /// the standard "bumped-curve" DV01 definition. CMS coupons are
+    /// replicated over `market.cms` and capped / floored coupons priced
+    /// off `market.cap_surface`, with vols moved by `vol_shift_bp`.
     fn pv_under_shift(
         &self,
-        valuation_date: NaiveDate,
-        yts: &YieldTermStructure,
+        market: &IrMarketContext,
         rate_shift_bp: f64,
+        vol_shift_bp: f64,
     ) -> Result<f64> {
+        Ok(self
+            .cashflows_under_shift(market, rate_shift_bp, vol_shift_bp)?
+            .iter()
+            .map(|cf| cf.present_value)
+            .sum())
+    }
+
+    /// Every leg's coupons and final principal under the shifts of
+    /// [`InterestRateSwap::pv_under_shift`].
+    fn cashflows_under_shift(
+        &self,
+        market: &IrMarketContext,
+        rate_shift_bp: f64,
+        vol_shift_bp: f64,
+    ) -> Result<Vec<InterestRateCashflow>> {
+        let (valuation_date, yts) = (market.valuation_date, &market.curve);
         let method = &InterpolationMethodEnum::PiecewiseLinearContinuous;
-        let mut total_npv = 0.0_f64;
+        let mut flows = Vec::new();
         for leg in &self.legs {
             let schedule = leg.generate_schedule(valuation_date)?;
             let direction_sign = match leg.direction {
@@ -404,8 +482,8 @@ impl InterestRateSwap {
                     .schedule_detail
                     .day_counter
                     .year_fraction(period.accrual_start_date, period.accrual_end_date)?;
-                let reset_rate = match leg.swap_type {
-                    InterestRateSwapLegType::Fixed { coupon } => coupon,
+                let reset_rate = match &leg.swap_type {
+                    InterestRateSwapLegType::Fixed { coupon } => *coupon,
                     InterestRateSwapLegType::Float { spread } => {
                         let df_start =
                             yts.shifted_discount(period.accrual_start_date, method, rate_shift_bp)?;
@@ -413,26 +491,101 @@ impl InterestRateSwap {
                             yts.shifted_discount(period.accrual_end_date, method, rate_shift_bp)?;
                         (df_start / df_end - 1.0) / year_fraction + spread
                     }
+                    InterestRateSwapLegType::Cms {
+                        tenor,
+                        fixed_frequency,
+                        spread,
+                    } => {
+                        let cms = market.cms.as_ref().ok_or_else(|| {
+                            Error::InvalidData(
+                                "CMS leg: IrMarketContext.cms must be present".to_string(),
+                            )
+                        })?;
+                        let (coupon, smile) = cms_fixing(
+                            valuation_date,
+                            period,
+                            *tenor,
+                            fixed_frequency,
+                            yts,
+                            cms,
+                            rate_shift_bp,
+                            vol_shift_bp,
+                        )?;
+                        coupon.rate(&smile, cms.model)? + spread
+                    }
+                    InterestRateSwapLegType::CappedFloored {
+                        spread,
+                        cap,
+                        floor,
+                        style,
+                    } => {
+                        let vs = market.cap_surface.as_ref().ok_or_else(|| {
+                            Error::InvalidData(
+                                "capped / floored leg: IrMarketContext.cap_surface must be present"
+                                    .to_string(),
+                            )
+                        })?;
+                        let df_start =
+                            yts.shifted_discount(period.accrual_start_date, method, rate_shift_bp)?;
+                        let df_end =
+                            yts.shifted_discount(period.accrual_end_date, method, rate_shift_bp)?;
+                        let forward = (df_start / df_end - 1.0) / year_fraction;
+                        let strip = CapletStrip {
+                            style: *style,
+                            valuation_date,
+                            accrual_day_counter: leg.schedule_detail.day_counter.as_ref(),
+                        };
+                        // min(max(r + s, F), C) = r + s + (F − s − r)⁺ − (r − (C − s))⁺
+                        let mut rate = forward + spread;
+                        if let Some(floor) = floor {
+                            let strike = floor - spread;
+                            let variance =
+                                caplet_variance(&strip, period, strike, vs, vol_shift_bp)?;
+                            rate += bachelier_put(forward, strike, variance);
+                        }
+                        if let Some(cap) = cap {
+                            let strike = cap - spread;
+                            let variance =
+                                caplet_variance(&strip, period, strike, vs, vol_shift_bp)?;
+                            rate -= bachelier_call(forward, strike, variance);
+                        }
+                        rate
+                    }
                 };
                 let df_pay = yts.shifted_discount(period.pay_date, method, rate_shift_bp)?;
                 let payment = reset_rate * year_fraction * period.balance;
-                total_npv += payment * df_pay * direction_sign;
+                let (kind, fixing_date) = leg.coupon_kind(period, valuation_date);
+                flows.push(InterestRateCashflow::coupon(
+                    kind,
+                    fixing_date,
+                    period,
+                    reset_rate,
+                    period.balance,
+                    payment * direction_sign,
+                    df_pay,
+                ));
             }
             // Bond-style return of notional on the final pay date so
             // per-leg NPVs match expected values; matched-notional IRS
             // have the two legs' principals cancel.
             if let Some(last) = schedule.last() {
                 let df_last = yts.shifted_discount(last.pay_date, method, rate_shift_bp)?;
-                total_npv += last.balance * df_last * direction_sign;
+                flows.push(InterestRateCashflow::single(
+                    CashflowKind::Fixed,
+                    last.pay_date,
+                    last.balance,
+                    last.balance * direction_sign,
+                    df_last,
+                ));
             }
         }
-        Ok(total_npv)
+        Ok(flows)
     }
 }
 
 impl IRDerivatives for InterestRateSwap {
     fn mtm(&self, market: &IrMarketContext) -> Result<CurrencyValue> {
-        let pv = self.pv_under_shift(market.valuation_date, &market.curve, 0.0)?;
+        let pv = self.pv_under_shift(market, 0.0, 0.0)?;
         Ok(CurrencyValue {
             currency: self.currency()?,
             value: pv,
@@ -442,8 +595,8 @@ impl IRDerivatives for InterestRateSwap {
     /// DV01 = PV(y + 1bp) − PV(y). Receiver-fixed swap DV01 is
     /// negative (PV falls as rates rise); payer-fixed is positive.
     fn dv01(&self, market: &IrMarketContext) -> Result<f64> {
-        let base = self.pv_under_shift(market.valuation_date, &market.curve, 0.0)?;
-        let up = self.pv_under_shift(market.valuation_date, &market.curve, 1.0)?;
+        let base = self.pv_under_shift(market, 0.0, 0.0)?;
+        let up = self.pv_under_shift(market, 1.0, 0.0)?;
         Ok(up - base)
     }
 
@@ -467,18 +620,36 @@ impl IRDerivatives for InterestRateSwap {
                 )));
             }
         }
-        let base = self.pv_under_shift(market.valuation_date, &market.curve, 0.0)?;
-        let up = self.pv_under_shift(market.valuation_date, &market.curve, rate_shift_bp)?;
-        let down = self.pv_under_shift(market.valuation_date, &market.curve, -rate_shift_bp)?;
+        let base = self.pv_under_shift(market, 0.0, 0.0)?;
+        let up = self.pv_under_shift(market, rate_shift_bp, 0.0)?;
+        let down = self.pv_under_shift(market, -rate_shift_bp, 0.0)?;
         Ok(up + down - 2.0 * base)
     }
 
     /// Vega is identically zero for a vanilla interest-rate swap —
     /// there's no optionality, so PV is a pure linear function of
     /// forward rates and discount factors with zero vol sensitivity.
-    /// `vol_shift_bp` is ignored.
-    fn vega(&self, _market: &IrMarketContext, _vol_shift_bp: f64) -> Result<f64> {
-        Ok(0.0)
+    /// CMS legs carry convexity and capped / floored legs embed options,
+    /// so swaps with either bump every swaption-cube and caplet vol by
+    /// `vol_shift_bp`.
+    fn vega(&self, market: &IrMarketContext, vol_shift_bp: f64) -> Result<f64> {
+        let has_optionality = self.legs.iter().any(|leg| {
+            matches!(
+                leg.swap_type,
+                InterestRateSwapLegType::Cms { .. } | InterestRateSwapLegType::CappedFloored { .. }
+            )
+        });
+        if !has_optionality {
+            return Ok(0.0);
+        }
+        Ok(self.pv_under_shift(market, 0.0, vol_shift_bp)?
+            - self.pv_under_shift(market, 0.0, 0.0)?)
+    }
+
+    /// Coupons of every leg, fixed-leg coupons as fixed and the rest as
+    /// projected, followed by each leg's principal on its last pay date.
+    fn cashflows(&self, market: &IrMarketContext) -> Result<Vec<InterestRateCashflow>> {
+        self.cashflows_under_shift(market, 0.0, 0.0)
     }
 }
 
@@ -515,17 +686,6 @@ impl InterestRateQuote for InterestRateSwap {
     }
 }
 
-#[derive(Deserialize, Serialize, PartialEq, Default, Debug)]
-pub struct InterestRateCashflow {
-    pub day_counts: Option<i64>,
-    pub notional: Option<f64>,
-    pub principal: Option<f64>,
-    pub reset_rate: Option<f64>,
-    pub payment: Option<f64>,
-    pub discount: Option<f64>,
-    pub present_value: Option<f64>,
-}
-
 #[cfg(test)]
 mod tests {
     use super::{
@@ -1289,10 +1449,13 @@ mod tests {
     /// * `vega == 0` exactly (no optionality).
     /// * `modified_duration > 0` for receive-fixed (PV rises as rates
     ///   fall, by definition).
+    /// * `cashflows` present values add back up to `mtm`, and the ladder
+    ///   round-trips through JSON.
     #[test]
     fn ir_derivatives_trait_on_usd_sofr_5y_swap() -> Result<()> {
         use crate::derivatives::interestrate::basic::{
-            DEFAULT_RATE_SHIFT_BP, IRDerivatives, RateShiftMode,
+            CashflowKind, DEFAULT_RATE_SHIFT_BP, IRDerivatives, InterestRateCashflow,
+            RateShiftMode, cashflows_to_csv, cashflows_to_json,
         };
         use crate::markets::interestrate::market_context::IrMarketContext;
 
@@ -1405,6 +1568,38 @@ mod tests {
             assert!(swap.gamma(&ctx, DEFAULT_RATE_SHIFT_BP, mode).is_err());
         }
 
+        // Cash-flow ladder: five coupons per leg plus both principals.
+        let flows = swap.cashflows(&ctx)?;
+        assert_eq!(flows.len(), 12);
+        let pv: f64 = flows.iter().map(|cf| cf.present_value).sum();
+        assert!((pv - mtm.value).abs() < 1e-6);
+        for cf in &flows[..5] {
+            assert_eq!(cf.kind, CashflowKind::Fixed);
+            assert_eq!(cf.fixing_date, None);
+            assert_eq!(cf.rate, Some(0.036880));
+            assert!(cf.amount > 0.0);
+        }
+        for cf in &flows[6..11] {
+            assert_eq!(cf.kind, CashflowKind::Projected);
+            assert!(cf.fixing_date.is_some() && cf.amount < 0.0);
+        }
+        assert_eq!(flows[5].rate, None);
+        assert_eq!(flows[5].amount, 10_000_000.0);
+        // A float coupon whose reset has passed is already fixed.
+        let first = &swap.legs[1].schedule[0];
+        assert_eq!(
+            swap.legs[1].coupon_kind(first, first.reset_date),
+            (CashflowKind::Fixed, Some(first.reset_date))
+        );
+        assert_eq!(
+            swap.legs[1].coupon_kind(first, valuation_date),
+            (CashflowKind::Projected, Some(first.reset_date))
+        );
+        assert_eq!(cashflows_to_csv(&flows).lines().count(), 13);
+        let parsed: Vec<InterestRateCashflow> =
+            serde_json::from_str(&cashflows_to_json(&flows)?).unwrap();
+        assert_eq!(parsed, flows);
+
         Ok(())
     }
 }
@@ -1,3 +1,4 @@
 pub mod forex;
+pub mod inflation;
 pub mod interestrate;
 pub mod termstructures;
@@ -40,7 +40,24 @@
 //!      ├─ Calibrator::calibrate(&ctx)                        ← models/*
 //!      └─ Simulator::from_market(&ctx)                       ← MC paths
 //! ```
+//!
+//! # Scenario shifts
+//!
+//! Bump-and-reprice Greeks go through the `*_under` lookups with an
+//! [`FxMarketShift`], which moves the inputs without rebuilding any
+//! aggregator:
+//!
+//! ```text
+//!   v'    = v + days
+//!   F'(T) = (S + dS + (pts + dpts)/conv) · exp((dr_d − dr_f) · τ(v', T))
+//!   P'(T) = P(T) · exp(−dr_d · τ(v, T)) / (P(v') · exp(−dr_d · τ(v, v')))
+//!   σ'    = σ(T, K) + dσ
+//! ```
+//!
+//! Rolling the date keeps every quote for a given delivery date fixed,
+//! so theta is the pure carry-and-decay of the trade.
 
+use crate::derivatives::forex::basic::FXUnderlying;
 use crate::error::{Error, Result};
 use crate::markets::forex::quotes::forwardpoints::{FXForwardHelper, FXForwardQuote};
 use crate::markets::forex::quotes::volsurface::{FXDeltaVolPillar, FXVolQuote, FXVolSurface};
@@ -49,8 +66,10 @@ use crate::markets::termstructures::yieldcurve::{
 };
 use crate::time::calendars::Calendar;
 use crate::time::daycounters::DayCounters;
+use crate::time::daycounters::actual365fixed::Actual365Fixed;
 use chrono::{Duration, NaiveDate};
 use iso_currency::Currency;
+use std::str::FromStr;
 
 /// Bundled FX market data for a single pair / valuation date.
 ///
@@ -76,6 +95,24 @@ pub struct FxMarketContext {
     pub vol_surface: FXVolSurface,
 }
 
+/// Parallel move of the FX market inputs, used for bump-and-reprice
+/// Greeks. The default is no move.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct FxMarketShift {
+    /// Absolute spot move.
+    pub spot: f64,
+    /// Absolute implied-vol move (decimal).
+    pub vol: f64,
+    /// Domestic rate move in basis points.
+    pub rate_d_bp: f64,
+    /// Foreign rate move in basis points.
+    pub rate_f_bp: f64,
+    /// Forward-point move, in points.
+    pub forward_points: f64,
+    /// Calendar days the valuation date rolls forward.
+    pub days: i64,
+}
+
 impl FxMarketContext {
     /// Aggregator-level constructor — every field pre-built.
     #[allow(clippy::too_many_arguments)]
@@ -109,7 +146,8 @@ impl FxMarketContext {
     /// * FX forward helper: `Vec<FXForwardQuote>` — tenor / value
     ///   pairs, turned into a dated ladder at lookup time.
     /// * FX vol surface: `Vec<FXDeltaVolPillar>` — per-expiry
-    ///   delta-quoted smiles, calibrated via [`FXVolSurface::new`].
+    ///   delta-quoted smiles, calibrated via [`FXVolSurface::for_pair`]
+    ///   so tenor-quoted pillars resolve on the pair's option date rules.
     ///
     /// `calendar` / `day_counter` are consumed for each leg's
     /// `YieldTermStructure`; callers typically pass a joint calendar
@@ -141,7 +179,11 @@ impl FxMarketContext {
             foreign_ir.get_stripped_curve()?,
         );
         let forwards = FXForwardHelper::new(valuation_date, spot, fx_forward_quotes);
-        let vol_surface = FXVolSurface::new(valuation_date, vol_pillars)?;
+        let vol_surface =
+            match FXUnderlying::from_str(&format!("{}{}", pair.1.code(), pair.0.code())) {
+                Ok(underlying) => FXVolSurface::for_pair(&underlying, valuation_date, vol_pillars)?,
+                Err(_) => FXVolSurface::new(valuation_date, vol_pillars)?,
+            };
         Ok(Self::new(
             valuation_date,
             spot,
@@ -195,6 +237,92 @@ impl FxMarketContext {
         self.vol_surface.volatility(expiry, strike)
     }
 
+    /// Vol time between two dates on the surface's clock (Act/365 when it
+    /// has none).
+    pub fn vol_time(&self, start: NaiveDate, end: NaiveDate) -> Result<f64> {
+        self.vol_surface.vol_time(start, end)
+    }
+
+    /// `sigma`, quoted from the valuation date to `expiry`, restated from
+    /// the shift's rolled valuation date: the roll consumes its share of
+    /// vol time, leaving
+    ///
+    /// ```text
+    ///     σ_roll² · T_roll = σ² · T · τ(roll, expiry) / τ(valuation, expiry)
+    /// ```
+    ///
+    /// with `T` in Act/365 and `τ` in vol time.
+    pub fn rolled_vol(&self, expiry: NaiveDate, sigma: f64, shift: &FxMarketShift) -> Result<f64> {
+        if shift.days == 0 {
+            return Ok(sigma);
+        }
+        let rolled = self.valuation_date_under(shift);
+        let day_counter = Actual365Fixed::default();
+        let t = day_counter.year_fraction(self.valuation_date, expiry)?;
+        let t_roll = day_counter.year_fraction(rolled, expiry)?;
+        let share = self.vol_time(rolled, expiry)? / self.vol_time(self.valuation_date, expiry)?;
+        Ok(sigma * (t * share / t_roll).sqrt())
+    }
+
+    /// Valuation date after the shift's roll.
+    pub fn valuation_date_under(&self, shift: &FxMarketShift) -> NaiveDate {
+        self.valuation_date + Duration::days(shift.days)
+    }
+
+    /// Shifted outright forward for `date`; `converter` turns points
+    /// into price units (see `FXUnderlying::forward_points_converter`).
+    /// `None` when `date` is outside the forward-point ladder.
+    pub fn forward_under(
+        &self,
+        date: NaiveDate,
+        calendar: &dyn Calendar,
+        converter: f64,
+        shift: &FxMarketShift,
+    ) -> Result<Option<f64>> {
+        let Some(points) = self.forwards.get_forward(date, calendar)? else {
+            return Ok(None);
+        };
+        let outright = self.spot + shift.spot + (points + shift.forward_points) / converter;
+        let tau =
+            Actual365Fixed::default().year_fraction(self.valuation_date_under(shift), date)?;
+        Ok(Some(
+            outright * ((shift.rate_d_bp - shift.rate_f_bp) * 1.0e-4 * tau).exp(),
+        ))
+    }
+
+    /// Shifted domestic discount factor from the rolled valuation date
+    /// to `date`.
+    pub fn discount_d_under(
+        &self,
+        date: NaiveDate,
+        method: &InterpolationMethodEnum,
+        shift: &FxMarketShift,
+    ) -> Result<f64> {
+        let end = self
+            .domestic_curve
+            .shifted_discount(date, method, shift.rate_d_bp)?;
+        if shift.days == 0 {
+            return Ok(end);
+        }
+        let start = self.domestic_curve.shifted_discount(
+            self.valuation_date_under(shift),
+            method,
+            shift.rate_d_bp,
+        )?;
+        Ok(end / start)
+    }
+
+    /// Shifted implied vol at `expiry` and `strike`, seen from the rolled
+    /// valuation date (see [`Self::rolled_vol`]).
+    pub fn implied_vol_under(
+        &self,
+        expiry: NaiveDate,
+        strike: f64,
+        shift: &FxMarketShift,
+    ) -> Result<f64> {
+        Ok(self.rolled_vol(expiry, self.implied_vol(expiry, strike)?, shift)? + shift.vol)
+    }
+
     /// Constructor for **linear FX products** (forwards, spots,
     /// swaps) that don't read the vol surface. Fills in a trivial
     /// one-pillar flat-vol surface and uses the domestic curve as
@@ -235,10 +363,10 @@ impl FxMarketContext {
 /// `FXVolSurface::new` can build its quadratic skew.
 fn trivial_vol_surface(valuation_date: NaiveDate) -> Result<FXVolSurface> {
     let pillar_expiry = valuation_date + Duration::days(365);
-    let pillar = FXDeltaVolPillar {
-        expiry: pillar_expiry,
-        forward: 1.0,
-        quotes: vec![
+    let pillar = FXDeltaVolPillar::new(
+        pillar_expiry.into(),
+        1.0,
+        vec![
             FXVolQuote::Atm(0.10),
             FXVolQuote::Put {
                 delta: 0.25,
@@ -249,7 +377,7 @@ fn trivial_vol_surface(valuation_date: NaiveDate) -> Result<FXVolSurface> {
                 vol: 0.10,
             },
         ],
-    };
+    );
     FXVolSurface::new(valuation_date, vec![pillar])
 }
 
@@ -259,10 +387,10 @@ mod tests {
     use crate::markets::forex::quotes::volsurface::FXVolQuote;
 
     fn toy_vol_pillar(expiry: NaiveDate, forward: f64) -> FXDeltaVolPillar {
-        FXDeltaVolPillar {
-            expiry,
+        FXDeltaVolPillar::new(
+            expiry.into(),
             forward,
-            quotes: vec![
+            vec![
                 FXVolQuote::Atm(0.08),
                 FXVolQuote::Put {
                     delta: 0.25,
@@ -273,7 +401,7 @@ mod tests {
                     vol: 0.079,
                 },
             ],
-        }
+        )
     }
 
     #[test]
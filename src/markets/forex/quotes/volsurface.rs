@@ -12,34 +12,104 @@
 //! identically zero at the three quadratic anchors). Outside the range of
 //! quoted strikes, `A ≡ 0`.
 //!
+//! # Smile models
+//!
+//! The quadratic above is the default [`FXSmileModel`]. A pillar can instead
+//! fit one of
+//!
+//! * [`FXSmileModel::VannaVolga`] – Castagna–Mercurio through the 25Δ put,
+//!   ATM and 25Δ call; other deltas are not matched
+//! * [`FXSmileModel::Sabr`]       – Hagan SABR, `β = 0.5`, least squares
+//! * [`FXSmileModel::Svi`]        – raw SVI total variance, least squares
+//! * [`FXSmileModel::Ssvi`]       – a single SSVI slice, least squares
+//!
+//! Market strangles are resolved against the quadratic smile first, so every
+//! model is fitted to plain `(strike, vol)` quotes. Beyond the quoted
+//! strikes, Vanna–Volga and SABR continue linearly in total variance with
+//! slopes inside Lee's bound `|∂w/∂k| ≤ 2`; SVI slopes `b(1 ± ρ)` are held
+//! inside the same bound, and SSVI obeys the Gatheral–Jacquier
+//! no-butterfly conditions. [`FXVolSurface::fit_report`] lists quoted and
+//! fitted vols per pillar.
+//!
+//! # Static arbitrage
+//!
+//! [`FXVolSurface::arbitrage_report`] lists butterfly and calendar
+//! violations (see [`crate::models::common::static_arbitrage`]) on a
+//! log-moneyness grid shared by all pillars, so that calendar checks
+//! compare pillars at the same `ln(K/F)`. [`FXVolSurface::repair_arbitrage`]
+//! adjusts the smiles on that grid before they feed pricing or
+//! [`crate::models::forex::dupire_local_vol`].
+//!
 //! # Quote conventions
 //!
 //! Each `FXDeltaVolPillar` is a bag of [`FXVolQuote`]s. Supported quote types
 //! mirror the market-standard FX vol surface contribution formats:
 //!
-//! * [`FXVolQuote::Atm`]          – at-the-money vol (DNS convention)
+//! * [`FXVolQuote::Atm`]          – at-the-money vol at the pillar's
+//!   [`FXAtmConvention`] strike (delta-neutral straddle, forward or spot)
 //! * [`FXVolQuote::Call`] /[`FXVolQuote::Put`]     – direct call/put vols at a
-//!   forward delta
+//!   delta in the pillar's [`FXDeltaConvention`]
 //! * [`FXVolQuote::RiskReversal`] – σ_call(Δ) − σ_put(Δ)
 //! * [`FXVolQuote::Butterfly`]    – simple BF: (σ_call + σ_put) / 2 − σ_atm
+//! * [`FXVolQuote::MarketButterfly`] – broker BF: a strangle at the Δ
+//!   strikes of the single vol σ_ms = σ_atm + BF, priced at σ_ms
+//!
+//! A delta can be supplied either as (Call, Put), as (RiskReversal,
+//! Butterfly) or as (RiskReversal, MarketButterfly). If several appear for
+//! the same Δ, direct Call/Put values win, then the simple butterfly.
 //!
-//! A delta can be supplied either as (Call, Put) or as (RiskReversal,
-//! Butterfly). If both appear for the same Δ, the direct Call/Put values win.
+//! A market butterfly does not pin the wing vols directly. Writing the smile
+//! butterfly as `bf`, the wings are `σ_atm + bf ± RR/2` and `bf` is solved
+//! (secant, Gauss–Seidel across deltas) so that
 //!
-//! All pillars use **forward delta, premium-excluded** — the standard
-//! convention for EURUSD and other G10 pairs at tenors ≥ 1 year.
+//! ```text
+//!     P(K_p^ms, σ(K_p^ms)) + C(K_c^ms, σ(K_c^ms)) = P(K_p^ms, σ_ms) + C(K_c^ms, σ_ms)
+//! ```
+//!
+//! Each pillar states its [`FXDeltaConvention`]. With `DF_f` the foreign
+//! discount factor to expiry and `ω = ±1` for calls / puts:
+//!
+//! ```text
+//!     forward            Δ = ω·N(ω·d1)
+//!     spot               Δ = DF_f · ω·N(ω·d1)
+//!     forward, prem-adj  Δ = ω·(K/F)·N(ω·d2)
+//!     spot, prem-adj     Δ = DF_f · ω·(K/F)·N(ω·d2)
+//! ```
+//!
+//! Premium-excluded deltas invert in closed form. Premium-adjusted deltas
+//! are solved by bisection; for calls the search runs above the strike
+//! that maximises `(K/F)·N(d2)`, where the delta is monotone.
 //!
 //! # Expiry interpolation
 //!
-//! Total variance `V = σ² · τ` is linear in calendar time between pillars
-//! (business-time adjustments are not wired up). Extrapolation keeps constant
-//! variance-per-time.
+//! Total variance `V = σ² · T`, with `T` in Act/365, is linear in vol time
+//! `τ` between pillars; extrapolation keeps constant variance per unit of
+//! vol time:
+//!
+//! ```text
+//!     V(T) = V₁ + (τ − τ₁)/(τ₂ − τ₁) · (V₂ − V₁),    σ(T) = sqrt(V(T) / T)
+//! ```
+//!
+//! Vol time is Act/365 unless the surface carries a [`VolClock`], which
+//! weights weekends, holidays and event dates.
 
+use crate::derivatives::forex::basic::FXUnderlying;
 use crate::error::{Error, Result};
-use crate::math::normal::{cdf, inverse_cdf};
+use crate::math::normal::{cdf, inverse_cdf, pdf};
+use crate::math::optimize::{NelderMeadOptions, nelder_mead};
+use crate::models::common::black_scholes::{bs_call_forward, bs_put_forward};
+use crate::models::common::static_arbitrage::{
+    ArbitrageReport, butterfly_violations, calendar_violation, convexify,
+};
+use crate::models::common::vol_conversion::VolQuoteType;
+use crate::models::forex::sabr::{SabrParams, hagan_implied_vol};
+use crate::models::forex::sabr_calibrator;
 use crate::time::daycounters::DayCounters;
 use crate::time::daycounters::actual365fixed::Actual365Fixed;
+use crate::time::period::Period;
+use crate::time::volclock::VolClock;
 use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
 
 // ---------------------------------------------------------------------------
 // Public API types
@@ -47,30 +117,182 @@ use chrono::NaiveDate;
 
 /// One market quote contributing to the FX volatility smile at a single
 /// expiry. All `vol` values are annualised decimals (e.g. 0.0719 for 7.19 %).
-/// All `delta` values are absolute forward deltas in (0, 0.5).
+/// All `delta` values are absolute deltas in (0, 0.5), measured in the
+/// pillar's [`FXDeltaConvention`].
 #[derive(Clone, Copy, Debug)]
 pub enum FXVolQuote {
-    /// At-the-money vol. Applied to the delta-neutral straddle strike
-    /// `K_ATM = F · exp(σ²·T / 2)`.
+    /// At-the-money vol, applied at the strike selected by the pillar's
+    /// [`FXAtmConvention`] (`FXDeltaVolPillar::atm_convention`).
     Atm(f64),
-    /// Direct call-side vol at the given forward delta.
+    /// Direct call-side vol at the given delta.
     Call { delta: f64, vol: f64 },
-    /// Direct put-side vol at the given forward delta.
+    /// Direct put-side vol at the given delta.
     Put { delta: f64, vol: f64 },
     /// Risk-reversal: σ_call(Δ) − σ_put(Δ).
     RiskReversal { delta: f64, vol: f64 },
     /// Simple butterfly: (σ_call(Δ) + σ_put(Δ)) / 2 − σ_atm.
     Butterfly { delta: f64, vol: f64 },
+    /// Market (broker) butterfly: the strangle struck at the Δ strikes of
+    /// the single vol σ_atm + BF, priced at that vol, which the smile must
+    /// reprice.
+    MarketButterfly { delta: f64, vol: f64 },
+}
+
+/// Strike of the ATM quote.
+#[derive(Deserialize, Serialize, Copy, Clone, Debug, Default, PartialEq)]
+pub enum FXAtmConvention {
+    /// Delta-neutral straddle: `F·exp(σ²T/2)`, or `F·exp(−σ²T/2)` when the
+    /// pillar's deltas are premium-adjusted.
+    #[default]
+    DeltaNeutral,
+    /// ATM forward, `K = F`.
+    Forward,
+    /// ATM spot, `K = S`.
+    Spot { spot: f64 },
+}
+
+/// Functional form of the smile at each expiry.
+#[derive(Deserialize, Serialize, Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum FXSmileModel {
+    /// Quadratic in `N(d)` plus a piecewise-linear residual; exact at
+    /// every quote.
+    #[default]
+    Quadratic,
+    /// Castagna–Mercurio second-order Vanna–Volga through the 25Δ put,
+    /// ATM and 25Δ call.
+    VannaVolga,
+    /// Hagan lognormal SABR with `β = 0.5`, least-squares on the quotes.
+    Sabr,
+    /// Raw SVI in total variance, least-squares on the quotes.
+    Svi,
+    /// One SSVI slice: `θ`, `ρ`, `φ` within the Gatheral–Jacquier bounds.
+    Ssvi,
+}
+
+/// How a quoted or reported FX delta is measured: against spot or the
+/// forward, and with or without the premium paid in the base currency.
+#[derive(Deserialize, Serialize, Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum FXDeltaConvention {
+    Spot,
+    /// The G10 convention beyond one year, and for EM pairs at every tenor.
+    #[default]
+    Forward,
+    SpotPremiumAdjusted,
+    ForwardPremiumAdjusted,
+}
+
+impl FXDeltaConvention {
+    pub fn is_spot(self) -> bool {
+        matches!(
+            self,
+            FXDeltaConvention::Spot | FXDeltaConvention::SpotPremiumAdjusted
+        )
+    }
+
+    pub fn is_premium_adjusted(self) -> bool {
+        matches!(
+            self,
+            FXDeltaConvention::SpotPremiumAdjusted | FXDeltaConvention::ForwardPremiumAdjusted
+        )
+    }
+}
+
+/// Expiry of a vol pillar: a date, or a market tenor (`1W`, `1M`, `1Y`)
+/// resolved on the pair's option date rules by [`FXVolSurface::for_pair`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FXPillarExpiry {
+    Date(NaiveDate),
+    Tenor(Period),
+}
+
+impl From<NaiveDate> for FXPillarExpiry {
+    fn from(date: NaiveDate) -> Self {
+        FXPillarExpiry::Date(date)
+    }
 }
 
 /// All quotes for a single expiry pillar, plus the outright forward used to
 /// convert deltas into strikes.
 #[derive(Clone, Debug)]
 pub struct FXDeltaVolPillar {
-    pub expiry: NaiveDate,
+    pub expiry: FXPillarExpiry,
     /// Forward `F` at this expiry.
     pub forward: f64,
     pub quotes: Vec<FXVolQuote>,
+    /// Convention of the quoted deltas.
+    pub delta_convention: FXDeltaConvention,
+    /// Foreign discount factor to expiry; only read by spot conventions.
+    pub foreign_discount: f64,
+    /// Strike the ATM quote refers to.
+    pub atm_convention: FXAtmConvention,
+    /// Smile fitted to the quotes.
+    pub smile_model: FXSmileModel,
+}
+
+impl FXDeltaVolPillar {
+    /// Pillar under the G10 market defaults: forward deltas, delta-neutral
+    /// ATM and the quadratic smile. Override them with the `with_*` setters.
+    pub fn new(expiry: FXPillarExpiry, forward: f64, quotes: Vec<FXVolQuote>) -> Self {
+        Self {
+            expiry,
+            forward,
+            quotes,
+            delta_convention: FXDeltaConvention::default(),
+            foreign_discount: 1.0,
+            atm_convention: FXAtmConvention::default(),
+            smile_model: FXSmileModel::default(),
+        }
+    }
+
+    pub fn with_delta_convention(mut self, delta_convention: FXDeltaConvention) -> Self {
+        self.delta_convention = delta_convention;
+        self
+    }
+
+    pub fn with_foreign_discount(mut self, foreign_discount: f64) -> Self {
+        self.foreign_discount = foreign_discount;
+        self
+    }
+
+    pub fn with_atm_convention(mut self, atm_convention: FXAtmConvention) -> Self {
+        self.atm_convention = atm_convention;
+        self
+    }
+
+    pub fn with_smile_model(mut self, smile_model: FXSmileModel) -> Self {
+        self.smile_model = smile_model;
+        self
+    }
+
+    /// This pillar with a tenor expiry replaced by its option expiry date
+    /// for `pair` traded on `valuation_date`
+    /// (see [`FXUnderlying::option_dates`]).
+    pub fn dated(&self, pair: &FXUnderlying, valuation_date: NaiveDate) -> Result<Self> {
+        let mut pillar = self.clone();
+        if let FXPillarExpiry::Tenor(tenor) = self.expiry {
+            pillar.expiry = pair.option_dates(tenor, valuation_date)?.expiry.into();
+        }
+        Ok(pillar)
+    }
+}
+
+/// Fitted against quoted vol at one resolved quote.
+#[derive(Deserialize, Serialize, Copy, Clone, Debug, PartialEq)]
+pub struct FXSmileResidual {
+    pub strike: f64,
+    pub quoted_vol: f64,
+    pub fitted_vol: f64,
+}
+
+/// Fit quality of one expiry pillar.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub struct FXSmileFit {
+    pub expiry: NaiveDate,
+    pub model: FXSmileModel,
+    /// One entry per quoted strike, ascending.
+    pub residuals: Vec<FXSmileResidual>,
+    /// Root-mean-square vol error.
+    pub rmse: f64,
 }
 
 /// Callable surface aggregating multiple expiry pillars.
@@ -78,6 +300,14 @@ pub struct FXDeltaVolPillar {
 pub struct FXVolSurface {
     valuation_date: NaiveDate,
     smiles: Vec<SmileSection>,
+    // Log-moneyness `ln(K/F)` the arbitrage checks run on at every pillar:
+    // each pillar's quotes, with `ARBITRAGE_SUBDIVISIONS − 1` points
+    // between neighbours, merged across pillars.
+    check_moneyness: Vec<f64>,
+    // Clock for expiry interpolation and its vol time to each pillar;
+    // without a clock the times are the pillars' Act/365 year fractions.
+    vol_clock: Option<VolClock>,
+    pillar_vol_times: Vec<f64>,
 }
 
 impl FXVolSurface {
@@ -93,19 +323,82 @@ impl FXVolSurface {
         let mut smiles: Vec<SmileSection> = pillars
             .iter()
             .map(|p| {
-                let yf = day_counter.year_fraction(valuation_date, p.expiry)?;
-                SmileSection::from_pillar(p, yf)
+                let expiry = match p.expiry {
+                    FXPillarExpiry::Date(date) => date,
+                    FXPillarExpiry::Tenor(tenor) => {
+                        return Err(Error::InvalidData(format!(
+                            "volatility pillar {tenor:?} is quoted by tenor; build the surface with FXVolSurface::for_pair"
+                        )));
+                    }
+                };
+                let yf = day_counter.year_fraction(valuation_date, expiry)?;
+                SmileSection::from_pillar(p, expiry, yf)
             })
             .collect::<Result<Vec<_>>>()?;
         smiles.sort_by_key(|s| s.expiry);
+        let mut check_moneyness: Vec<f64> = smiles
+            .iter()
+            .flat_map(|s| subdivide(&s.quoted).map(move |k| (k / s.forward).ln()))
+            .collect();
+        check_moneyness.sort_by(f64::total_cmp);
+        check_moneyness.dedup_by(|a, b| (*a - *b).abs() < 1e-12);
+        let pillar_vol_times = smiles.iter().map(|s| s.year_fraction).collect();
         Ok(Self {
             valuation_date,
             smiles,
+            check_moneyness,
+            vol_clock: None,
+            pillar_vol_times,
         })
     }
 
+    /// Build the surface for `pair`, resolving tenor-quoted pillars to
+    /// their option expiry dates first.
+    pub fn for_pair(
+        pair: &FXUnderlying,
+        valuation_date: NaiveDate,
+        pillars: Vec<FXDeltaVolPillar>,
+    ) -> Result<Self> {
+        let pillars = pillars
+            .iter()
+            .map(|p| p.dated(pair, valuation_date))
+            .collect::<Result<Vec<_>>>()?;
+        Self::new(valuation_date, pillars)
+    }
+
+    /// Interpolate between expiries in the vol time of `clock` instead of
+    /// Act/365. Pillar vols, and so pillar variances, are unchanged.
+    pub fn with_vol_clock(mut self, clock: VolClock) -> Result<Self> {
+        self.pillar_vol_times = self
+            .smiles
+            .iter()
+            .map(|s| clock.vol_time(self.valuation_date, s.expiry))
+            .collect();
+        if let Some(s) = self
+            .smiles
+            .iter()
+            .zip(&self.pillar_vol_times)
+            .find_map(|(s, &tau)| (tau <= 0.0).then_some(s))
+        {
+            return Err(Error::InvalidData(format!(
+                "volatility pillar {} has no vol time on the clock",
+                s.expiry
+            )));
+        }
+        self.vol_clock = Some(clock);
+        Ok(self)
+    }
+
+    /// Vol time from `start` to `end`: the clock's if set, else Act/365.
+    pub fn vol_time(&self, start: NaiveDate, end: NaiveDate) -> Result<f64> {
+        match &self.vol_clock {
+            Some(clock) => Ok(clock.vol_time(start, end)),
+            None => Actual365Fixed::default().year_fraction(start, end),
+        }
+    }
+
     /// Implied volatility at `(expiry, strike)`. Between pillars the total
-    /// variance `σ²·τ` is interpolated linearly in τ.
+    /// variance `σ²·T` is interpolated linearly in vol time.
     pub fn volatility(&self, expiry: NaiveDate, strike: f64) -> Result<f64> {
         if strike <= 0.0 {
             return Err(Error::InvalidData(format!(
@@ -126,25 +419,153 @@ impl FXVolSurface {
         match idx {
             Ok(i) => Ok(self.smiles[i].volatility(strike)),
             Err(pos) => {
-                if pos == 0 {
-                    Ok(self.smiles[0].volatility(strike))
-                } else if pos == self.smiles.len() {
-                    Ok(self.smiles[pos - 1].volatility(strike))
+                if pos == 0 || pos == self.smiles.len() {
+                    let i = pos.saturating_sub(1);
+                    let smile = &self.smiles[i];
+                    let sigma = smile.volatility(strike);
+                    if self.vol_clock.is_none() {
+                        return Ok(sigma);
+                    }
+                    let target_tau = self.vol_time(self.valuation_date, expiry)?;
+                    let scale =
+                        smile.year_fraction * target_tau / (self.pillar_vol_times[i] * target_yf);
+                    Ok(sigma * scale.sqrt())
                 } else {
                     let lo = &self.smiles[pos - 1];
                     let hi = &self.smiles[pos];
+                    let (tau_lo, tau_hi) =
+                        (self.pillar_vol_times[pos - 1], self.pillar_vol_times[pos]);
+                    let target_tau = self.vol_time(self.valuation_date, expiry)?;
                     let sigma_lo = lo.volatility(strike);
                     let sigma_hi = hi.volatility(strike);
                     let var_lo = sigma_lo * sigma_lo * lo.year_fraction;
                     let var_hi = sigma_hi * sigma_hi * hi.year_fraction;
-                    let weight =
-                        (target_yf - lo.year_fraction) / (hi.year_fraction - lo.year_fraction);
+                    let weight = (target_tau - tau_lo) / (tau_hi - tau_lo);
                     let var_target = var_lo + weight * (var_hi - var_lo);
                     Ok((var_target / target_yf).sqrt())
                 }
             }
         }
     }
+
+    /// `(strike, vol)` of the `delta` put, the delta-neutral straddle and
+    /// the `delta` call at `expiry`, with strikes in `convention` at the
+    /// surface's own vol there. The three carry the ATM, risk-reversal and
+    /// butterfly the smile reprices at any expiry, between pillars
+    /// included.
+    pub fn delta_strikes(
+        &self,
+        expiry: NaiveDate,
+        forward: f64,
+        foreign_discount: f64,
+        convention: FXDeltaConvention,
+        delta: f64,
+    ) -> Result<[(f64, f64); 3]> {
+        let t = Actual365Fixed::default().year_fraction(self.valuation_date, expiry)?;
+        let sqrt_t = t.sqrt();
+        // Each strike is a fixed point of `K = K(σ(K))`.
+        let solve = |strike_at: &dyn Fn(f64) -> Result<f64>| -> Result<(f64, f64)> {
+            let mut strike = forward;
+            for _ in 0..DELTA_STRIKE_ITERATIONS {
+                let next = strike_at(self.volatility(expiry, strike)?)?;
+                if (next - strike).abs() < 1e-12 * strike {
+                    return Ok((next, self.volatility(expiry, next)?));
+                }
+                strike = next;
+            }
+            Err(Error::InvalidData(format!(
+                "{delta} delta strike at {expiry} did not converge"
+            )))
+        };
+        let wing = |omega: f64| {
+            solve(&|sigma| {
+                strike_from_delta(
+                    omega,
+                    delta,
+                    sigma,
+                    forward,
+                    sqrt_t,
+                    convention,
+                    foreign_discount,
+                )
+            })
+        };
+        let half = if convention.is_premium_adjusted() {
+            -0.5
+        } else {
+            0.5
+        };
+        let atm = solve(&|sigma| Ok(forward * (half * sigma * sigma * t).exp()))?;
+        Ok([wing(-1.0)?, atm, wing(1.0)?])
+    }
+
+    /// Quoted against fitted vols at every pillar, in expiry order.
+    pub fn fit_report(&self) -> Vec<FXSmileFit> {
+        self.smiles.iter().map(SmileSection::fit_report).collect()
+    }
+
+    /// Butterfly violations on every pillar's smile and calendar
+    /// violations against the previous pillar at equal `ln(K/F)`. Both are
+    /// checked at every pillar's quoted moneyness and at points between
+    /// them.
+    pub fn arbitrage_report(&self) -> Result<ArbitrageReport> {
+        let mut violations = Vec::new();
+        for (i, smile) in self.smiles.iter().enumerate() {
+            let nodes = smile.arbitrage_grid(&self.check_moneyness);
+            violations.extend(butterfly_violations(
+                smile.expiry,
+                &nodes,
+                smile.forward,
+                smile.year_fraction,
+                VolQuoteType::Lognormal,
+            )?);
+            if let Some(previous) = i.checked_sub(1).map(|j| &self.smiles[j]) {
+                violations.extend(nodes.iter().filter_map(|&(k, sigma)| {
+                    calendar_violation(
+                        smile.expiry,
+                        k,
+                        previous.total_variance((k / smile.forward).ln()),
+                        sigma * sigma * smile.year_fraction,
+                    )
+                }));
+            }
+        }
+        Ok(ArbitrageReport { violations })
+    }
+
+    /// Minimally adjust each pillar's vols on the check grid, in expiry
+    /// order: vols below the previous pillar's total variance are raised to
+    /// it and calls are lowered onto their convex minorant, until both
+    /// hold. The pillar's smile model is then refitted to the adjusted
+    /// vols, which the quadratic smile matches exactly. Returns the report
+    /// of the repaired surface; the parametric models only approximate the
+    /// adjusted vols and may leave violations in it.
+    pub fn repair_arbitrage(&mut self) -> Result<ArbitrageReport> {
+        for i in 0..self.smiles.len() {
+            let smile = &self.smiles[i];
+            let (f, t) = (smile.forward, smile.year_fraction);
+            let previous = i.checked_sub(1).map(|j| &self.smiles[j]);
+            let calendar_floor = |k: f64| previous.map(|p| p.total_variance((k / f).ln()) / t);
+            let mut quoted = smile.arbitrage_grid(&self.check_moneyness);
+            for _ in 0..REPAIR_ROUNDS {
+                for node in quoted.iter_mut() {
+                    if let Some(floor) = calendar_floor(node.0) {
+                        node.1 = node.1.max(floor.sqrt());
+                    }
+                }
+                quoted = convexify(&quoted, f, t, VolQuoteType::Lognormal)?;
+                if quoted.iter().all(|&(k, sigma)| {
+                    calendar_floor(k).is_none_or(|floor| {
+                        calendar_violation(smile.expiry, k, floor * t, sigma * sigma * t).is_none()
+                    })
+                }) {
+                    break;
+                }
+            }
+            self.smiles[i] = smile.with_quotes(quoted)?;
+        }
+        self.arbitrage_report()
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -157,21 +578,60 @@ struct SmileSection {
     expiry: NaiveDate,
     year_fraction: f64,
     forward: f64,
-    sigma_ref: f64,
-    // Quadratic coefficients in x = N(d_a).
-    a: f64,
-    b: f64,
-    c: f64,
-    // Piecewise-linear A(ln(K/F)), sorted by log-moneyness. Residual is 0 at
-    // the quadratic anchors and equal to the fitting error at every other
-    // quoted pillar.
-    a_knots: Vec<(f64, f64)>,
-    // Extreme log-moneyness seen in the input quotes — outside this range
-    // A(·) is forced to zero.
-    log_m_min: f64,
-    log_m_max: f64,
+    model: FXSmileModel,
+    // Resolved `(strike, vol)` quotes, ascending in strike.
+    quoted: Vec<(f64, f64)>,
+    // Indices in `quoted` of the put closest to 25Δ, ATM and the matching
+    // call.
+    anchors: [usize; 3],
+    shape: SmileShape,
+}
+
+/// Per-model smile parameters.
+#[derive(Clone, Debug)]
+enum SmileShape {
+    Quadratic {
+        sigma_ref: f64,
+        // Quadratic coefficients in x = N(d_a).
+        a: f64,
+        b: f64,
+        c: f64,
+        // Piecewise-linear A(ln(K/F)), sorted by log-moneyness. Residual is
+        // 0 at the quadratic anchors and equal to the fitting error at every
+        // other quoted pillar.
+        a_knots: Vec<(f64, f64)>,
+        // Extreme log-moneyness seen in the input quotes — outside this
+        // range A(·) is forced to zero.
+        log_m_min: f64,
+        log_m_max: f64,
+    },
+    /// `(strike, vol)` at the 25Δ put, ATM and 25Δ call.
+    VannaVolga {
+        pivots: [(f64, f64); 3],
+        wings: VarianceWings,
+    },
+    Sabr {
+        params: SabrParams,
+        wings: VarianceWings,
+    },
+    /// SVI and SSVI slices, both stored in raw SVI form.
+    Svi(SviSlice),
 }
 
+/// Intervals each gap between quoted strikes is split into for the
+/// arbitrage checks.
+const ARBITRAGE_SUBDIVISIONS: usize = 4;
+
+/// Alternating calendar / butterfly passes per pillar in the repair.
+const REPAIR_ROUNDS: usize = 50;
+
+/// Gauss–Seidel sweeps over the market strangles before giving up.
+const MARKET_STRANGLE_SWEEPS: usize = 50;
+
+/// Fixed-point iterations for a strike quoted by delta at the surface's
+/// own vol.
+const DELTA_STRIKE_ITERATIONS: usize = 100;
+
 /// Normalised per-delta quote: every delta gets both a call and a put vol
 /// after RR/BF conversion.
 #[derive(Clone, Copy, Debug)]
@@ -179,48 +639,159 @@ struct DeltaPair {
     delta: f64,
     call_vol: f64,
     put_vol: f64,
+    /// `(risk reversal, market butterfly)` when the wings come from a
+    /// market strangle and still need fitting.
+    market_strangle: Option<(f64, f64)>,
 }
 
 impl SmileSection {
-    fn from_pillar(pillar: &FXDeltaVolPillar, year_fraction: f64) -> Result<Self> {
+    fn from_pillar(
+        pillar: &FXDeltaVolPillar,
+        expiry: NaiveDate,
+        year_fraction: f64,
+    ) -> Result<Self> {
         if year_fraction <= 0.0 {
             return Err(Error::InvalidData(format!(
                 "volatility pillar {} has non-positive year fraction",
-                pillar.expiry
+                expiry
             )));
         }
         if pillar.forward <= 0.0 {
             return Err(Error::InvalidData(format!(
                 "volatility pillar {} has non-positive forward {}",
-                pillar.expiry, pillar.forward
+                expiry, pillar.forward
             )));
         }
+        let (atm_vol, mut delta_pairs) = normalise_quotes(&pillar.quotes, expiry)?;
+        let section =
+            Self::fit_market_strangles(pillar, expiry, year_fraction, atm_vol, &mut delta_pairs)?;
+        let pivot = (0..delta_pairs.len())
+            .min_by(|&i, &j| {
+                let distance = |p: &DeltaPair| (p.delta - 0.25).abs();
+                distance(&delta_pairs[i]).total_cmp(&distance(&delta_pairs[j]))
+            })
+            .unwrap_or(0);
+        let n = delta_pairs.len();
+        section.refit(pillar.smile_model, [pivot, n, 2 * n - pivot])
+    }
+
+    /// Quadratic smile through the quotes, with every market strangle
+    /// resolved into smile call/put vols in `delta_pairs`.
+    fn fit_market_strangles(
+        pillar: &FXDeltaVolPillar,
+        expiry: NaiveDate,
+        year_fraction: f64,
+        atm_vol: f64,
+        delta_pairs: &mut [DeltaPair],
+    ) -> Result<Self> {
+        let mut section = Self::fit(pillar, expiry, year_fraction, atm_vol, delta_pairs)?;
+        if delta_pairs.iter().all(|p| p.market_strangle.is_none()) {
+            return Ok(section);
+        }
+
+        // Market strangles: move each smile butterfly until the smile
+        // reprices the one-vol strangle, sweeping until every pillar holds.
+        let t = year_fraction;
+        let strangle = |kp: f64, sigma_p: f64, kc: f64, sigma_c: f64| {
+            bs_put_forward(pillar.forward, kp, sigma_p, t, 1.0)
+                + bs_call_forward(pillar.forward, kc, sigma_c, t, 1.0)
+        };
+        for _ in 0..MARKET_STRANGLE_SWEEPS {
+            let mut worst: f64 = 0.0;
+            for i in 0..delta_pairs.len() {
+                let Some((rr, market_bf)) = delta_pairs[i].market_strangle else {
+                    continue;
+                };
+                let sigma_ms = atm_vol + market_bf;
+                let kp = section.strike(-1.0, delta_pairs[i].delta, sigma_ms, pillar)?;
+                let kc = section.strike(1.0, delta_pairs[i].delta, sigma_ms, pillar)?;
+                let target = strangle(kp, sigma_ms, kc, sigma_ms);
+                let bf = 0.5 * (delta_pairs[i].call_vol + delta_pairs[i].put_vol) - atm_vol;
+                let mut error = |bf: f64| -> Result<f64> {
+                    delta_pairs[i].call_vol = atm_vol + bf + 0.5 * rr;
+                    delta_pairs[i].put_vol = atm_vol + bf - 0.5 * rr;
+                    let trial = Self::fit(pillar, expiry, year_fraction, atm_vol, delta_pairs)?;
+                    Ok(strangle(kp, trial.volatility(kp), kc, trial.volatility(kc)) - target)
+                };
+                // Secant from the current smile butterfly; leaves the last
+                // iterate in `delta_pairs`.
+                let (mut x0, mut x1) = (bf, bf + 1.0e-4);
+                let (mut e0, mut e1) = (error(x0)?, error(x1)?);
+                worst = worst.max(e0.abs() / target);
+                for _ in 0..50 {
+                    if e1 == e0 || e1.abs() < 1e-15 * target {
+                        break;
+                    }
+                    let x2 = x1 - e1 * (x1 - x0) / (e1 - e0);
+                    (x0, e0) = (x1, e1);
+                    x1 = x2;
+                    e1 = error(x1)?;
+                }
+                section = Self::fit(pillar, expiry, year_fraction, atm_vol, delta_pairs)?;
+            }
+            if worst < 1e-12 {
+                return Ok(section);
+            }
+        }
+        Err(Error::InvalidData(format!(
+            "volatility pillar {}: market strangles did not converge",
+            expiry
+        )))
+    }
+
+    /// Strike of the OTM option with absolute delta `delta` at vol `sigma`
+    /// under the pillar's delta convention.
+    fn strike(&self, omega: f64, delta: f64, sigma: f64, pillar: &FXDeltaVolPillar) -> Result<f64> {
+        strike_from_delta(
+            omega,
+            delta,
+            sigma,
+            pillar.forward,
+            self.year_fraction.sqrt(),
+            pillar.delta_convention,
+            pillar.foreign_discount,
+        )
+    }
+
+    /// Smile through the ATM vol and the resolved call/put vols.
+    fn fit(
+        pillar: &FXDeltaVolPillar,
+        expiry: NaiveDate,
+        year_fraction: f64,
+        atm_vol: f64,
+        delta_pairs: &[DeltaPair],
+    ) -> Result<Self> {
         let sqrt_t = year_fraction.sqrt();
         let f = pillar.forward;
 
-        let (atm_vol, delta_pairs) = normalise_quotes(&pillar.quotes, pillar.expiry)?;
-        let sigma_ref = 1.5 * atm_vol;
-
         // 1) Each quoted pillar → (strike, vol) with that quote's own σ.
         //    Produces put strikes (increasing Δ from deep-OTM 10Δ to 25Δ),
         //    then ATM, then call strikes (outward to deep-OTM).
         let mut strikes_vols: Vec<(f64, f64)> = Vec::with_capacity(1 + 2 * delta_pairs.len());
         // Deep-OTM puts first (largest delta first when iterating outward).
         // Sort put strikes by delta descending: smallest delta = deepest OTM.
-        let mut sorted_deltas = delta_pairs.clone();
+        let mut sorted_deltas = delta_pairs.to_vec();
         sorted_deltas.sort_by(|a, b| a.delta.partial_cmp(&b.delta).unwrap());
         // Insert puts in deep-OTM → ATM order (delta ascending from 0.10 to 0.25
         // means strike ascending because smaller Δ → lower put strike).
+        let convention = pillar.delta_convention;
+        let df_f = pillar.foreign_discount;
+        if convention.is_spot() && df_f <= 0.0 {
+            return Err(Error::InvalidData(format!(
+                "volatility pillar {} quotes spot deltas but has foreign discount {}",
+                expiry, df_f
+            )));
+        }
         for p in &sorted_deltas {
-            let k = strike_from_put_delta(p.delta, p.put_vol, f, sqrt_t);
+            let k = strike_from_delta(-1.0, p.delta, p.put_vol, f, sqrt_t, convention, df_f)?;
             strikes_vols.push((k, p.put_vol));
         }
         // ATM.
-        strikes_vols.push((atm_dns_strike(atm_vol, f, year_fraction), atm_vol));
+        strikes_vols.push((atm_strike(pillar, atm_vol, year_fraction), atm_vol));
         // Calls in ATM → deep-OTM order (delta descending from 0.25 to 0.10
         // means strike ascending).
         for p in sorted_deltas.iter().rev() {
-            let k = strike_from_call_delta(p.delta, p.call_vol, f, sqrt_t);
+            let k = strike_from_delta(1.0, p.delta, p.call_vol, f, sqrt_t, convention, df_f)?;
             strikes_vols.push((k, p.call_vol));
         }
 
@@ -230,11 +801,27 @@ impl SmileSection {
                 return Err(Error::InvalidData(format!(
                     "volatility pillar {} produced non-monotonic strikes {:?} — \
                      likely duplicated or inconsistent deltas",
-                    pillar.expiry, strikes_vols
+                    expiry, strikes_vols
                 )));
             }
         }
 
+        let atm_idx = sorted_deltas.len();
+        Self::quadratic(expiry, year_fraction, f, strikes_vols, atm_idx)
+    }
+
+    /// Quadratic smile through `(strike, vol)` quotes sorted by strike,
+    /// `atm_idx` indexing the ATM quote.
+    fn quadratic(
+        expiry: NaiveDate,
+        year_fraction: f64,
+        f: f64,
+        strikes_vols: Vec<(f64, f64)>,
+        atm_idx: usize,
+    ) -> Result<Self> {
+        let sqrt_t = year_fraction.sqrt();
+        let sigma_ref = 1.5 * strikes_vols[atm_idx].1;
+
         // 2) Map strikes to x = N(d_a).
         let xs: Vec<f64> = strikes_vols
             .iter()
@@ -243,7 +830,6 @@ impl SmileSection {
 
         // 3) Quadratic anchors: lowest strike (deepest OTM put), ATM, highest
         //    strike (deepest OTM call).
-        let atm_idx = sorted_deltas.len();
         let lo_idx = 0;
         let hi_idx = strikes_vols.len() - 1;
         let (a, b, c) = fit_quadratic_three_points(
@@ -275,33 +861,379 @@ impl SmileSection {
         let log_m_max = (strikes_vols[hi_idx].0 / f).ln();
 
         Ok(SmileSection {
-            expiry: pillar.expiry,
+            expiry,
             year_fraction,
             forward: f,
-            sigma_ref,
-            a,
-            b,
-            c,
-            a_knots,
-            log_m_min,
-            log_m_max,
+            model: FXSmileModel::Quadratic,
+            anchors: [0, atm_idx, strikes_vols.len() - 1],
+            quoted: strikes_vols,
+            shape: SmileShape::Quadratic {
+                sigma_ref,
+                a,
+                b,
+                c,
+                a_knots,
+                log_m_min,
+                log_m_max,
+            },
+        })
+    }
+
+    /// Replace the quadratic shape by `model`, fitted to the resolved
+    /// quotes. `anchors` index the 25Δ put, ATM and 25Δ call quotes.
+    fn refit(self, model: FXSmileModel, anchors: [usize; 3]) -> Result<Self> {
+        let (f, t) = (self.forward, self.year_fraction);
+        let n = anchors[1];
+        let last = self.quoted.len() - 1;
+        let k_range = ((self.quoted[0].0 / f).ln(), (self.quoted[last].0 / f).ln());
+        let shape = match model {
+            FXSmileModel::Quadratic => return Ok(Self { anchors, ..self }),
+            FXSmileModel::VannaVolga => {
+                let pivots = anchors.map(|i| self.quoted[i]);
+                let wings =
+                    VarianceWings::new(|k| vanna_volga_vol(&pivots, f, t, f * k.exp()), k_range, t);
+                SmileShape::VannaVolga { pivots, wings }
+            }
+            FXSmileModel::Sabr => {
+                let (_, atm_vol) = self.quoted[n];
+                let initial =
+                    SabrParams::new(atm_vol * f.powf(1.0 - SABR_BETA), SABR_BETA, 0.0, 0.5);
+                let strikes: Vec<f64> = self.quoted.iter().map(|q| q.0).collect();
+                let vols: Vec<f64> = self.quoted.iter().map(|q| q.1).collect();
+                let targets = sabr_calibrator::targets_from_grid(&strikes, &vols);
+                let params =
+                    sabr_calibrator::calibrate(initial, f, &targets, t, FIT_OPTIONS).params;
+                let wings = VarianceWings::new(
+                    |k| hagan_implied_vol(&params, f, f * k.exp(), t),
+                    k_range,
+                    t,
+                );
+                SmileShape::Sabr { params, wings }
+            }
+            FXSmileModel::Svi | FXSmileModel::Ssvi => {
+                let points: Vec<(f64, f64)> = self
+                    .quoted
+                    .iter()
+                    .map(|&(k, sigma)| ((k / f).ln(), sigma))
+                    .collect();
+                SmileShape::Svi(SviSlice::fit(model, &points, n, t)?)
+            }
+        };
+        Ok(Self {
+            model,
+            anchors,
+            shape,
+            ..self
         })
     }
 
     fn volatility(&self, strike: f64) -> f64 {
-        let d_a = (self.forward / strike).ln() / (self.sigma_ref * self.year_fraction.sqrt());
-        let x = cdf(d_a);
-        let sigma_q = self.a * x * x + self.b * x + self.c;
-        let log_m = (strike / self.forward).ln();
-        let residual = if log_m < self.log_m_min || log_m > self.log_m_max {
-            0.0
+        let (f, t) = (self.forward, self.year_fraction);
+        let log_m = (strike / f).ln();
+        match &self.shape {
+            SmileShape::Quadratic {
+                sigma_ref,
+                a,
+                b,
+                c,
+                a_knots,
+                log_m_min,
+                log_m_max,
+            } => {
+                let d_a = (f / strike).ln() / (sigma_ref * t.sqrt());
+                let x = cdf(d_a);
+                let sigma_q = a * x * x + b * x + c;
+                let residual = if log_m < *log_m_min || log_m > *log_m_max {
+                    0.0
+                } else {
+                    piecewise_linear_interp(a_knots, log_m)
+                };
+                sigma_q + residual
+            }
+            SmileShape::VannaVolga { pivots, wings } => wings
+                .volatility(log_m, t)
+                .unwrap_or_else(|| vanna_volga_vol(pivots, f, t, strike)),
+            SmileShape::Sabr { params, wings } => wings
+                .volatility(log_m, t)
+                .unwrap_or_else(|| hagan_implied_vol(params, f, strike, t)),
+            SmileShape::Svi(svi) => (svi.total_variance(log_m) / t).sqrt(),
+        }
+    }
+
+    fn fit_report(&self) -> FXSmileFit {
+        let residuals: Vec<FXSmileResidual> = self
+            .quoted
+            .iter()
+            .map(|&(strike, quoted_vol)| FXSmileResidual {
+                strike,
+                quoted_vol,
+                fitted_vol: self.volatility(strike),
+            })
+            .collect();
+        let sse: f64 = residuals
+            .iter()
+            .map(|r| (r.fitted_vol - r.quoted_vol).powi(2))
+            .sum();
+        FXSmileFit {
+            expiry: self.expiry,
+            model: self.model,
+            rmse: (sse / residuals.len() as f64).sqrt(),
+            residuals,
+        }
+    }
+
+    /// Same model refitted to `quoted`, which must contain strikes at or
+    /// next to the current anchors.
+    fn with_quotes(&self, quoted: Vec<(f64, f64)>) -> Result<Self> {
+        let anchors = self.anchors.map(|i| {
+            let target = self.quoted[i].0;
+            (0..quoted.len())
+                .min_by(|&a, &b| {
+                    (quoted[a].0 - target)
+                        .abs()
+                        .total_cmp(&(quoted[b].0 - target).abs())
+                })
+                .unwrap_or(0)
+        });
+        Self::quadratic(
+            self.expiry,
+            self.year_fraction,
+            self.forward,
+            quoted,
+            anchors[1],
+        )?
+        .refit(self.model, anchors)
+    }
+
+    fn total_variance(&self, log_m: f64) -> f64 {
+        self.volatility(self.forward * log_m.exp()).powi(2) * self.year_fraction
+    }
+
+    /// `(strike, vol)` at every log-moneyness in `moneyness`.
+    fn arbitrage_grid(&self, moneyness: &[f64]) -> Vec<(f64, f64)> {
+        moneyness
+            .iter()
+            .map(|&m| self.forward * m.exp())
+            .map(|k| (k, self.volatility(k)))
+            .collect()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Parametric smiles
+// ---------------------------------------------------------------------------
+
+/// CEV exponent of the SABR smile, the FX convention of `sabr_calibrator`.
+const SABR_BETA: f64 = 0.5;
+
+/// Nelder–Mead settings for the least-squares smile fits.
+const FIT_OPTIONS: NelderMeadOptions = NelderMeadOptions {
+    max_iter: 4000,
+    ftol: 1.0e-20,
+    xtol: 1.0e-12,
+    step_frac: 0.1,
+};
+
+/// Total variance linear in log-moneyness beyond the quoted strikes. The
+/// slopes continue the body's but are clamped to point away from it and to
+/// Lee's moment bound `|∂w/∂k| ≤ 2`.
+#[derive(Clone, Copy, Debug)]
+struct VarianceWings {
+    k_min: f64,
+    k_max: f64,
+    w_min: f64,
+    w_max: f64,
+    slope_left: f64,
+    slope_right: f64,
+}
+
+impl VarianceWings {
+    /// Wings of the smile `body(ln(K/F))` outside `[k_min, k_max]`.
+    fn new(body: impl Fn(f64) -> f64, (k_min, k_max): (f64, f64), t: f64) -> Self {
+        let w = |k: f64| body(k).powi(2) * t;
+        let h = 1e-4 * (k_max - k_min);
+        Self {
+            k_min,
+            k_max,
+            w_min: w(k_min),
+            w_max: w(k_max),
+            slope_left: ((w(k_min + h) - w(k_min)) / h).clamp(-2.0, 0.0),
+            slope_right: ((w(k_max) - w(k_max - h)) / h).clamp(0.0, 2.0),
+        }
+    }
+
+    /// Wing vol at log-moneyness `k`, or `None` inside the quoted range.
+    fn volatility(&self, k: f64, t: f64) -> Option<f64> {
+        let w = if k < self.k_min {
+            self.w_min + self.slope_left * (k - self.k_min)
+        } else if k > self.k_max {
+            self.w_max + self.slope_right * (k - self.k_max)
         } else {
-            piecewise_linear_interp(&self.a_knots, log_m)
+            return None;
         };
-        sigma_q + residual
+        Some((w / t).sqrt())
     }
 }
 
+/// Castagna–Mercurio second-order Vanna–Volga vol through three
+/// `(strike, vol)` pivots, the middle one ATM:
+///
+/// ```text
+///     σ(K) = σ₂ + (−σ₂ + √(σ₂² + d₁d₂(K)·(2σ₂·D₁(K) + D₂(K)))) / d₁d₂(K)
+///     D₁(K) = Σ yᵢ(K)·σᵢ − σ₂
+///     D₂(K) = y₁(K)·d₁d₂(K₁)·(σ₁ − σ₂)² + y₃(K)·d₁d₂(K₃)·(σ₃ − σ₂)²
+/// ```
+///
+/// with `yᵢ` the log-strike Lagrange weights and `d₁, d₂` at `σ₂`. Falls
+/// back to the first-order `Σ yᵢ·σᵢ` where the root is complex.
+fn vanna_volga_vol(pivots: &[(f64, f64); 3], forward: f64, t: f64, strike: f64) -> f64 {
+    let [(k1, s1), (k2, s2), (k3, s3)] = *pivots;
+    let ln = |a: f64, b: f64| (a / b).ln();
+    let y1 = ln(k2, strike) * ln(k3, strike) / (ln(k2, k1) * ln(k3, k1));
+    let y2 = ln(strike, k1) * ln(k3, strike) / (ln(k2, k1) * ln(k3, k2));
+    let y3 = ln(strike, k1) * ln(strike, k2) / (ln(k3, k1) * ln(k3, k2));
+    let first_order = y1 * s1 + y2 * s2 + y3 * s3;
+
+    let s = s2 * t.sqrt();
+    let d1d2 = |k: f64| {
+        let d1 = (forward / k).ln() / s + 0.5 * s;
+        d1 * (d1 - s)
+    };
+    let d2_term = y1 * d1d2(k1) * (s1 - s2).powi(2) + y3 * d1d2(k3) * (s3 - s2).powi(2);
+    let x = 2.0 * s2 * (first_order - s2) + d2_term;
+    let p = d1d2(strike);
+    if p.abs() < 1e-10 {
+        return s2 + x / (2.0 * s2);
+    }
+    let discriminant = s2 * s2 + p * x;
+    if discriminant < 0.0 {
+        return first_order;
+    }
+    s2 + (discriminant.sqrt() - s2) / p
+}
+
+/// Raw SVI total variance
+///
+/// ```text
+///     w(k) = a + b·(ρ·(k − m) + √((k − m)² + s²))
+/// ```
+///
+/// An SSVI slice `w(k) = θ/2·(1 + ρφk + √((φk + ρ)² + 1 − ρ²))` is the raw
+/// slice with `a = θ(1 − ρ²)/2`, `b = θφ/2`, `m = −ρ/φ`, `s = √(1 − ρ²)/φ`.
+#[derive(Clone, Copy, Debug)]
+struct SviSlice {
+    a: f64,
+    b: f64,
+    rho: f64,
+    m: f64,
+    s: f64,
+}
+
+impl SviSlice {
+    fn total_variance(&self, k: f64) -> f64 {
+        let x = k - self.m;
+        self.a + self.b * (self.rho * x + (x * x + self.s * self.s).sqrt())
+    }
+
+    /// Least-squares fit in vol to `(ln(K/F), σ)` points, `atm` indexing
+    /// the ATM quote. SVI is mapped so that the wing slopes `b(1 ± ρ)` stay
+    /// within Lee's bound and the minimum variance is positive; SSVI so
+    /// that `θφ(1 + |ρ|) < 4` and `θφ²(1 + |ρ|) ≤ 4`, which rules out
+    /// butterfly arbitrage on the whole slice.
+    fn fit(model: FXSmileModel, points: &[(f64, f64)], atm: usize, t: f64) -> Result<Self> {
+        let variances: Vec<(f64, f64)> = points
+            .iter()
+            .map(|&(k, sigma)| (k, sigma * sigma * t))
+            .collect();
+        // Seed from the chords either side of ATM.
+        let (k0, w0) = variances[atm];
+        let (kl, wl) = variances[0];
+        let (kr, wr) = variances[variances.len() - 1];
+        let slope_left = ((wl - w0) / (kl - k0)).min(-1e-4);
+        let slope_right = ((wr - w0) / (kr - k0)).max(1e-4);
+        let b0 = 0.5 * (slope_right - slope_left);
+        let rho0 = ((slope_right + slope_left) / (slope_right - slope_left)).clamp(-0.9, 0.9);
+        let logit = |p: f64| (p / (1.0 - p)).ln();
+
+        let ssvi = model == FXSmileModel::Ssvi;
+        let reify = if ssvi {
+            Self::from_ssvi
+        } else {
+            Self::from_svi
+        };
+        let x0 = if ssvi {
+            let phi = (2.0 * b0 / w0 / ssvi_phi_max(w0, rho0)).clamp(0.05, 0.95);
+            vec![w0.ln(), rho0.atanh(), logit(phi)]
+        } else {
+            let w_floor = variances.iter().map(|v| v.1).fold(f64::INFINITY, f64::min);
+            let b = (0.5 * b0 * (1.0 + rho0.abs())).clamp(1e-4, 0.95);
+            vec![
+                logit(b),
+                rho0.atanh(),
+                k0,
+                (0.5 * (kr - kl)).ln(),
+                (0.9 * w_floor).ln(),
+            ]
+        };
+        let objective = |x: &[f64]| -> f64 {
+            let slice = reify(x);
+            points
+                .iter()
+                .map(|&(k, sigma)| ((slice.total_variance(k) / t).sqrt() - sigma).powi(2))
+                .sum::<f64>()
+        };
+        // Restart from the best vertex so the simplex can re-expand.
+        let mut best = nelder_mead(objective, &x0, FIT_OPTIONS);
+        for _ in 0..2 {
+            best = nelder_mead(objective, &best.x, FIT_OPTIONS);
+        }
+        if !best.f.is_finite() {
+            return Err(Error::InvalidData(format!(
+                "{:?} fit to {} quotes did not produce a finite error",
+                model,
+                points.len()
+            )));
+        }
+        Ok(reify(&best.x))
+    }
+
+    fn from_svi(x: &[f64]) -> Self {
+        let rho = x[1].tanh();
+        let b = 2.0 / (1.0 + rho.abs()) * logistic(x[0]);
+        let s = x[3].exp();
+        Self {
+            a: x[4].exp() - b * s * (1.0 - rho * rho).sqrt(),
+            b,
+            rho,
+            m: x[2],
+            s,
+        }
+    }
+
+    fn from_ssvi(x: &[f64]) -> Self {
+        let theta = x[0].exp();
+        let rho = x[1].tanh();
+        let phi = ssvi_phi_max(theta, rho) * logistic(x[2]);
+        Self {
+            a: 0.5 * theta * (1.0 - rho * rho),
+            b: 0.5 * theta * phi,
+            rho,
+            m: -rho / phi,
+            s: (1.0 - rho * rho).sqrt() / phi,
+        }
+    }
+}
+
+/// Largest `φ` meeting both Gatheral–Jacquier bounds at `(θ, ρ)`.
+fn ssvi_phi_max(theta: f64, rho: f64) -> f64 {
+    let bound = 4.0 / (theta * (1.0 + rho.abs()));
+    bound.min(bound.sqrt())
+}
+
+fn logistic(x: f64) -> f64 {
+    1.0 / (1.0 + (-x).exp())
+}
+
 // ---------------------------------------------------------------------------
 // Quote normalisation
 // ---------------------------------------------------------------------------
@@ -354,6 +1286,10 @@ fn normalise_quotes(quotes: &[FXVolQuote], expiry: NaiveDate) -> Result<(f64, Ve
                 validate_delta(delta, expiry)?;
                 find_or_insert(&mut buckets, delta).bf = Some(vol);
             }
+            FXVolQuote::MarketButterfly { delta, vol } => {
+                validate_delta(delta, expiry)?;
+                find_or_insert(&mut buckets, delta).market_bf = Some(vol);
+            }
         }
     }
 
@@ -387,6 +1323,7 @@ struct PartialDelta {
     put: Option<f64>,
     rr: Option<f64>,
     bf: Option<f64>,
+    market_bf: Option<f64>,
 }
 
 fn resolve_partial(
@@ -401,6 +1338,7 @@ fn resolve_partial(
             delta,
             call_vol,
             put_vol,
+            market_strangle: None,
         });
     }
     if let (Some(rr), Some(bf)) = (partial.rr, partial.bf) {
@@ -410,11 +1348,22 @@ fn resolve_partial(
             delta,
             call_vol,
             put_vol,
+            market_strangle: None,
+        });
+    }
+    // A market butterfly seeds the smile butterfly; the fit refines it.
+    if let (Some(rr), Some(bf)) = (partial.rr, partial.market_bf) {
+        return Ok(DeltaPair {
+            delta,
+            call_vol: atm_vol + bf + 0.5 * rr,
+            put_vol: atm_vol + bf - 0.5 * rr,
+            market_strangle: Some((rr, bf)),
         });
     }
     Err(Error::InvalidData(format!(
         "volatility pillar {} at delta {}: need either a matched \
-         Call+Put pair or a matched RiskReversal+Butterfly pair",
+         Call+Put pair or a RiskReversal matched with a Butterfly or \
+         MarketButterfly",
         expiry, delta
     )))
 }
@@ -433,8 +1382,17 @@ fn validate_delta(delta: f64, expiry: NaiveDate) -> Result<()> {
 // Delta/strike helpers
 // ---------------------------------------------------------------------------
 
-fn atm_dns_strike(sigma: f64, forward: f64, year_fraction: f64) -> f64 {
-    forward * (0.5 * sigma * sigma * year_fraction).exp()
+/// Strike the ATM quote refers to under the pillar's conventions.
+fn atm_strike(pillar: &FXDeltaVolPillar, sigma: f64, year_fraction: f64) -> f64 {
+    let forward = pillar.forward;
+    match pillar.atm_convention {
+        FXAtmConvention::DeltaNeutral if pillar.delta_convention.is_premium_adjusted() => {
+            forward * (-0.5 * sigma * sigma * year_fraction).exp()
+        }
+        FXAtmConvention::DeltaNeutral => forward * (0.5 * sigma * sigma * year_fraction).exp(),
+        FXAtmConvention::Forward => forward,
+        FXAtmConvention::Spot { spot } => spot,
+    }
 }
 
 /// OTM call of delta `delta` (0 < delta < 0.5).
@@ -451,6 +1409,73 @@ fn strike_from_put_delta(delta: f64, sigma: f64, forward: f64, sqrt_t: f64) -> f
     forward * (0.5 * sigma * sigma * sqrt_t * sqrt_t - d1 * sigma * sqrt_t).exp()
 }
 
+/// Strike of the OTM option (`ω = 1` call, `−1` put) with absolute delta
+/// `delta` under `convention`.
+fn strike_from_delta(
+    omega: f64,
+    delta: f64,
+    sigma: f64,
+    forward: f64,
+    sqrt_t: f64,
+    convention: FXDeltaConvention,
+    foreign_discount: f64,
+) -> Result<f64> {
+    let delta = if convention.is_spot() {
+        delta / foreign_discount
+    } else {
+        delta
+    };
+    if !(0.0 < delta && delta < 1.0) {
+        return Err(Error::InvalidData(format!(
+            "forward delta {} out of range after removing the foreign discount",
+            delta
+        )));
+    }
+    // The premium-excluded strike bounds the adjusted one: the premium
+    // only lowers a call delta and deepens a put delta.
+    let unadjusted = if omega > 0.0 {
+        strike_from_call_delta(delta, sigma, forward, sqrt_t)
+    } else {
+        strike_from_put_delta(delta, sigma, forward, sqrt_t)
+    };
+    if !convention.is_premium_adjusted() {
+        return Ok(unadjusted);
+    }
+    let s = sigma * sqrt_t;
+    let adjusted = |k: f64| (k / forward) * cdf(omega * ((forward / k).ln() / s - 0.5 * s)) - delta;
+    if omega < 0.0 {
+        let lo = forward * (-12.0 * s).exp();
+        return Ok(bisect(adjusted, lo, unadjusted));
+    }
+    // (K/F)·N(d2) peaks where s·N(d2) = φ(d2).
+    let d2_peak = bisect(|d2| s * cdf(d2) - pdf(d2), -s, 12.0);
+    let k_peak = forward * (-d2_peak * s - 0.5 * s * s).exp();
+    if adjusted(k_peak) < 0.0 {
+        return Err(Error::InvalidData(format!(
+            "premium-adjusted call delta {} exceeds the attainable maximum",
+            delta
+        )));
+    }
+    Ok(bisect(adjusted, k_peak, unadjusted))
+}
+
+/// Root of `f` on `[lo, hi]` by bisection; `f` must change sign.
+fn bisect<F: Fn(f64) -> f64>(f: F, mut lo: f64, mut hi: f64) -> f64 {
+    let f_lo = f(lo);
+    for _ in 0..200 {
+        let mid = 0.5 * (lo + hi);
+        if (f(mid) > 0.0) == (f_lo > 0.0) {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+        if hi - lo <= 1e-15 * hi.abs().max(1.0) {
+            break;
+        }
+    }
+    0.5 * (lo + hi)
+}
+
 /// Fit σ = a·x² + b·x + c through three `(x, σ)` points. Lagrange-equivalent
 /// closed form.
 fn fit_quadratic_three_points(p0: (f64, f64), p1: (f64, f64), p2: (f64, f64)) -> (f64, f64, f64) {
@@ -469,6 +1494,15 @@ fn fit_quadratic_three_points(p0: (f64, f64), p1: (f64, f64), p2: (f64, f64)) ->
     (a, b, c)
 }
 
+/// Quote strikes with `ARBITRAGE_SUBDIVISIONS − 1` log-spaced strikes
+/// inserted between each neighbouring pair.
+fn subdivide(quoted: &[(f64, f64)]) -> impl Iterator<Item = f64> + '_ {
+    std::iter::once(quoted[0].0).chain(quoted.windows(2).flat_map(|w| {
+        let step = (w[1].0 / w[0].0).ln() / ARBITRAGE_SUBDIVISIONS as f64;
+        (1..=ARBITRAGE_SUBDIVISIONS).map(move |j| w[0].0 * (step * j as f64).exp())
+    }))
+}
+
 fn piecewise_linear_interp(knots: &[(f64, f64)], x: f64) -> f64 {
     if knots.is_empty() {
         return 0.0;
@@ -498,8 +1532,19 @@ fn piecewise_linear_interp(knots: &[(f64, f64)], x: f64) -> f64 {
 
 #[cfg(test)]
 mod tests {
-    use super::{FXDeltaVolPillar, FXVolQuote, FXVolSurface, fit_quadratic_three_points};
+    use super::{
+        FXAtmConvention, FXDeltaConvention, FXDeltaVolPillar, FXPillarExpiry, FXSmileModel,
+        FXVolQuote, FXVolSurface, fit_quadratic_three_points, strike_from_call_delta,
+        strike_from_delta,
+    };
+    use crate::derivatives::forex::basic::FXUnderlying;
     use crate::error::Result;
+    use crate::math::normal::cdf;
+    use crate::models::common::black_scholes::{bs_call_forward, bs_put_forward};
+    use crate::models::common::static_arbitrage::ArbitrageKind;
+    use crate::time::calendars::Target;
+    use crate::time::period::Period;
+    use crate::time::volclock::{VolClock, VolEvent};
     use chrono::NaiveDate;
 
     #[test]
@@ -524,10 +1569,10 @@ mod tests {
         let expiry = NaiveDate::from_ymd_opt(2031, 4, 23).unwrap();
         let surface = FXVolSurface::new(
             valuation_date,
-            vec![FXDeltaVolPillar {
-                expiry,
-                forward: 1.2376,
-                quotes: vec![
+            vec![FXDeltaVolPillar::new(
+                expiry.into(),
+                1.2376,
+                vec![
                     FXVolQuote::Atm(0.0769),
                     FXVolQuote::Put {
                         delta: 0.10,
@@ -546,7 +1591,7 @@ mod tests {
                         vol: 0.093325,
                     },
                 ],
-            }],
+            )],
         )?;
 
         let vol = surface.volatility(expiry, 1.2995)?;
@@ -584,10 +1629,10 @@ mod tests {
         let call_10 = 0.093325;
         let direct = FXVolSurface::new(
             valuation_date,
-            vec![FXDeltaVolPillar {
-                expiry,
-                forward: 1.2376,
-                quotes: vec![
+            vec![FXDeltaVolPillar::new(
+                expiry.into(),
+                1.2376,
+                vec![
                     FXVolQuote::Atm(atm_vol),
                     FXVolQuote::Put {
                         delta: 0.10,
@@ -606,7 +1651,7 @@ mod tests {
                         vol: call_10,
                     },
                 ],
-            }],
+            )],
         )?;
         // Exact conversion: RR = call − put, BF = (call + put)/2 − atm.
         let rr_25 = call_25 - put_25;
@@ -615,10 +1660,10 @@ mod tests {
         let bf_10 = 0.5 * (call_10 + put_10) - atm_vol;
         let rr_bf = FXVolSurface::new(
             valuation_date,
-            vec![FXDeltaVolPillar {
-                expiry,
-                forward: 1.2376,
-                quotes: vec![
+            vec![FXDeltaVolPillar::new(
+                expiry.into(),
+                1.2376,
+                vec![
                     FXVolQuote::Atm(atm_vol),
                     FXVolQuote::RiskReversal {
                         delta: 0.25,
@@ -637,7 +1682,7 @@ mod tests {
                         vol: bf_10,
                     },
                 ],
-            }],
+            )],
         )?;
         for k in [1.10, 1.20, 1.2376, 1.2995, 1.42, 1.65] {
             let v1 = direct.volatility(expiry, k)?;
@@ -660,10 +1705,10 @@ mod tests {
     fn three_delta_pillars_reprice_every_quote() -> Result<()> {
         let valuation_date = NaiveDate::from_ymd_opt(2026, 4, 21).unwrap();
         let expiry = NaiveDate::from_ymd_opt(2027, 4, 23).unwrap();
-        let pillar = FXDeltaVolPillar {
-            expiry,
-            forward: 1.19,
-            quotes: vec![
+        let pillar = FXDeltaVolPillar::new(
+            expiry.into(),
+            1.19,
+            vec![
                 FXVolQuote::Atm(0.065),
                 FXVolQuote::Put {
                     delta: 0.10,
@@ -690,7 +1735,7 @@ mod tests {
                     vol: 0.079,
                 },
             ],
-        };
+        );
         let surface = FXVolSurface::new(valuation_date, vec![pillar.clone()])?;
 
         // Rebuild the strike for each quote using the quote's own σ (same math
@@ -735,10 +1780,10 @@ mod tests {
         // No ATM.
         let no_atm = FXVolSurface::new(
             valuation_date,
-            vec![FXDeltaVolPillar {
-                expiry,
-                forward: 1.19,
-                quotes: vec![
+            vec![FXDeltaVolPillar::new(
+                expiry.into(),
+                1.19,
+                vec![
                     FXVolQuote::Put {
                         delta: 0.25,
                         vol: 0.07,
@@ -748,24 +1793,24 @@ mod tests {
                         vol: 0.07,
                     },
                 ],
-            }],
+            )],
         );
         assert!(no_atm.is_err(), "missing ATM must fail");
 
         // 25Δ call quote but no matching put (or RR/BF).
         let half_pair = FXVolSurface::new(
             valuation_date,
-            vec![FXDeltaVolPillar {
-                expiry,
-                forward: 1.19,
-                quotes: vec![
+            vec![FXDeltaVolPillar::new(
+                expiry.into(),
+                1.19,
+                vec![
                     FXVolQuote::Atm(0.07),
                     FXVolQuote::Call {
                         delta: 0.25,
                         vol: 0.075,
                     },
                 ],
-            }],
+            )],
         );
         assert!(half_pair.is_err(), "half-pair at a delta must fail");
     }
@@ -776,28 +1821,30 @@ mod tests {
         let valuation_date = NaiveDate::from_ymd_opt(2026, 4, 21).unwrap();
         let t1 = NaiveDate::from_ymd_opt(2027, 4, 23).unwrap();
         let t2 = NaiveDate::from_ymd_opt(2031, 4, 23).unwrap();
-        let mk = |expiry: NaiveDate, atm: f64, fwd: f64| FXDeltaVolPillar {
-            expiry,
-            forward: fwd,
-            quotes: vec![
-                FXVolQuote::Atm(atm),
-                FXVolQuote::Put {
-                    delta: 0.10,
-                    vol: atm + 0.01,
-                },
-                FXVolQuote::Put {
-                    delta: 0.25,
-                    vol: atm + 0.003,
-                },
-                FXVolQuote::Call {
-                    delta: 0.25,
-                    vol: atm + 0.003,
-                },
-                FXVolQuote::Call {
-                    delta: 0.10,
-                    vol: atm + 0.01,
-                },
-            ],
+        let mk = |expiry: NaiveDate, atm: f64, fwd: f64| {
+            FXDeltaVolPillar::new(
+                expiry.into(),
+                fwd,
+                vec![
+                    FXVolQuote::Atm(atm),
+                    FXVolQuote::Put {
+                        delta: 0.10,
+                        vol: atm + 0.01,
+                    },
+                    FXVolQuote::Put {
+                        delta: 0.25,
+                        vol: atm + 0.003,
+                    },
+                    FXVolQuote::Call {
+                        delta: 0.25,
+                        vol: atm + 0.003,
+                    },
+                    FXVolQuote::Call {
+                        delta: 0.10,
+                        vol: atm + 0.01,
+                    },
+                ],
+            )
         };
         let surface = FXVolSurface::new(
             valuation_date,
@@ -808,4 +1855,400 @@ mod tests {
         assert!(vol_mid > 0.06 - 1e-3 && vol_mid < 0.08 + 1e-3);
         Ok(())
     }
+
+    #[test]
+    fn tenor_pillars_resolve_to_option_expiries() -> Result<()> {
+        let valuation_date = NaiveDate::from_ymd_opt(2026, 4, 21).unwrap();
+        let pillar = |expiry: FXPillarExpiry| {
+            FXDeltaVolPillar::new(
+                expiry,
+                1.17,
+                vec![
+                    FXVolQuote::Atm(0.07),
+                    FXVolQuote::RiskReversal {
+                        delta: 0.25,
+                        vol: 0.004,
+                    },
+                    FXVolQuote::Butterfly {
+                        delta: 0.25,
+                        vol: 0.002,
+                    },
+                ],
+            )
+        };
+        let tenor = pillar(FXPillarExpiry::Tenor(Period::Months(1)));
+        assert!(FXVolSurface::new(valuation_date, vec![tenor.clone()]).is_err());
+
+        let pair = FXUnderlying::EURUSD;
+        let expiry = pair.option_dates(Period::Months(1), valuation_date)?.expiry;
+        let by_tenor = FXVolSurface::for_pair(&pair, valuation_date, vec![tenor])?;
+        let by_date = FXVolSurface::new(valuation_date, vec![pillar(expiry.into())])?;
+        assert_eq!(by_tenor.fit_report()[0].expiry, expiry);
+        assert_eq!(
+            by_tenor.volatility(expiry, 1.18)?,
+            by_date.volatility(expiry, 1.18)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn vol_clock_weights_weekends_and_events() -> Result<()> {
+        let valuation_date = NaiveDate::from_ymd_opt(2026, 4, 21).unwrap();
+        let date = |m, d| NaiveDate::from_ymd_opt(2026, m, d).unwrap();
+        let mk = |expiry: NaiveDate, atm: f64| {
+            FXDeltaVolPillar::new(
+                expiry.into(),
+                1.17,
+                vec![
+                    FXVolQuote::Atm(atm),
+                    FXVolQuote::Put {
+                        delta: 0.25,
+                        vol: atm + 0.003,
+                    },
+                    FXVolQuote::Call {
+                        delta: 0.25,
+                        vol: atm + 0.004,
+                    },
+                ],
+            )
+        };
+        let pillars = || vec![mk(date(5, 21), 0.07), mk(date(7, 21), 0.075)];
+        let calendar_time = FXVolSurface::new(valuation_date, pillars())?;
+        let fomc = VolEvent {
+            name: "FOMC".to_string(),
+            date: date(4, 29),
+            weight: 3.0,
+        };
+        let clock = VolClock::new(Box::new(Target), 0.0, 0.5, vec![fomc])?;
+        let surface = FXVolSurface::new(valuation_date, pillars())?.with_vol_clock(clock)?;
+
+        let k = 1.18;
+        assert_eq!(
+            surface.volatility(date(5, 21), k)?,
+            calendar_time.volatility(date(5, 21), k)?
+        );
+        let variance = |expiry: NaiveDate| -> Result<f64> {
+            let t = (expiry - valuation_date).num_days() as f64 / 365.0;
+            Ok(surface.volatility(expiry, k)?.powi(2) * t)
+        };
+        // Weekends carry no variance, before the first pillar and between
+        // pillars.
+        assert!((variance(date(4, 26))? - variance(date(4, 24))?).abs() < 1e-15);
+        assert!((variance(date(6, 7))? - variance(date(6, 5))?).abs() < 1e-15);
+        // FOMC day counts four ordinary days.
+        let ordinary = variance(date(4, 28))? - variance(date(4, 27))?;
+        let fomc_day = variance(date(4, 29))? - variance(date(4, 28))?;
+        assert!((fomc_day - 4.0 * ordinary).abs() < 1e-12);
+        Ok(())
+    }
+
+    /// USDJPY-style quotes: every convention's strike reprices its own
+    /// delta, and a premium-adjusted surface returns the quoted vols there.
+    #[test]
+    fn delta_conventions_invert_to_their_quotes() -> Result<()> {
+        let (f, sigma, t, df_f): (f64, f64, f64, f64) = (150.0, 0.11, 0.5, 0.98);
+        let s = sigma * t.sqrt();
+        for convention in [
+            FXDeltaConvention::Spot,
+            FXDeltaConvention::Forward,
+            FXDeltaConvention::SpotPremiumAdjusted,
+            FXDeltaConvention::ForwardPremiumAdjusted,
+        ] {
+            for omega in [1.0, -1.0] {
+                let k = strike_from_delta(omega, 0.25, sigma, f, t.sqrt(), convention, df_f)?;
+                let d1 = (f / k).ln() / s + 0.5 * s;
+                let mut delta = if convention.is_premium_adjusted() {
+                    k / f * cdf(omega * (d1 - s))
+                } else {
+                    cdf(omega * d1)
+                };
+                if convention.is_spot() {
+                    delta *= df_f;
+                }
+                assert!(
+                    (delta - 0.25).abs() < 1e-12,
+                    "{convention:?} {omega}: {delta}"
+                );
+            }
+        }
+        // The premium pushes an adjusted 25Δ call strike below the
+        // unadjusted one.
+        let k_pa = strike_from_delta(
+            1.0,
+            0.25,
+            sigma,
+            f,
+            t.sqrt(),
+            FXDeltaConvention::ForwardPremiumAdjusted,
+            1.0,
+        )?;
+        assert!(k_pa < strike_from_call_delta(0.25, sigma, f, t.sqrt()));
+
+        let valuation_date = NaiveDate::from_ymd_opt(2026, 4, 21).unwrap();
+        let expiry = NaiveDate::from_ymd_opt(2026, 10, 20).unwrap();
+        let quotes = vec![
+            FXVolQuote::Atm(0.10),
+            FXVolQuote::Put {
+                delta: 0.25,
+                vol: 0.115,
+            },
+            FXVolQuote::Call {
+                delta: 0.25,
+                vol: 0.095,
+            },
+        ];
+        let surface = FXVolSurface::new(
+            valuation_date,
+            vec![
+                FXDeltaVolPillar::new(expiry.into(), f, quotes)
+                    .with_delta_convention(FXDeltaConvention::SpotPremiumAdjusted)
+                    .with_foreign_discount(df_f),
+            ],
+        )?;
+        let sqrt_t = (182.0_f64 / 365.0).sqrt();
+        for (omega, vol) in [(1.0, 0.095), (-1.0, 0.115)] {
+            let k = strike_from_delta(
+                omega,
+                0.25,
+                vol,
+                f,
+                sqrt_t,
+                FXDeltaConvention::SpotPremiumAdjusted,
+                df_f,
+            )?;
+            assert!((surface.volatility(expiry, k)? - vol).abs() < 1e-10);
+        }
+        Ok(())
+    }
+
+    /// USDJPY-style broker quotes: the fitted smile reprices each one-vol
+    /// market strangle, and the ATM vol sits at the premium-adjusted DNS
+    /// strike.
+    #[test]
+    fn market_strangles_reprice_and_atm_conventions_hold() -> Result<()> {
+        let valuation_date = NaiveDate::from_ymd_opt(2026, 4, 21).unwrap();
+        let expiry = NaiveDate::from_ymd_opt(2027, 4, 21).unwrap();
+        let (f, atm, t) = (148.0, 0.10, 1.0);
+        let pillar = |atm_convention| {
+            FXDeltaVolPillar::new(
+                expiry.into(),
+                f,
+                vec![
+                    FXVolQuote::Atm(atm),
+                    FXVolQuote::RiskReversal {
+                        delta: 0.25,
+                        vol: -0.02,
+                    },
+                    FXVolQuote::MarketButterfly {
+                        delta: 0.25,
+                        vol: 0.003,
+                    },
+                    FXVolQuote::RiskReversal {
+                        delta: 0.10,
+                        vol: -0.04,
+                    },
+                    FXVolQuote::MarketButterfly {
+                        delta: 0.10,
+                        vol: 0.011,
+                    },
+                ],
+            )
+            .with_delta_convention(FXDeltaConvention::SpotPremiumAdjusted)
+            .with_foreign_discount(0.96)
+            .with_atm_convention(atm_convention)
+        };
+        let surface =
+            FXVolSurface::new(valuation_date, vec![pillar(FXAtmConvention::DeltaNeutral)])?;
+        let k_atm = f * (-0.5 * atm * atm * t).exp();
+        assert!((surface.volatility(expiry, k_atm)? - atm).abs() < 1e-12);
+        for (delta, bf) in [(0.25, 0.003), (0.10, 0.011)] {
+            let sigma_ms = atm + bf;
+            let strike = |omega| {
+                strike_from_delta(
+                    omega,
+                    delta,
+                    sigma_ms,
+                    f,
+                    1.0,
+                    FXDeltaConvention::SpotPremiumAdjusted,
+                    0.96,
+                )
+            };
+            let (kp, kc) = (strike(-1.0)?, strike(1.0)?);
+            let target =
+                bs_put_forward(f, kp, sigma_ms, t, 1.0) + bs_call_forward(f, kc, sigma_ms, t, 1.0);
+            let smile = bs_put_forward(f, kp, surface.volatility(expiry, kp)?, t, 1.0)
+                + bs_call_forward(f, kc, surface.volatility(expiry, kc)?, t, 1.0);
+            assert!(
+                (smile - target).abs() < 1e-10 * target,
+                "{delta}: {smile} vs {target}"
+            );
+        }
+
+        let forward_atm =
+            FXVolSurface::new(valuation_date, vec![pillar(FXAtmConvention::Forward)])?;
+        assert!((forward_atm.volatility(expiry, f)? - atm).abs() < 1e-12);
+        let spot_atm = FXVolSurface::new(
+            valuation_date,
+            vec![pillar(FXAtmConvention::Spot { spot: 150.0 })],
+        )?;
+        assert!((spot_atm.volatility(expiry, 150.0)? - atm).abs() < 1e-12);
+        Ok(())
+    }
+
+    #[test]
+    fn parametric_smiles_fit_quotes_with_arbitrage_free_wings() -> Result<()> {
+        let valuation_date = NaiveDate::from_ymd_opt(2026, 4, 21).unwrap();
+        let expiry = NaiveDate::from_ymd_opt(2031, 4, 23).unwrap();
+        let (f, t) = (1.2376, 1828.0 / 365.0);
+        let surface = |smile_model| {
+            FXVolSurface::new(
+                valuation_date,
+                vec![
+                    FXDeltaVolPillar::new(
+                        expiry.into(),
+                        f,
+                        vec![
+                            FXVolQuote::Atm(0.0769),
+                            FXVolQuote::Put {
+                                delta: 0.10,
+                                vol: 0.089125,
+                            },
+                            FXVolQuote::Put {
+                                delta: 0.25,
+                                vol: 0.07989,
+                            },
+                            FXVolQuote::Call {
+                                delta: 0.25,
+                                vol: 0.081865,
+                            },
+                            FXVolQuote::Call {
+                                delta: 0.10,
+                                vol: 0.093325,
+                            },
+                        ],
+                    )
+                    .with_smile_model(smile_model),
+                ],
+            )
+        };
+        for (model, tolerance) in [
+            (FXSmileModel::Quadratic, 1e-12),
+            (FXSmileModel::VannaVolga, 1e-3),
+            (FXSmileModel::Sabr, 5e-4),
+            (FXSmileModel::Svi, 1e-8),
+            (FXSmileModel::Ssvi, 5e-4),
+        ] {
+            let surface = surface(model)?;
+            let report = surface.fit_report();
+            assert_eq!(report.len(), 1);
+            assert_eq!(report[0].model, model);
+            assert_eq!(report[0].residuals.len(), 5);
+            assert!(report[0].rmse < tolerance, "{model:?}: {:?}", report[0]);
+            if model == FXSmileModel::VannaVolga {
+                // The 25Δ and ATM pivots are exact.
+                for r in &report[0].residuals[1..4] {
+                    assert!((r.fitted_vol - r.quoted_vol).abs() < 1e-10, "{r:?}");
+                }
+            }
+
+            // Calls decrease and are convex in strike far into both wings,
+            // and total variance grows no faster than Lee's bound.
+            let call = |k: f64| -> Result<f64> {
+                Ok(bs_call_forward(
+                    f,
+                    k,
+                    surface.volatility(expiry, k)?,
+                    t,
+                    1.0,
+                ))
+            };
+            let h = 1e-4;
+            for i in -60..=60 {
+                let k = f * (0.05 * i as f64).exp();
+                let (down, mid, up) = (call(k - h)?, call(k)?, call(k + h)?);
+                assert!(up <= down + 1e-14, "{model:?}: slope at {k}");
+                assert!(up - 2.0 * mid + down >= -1e-12, "{model:?}: density at {k}");
+            }
+            for k in [-3.0_f64, 3.0] {
+                let w = |k: f64| -> Result<f64> {
+                    Ok(surface.volatility(expiry, f * k.exp())?.powi(2) * t)
+                };
+                assert!(
+                    ((w(k * 1.01)? - w(k)?) / (0.01 * k)).abs() <= 2.0,
+                    "{model:?}"
+                );
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn arbitrage_report_flags_and_repair_removes_violations() -> Result<()> {
+        let valuation_date = NaiveDate::from_ymd_opt(2026, 4, 21).unwrap();
+        let pillar = |expiry: NaiveDate, atm, wing_25, wing_10| {
+            FXDeltaVolPillar::new(
+                expiry.into(),
+                1.10,
+                vec![
+                    FXVolQuote::Atm(atm),
+                    FXVolQuote::RiskReversal {
+                        delta: 0.25,
+                        vol: 0.0,
+                    },
+                    FXVolQuote::Butterfly {
+                        delta: 0.25,
+                        vol: wing_25,
+                    },
+                    FXVolQuote::RiskReversal {
+                        delta: 0.10,
+                        vol: 0.0,
+                    },
+                    FXVolQuote::Butterfly {
+                        delta: 0.10,
+                        vol: wing_10,
+                    },
+                ],
+            )
+        };
+        let one_year = NaiveDate::from_ymd_opt(2027, 4, 21).unwrap();
+        let two_years = NaiveDate::from_ymd_opt(2028, 4, 21).unwrap();
+
+        let clean = FXVolSurface::new(
+            valuation_date,
+            vec![
+                pillar(one_year, 0.10, 0.004, 0.012),
+                pillar(two_years, 0.10, 0.004, 0.012),
+            ],
+        )?;
+        assert!(clean.arbitrage_report()?.is_arbitrage_free());
+
+        // 1Y wings that fall back from 25Δ to 10Δ, and a 2Y ATM whose
+        // total variance is below the 1Y one.
+        let mut surface = FXVolSurface::new(
+            valuation_date,
+            vec![
+                pillar(one_year, 0.10, 0.06, 0.0),
+                pillar(two_years, 0.065, 0.004, 0.012),
+            ],
+        )?;
+        let report = surface.arbitrage_report()?;
+        assert!(report.count(ArbitrageKind::Butterfly) > 0, "{report:?}");
+        assert!(report.count(ArbitrageKind::Calendar) > 0, "{report:?}");
+        assert!(
+            report
+                .violations
+                .iter()
+                .filter(|v| v.kind == ArbitrageKind::Calendar)
+                .all(|v| v.expiry == two_years)
+        );
+
+        let repaired = surface.repair_arbitrage()?;
+        assert!(repaired.is_arbitrage_free(), "{repaired:?}");
+        assert!(surface.fit_report().iter().all(|fit| fit.rmse < 1e-12));
+        // The 1Y ATM vol was already on the convex minorant.
+        let k_atm = 1.10 * (0.5 * 0.01_f64 * (365.0 / 365.0)).exp();
+        assert!((surface.volatility(one_year, k_atm)? - 0.10).abs() < 1e-10);
+        Ok(())
+    }
 }
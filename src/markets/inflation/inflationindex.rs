@@ -0,0 +1,204 @@
+//! Consumer-price indices with a publication lag, reference-date
+//! interpolation and a history of published fixings.
+//!
+//! CPI prints are monthly and arrive weeks after the month they measure,
+//! so inflation-linked cash flows reference the index with an
+//! observation lag. For a reference date `d` in month `M` and a lag of
+//! `L` months:
+//!
+//! ```text
+//!   Flat     I(d) = CPI(M − L)                                  (UK RPI swaps, HICPxT swaps)
+//!   Linear   I(d) = CPI(M − L) + (day(d) − 1)/days(M) · [CPI(M − L + 1) − CPI(M − L)]
+//!                                                               (TIPS, linkers, US CPI swaps)
+//! ```
+//!
+//! Months are keyed by their first calendar day. Months not yet
+//! published are projected off a [`ZeroInflationTermStructure`].
+
+use chrono::{Datelike, Months, NaiveDate};
+use iso_currency::Currency;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::error::{Error, Result};
+use crate::markets::termstructures::inflationcurve::ZeroInflationTermStructure;
+
+/// How the index is read between monthly prints.
+#[derive(Deserialize, Serialize, Copy, Clone, PartialEq, Debug)]
+pub enum InflationInterpolation {
+    Flat,
+    Linear,
+}
+
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Deserialize, Serialize, PartialEq, Debug)]
+pub enum InflationIndexEnum {
+    /// UK Retail Prices Index; swaps reference it flat, two months lagged.
+    UKRPI,
+    /// Eurozone HICP excluding tobacco; swaps reference it flat, three
+    /// months lagged.
+    HICPxT,
+    /// US CPI-U NSA; swaps and TIPS interpolate linearly, three months
+    /// lagged.
+    USCPI,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct InflationIndex {
+    pub name: String,
+    pub currency: Currency,
+    /// Observation lag in months.
+    pub observation_lag: u32,
+    pub interpolation: InflationInterpolation,
+    /// Published prints keyed by the first day of the month they measure.
+    pub fixings: BTreeMap<NaiveDate, f64>,
+}
+
+/// First calendar day of `date`'s month.
+pub fn month_start(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).unwrap()
+}
+
+/// Whole months from `from`'s month to `to`'s month.
+pub fn months_between(from: NaiveDate, to: NaiveDate) -> i32 {
+    (to.year() - from.year()) * 12 + to.month() as i32 - from.month() as i32
+}
+
+fn days_in_month(month: NaiveDate) -> f64 {
+    let next = month + Months::new(1);
+    (next - month).num_days() as f64
+}
+
+impl InflationIndex {
+    pub fn new(
+        name: &str,
+        currency: Currency,
+        observation_lag: u32,
+        interpolation: InflationInterpolation,
+    ) -> Self {
+        Self {
+            name: name.to_string(),
+            currency,
+            observation_lag,
+            interpolation,
+            fixings: BTreeMap::new(),
+        }
+    }
+
+    pub fn from_enum(code: InflationIndexEnum) -> Self {
+        match code {
+            InflationIndexEnum::UKRPI => {
+                Self::new("UKRPI", Currency::GBP, 2, InflationInterpolation::Flat)
+            }
+            InflationIndexEnum::HICPxT => {
+                Self::new("HICPxT", Currency::EUR, 3, InflationInterpolation::Flat)
+            }
+            InflationIndexEnum::USCPI => {
+                Self::new("USCPI", Currency::USD, 3, InflationInterpolation::Linear)
+            }
+        }
+    }
+
+    /// Same index read with a different interpolation (e.g. HICPxT on a
+    /// linker rather than a swap).
+    pub fn with_interpolation(mut self, interpolation: InflationInterpolation) -> Self {
+        self.interpolation = interpolation;
+        self
+    }
+
+    /// Record the print for the month containing `month`.
+    pub fn add_fixing(&mut self, month: NaiveDate, value: f64) {
+        self.fixings.insert(month_start(month), value);
+    }
+
+    /// Published print for the month containing `month`, if any.
+    pub fn fixing(&self, month: NaiveDate) -> Option<f64> {
+        self.fixings.get(&month_start(month)).copied()
+    }
+
+    /// Latest published month and its print.
+    pub fn last_fixing(&self) -> Option<(NaiveDate, f64)> {
+        self.fixings.iter().next_back().map(|(m, v)| (*m, *v))
+    }
+
+    /// Lagged month referenced on `date` and the interpolation weight on
+    /// the following month (zero when flat).
+    pub fn reference_month(&self, date: NaiveDate) -> (NaiveDate, f64) {
+        let month = month_start(date) - Months::new(self.observation_lag);
+        let weight = match self.interpolation {
+            InflationInterpolation::Flat => 0.0,
+            InflationInterpolation::Linear => {
+                (date.day() - 1) as f64 / days_in_month(month_start(date))
+            }
+        };
+        (month, weight)
+    }
+
+    /// Print for `month`: published if available, else projected off
+    /// `curve`.
+    pub fn month_value(
+        &self,
+        month: NaiveDate,
+        curve: Option<&ZeroInflationTermStructure>,
+    ) -> Result<f64> {
+        if let Some(value) = self.fixing(month) {
+            return Ok(value);
+        }
+        match curve {
+            Some(curve) if month_start(month) > curve.base_month => {
+                Ok(curve.projected_fixing(month))
+            }
+            _ => Err(Error::InvalidData(format!(
+                "{} has no fixing for {}",
+                self.name,
+                month_start(month)
+            ))),
+        }
+    }
+
+    /// Lagged, interpolated index level referenced on `date`.
+    pub fn reference_value(
+        &self,
+        date: NaiveDate,
+        curve: Option<&ZeroInflationTermStructure>,
+    ) -> Result<f64> {
+        let (month, weight) = self.reference_month(date);
+        let first = self.month_value(month, curve)?;
+        if weight == 0.0 {
+            return Ok(first);
+        }
+        let second = self.month_value(month + Months::new(1), curve)?;
+        Ok(first + weight * (second - first))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn d(y: i32, m: u32, dd: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, dd).unwrap()
+    }
+
+    /// Flat reads the lagged month; linear blends it with the next one
+    /// by day of month.
+    #[test]
+    fn lagged_reference_values() -> Result<()> {
+        let mut rpi = InflationIndex::from_enum(InflationIndexEnum::UKRPI);
+        rpi.add_fixing(d(2026, 1, 1), 400.0);
+        rpi.add_fixing(d(2026, 2, 20), 402.0);
+        assert_eq!(rpi.reference_value(d(2026, 3, 17), None)?, 400.0);
+        assert_eq!(rpi.reference_value(d(2026, 4, 1), None)?, 402.0);
+        assert!(rpi.reference_value(d(2026, 5, 1), None).is_err());
+
+        let mut cpi = InflationIndex::from_enum(InflationIndexEnum::USCPI);
+        cpi.add_fixing(d(2026, 1, 1), 320.0);
+        cpi.add_fixing(d(2026, 2, 1), 321.0);
+        // 16 April: 15/30 of the way from January to February.
+        let v = cpi.reference_value(d(2026, 4, 16), None)?;
+        assert!((v - 320.5).abs() < 1e-12);
+        assert_eq!(cpi.reference_value(d(2026, 4, 1), None)?, 320.0);
+        assert_eq!(cpi.last_fixing(), Some((d(2026, 2, 1), 321.0)));
+        Ok(())
+    }
+}
@@ -12,6 +12,9 @@
 //! * An optional [`IRNormalVolSurface`] — present when the product is
 //!   vol-sensitive (caps, floors, swaptions). Pure linear products
 //!   (vanilla swaps) can be priced without it.
+//! * An optional [`CmsMarket`] — swaption cube plus annuity-mapping
+//!   choice, required by CMS legs, CMS caps / floors and CMS spread
+//!   options.
 //! * Currency metadata for sanity-checking trade vs. market
 //!   currencies.
 //!
@@ -40,6 +43,7 @@ use crate::markets::interestrate::volsurface::{IRCapMarketData, IRNormalVolSurfa
 use crate::markets::termstructures::yieldcurve::{
     InterpolationMethodEnum, YieldTermMarketData, YieldTermStructure,
 };
+use crate::models::interestrate::cms::CmsMarket;
 use crate::time::calendars::Calendar;
 use crate::time::daycounters::DayCounters;
 use chrono::NaiveDate;
@@ -54,6 +58,9 @@ pub struct IrMarketContext {
     /// `None` when pricing a vol-independent product (e.g. vanilla
     /// IRS). Must be `Some` for cap/floor/swaption valuation.
     pub cap_surface: Option<IRNormalVolSurface>,
+    /// Swaption smiles for CMS products; `None` unless set through
+    /// [`IrMarketContext::with_cms`].
+    pub cms: Option<CmsMarket>,
 }
 
 impl IrMarketContext {
@@ -69,9 +76,16 @@ impl IrMarketContext {
             currency,
             curve,
             cap_surface,
+            cms: None,
         }
     }
 
+    /// Attach the swaption cube CMS products are replicated over.
+    pub fn with_cms(mut self, cms: CmsMarket) -> Self {
+        self.cms = Some(cms);
+        self
+    }
+
     /// Raw-quote-level constructor. `cap_md` is optional — omit for
     /// products that don't need a vol surface.
     pub fn from_raw_quotes(
@@ -14,6 +14,19 @@
 //!   * piecewise-linear in strike within each pillar, flat extrapolation
 //!     outside the quoted strike range.
 //!
+//! After stripping, [`IRNormalVolSurface::fit_sabr_smiles`] can replace
+//! the linear strike interpolation with a normal or shifted-lognormal
+//! SABR section per pillar ([`crate::models::interestrate::sabr`]), whose
+//! wings are extrapolated in price space without butterfly arbitrage.
+//! Queries in lognormal or shifted-lognormal terms go through
+//! [`IRNormalVolSurface::caplet_volatility_quoted`].
+//!
+//! [`IRNormalVolSurface::arbitrage_report`] lists butterfly and calendar
+//! violations of the stripped caplet vols (see
+//! [`crate::models::common::static_arbitrage`]), and
+//! [`IRNormalVolSurface::repair_arbitrage`] adjusts the nodes to remove
+//! them before the surface is used for pricing.
+//!
 //! The surface participates in the Observable/Observer pattern so a caller
 //! can wire it into a reactive market-data pipeline. Explicit rebuild via
 //! [`IRNormalVolSurface::rebuild`] is also supported for the common case
@@ -24,7 +37,13 @@ use crate::derivatives::interestrate::basic::{CapFloorKind, CapStyle, caplet_tot
 use crate::derivatives::interestrate::swap::InterestRateSchedulePeriod;
 use crate::error::{Error, Result};
 use crate::markets::termstructures::yieldcurve::{InterpolationMethodEnum, YieldTermStructure};
+use crate::math::optimize::NelderMeadOptions;
 use crate::models::common::bachelier::{bachelier_call, bachelier_put};
+use crate::models::common::static_arbitrage::{
+    ArbitrageReport, butterfly_violations, calendar_violation, convexify,
+};
+use crate::models::common::vol_conversion::{VolQuoteType, convert_vol, option_price};
+use crate::models::interestrate::sabr::{RatesSabrKind, SabrSmileSection, fit_smile_section};
 use crate::patterns::observer::{Observable, Observer};
 use crate::time::daycounters::DayCounters;
 use crate::time::daycounters::actual365fixed::Actual365Fixed;
@@ -56,6 +75,76 @@ pub struct CapQuote {
 }
 
 impl CapQuote {
+    /// NPV of this cap under a single flat vol quoted in convention
+    /// `quote_type`. Each caplet's effective expiry is its total
+    /// variance over `vol²` (see [`caplet_total_variance`]), so
+    /// backward-compounded styles convert consistently.
+    pub fn npv_from_flat_vol(
+        &self,
+        yts: &YieldTermStructure,
+        valuation_date: NaiveDate,
+        vol: f64,
+        quote_type: VolQuoteType,
+    ) -> Result<f64> {
+        let vol_time = Actual365Fixed::default();
+        let dir_sign = self.direction as i8 as f64;
+        let mut npv = 0.0_f64;
+        for period in &self.schedule {
+            let yf_start = vol_time.year_fraction(valuation_date, period.accrual_start_date)?;
+            let yf_end = vol_time.year_fraction(valuation_date, period.accrual_end_date)?;
+            // Unit-vol variance is the effective option time.
+            let t = caplet_total_variance(self.style, 1.0, yf_start, yf_end);
+            let (tau, forward, df_pay) = period_forward(yts, self, period)?;
+            let opt = option_price(
+                vol,
+                quote_type,
+                forward,
+                self.strike,
+                t,
+                self.kind == CapFloorKind::Cap,
+            )
+            .ok_or_else(|| {
+                Error::InvalidData(format!(
+                    "cap strike {} cannot be quoted as {quote_type:?} at forward {forward}",
+                    self.strike
+                ))
+            })?;
+            npv += dir_sign * self.notional * tau * df_pay * opt;
+        }
+        Ok(npv)
+    }
+
+    /// Flat vol in convention `quote_type` that reprices `market_npv`.
+    /// Chaining with [`CapQuote::npv_from_flat_vol`] converts flat cap
+    /// vol quotes between normal, lognormal and shifted-lognormal terms.
+    pub fn flat_vol(
+        &self,
+        yts: &YieldTermStructure,
+        valuation_date: NaiveDate,
+        quote_type: VolQuoteType,
+    ) -> Result<f64> {
+        let mut convergency = SimpleConvergency {
+            eps: 1e-12_f64,
+            max_iter: 200,
+        };
+        let upper = match quote_type {
+            VolQuoteType::Normal => 0.10,
+            _ => 5.0,
+        };
+        let mut residual = |vol: f64| -> f64 {
+            match self.npv_from_flat_vol(yts, valuation_date, vol, quote_type) {
+                Ok(npv) => npv - self.market_npv,
+                Err(_) => f64::NAN,
+            }
+        };
+        find_root_brent(1.0e-6_f64, upper, &mut residual, &mut convergency).map_err(|e| {
+            Error::InvalidData(format!(
+                "flat {quote_type:?} vol solve failed at strike {}: {:?}",
+                self.strike, e
+            ))
+        })
+    }
+
     fn last_accrual_start(&self) -> Result<NaiveDate> {
         self.schedule
             .last()
@@ -136,10 +225,17 @@ pub struct CapletVolPillar {
 /// the union of all seen maturities. Non-rectangular grids are supported —
 /// at a given expiry, a strike is represented by the most-recent (≤ expiry)
 /// stripped σ on its column.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct IRNormalVolSurface {
     pub valuation_date: NaiveDate,
     pub pillars: Vec<CapletVolPillar>,
+    /// Forward of the last caplet at each pillar, aligned with `pillars`.
+    /// Recorded by [`IRNormalVolSurface::rebuild`]; needed for SABR fits
+    /// and quote-convention conversion.
+    pub forwards: Vec<f64>,
+    /// SABR sections aligned with `pillars`. Empty ⇒ linear strike
+    /// interpolation.
+    pub smiles: Vec<SabrSmileSection>,
 }
 
 impl IRNormalVolSurface {
@@ -147,6 +243,8 @@ impl IRNormalVolSurface {
         Self {
             valuation_date,
             pillars: Vec::new(),
+            forwards: Vec::new(),
+            smiles: Vec::new(),
         }
     }
 
@@ -155,32 +253,234 @@ impl IRNormalVolSurface {
     pub fn rebuild(&mut self, yts: &YieldTermStructure, md: &IRCapMarketData) -> Result<()> {
         self.valuation_date = md.valuation_date;
         self.pillars = strip_caplet_vols(yts, md)?;
+        self.forwards = self
+            .pillars
+            .iter()
+            .map(|p| pillar_forward(yts, md, p.expiry))
+            .collect::<Result<_>>()?;
+        self.smiles.clear();
         Ok(())
     }
 
+    /// Fit one SABR section per pillar to its stripped `(strike, σ)`
+    /// nodes, `β` fixed (ignored for normal SABR). Subsequent queries use
+    /// the sections instead of linear interpolation. Returns the per-
+    /// pillar normal-vol RMSE.
+    pub fn fit_sabr_smiles(
+        &mut self,
+        kind: RatesSabrKind,
+        beta: f64,
+        options: NelderMeadOptions,
+    ) -> Result<Vec<f64>> {
+        if self.forwards.len() != self.pillars.len() {
+            return Err(Error::InvalidData(
+                "SABR smile fit needs one forward per pillar".to_string(),
+            ));
+        }
+        let vol_time = Actual365Fixed::default();
+        let mut smiles = Vec::with_capacity(self.pillars.len());
+        let mut rmses = Vec::with_capacity(self.pillars.len());
+        for (pillar, &forward) in self.pillars.iter().zip(self.forwards.iter()) {
+            let t = vol_time.year_fraction(self.valuation_date, pillar.expiry)?;
+            let (section, rmse) =
+                fit_smile_section(kind, beta, forward, t, &pillar.nodes, options)?;
+            smiles.push(section);
+            rmses.push(rmse);
+        }
+        self.smiles = smiles;
+        Ok(rmses)
+    }
+
+    fn pillar_index(&self, expiry: NaiveDate) -> Result<usize> {
+        if self.pillars.is_empty() {
+            return Err(Error::InvalidData(
+                "IRNormalVolSurface has not been stripped (no pillars)".to_string(),
+            ));
+        }
+        Ok(self
+            .pillars
+            .iter()
+            .position(|p| expiry <= p.expiry)
+            .unwrap_or(self.pillars.len() - 1))
+    }
+
     /// Caplet vol at `(expiry, strike)`:
     ///
     /// * Expiry: piecewise-flat right-continuous — the first pillar with
     ///   `expiry ≥ target` is used, flat extrapolation beyond the last pillar.
-    /// * Strike within the chosen pillar: linear interpolation between the
-    ///   bracketing `(strike, σ)` nodes, flat extrapolation outside the range.
+    /// * Strike within the chosen pillar: the pillar's SABR section if
+    ///   [`IRNormalVolSurface::fit_sabr_smiles`] has run, otherwise linear
+    ///   interpolation between the bracketing `(strike, σ)` nodes, flat
+    ///   extrapolation outside the range.
     ///
     /// Errors if the surface has not been stripped.
     pub fn caplet_volatility(&self, expiry: NaiveDate, strike: f64) -> Result<f64> {
-        if self.pillars.is_empty() {
+        self.pillar_volatility(self.pillar_index(expiry)?, strike)
+    }
+
+    /// Strike interpolation within pillar `i`.
+    fn pillar_volatility(&self, i: usize, strike: f64) -> Result<f64> {
+        if let Some(section) = self.smiles.get(i) {
+            return section.normal_vol(strike).ok_or_else(|| {
+                Error::InvalidData(format!("SABR smile has no normal vol at strike {strike}"))
+            });
+        }
+        interpolate_nodes(&self.pillars[i].nodes, strike)
+    }
+
+    /// [`IRNormalVolSurface::caplet_volatility`] converted to `quote`
+    /// (normal, lognormal or shifted-lognormal) at the pillar forward,
+    /// price-equivalently over the year fraction to `expiry`. Errors if
+    /// no forwards are recorded or the strike cannot be quoted that way.
+    pub fn caplet_volatility_quoted(
+        &self,
+        expiry: NaiveDate,
+        strike: f64,
+        quote: VolQuoteType,
+    ) -> Result<f64> {
+        let normal = self.caplet_volatility(expiry, strike)?;
+        if quote == VolQuoteType::Normal {
+            return Ok(normal);
+        }
+        let forward = *self
+            .forwards
+            .get(self.pillar_index(expiry)?)
+            .ok_or_else(|| {
+                Error::InvalidData("vol conversion needs the pillar forwards".to_string())
+            })?;
+        let t = Actual365Fixed::default().year_fraction(self.valuation_date, expiry)?;
+        convert_vol(normal, VolQuoteType::Normal, quote, forward, strike, t).ok_or_else(|| {
+            Error::InvalidData(format!(
+                "strike {strike} cannot be quoted as {quote:?} at forward {forward}"
+            ))
+        })
+    }
+
+    /// Butterfly violations on every pillar's smile and calendar
+    /// violations against the previous pillar at equal strike, in normal
+    /// vol terms. Both are checked at the node strikes of every pillar;
+    /// between nodes the linear vol interpolation kinks the price at each
+    /// node, which is not a property of the quotes. Errors if no forwards
+    /// are recorded.
+    pub fn arbitrage_report(&self) -> Result<ArbitrageReport> {
+        let strikes = self.check_strikes()?;
+        let times = self.pillar_times()?;
+        let mut violations = Vec::new();
+        for (i, pillar) in self.pillars.iter().enumerate() {
+            let nodes = self.pillar_grid(i, &strikes)?;
+            violations.extend(butterfly_violations(
+                pillar.expiry,
+                &nodes,
+                self.forwards[i],
+                times[i],
+                VolQuoteType::Normal,
+            )?);
+            if i > 0 {
+                for &(k, sigma) in &nodes {
+                    let w_earlier = self.pillar_volatility(i - 1, k)?.powi(2) * times[i - 1];
+                    violations.extend(calendar_violation(
+                        pillar.expiry,
+                        k,
+                        w_earlier,
+                        sigma * sigma * times[i],
+                    ));
+                }
+            }
+        }
+        Ok(ArbitrageReport { violations })
+    }
+
+    /// Minimally adjust each pillar's vols on the union of node strikes, in
+    /// expiry order: vols below the previous pillar's total variance are
+    /// raised to it and calls are lowered onto their convex minorant, until
+    /// both hold. The adjusted vols become the pillar's nodes, and SABR sections,
+    /// if fitted, are refitted to them with the same kind and `β`. Returns
+    /// the report of the repaired surface; refitted SABR sections only
+    /// approximate the adjusted vols and may leave violations in it.
+    pub fn repair_arbitrage(&mut self) -> Result<ArbitrageReport> {
+        let strikes = self.check_strikes()?;
+        let times = self.pillar_times()?;
+        for i in 0..self.pillars.len() {
+            let (forward, t) = (self.forwards[i], times[i]);
+            let floors = match i {
+                0 => None,
+                _ => Some(
+                    strikes
+                        .iter()
+                        .map(|&k| Ok(self.pillar_volatility(i - 1, k)?.powi(2) * times[i - 1] / t))
+                        .collect::<Result<Vec<f64>>>()?,
+                ),
+            };
+            let mut nodes = self.pillar_grid(i, &strikes)?;
+            for _ in 0..REPAIR_ROUNDS {
+                if let Some(floors) = &floors {
+                    for (node, floor) in nodes.iter_mut().zip(floors) {
+                        node.1 = node.1.max(floor.sqrt());
+                    }
+                }
+                nodes = convexify(&nodes, forward, t, VolQuoteType::Normal)?;
+                let calendar_holds = floors.as_ref().is_none_or(|floors| {
+                    nodes.iter().zip(floors).all(|(&(k, sigma), floor)| {
+                        calendar_violation(self.pillars[i].expiry, k, floor * t, sigma * sigma * t)
+                            .is_none()
+                    })
+                });
+                if calendar_holds {
+                    break;
+                }
+            }
+            if let Some(section) = self.smiles.get(i) {
+                let (refitted, _) = fit_smile_section(
+                    section.kind,
+                    section.params.beta,
+                    forward,
+                    t,
+                    &nodes,
+                    NelderMeadOptions::default(),
+                )?;
+                self.smiles[i] = refitted;
+            }
+            self.pillars[i].nodes = nodes;
+        }
+        self.arbitrage_report()
+    }
+
+    /// Union of the pillars' node strikes.
+    fn check_strikes(&self) -> Result<Vec<f64>> {
+        if self.forwards.len() != self.pillars.len() {
             return Err(Error::InvalidData(
-                "IRNormalVolSurface has not been stripped (no pillars)".to_string(),
+                "arbitrage checks need one forward per pillar".to_string(),
             ));
         }
-        let pillar = self
+        let mut strikes: Vec<f64> = self
             .pillars
             .iter()
-            .find(|p| expiry <= p.expiry)
-            .unwrap_or_else(|| self.pillars.last().unwrap());
-        interpolate_nodes(&pillar.nodes, strike)
+            .flat_map(|p| p.nodes.iter().map(|n| n.0))
+            .collect();
+        strikes.sort_by(f64::total_cmp);
+        strikes.dedup_by(|a, b| (*a - *b).abs() < 1e-12);
+        Ok(strikes)
+    }
+
+    fn pillar_times(&self) -> Result<Vec<f64>> {
+        let vol_time = Actual365Fixed::default();
+        self.pillars
+            .iter()
+            .map(|p| vol_time.year_fraction(self.valuation_date, p.expiry))
+            .collect()
+    }
+
+    fn pillar_grid(&self, i: usize, strikes: &[f64]) -> Result<Vec<(f64, f64)>> {
+        strikes
+            .iter()
+            .map(|&k| Ok((k, self.pillar_volatility(i, k)?)))
+            .collect()
     }
 }
 
+/// Alternating calendar / butterfly passes per pillar in the repair.
+const REPAIR_ROUNDS: usize = 50;
+
 /// Linear-in-strike interpolation inside a pillar, flat outside. Errors if
 /// the pillar is empty (should never happen post-strip).
 fn interpolate_nodes(nodes: &[(f64, f64)], strike: f64) -> Result<f64> {
@@ -372,22 +672,7 @@ fn price_cap_with_strip(
         let yf_end = vol_time.year_fraction(valuation_date, period.accrual_end_date)?;
         let v = caplet_total_variance(quote.style, sigma, yf_start, yf_end);
 
-        let tau = quote
-            .accrual_day_counter
-            .year_fraction(period.accrual_start_date, period.accrual_end_date)?;
-        let df_start = yts.discount(
-            period.accrual_start_date,
-            &InterpolationMethodEnum::StepFunctionForward,
-        )?;
-        let df_end = yts.discount(
-            period.accrual_end_date,
-            &InterpolationMethodEnum::StepFunctionForward,
-        )?;
-        let df_pay = yts.discount(
-            period.pay_date,
-            &InterpolationMethodEnum::StepFunctionForward,
-        )?;
-        let forward = (df_start / df_end - 1.0) / tau;
+        let (tau, forward, df_pay) = period_forward(yts, quote, period)?;
 
         let opt = match quote.kind {
             CapFloorKind::Cap => bachelier_call(forward, quote.strike, v),
@@ -399,6 +684,50 @@ fn price_cap_with_strip(
     Ok(npv)
 }
 
+/// Forward of the caplet accruing from `expiry`, taken from the first
+/// cap quote whose schedule has a period starting there.
+fn pillar_forward(
+    yts: &YieldTermStructure,
+    md: &IRCapMarketData,
+    expiry: NaiveDate,
+) -> Result<f64> {
+    let (quote, period) = md
+        .cap_quotes
+        .iter()
+        .find_map(|q| {
+            q.schedule
+                .iter()
+                .find(|p| p.accrual_start_date == expiry)
+                .map(|p| (q, p))
+        })
+        .ok_or_else(|| Error::InvalidData(format!("no caplet starts at pillar {expiry}")))?;
+    Ok(period_forward(yts, quote, period)?.1)
+}
+
+/// `(τ, forward, P(0, pay))` for one caplet period of `quote`.
+fn period_forward(
+    yts: &YieldTermStructure,
+    quote: &CapQuote,
+    period: &InterestRateSchedulePeriod,
+) -> Result<(f64, f64, f64)> {
+    let tau = quote
+        .accrual_day_counter
+        .year_fraction(period.accrual_start_date, period.accrual_end_date)?;
+    let df_start = yts.discount(
+        period.accrual_start_date,
+        &InterpolationMethodEnum::StepFunctionForward,
+    )?;
+    let df_end = yts.discount(
+        period.accrual_end_date,
+        &InterpolationMethodEnum::StepFunctionForward,
+    )?;
+    let df_pay = yts.discount(
+        period.pay_date,
+        &InterpolationMethodEnum::StepFunctionForward,
+    )?;
+    Ok((tau, (df_start / df_end - 1.0) / tau, df_pay))
+}
+
 fn caplet_sigma_for_start(start: NaiveDate, strip: &[StripPoint], fallback: f64) -> f64 {
     for s in strip {
         if start <= s.expiry {
@@ -415,6 +744,13 @@ fn caplet_sigma_for_start(start: NaiveDate, strip: &[StripPoint], fallback: f64)
 #[cfg(test)]
 mod tests {
     use super::{CapletVolPillar, IRNormalVolSurface};
+    use crate::math::optimize::NelderMeadOptions;
+    use crate::models::common::static_arbitrage::ArbitrageKind;
+    use crate::models::common::vol_conversion::{VolQuoteType, convert_vol};
+    use crate::models::forex::sabr::SabrParams;
+    use crate::models::interestrate::sabr::{RatesSabrKind, SabrSmileSection};
+    use crate::time::daycounters::DayCounters;
+    use crate::time::daycounters::actual365fixed::Actual365Fixed;
     use chrono::NaiveDate;
 
     /// Single-node pillar behaves like the old ATM-only surface.
@@ -501,6 +837,77 @@ mod tests {
         assert!((q(0.060) - 0.0085).abs() < 1e-15);
     }
 
+    /// SABR fitted on top of a stripped pillar reproduces the nodes,
+    /// replaces flat strike extrapolation with a smile, and supports
+    /// lognormal queries.
+    #[test]
+    fn sabr_smile_replaces_linear_interpolation() {
+        let vd = NaiveDate::from_ymd_opt(2026, 4, 22).unwrap();
+        let exp = NaiveDate::from_ymd_opt(2029, 4, 22).unwrap();
+        let mut surface = IRNormalVolSurface::new(vd);
+        let generator = SabrSmileSection {
+            kind: RatesSabrKind::Normal,
+            params: SabrParams::new(0.009, 0.0, -0.25, 0.4),
+            forward: 0.03,
+            t: Actual365Fixed::default().year_fraction(vd, exp).unwrap(),
+            left_cutoff: f64::NEG_INFINITY,
+            right_cutoff: f64::INFINITY,
+        };
+        let nodes: Vec<(f64, f64)> = [0.015, 0.025, 0.03, 0.035, 0.045]
+            .iter()
+            .map(|&k| (k, generator.normal_vol(k).unwrap()))
+            .collect();
+        surface.pillars = vec![CapletVolPillar {
+            expiry: exp,
+            nodes: nodes.clone(),
+        }];
+        let flat_wing = surface.caplet_volatility(exp, 0.0).unwrap();
+
+        // Forwards are required for the fit.
+        let opts = NelderMeadOptions {
+            max_iter: 2_000,
+            ftol: 1.0e-18,
+            xtol: 1.0e-12,
+            step_frac: 0.2,
+        };
+        assert!(
+            surface
+                .fit_sabr_smiles(RatesSabrKind::Normal, 0.0, opts)
+                .is_err()
+        );
+        surface.forwards = vec![0.03];
+        let rmse = surface
+            .fit_sabr_smiles(RatesSabrKind::Normal, 0.0, opts)
+            .unwrap();
+        assert!(rmse[0] < 1e-6);
+
+        for &(k, sigma) in &nodes {
+            assert!((surface.caplet_volatility(exp, k).unwrap() - sigma).abs() < 2e-6);
+        }
+        // Negative skew continues past the last node instead of flattening.
+        assert!(surface.caplet_volatility(exp, 0.0).unwrap() > flat_wing);
+
+        let black = surface
+            .caplet_volatility_quoted(exp, 0.035, VolQuoteType::Lognormal)
+            .unwrap();
+        let t = Actual365Fixed::default().year_fraction(vd, exp).unwrap();
+        let normal = convert_vol(
+            black,
+            VolQuoteType::Lognormal,
+            VolQuoteType::Normal,
+            0.03,
+            0.035,
+            t,
+        )
+        .unwrap();
+        assert!((normal - surface.caplet_volatility(exp, 0.035).unwrap()).abs() < 1e-10);
+        assert!(
+            surface
+                .caplet_volatility_quoted(exp, -0.01, VolQuoteType::Lognormal)
+                .is_err()
+        );
+    }
+
     #[test]
     fn empty_surface_errors() {
         let vd = NaiveDate::from_ymd_opt(2026, 4, 22).unwrap();
@@ -511,4 +918,52 @@ mod tests {
                 .is_err()
         );
     }
+
+    /// A 1Y vol spike is a butterfly, a 2Y pillar with less total variance
+    /// than 1Y is a calendar violation; repair removes both.
+    #[test]
+    fn arbitrage_report_and_repair() {
+        let vd = NaiveDate::from_ymd_opt(2026, 4, 22).unwrap();
+        let one_year = NaiveDate::from_ymd_opt(2027, 4, 22).unwrap();
+        let two_years = NaiveDate::from_ymd_opt(2028, 4, 24).unwrap();
+        let mut surface = IRNormalVolSurface::new(vd);
+        surface.pillars = vec![
+            CapletVolPillar {
+                expiry: one_year,
+                nodes: vec![
+                    (0.02, 0.0100),
+                    (0.03, 0.0100),
+                    (0.035, 0.0150),
+                    (0.04, 0.0100),
+                ],
+            },
+            CapletVolPillar {
+                expiry: two_years,
+                nodes: vec![(0.02, 0.0070), (0.03, 0.0065), (0.04, 0.0070)],
+            },
+        ];
+        assert!(surface.arbitrage_report().is_err());
+        surface.forwards = vec![0.032, 0.033];
+
+        let report = surface.arbitrage_report().unwrap();
+        let butterflies: Vec<_> = report
+            .violations
+            .iter()
+            .filter(|v| v.kind == ArbitrageKind::Butterfly)
+            .collect();
+        assert!(!butterflies.is_empty());
+        assert!(butterflies.iter().all(|v| v.expiry == one_year));
+        assert!(butterflies.iter().any(|v| v.strike == 0.035));
+        assert!(report.count(ArbitrageKind::Calendar) > 0);
+
+        let repaired = surface.repair_arbitrage().unwrap();
+        assert!(repaired.is_arbitrage_free(), "{repaired:?}");
+        // The low wing of 1Y was already convex, the spike came down and
+        // the 2Y ATM was lifted to the 1Y total variance.
+        assert_eq!(surface.caplet_volatility(one_year, 0.02).unwrap(), 0.0100);
+        assert!(surface.caplet_volatility(one_year, 0.035).unwrap() < 0.0150);
+        let t = |d| Actual365Fixed::default().year_fraction(vd, d).unwrap();
+        let w = |d, k| surface.caplet_volatility(d, k).unwrap().powi(2) * t(d);
+        assert!(w(two_years, 0.03) >= w(one_year, 0.03) * (1.0 - 1e-12));
+    }
 }
@@ -1 +1,4 @@
+pub mod defaultcurve;
+pub mod inflationcurve;
+pub mod parametric;
 pub mod yieldcurve;
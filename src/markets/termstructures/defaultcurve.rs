@@ -0,0 +1,262 @@
+//! Default-probability term structure with piecewise-flat hazard rates,
+//! bootstrapped from standard CDS par spreads or upfronts.
+//!
+//! Hazard rate `λ_i` applies on `(T_{i−1}, T_i]`, with `T_0` the
+//! valuation date and flat extrapolation past the last pillar; times are
+//! ACT/365F:
+//!
+//! ```text
+//!   Q(t) = exp(−Σ λ_i · |(T_{i−1}, T_i] ∩ (0, t]|)
+//! ```
+//!
+//! Each quote is a standard contract (see
+//! [`crate::derivatives::credit::cds`]) traded on the valuation date; its
+//! pillar is the end of protection (the day after maturity) and `λ_i` is
+//! solved so the contract's clean upfront matches the quote (zero for a
+//! par spread). Besides CDS
+//! pricing the curve supplies survival and default probabilities for CVA.
+
+use chrono::NaiveDate;
+use roots::{SimpleConvergency, find_root_brent};
+
+use crate::derivatives::credit::cds::CdsSchedule;
+use crate::error::{Error, Result};
+use crate::markets::termstructures::yieldcurve::YieldCurve;
+use crate::time::daycounters::DayCounters;
+use crate::time::daycounters::actual365fixed::Actual365Fixed;
+use crate::time::period::{ONE_DAY, Period};
+
+/// How a CDS is quoted.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum CdsQuoteType {
+    /// Running spread at which the contract has zero upfront.
+    ParSpread(f64),
+    /// Clean upfront per unit notional paid by the buyer, on a standard
+    /// running `coupon`.
+    Upfront { upfront: f64, coupon: f64 },
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct CdsQuote {
+    pub tenor: Period,
+    pub quote: CdsQuoteType,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct HazardRateCurve {
+    pub valuation_date: NaiveDate,
+    /// `(pillar date, hazard rate up to it)` in increasing date order.
+    pub pillars: Vec<(NaiveDate, f64)>,
+    /// Recovery the curve was bootstrapped with.
+    pub recovery_rate: f64,
+}
+
+impl HazardRateCurve {
+    pub fn new(
+        valuation_date: NaiveDate,
+        pillars: Vec<(NaiveDate, f64)>,
+        recovery_rate: f64,
+    ) -> Self {
+        Self {
+            valuation_date,
+            pillars,
+            recovery_rate,
+        }
+    }
+
+    fn time(&self, date: NaiveDate) -> Result<f64> {
+        Actual365Fixed::default().year_fraction(self.valuation_date, date)
+    }
+
+    /// Hazard rate in force at `date`.
+    pub fn hazard_rate(&self, date: NaiveDate) -> f64 {
+        self.pillars
+            .iter()
+            .find(|p| date <= p.0)
+            .or(self.pillars.last())
+            .map_or(0.0, |p| p.1)
+    }
+
+    /// Probability of surviving from the valuation date to `date`.
+    pub fn survival_probability(&self, date: NaiveDate) -> Result<f64> {
+        let t = self.time(date)?;
+        if t <= 0.0 {
+            return Ok(1.0);
+        }
+        let mut integral = 0.0;
+        let mut previous = 0.0;
+        for &(pillar, lambda) in &self.pillars {
+            let end = self.time(pillar)?.min(t);
+            integral += lambda * (end - previous);
+            previous = end;
+            if end >= t {
+                return Ok((-integral).exp());
+            }
+        }
+        integral += self.pillars.last().map_or(0.0, |p| p.1) * (t - previous);
+        Ok((-integral).exp())
+    }
+
+    /// Probability of default in `(start, end]`, seen from the valuation
+    /// date.
+    pub fn default_probability(&self, start: NaiveDate, end: NaiveDate) -> Result<f64> {
+        Ok(self.survival_probability(start)? - self.survival_probability(end)?)
+    }
+
+    /// Bootstrap from standard contracts traded on `valuation_date`.
+    pub fn bootstrap(
+        valuation_date: NaiveDate,
+        quotes: &[CdsQuote],
+        recovery_rate: f64,
+        yts: &dyn YieldCurve,
+    ) -> Result<Self> {
+        let contracts = quotes
+            .iter()
+            .map(|q| Ok((CdsSchedule::standard(valuation_date, q.tenor)?, q.quote)))
+            .collect::<Result<Vec<_>>>()?;
+        Self::bootstrap_contracts(valuation_date, contracts, recovery_rate, yts)
+    }
+
+    fn bootstrap_contracts(
+        valuation_date: NaiveDate,
+        mut contracts: Vec<(CdsSchedule, CdsQuoteType)>,
+        recovery_rate: f64,
+        yts: &dyn YieldCurve,
+    ) -> Result<Self> {
+        contracts.sort_by_key(|c| c.0.maturity_date);
+        let mut curve = Self::new(valuation_date, Vec::new(), recovery_rate);
+        for (schedule, quote) in contracts {
+            let pillar = schedule.protection_end();
+            if curve.pillars.last().is_some_and(|p| p.0 >= pillar) {
+                return Err(Error::InvalidData(format!(
+                    "duplicate CDS maturity {}",
+                    schedule.maturity_date
+                )));
+            }
+            let (coupon, target) = match quote {
+                CdsQuoteType::ParSpread(spread) => (spread, 0.0),
+                CdsQuoteType::Upfront { upfront, coupon } => (coupon, upfront),
+            };
+            let mut residual = |lambda: f64| -> f64 {
+                let mut trial = curve.clone();
+                trial.pillars.push((pillar, lambda));
+                schedule
+                    .upfront(coupon, recovery_rate, &trial, yts)
+                    .map_or(f64::NAN, |u| u - target)
+            };
+            let mut convergency = SimpleConvergency {
+                eps: 1e-14_f64,
+                max_iter: 200,
+            };
+            let lambda = find_root_brent(0.0_f64, 10.0_f64, &mut residual, &mut convergency)
+                .map_err(|e| {
+                    Error::InvalidData(format!("hazard rate to {} did not solve: {:?}", pillar, e))
+                })?;
+            curve.pillars.push((pillar, lambda));
+        }
+        Ok(curve)
+    }
+
+    /// Par spreads of the contracts whose protection ends on each pillar.
+    pub fn par_spreads(&self, yts: &dyn YieldCurve) -> Result<Vec<f64>> {
+        self.pillars
+            .iter()
+            .map(|p| {
+                CdsSchedule::new(self.valuation_date, p.0 - ONE_DAY).par_spread(
+                    self.recovery_rate,
+                    self,
+                    yts,
+                )
+            })
+            .collect()
+    }
+
+    /// Curve rebootstrapped at `recovery_rate` from this curve's pillar
+    /// par spreads shifted by `spread_shift`.
+    pub fn bumped(
+        &self,
+        spread_shift: f64,
+        recovery_rate: f64,
+        yts: &dyn YieldCurve,
+    ) -> Result<Self> {
+        let contracts = self
+            .pillars
+            .iter()
+            .zip(self.par_spreads(yts)?)
+            .map(|(p, s)| {
+                (
+                    CdsSchedule::new(self.valuation_date, p.0 - ONE_DAY),
+                    CdsQuoteType::ParSpread(s + spread_shift),
+                )
+            })
+            .collect();
+        Self::bootstrap_contracts(self.valuation_date, contracts, recovery_rate, yts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::markets::termstructures::yieldcurve::flat_rate_curve;
+
+    fn d(y: i32, m: u32, dd: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, dd).unwrap()
+    }
+
+    /// Flat spreads give a flat hazard close to the credit triangle
+    /// `s/(1 − R)`; mixed par and upfront quotes are all repriced.
+    #[test]
+    fn bootstrap_reprices_quotes() -> Result<()> {
+        let valuation_date = d(2026, 8, 12);
+        let yts = flat_rate_curve(valuation_date, 0.03);
+        let flat: Vec<CdsQuote> = [1, 3, 5, 10]
+            .iter()
+            .map(|&y| CdsQuote {
+                tenor: Period::Years(y),
+                quote: CdsQuoteType::ParSpread(0.012),
+            })
+            .collect();
+        let curve = HazardRateCurve::bootstrap(valuation_date, &flat, 0.4, &yts)?;
+        for p in &curve.pillars {
+            assert!((p.1 / 0.02 - 1.0).abs() < 0.02, "{:?}", p);
+            assert!((p.1 - curve.pillars[0].1).abs() < 1e-4);
+        }
+
+        let quotes = vec![
+            CdsQuote {
+                tenor: Period::Months(6),
+                quote: CdsQuoteType::ParSpread(0.004),
+            },
+            CdsQuote {
+                tenor: Period::Years(3),
+                quote: CdsQuoteType::Upfront {
+                    upfront: 0.012,
+                    coupon: 0.01,
+                },
+            },
+            CdsQuote {
+                tenor: Period::Years(5),
+                quote: CdsQuoteType::Upfront {
+                    upfront: 0.035,
+                    coupon: 0.01,
+                },
+            },
+        ];
+        let curve = HazardRateCurve::bootstrap(valuation_date, &quotes, 0.4, &yts)?;
+        for q in &quotes {
+            let s = CdsSchedule::standard(valuation_date, q.tenor)?;
+            let (coupon, target) = match q.quote {
+                CdsQuoteType::ParSpread(s) => (s, 0.0),
+                CdsQuoteType::Upfront { upfront, coupon } => (coupon, upfront),
+            };
+            assert!((s.upfront(coupon, 0.4, &curve, &yts)? - target).abs() < 1e-12);
+        }
+        let q5 = curve.survival_probability(d(2031, 12, 20))?;
+        assert!(0.8 < q5 && q5 < 0.95, "{q5}");
+        assert!(
+            (curve.default_probability(valuation_date, d(2031, 12, 20))? - (1.0 - q5)).abs()
+                < 1e-15
+        );
+        Ok(())
+    }
+}
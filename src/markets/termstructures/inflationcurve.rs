@@ -0,0 +1,270 @@
+//! Zero-coupon inflation term structure bootstrapped from zero-coupon
+//! inflation swap quotes, with optional monthly seasonality.
+//!
+//! The curve is anchored at the latest published print `(m₀, I₀)` and
+//! stores annually-compounded zero inflation rates at monthly pillars;
+//! times are whole months over twelve, which matches how CPI accrues:
+//!
+//! ```text
+//!   I(m) = I₀ · (1 + z(m))^{t(m)} · s(m)/s(m₀),   t(m) = months(m₀, m)/12
+//! ```
+//!
+//! `z` is linear in `t` between pillars and flat outside. A ZC inflation
+//! swap of tenor `n` years traded at `K` pays `I(T)/I(S) − 1` against
+//! `(1 + K)^n − 1` at maturity, with `I(·)` the index's lagged,
+//! interpolated reference value. Both legs pay on the same date, so the
+//! fair rate does not depend on nominal discounting and each pillar is
+//! solved in turn so the swap's inflation ratio hits `(1 + K)^n`.
+//!
+//! Seasonality is a set of multiplicative month-of-year factors. Only
+//! ratios enter, so whole-year horizons from the base month are
+//! unaffected; the bootstrap runs with the seasonality in place, so the
+//! stored `z` are de-seasonalised rates.
+
+use chrono::{Datelike, Months, NaiveDate};
+use roots::{SimpleConvergency, find_root_brent};
+
+use crate::error::{Error, Result};
+use crate::markets::inflation::inflationindex::{InflationIndex, month_start, months_between};
+use crate::time::period::Period;
+
+/// Multiplicative month-of-year factors, January first.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Seasonality {
+    pub factors: [f64; 12],
+}
+
+impl Seasonality {
+    pub fn new(factors: [f64; 12]) -> Result<Self> {
+        if factors.iter().any(|f| *f <= 0.0) {
+            return Err(Error::InvalidData(
+                "seasonality factors must be positive".to_string(),
+            ));
+        }
+        Ok(Self { factors })
+    }
+
+    /// Seasonal adjustment of `month` relative to `base_month`.
+    pub fn adjustment(&self, base_month: NaiveDate, month: NaiveDate) -> f64 {
+        self.factors[month.month0() as usize] / self.factors[base_month.month0() as usize]
+    }
+}
+
+/// Spot-starting zero-coupon inflation swap quote.
+#[derive(Debug)]
+pub struct ZeroCouponInflationSwapHelper {
+    /// Whole months or years.
+    pub tenor: Period,
+    pub rate: f64,
+}
+
+#[derive(Clone, Debug)]
+pub struct ZeroInflationTermStructure {
+    /// Latest published month the curve grows from.
+    pub base_month: NaiveDate,
+    pub base_fixing: f64,
+    /// `(month, zero inflation rate)` in increasing month order.
+    pub pillars: Vec<(NaiveDate, f64)>,
+    pub seasonality: Option<Seasonality>,
+}
+
+impl ZeroInflationTermStructure {
+    pub fn new(
+        base_month: NaiveDate,
+        base_fixing: f64,
+        pillars: Vec<(NaiveDate, f64)>,
+        seasonality: Option<Seasonality>,
+    ) -> Self {
+        Self {
+            base_month: month_start(base_month),
+            base_fixing,
+            pillars,
+            seasonality,
+        }
+    }
+
+    fn time(&self, month: NaiveDate) -> f64 {
+        months_between(self.base_month, month) as f64 / 12.0
+    }
+
+    /// De-seasonalised zero inflation rate to `month`.
+    pub fn zero_rate(&self, month: NaiveDate) -> f64 {
+        let t = self.time(month);
+        let Some(&(first_month, first_rate)) = self.pillars.first() else {
+            return 0.0;
+        };
+        if t <= self.time(first_month) {
+            return first_rate;
+        }
+        for pair in self.pillars.windows(2) {
+            let (t1, t2) = (self.time(pair[0].0), self.time(pair[1].0));
+            if t <= t2 {
+                return pair[0].1 + (t - t1) / (t2 - t1) * (pair[1].1 - pair[0].1);
+            }
+        }
+        self.pillars.last().unwrap().1
+    }
+
+    /// Projected print for the month containing `month`.
+    pub fn projected_fixing(&self, month: NaiveDate) -> f64 {
+        let seasonal = self
+            .seasonality
+            .as_ref()
+            .map_or(1.0, |s| s.adjustment(self.base_month, month));
+        self.base_fixing * (1.0 + self.zero_rate(month)).powf(self.time(month)) * seasonal
+    }
+
+    /// Bootstrap from spot-starting ZC swap quotes on `index`, anchored
+    /// at its latest published print.
+    pub fn bootstrap(
+        valuation_date: NaiveDate,
+        index: &InflationIndex,
+        helpers: &[ZeroCouponInflationSwapHelper],
+        seasonality: Option<Seasonality>,
+    ) -> Result<Self> {
+        let (base_month, base_fixing) = index.last_fixing().ok_or_else(|| {
+            Error::InvalidData(format!("{} has no published fixings", index.name))
+        })?;
+        let mut curve = Self::new(base_month, base_fixing, Vec::new(), seasonality);
+
+        let mut quotes = helpers
+            .iter()
+            .map(|h| Ok((h.tenor.years()?, h)))
+            .collect::<Result<Vec<_>>>()?;
+        quotes.sort_by(|a, b| a.0.total_cmp(&b.0));
+        for (years, helper) in quotes {
+            let maturity = (valuation_date + helper.tenor)?;
+            // Latest month the swap's final reference value reads.
+            let (month, weight) = index.reference_month(maturity);
+            let pillar = if weight > 0.0 {
+                month + Months::new(1)
+            } else {
+                month
+            };
+            let after = curve.pillars.last().map_or(base_month, |p| p.0);
+            if pillar <= after {
+                return Err(Error::InvalidData(format!(
+                    "{:?} swap references {} which is not after {}",
+                    helper.tenor, pillar, after
+                )));
+            }
+            let target = (1.0 + helper.rate).powf(years);
+            let mut residual = |z: f64| -> f64 {
+                let mut trial = curve.clone();
+                trial.pillars.push((pillar, z));
+                let ratio = index
+                    .reference_value(maturity, Some(&trial))
+                    .and_then(|end| Ok(end / index.reference_value(valuation_date, Some(&trial))?));
+                ratio.map_or(f64::NAN, |r| r - target)
+            };
+            let mut convergency = SimpleConvergency {
+                eps: 1e-14_f64,
+                max_iter: 200,
+            };
+            let z = find_root_brent(-0.5_f64, 1.0_f64, &mut residual, &mut convergency).map_err(
+                |e| {
+                    Error::InvalidData(format!(
+                        "{:?} inflation pillar did not solve: {:?}",
+                        helper.tenor, e
+                    ))
+                },
+            )?;
+            curve.pillars.push((pillar, z));
+        }
+        Ok(curve)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::markets::inflation::inflationindex::{InflationIndexEnum, InflationInterpolation};
+
+    fn d(y: i32, m: u32, dd: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, dd).unwrap()
+    }
+
+    fn helpers() -> Vec<ZeroCouponInflationSwapHelper> {
+        [
+            (1, 0.031),
+            (2, 0.028),
+            (5, 0.026),
+            (10, 0.0255),
+            (30, 0.027),
+        ]
+        .iter()
+        .map(|&(y, rate)| ZeroCouponInflationSwapHelper {
+            tenor: Period::Years(y),
+            rate,
+        })
+        .collect()
+    }
+
+    fn implied_rate(
+        index: &InflationIndex,
+        curve: &ZeroInflationTermStructure,
+        start: NaiveDate,
+        years: u32,
+    ) -> Result<f64> {
+        let maturity = (start + Period::Years(years))?;
+        let ratio = index.reference_value(maturity, Some(curve))?
+            / index.reference_value(start, Some(curve))?;
+        Ok(ratio.powf(1.0 / years as f64) - 1.0)
+    }
+
+    /// The bootstrapped curve reprices every quote, for flat and linear
+    /// index interpolation, with and without seasonality.
+    #[test]
+    fn bootstrap_reprices_quotes() -> Result<()> {
+        let valuation_date = d(2026, 5, 20);
+        let mut seasonal = [1.0; 12];
+        seasonal[0] = 0.994;
+        seasonal[6] = 0.997;
+        seasonal[11] = 1.004;
+        for interpolation in [InflationInterpolation::Flat, InflationInterpolation::Linear] {
+            for seasonality in [None, Some(Seasonality::new(seasonal)?)] {
+                let mut index = InflationIndex::from_enum(InflationIndexEnum::HICPxT)
+                    .with_interpolation(interpolation);
+                index.add_fixing(d(2026, 2, 1), 127.3);
+                index.add_fixing(d(2026, 3, 1), 128.1);
+                let curve = ZeroInflationTermStructure::bootstrap(
+                    valuation_date,
+                    &index,
+                    &helpers(),
+                    seasonality,
+                )?;
+                for h in helpers() {
+                    let Period::Years(y) = h.tenor else {
+                        unreachable!()
+                    };
+                    let rate = implied_rate(&index, &curve, valuation_date, y)?;
+                    assert!((rate - h.rate).abs() < 1e-12, "{y}y {rate}");
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Seasonality moves off-cycle months but not whole years from the
+    /// base month.
+    #[test]
+    fn seasonality_is_relative_to_base_month() -> Result<()> {
+        let base = d(2026, 3, 1);
+        let pillars = vec![(d(2031, 3, 1), 0.025)];
+        let mut factors = [1.0; 12];
+        factors[6] = 1.01;
+        let plain = ZeroInflationTermStructure::new(base, 100.0, pillars.clone(), None);
+        let seasonal =
+            ZeroInflationTermStructure::new(base, 100.0, pillars, Some(Seasonality::new(factors)?));
+        let march = d(2029, 3, 1);
+        assert_eq!(
+            plain.projected_fixing(march),
+            seasonal.projected_fixing(march)
+        );
+        let july = d(2029, 7, 1);
+        let ratio = seasonal.projected_fixing(july) / plain.projected_fixing(july);
+        assert!((ratio - 1.01).abs() < 1e-14);
+        assert!((plain.projected_fixing(d(2028, 3, 1)) - 100.0 * 1.025_f64.powi(2)).abs() < 1e-12);
+        Ok(())
+    }
+}
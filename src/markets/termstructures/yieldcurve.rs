@@ -2,6 +2,7 @@ use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 use std::any::Any;
 use std::cell::RefCell;
+use std::fmt::Debug;
 use std::rc::{Rc, Weak};
 
 use crate::derivatives::interestrate::swap::InterestRateSwap;
@@ -310,6 +311,101 @@ impl YieldTermStructure {
     }
 }
 
+/// Read-only curve interface shared by the bootstrapped
+/// [`YieldTermStructure`] and the parametric curves in
+/// [`crate::markets::termstructures::parametric`], so pricers can take
+/// either. Zero rates are continuously compounded on ACT/365F from
+/// [`YieldCurve::valuation_date`]; curves without interpolation choices
+/// ignore `interpolation_method_enum`.
+pub trait YieldCurve: Debug {
+    fn valuation_date(&self) -> NaiveDate;
+
+    fn zero_rate(
+        &self,
+        date: NaiveDate,
+        interpolation_method_enum: &InterpolationMethodEnum,
+    ) -> Result<f64>;
+
+    fn discount(
+        &self,
+        date: NaiveDate,
+        interpolation_method_enum: &InterpolationMethodEnum,
+    ) -> Result<f64> {
+        let zero_rate = self.zero_rate(date, interpolation_method_enum)?;
+        let duration = Actual365Fixed::default().year_fraction(self.valuation_date(), date)?;
+        Ok((-zero_rate * duration).exp())
+    }
+
+    /// Discount factor under a parallel zero-rate shift of `shift_bp`
+    /// basis points.
+    fn shifted_discount(
+        &self,
+        date: NaiveDate,
+        interpolation_method_enum: &InterpolationMethodEnum,
+        shift_bp: f64,
+    ) -> Result<f64> {
+        let base = self.discount(date, interpolation_method_enum)?;
+        let yf = Actual365Fixed::default().year_fraction(self.valuation_date(), date)?;
+        Ok(base * (-shift_bp * 1.0e-4 * yf).exp())
+    }
+
+    /// Continuously-compounded forward rate over `[start, start + tenor]`.
+    fn forward_rate(
+        &self,
+        accrual_start_date: NaiveDate,
+        tenor: Period,
+        interpolation_method_enum: &InterpolationMethodEnum,
+    ) -> Result<f64> {
+        let accrual_end_date = (accrual_start_date + tenor)?;
+        let dc = Actual365Fixed::default();
+        let year_fraction_1 = dc.year_fraction(self.valuation_date(), accrual_start_date)?;
+        let year_fraction_2 = dc.year_fraction(self.valuation_date(), accrual_end_date)?;
+        let df_1 = self.discount(accrual_start_date, interpolation_method_enum)?;
+        let df_2 = self.discount(accrual_end_date, interpolation_method_enum)?;
+        Ok((df_1 / df_2).ln() / (year_fraction_2 - year_fraction_1))
+    }
+}
+
+impl YieldCurve for YieldTermStructure {
+    fn valuation_date(&self) -> NaiveDate {
+        self.valuation_date
+    }
+
+    fn zero_rate(
+        &self,
+        date: NaiveDate,
+        interpolation_method_enum: &InterpolationMethodEnum,
+    ) -> Result<f64> {
+        YieldTermStructure::zero_rate(self, date, interpolation_method_enum)
+    }
+
+    fn discount(
+        &self,
+        date: NaiveDate,
+        interpolation_method_enum: &InterpolationMethodEnum,
+    ) -> Result<f64> {
+        YieldTermStructure::discount(self, date, interpolation_method_enum)
+    }
+
+    fn shifted_discount(
+        &self,
+        date: NaiveDate,
+        interpolation_method_enum: &InterpolationMethodEnum,
+        shift_bp: f64,
+    ) -> Result<f64> {
+        YieldTermStructure::shifted_discount(self, date, interpolation_method_enum, shift_bp)
+    }
+
+    fn forward_rate(
+        &self,
+        accrual_start_date: NaiveDate,
+        tenor: Period,
+        interpolation_method_enum: &InterpolationMethodEnum,
+    ) -> Result<f64> {
+        YieldTermStructure::forward_rate(self, accrual_start_date, tenor, interpolation_method_enum)
+    }
+}
+
 impl Observer for YieldTermStructure {
     fn update(&mut self, observable: &dyn Observable) -> Result<()> {
         if let Some(concrete_observable) = observable.as_any().downcast_ref::<YieldTermMarketData>()
@@ -324,6 +420,41 @@ impl Observer for YieldTermStructure {
         self
     }
 }
+
+/// Test curve on `valuation_date` through `(date, zero rate)` swap
+/// pillars, Act/365 on the TARGET calendar.
+#[cfg(test)]
+pub(crate) fn zero_rate_curve(
+    valuation_date: NaiveDate,
+    pillars: &[(NaiveDate, f64)],
+) -> YieldTermStructure {
+    let pillars = pillars
+        .iter()
+        .map(|&(date, rate)| StrippedCurve {
+            first_settle_date: valuation_date,
+            date,
+            market_rate: rate,
+            zero_rate: rate,
+            discount: 1.0,
+            source: InterestRateQuoteEnum::Swap,
+            hidden_pillar: false,
+        })
+        .collect();
+    YieldTermStructure::new(
+        Box::new(crate::time::calendars::Target),
+        Box::new(Actual365Fixed::default()),
+        valuation_date,
+        pillars,
+    )
+}
+
+/// [`zero_rate_curve`] flat at `rate` from 2026 to 2060.
+#[cfg(test)]
+pub(crate) fn flat_rate_curve(valuation_date: NaiveDate, rate: f64) -> YieldTermStructure {
+    let date = |year| NaiveDate::from_ymd_opt(year, 1, 1).unwrap();
+    zero_rate_curve(valuation_date, &[(date(2026), rate), (date(2060), rate)])
+}
+
 #[cfg(test)]
 mod tests {
     use super::{
@@ -1,2 +1,4 @@
+pub mod interpolation;
+pub mod linalg;
 pub mod normal;
 pub mod optimize;
@@ -0,0 +1,20 @@
+//! One-dimensional interpolation on `(x, y)` node lists, shared by the
+//! swaption-cube and futures-option smile lookups.
+
+/// Piecewise-linear value at `x` through `(x, y)` nodes sorted by `x`,
+/// flat outside them.
+pub fn interpolate_flat(nodes: &[(f64, f64)], x: f64) -> f64 {
+    let Some(first) = nodes.first() else {
+        return 0.0;
+    };
+    if x <= first.0 {
+        return first.1;
+    }
+    for pair in nodes.windows(2) {
+        if x <= pair[1].0 {
+            let w = (x - pair[0].0) / (pair[1].0 - pair[0].0);
+            return pair[0].1 + w * (pair[1].1 - pair[0].1);
+        }
+    }
+    nodes.last().unwrap().1
+}
@@ -0,0 +1,68 @@
+//! Small dense linear-algebra helpers for regression-sized problems
+//! (a handful of unknowns, many observations).
+
+/// Ordinary least squares `min ‖X β − y‖²` via the normal equations,
+/// solved by Gauss–Jordan elimination with partial pivoting. Columns are
+/// rescaled to unit RMS before solving so polynomial bases in raw rates /
+/// spots and exponential bases stay well conditioned. `None` if the
+/// system is (numerically) singular or there are no rows.
+#[allow(clippy::needless_range_loop)] // dense matrix indexing is idiomatic here
+pub fn least_squares(rows: &[Vec<f64>], y: &[f64]) -> Option<Vec<f64>> {
+    let n = rows.len();
+    let p = rows.first()?.len();
+    let scale: Vec<f64> = (0..p)
+        .map(|j| {
+            let rms = (rows.iter().map(|r| r[j] * r[j]).sum::<f64>() / n as f64).sqrt();
+            if rms > 0.0 { rms } else { 1.0 }
+        })
+        .collect();
+
+    let mut a = vec![vec![0.0_f64; p + 1]; p];
+    for (row, &yi) in rows.iter().zip(y.iter()) {
+        for i in 0..p {
+            let xi = row[i] / scale[i];
+            for j in 0..p {
+                a[i][j] += xi * row[j] / scale[j];
+            }
+            a[i][p] += xi * yi;
+        }
+    }
+    for col in 0..p {
+        let pivot = (col..p)
+            .max_by(|&i, &j| a[i][col].abs().partial_cmp(&a[j][col].abs()).unwrap())
+            .unwrap();
+        if a[pivot][col].abs() < 1e-12 * n as f64 {
+            return None;
+        }
+        a.swap(col, pivot);
+        for r in 0..p {
+            if r != col {
+                let f = a[r][col] / a[col][col];
+                for c in col..=p {
+                    a[r][c] -= f * a[col][c];
+                }
+            }
+        }
+    }
+    Some((0..p).map(|i| a[i][p] / a[i][i] / scale[i]).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::least_squares;
+
+    /// Exact data on a quadratic is recovered; a rank-deficient design
+    /// is rejected.
+    #[test]
+    fn recovers_polynomial_and_rejects_singular() {
+        let xs = [0.5, 1.0, 2.0, 3.5, 5.0];
+        let rows: Vec<Vec<f64>> = xs.iter().map(|&x| vec![1.0, x, x * x]).collect();
+        let y: Vec<f64> = xs.iter().map(|&x| 2.0 - 0.3 * x + 0.05 * x * x).collect();
+        let beta = least_squares(&rows, &y).unwrap();
+        for (b, e) in beta.iter().zip([2.0, -0.3, 0.05]) {
+            assert!((b - e).abs() < 1e-10);
+        }
+        let singular: Vec<Vec<f64>> = xs.iter().map(|&x| vec![x, 2.0 * x]).collect();
+        assert!(least_squares(&singular, &y).is_none());
+    }
+}
@@ -177,6 +177,18 @@ where
     }
 }
 
+/// `ln(1 + eˣ)`: maps an unconstrained optimiser coordinate onto a
+/// positive parameter. Linear beyond `x = 35`, where `eˣ` swamps the 1.
+pub fn softplus(x: f64) -> f64 {
+    if x > 35.0 { x } else { (1.0 + x.exp()).ln() }
+}
+
+/// Inverse of [`softplus`], `ln(eʸ − 1)`, for starting points; `y > 0`.
+pub fn inv_softplus(y: f64) -> f64 {
+    assert!(y > 0.0);
+    if y > 35.0 { y } else { (y.exp() - 1.0).ln() }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -240,4 +252,14 @@ mod tests {
         );
         assert!(m.iterations <= 5);
     }
+
+    /// Softplus round-trip: `softplus(inv_softplus(y)) = y` for `y > 0`.
+    #[test]
+    fn softplus_inverse_roundtrip() {
+        for &y in &[0.001_f64, 0.01, 0.1, 1.0, 10.0, 100.0] {
+            let x = inv_softplus(y);
+            let y_back = softplus(x);
+            assert!((y_back - y).abs() < 1e-10, "y={}: back {}", y, y_back);
+        }
+    }
 }
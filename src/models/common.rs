@@ -1,10 +1,16 @@
 //! Asset-class-neutral analytics primitives used by every model
 //! family: path simulation plumbing, closed-form BS/Bachelier pricers,
-//! CIR moments, and the COS characteristic-function pricer.
+//! CIR moments, the COS characteristic-function pricer, the
+//! Longstaff–Schwartz early-exercise engine, normal / lognormal /
+//! shifted-lognormal vol-quote conversion, and static-arbitrage checks on
+//! vol smiles.
 
 pub mod bachelier;
 pub mod black_scholes;
 pub mod calibration;
 pub mod cir;
 pub mod cos_pricer;
+pub mod longstaff_schwartz;
 pub mod simulation;
+pub mod static_arbitrage;
+pub mod vol_conversion;
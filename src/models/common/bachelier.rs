@@ -50,9 +50,52 @@ pub fn bachelier_vega_variance(forward: f64, strike: f64, variance: f64) -> f64
     pdf(d) / (2.0 * sqrt_v)
 }
 
+/// Bachelier implied normal vol `σ` for an **undiscounted** target price
+/// at expiry `t` (so that `V = σ² · t`). Bracketed bisection on
+/// `σ ∈ (0, 1)` — 10 000 bp / y comfortably covers every rate market.
+/// Returns `None` if the target is outside the achievable price range
+/// (below intrinsic or above the σ = 1 price).
+pub fn bachelier_implied_vol(
+    target: f64,
+    forward: f64,
+    strike: f64,
+    t: f64,
+    is_call: bool,
+) -> Option<f64> {
+    if t <= 0.0 {
+        return None;
+    }
+    let pricer = |sigma: f64| -> f64 {
+        let v = sigma * sigma * t;
+        if is_call {
+            bachelier_call(forward, strike, v)
+        } else {
+            bachelier_put(forward, strike, v)
+        }
+    };
+    let tol = 1.0e-14_f64;
+    let mut lo = 0.0_f64;
+    let mut hi = 1.0_f64;
+    if target < pricer(lo) - tol || target > pricer(hi) + tol {
+        return None;
+    }
+    for _ in 0..200 {
+        let mid = 0.5 * (lo + hi);
+        if hi - lo < 1.0e-14 {
+            return Some(mid);
+        }
+        if pricer(mid) < target {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    Some(0.5 * (lo + hi))
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{bachelier_call, bachelier_put, bachelier_vega_variance};
+    use super::{bachelier_call, bachelier_implied_vol, bachelier_put, bachelier_vega_variance};
 
     /// ATM call: F = K ⇒ d = 0 ⇒ Call = √V · φ(0) = √V / √(2π).
     #[test]
@@ -106,4 +149,23 @@ mod tests {
             / (2.0 * eps);
         assert!((analytic - fd).abs() < 1e-5);
     }
+
+    /// Implied-vol round trip across ITM / ATM / OTM strikes, calls and puts.
+    #[test]
+    fn implied_vol_round_trip() {
+        let t = 2.0_f64;
+        for &(k, is_call) in &[(0.025, true), (0.03, true), (0.035, false), (0.04, true)] {
+            let sigma = 0.0085_f64;
+            let v = sigma * sigma * t;
+            let price = if is_call {
+                bachelier_call(0.03, k, v)
+            } else {
+                bachelier_put(0.03, k, v)
+            };
+            let iv = bachelier_implied_vol(price, 0.03, k, t, is_call).expect("IV exists");
+            assert!((iv - sigma).abs() < 1e-9, "K={} iv={} vs {}", k, iv, sigma);
+        }
+        // Below intrinsic ⇒ no solution.
+        assert!(bachelier_implied_vol(0.001, 0.04, 0.03, 1.0, true).is_none());
+    }
 }
@@ -48,11 +48,17 @@ pub struct CalibrationReport<P> {
 /// | [`FxHhwSmileCalibrator`][hhws] | `MarketSmileStrip` | `FxHhwParams` |
 /// | [`FxHlmmSmileCalibrator`][hlms] | `MarketSmileStrip` | `FxHlmmParams` |
 /// | [`SabrTimeDependentSurfaceCalibrator`][sbrts] | `Vec<MarketSmileStrip>` | `TimeDependentSabrParams` |
+/// | [`G2ppSwaptionCalibrator`][g2s] | `SwaptionCube` | `G2pp` |
+/// | [`CheyetteSwaptionCalibrator`][chs] | `SwaptionCube` | `Cheyette` |
+/// | [`FmmCalibrator`][fmmc] | `IrVolMarket` | `Fmm` |
 ///
 /// [sbrs]: crate::models::forex::sabr_calibrator::SabrSmileCalibrator
 /// [hhws]: crate::models::forex::fx_hhw_calibrator::FxHhwSmileCalibrator
 /// [hlms]: crate::models::forex::fx_hlmm_calibrator::FxHlmmSmileCalibrator
 /// [sbrts]: crate::models::forex::sabr_time_dependent_calibrator::SabrTimeDependentSurfaceCalibrator
+/// [g2s]: crate::models::interestrate::g2pp_calibrator::G2ppSwaptionCalibrator
+/// [chs]: crate::models::interestrate::cheyette_calibrator::CheyetteSwaptionCalibrator
+/// [fmmc]: crate::models::interestrate::fmm_calibrator::FmmCalibrator
 pub trait Calibration {
     /// The market-data object this calibrator consumes.
     type Market;
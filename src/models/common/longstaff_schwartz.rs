@@ -0,0 +1,619 @@
+//! Least-squares Monte Carlo (Longstaff–Schwartz) for early-exercise
+//! products, driven by the [`DatedPaths`] output of
+//! [`simulate_at_dates`](crate::models::common::simulation::simulate_at_dates).
+//!
+//! The engine is model-agnostic: a product describes itself through
+//! [`LsmProduct`] on the simulator's state type — regression basis,
+//! exercise value, running cash flows and the numeraire — with every
+//! observation date of the paths treated as an exercise opportunity.
+//!
+//! ```text
+//!   training paths ──► fit_exercise_rule ──► ExerciseRule (+ diagnostics)
+//!                                              │
+//!   pricing paths  ──► lower_bound ◄───────────┤
+//!                                              │
+//!   model + outer  ──► andersen_broadie ◄──────┘   (nested simulation)
+//! ```
+//!
+//! Conventions, at observation `k` on a path that is still alive:
+//!
+//! 1. the holder receives `cashflow(k)` (coupon streams of cancellable
+//!    swaps, range-accrual fixings, …);
+//! 2. the holder may then exercise and receive `exercise_value(k)`,
+//!    terminating the product.
+//!
+//! All amounts are deflated by `numeraire(k)`, which must be normalised
+//! to `N(0) = 1` (the bank accounts of [`HullWhiteSimulator`],
+//! [`FmmSimulator`] and [`G2ppSimulator`] are). Exercise is only
+//! considered on paths with positive exercise value, and the regression
+//! is run on those paths only, as in the original paper.
+//!
+//! Pricing on an independent set of paths from the ones the rule was
+//! fitted on gives a low-biased estimate; [`andersen_broadie`] gives the
+//! matching high-biased dual estimate from the same rule, so the pair
+//! brackets the true price.
+//!
+//! [`HullWhiteSimulator`]: crate::models::interestrate::hull_white::HullWhiteSimulator
+//! [`FmmSimulator`]: crate::models::interestrate::fmm::FmmSimulator
+//! [`G2ppSimulator`]: crate::models::interestrate::g2pp::G2ppSimulator
+//!
+//! # Papers
+//!
+//! * **Longstaff, F. A., Schwartz, E. S. (2001)** — *Valuing American
+//!   Options by Simulation: A Simple Least-Squares Approach*, Review of
+//!   Financial Studies 14(1): 113–147. The regression-based exercise rule.
+//! * **Andersen, L., Broadie, M. (2004)** — *Primal-Dual Simulation
+//!   Algorithm for Pricing Multidimensional American Options*,
+//!   Management Science 50(9): 1222–1234. Duality upper bound from a
+//!   given exercise policy.
+
+use crate::error::Result;
+use crate::math::linalg;
+use crate::models::common::simulation::{DatedPaths, SimulationModel};
+use crate::time::daycounters::DayCounters;
+
+/// Early-exercise product seen through a simulator's state type `S`.
+/// `k` indexes [`DatedPaths::observation_dates`].
+pub trait LsmProduct<S> {
+    /// Regression basis functions at observation `k` (include a constant
+    /// term explicitly if wanted). The length must not depend on the
+    /// path.
+    fn basis(&self, k: usize, state: &S) -> Vec<f64>;
+
+    /// Value received on exercise at observation `k`, in currency at
+    /// `t_k`. Non-positive values are never exercised.
+    fn exercise_value(&self, k: usize, state: &S) -> f64;
+
+    /// Cash flow received at observation `k` while the product is still
+    /// alive (before the exercise decision at `k`). Defaults to none.
+    fn cashflow(&self, _k: usize, _state: &S) -> f64 {
+        0.0
+    }
+
+    /// Numeraire `N(t_k)` on the path, with `N(0) = 1`.
+    fn numeraire(&self, k: usize, state: &S) -> f64;
+}
+
+/// Regression output at one exercise date.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RegressionDiagnostics {
+    /// Observation index `k`.
+    pub index: usize,
+    /// Number of in-the-money paths the regression ran on.
+    pub n_itm: usize,
+    /// Least-squares coefficients on [`LsmProduct::basis`]; empty when
+    /// there were too few in-the-money paths to regress.
+    pub coefficients: Vec<f64>,
+    /// Coefficient of determination of the fit (deflated units).
+    pub r_squared: f64,
+    /// Root-mean-squared residual of the fit (deflated units).
+    pub residual_rmse: f64,
+}
+
+/// Fitted exercise policy: exercise at `k` iff the deflated exercise
+/// value is positive and exceeds `β_kᵀ · basis(k)`. The last observation
+/// needs no regression — continuation is worth zero there.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExerciseRule {
+    pub coefficients: Vec<Vec<f64>>,
+}
+
+impl ExerciseRule {
+    /// Exercise decision at observation `k` on `state`.
+    pub fn exercise<S, P: LsmProduct<S>>(&self, product: &P, k: usize, state: &S) -> bool {
+        let ex = product.exercise_value(k, state);
+        if ex <= 0.0 {
+            return false;
+        }
+        if k + 1 >= self.coefficients.len() {
+            return true;
+        }
+        let beta = &self.coefficients[k];
+        if beta.is_empty() {
+            // No regression available — never exercise early.
+            return false;
+        }
+        let continuation: f64 = product
+            .basis(k, state)
+            .iter()
+            .zip(beta.iter())
+            .map(|(x, b)| x * b)
+            .sum();
+        ex / product.numeraire(k, state) > continuation
+    }
+}
+
+/// Monte Carlo estimate with its standard error.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct LsmEstimate {
+    pub value: f64,
+    pub std_error: f64,
+}
+
+/// Nested-simulation settings for [`andersen_broadie`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct AndersenBroadie {
+    /// Number of pricing paths used as outer paths (the first `n_outer`).
+    pub n_outer: usize,
+    /// Inner paths per outer path and exercise date.
+    pub n_inner: usize,
+    /// Integration step cap for the inner simulation, in calendar days.
+    pub max_step_days: u32,
+}
+
+/// Full engine output.
+#[derive(Clone, Debug)]
+pub struct LsmResult {
+    /// Out-of-sample (low-biased) price.
+    pub lower_bound: LsmEstimate,
+    /// Andersen–Broadie (high-biased) price, when requested.
+    pub upper_bound: Option<LsmEstimate>,
+    pub rule: ExerciseRule,
+    /// One entry per exercise date except the last.
+    pub diagnostics: Vec<RegressionDiagnostics>,
+}
+
+/// Longstaff–Schwartz engine. Without an upper-bound configuration it
+/// only fits the rule and prices the lower bound.
+#[derive(Clone, Debug, Default)]
+pub struct LsmEngine {
+    pub upper_bound: Option<AndersenBroadie>,
+}
+
+impl LsmEngine {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_upper_bound(mut self, settings: AndersenBroadie) -> Self {
+        self.upper_bound = Some(settings);
+        self
+    }
+
+    /// Fit the rule on `training`, price the lower bound on `pricing`
+    /// (which should be an independent simulation) and, if configured,
+    /// the Andersen–Broadie upper bound with nested paths from `model`.
+    pub fn price<M, P>(
+        &self,
+        model: &mut M,
+        product: &P,
+        training: &DatedPaths<M::State>,
+        pricing: &DatedPaths<M::State>,
+        day_counter: &dyn DayCounters,
+    ) -> Result<LsmResult>
+    where
+        M: SimulationModel,
+        P: LsmProduct<M::State>,
+    {
+        let (rule, diagnostics) = fit_exercise_rule(product, training);
+        let lower_bound = lower_bound(product, &rule, pricing);
+        let upper_bound = self
+            .upper_bound
+            .map(|ab| {
+                andersen_broadie(
+                    model,
+                    product,
+                    &rule,
+                    lower_bound.value,
+                    pricing,
+                    ab,
+                    day_counter,
+                )
+            })
+            .transpose()?;
+        Ok(LsmResult {
+            lower_bound,
+            upper_bound,
+            rule,
+            diagnostics,
+        })
+    }
+}
+
+/// Backward-induction regression on `paths`: returns the exercise rule
+/// and per-date regression diagnostics.
+pub fn fit_exercise_rule<S: Clone, P: LsmProduct<S>>(
+    product: &P,
+    paths: &DatedPaths<S>,
+) -> (ExerciseRule, Vec<RegressionDiagnostics>) {
+    let n_dates = paths.observation_dates.len();
+    assert!(n_dates > 0, "need at least one exercise date");
+    let last = n_dates - 1;
+
+    // Deflated value of the policy's future flows from k onwards.
+    let mut values: Vec<f64> = paths
+        .paths
+        .iter()
+        .map(|p| {
+            let s = &p[last];
+            (product.cashflow(last, s) + product.exercise_value(last, s).max(0.0))
+                / product.numeraire(last, s)
+        })
+        .collect();
+
+    let mut coefficients = vec![Vec::new(); n_dates];
+    let mut diagnostics = Vec::with_capacity(last);
+    for k in (0..last).rev() {
+        let mut rows = Vec::new();
+        let mut targets = Vec::new();
+        let mut itm = Vec::new();
+        for (i, p) in paths.paths.iter().enumerate() {
+            let s = &p[k];
+            let ex = product.exercise_value(k, s);
+            if ex > 0.0 {
+                rows.push(product.basis(k, s));
+                targets.push(values[i]);
+                itm.push((i, ex / product.numeraire(k, s)));
+            }
+        }
+        let n_basis = rows.first().map_or(0, |r| r.len());
+        let (beta, r_squared, residual_rmse) = if rows.len() > n_basis && n_basis > 0 {
+            least_squares(&rows, &targets)
+        } else {
+            (Vec::new(), 0.0, 0.0)
+        };
+        if !beta.is_empty() {
+            for (row, &(i, ex)) in rows.iter().zip(itm.iter()) {
+                let fitted: f64 = row.iter().zip(beta.iter()).map(|(x, b)| x * b).sum();
+                if ex > fitted {
+                    values[i] = ex;
+                }
+            }
+        }
+        for (i, p) in paths.paths.iter().enumerate() {
+            let s = &p[k];
+            values[i] += product.cashflow(k, s) / product.numeraire(k, s);
+        }
+        diagnostics.push(RegressionDiagnostics {
+            index: k,
+            n_itm: rows.len(),
+            coefficients: beta.clone(),
+            r_squared,
+            residual_rmse,
+        });
+        coefficients[k] = beta;
+    }
+    diagnostics.reverse();
+    (ExerciseRule { coefficients }, diagnostics)
+}
+
+/// Deflated payoff of following `rule` along one path from observation
+/// `from` onwards.
+fn policy_payoff<S, P: LsmProduct<S>>(
+    product: &P,
+    rule: &ExerciseRule,
+    path: &[S],
+    from: usize,
+) -> f64 {
+    let mut total = 0.0;
+    for (k, s) in path.iter().enumerate().skip(from) {
+        let n = product.numeraire(k, s);
+        total += product.cashflow(k, s) / n;
+        if rule.exercise(product, k, s) {
+            return total + product.exercise_value(k, s) / n;
+        }
+    }
+    total
+}
+
+/// Price by following `rule` on `paths`. Low-biased when `paths` is
+/// independent of the paths the rule was fitted on.
+pub fn lower_bound<S: Clone, P: LsmProduct<S>>(
+    product: &P,
+    rule: &ExerciseRule,
+    paths: &DatedPaths<S>,
+) -> LsmEstimate {
+    let payoffs: Vec<f64> = paths
+        .paths
+        .iter()
+        .map(|p| policy_payoff(product, rule, p, 0))
+        .collect();
+    mean_and_error(&payoffs)
+}
+
+/// Andersen–Broadie duality upper bound for `rule`. `lower` is the
+/// policy value at time 0 (the lower-bound estimate), anchoring the
+/// martingale `M` built from nested continuation estimates:
+///
+/// ```text
+///   Z_k  = Σ_{i≤k} cf_i / N_i + h_k / N_k        (stop-at-k payoff)
+///   L_k  = Z_k if the rule exercises at k, else C_k = E_k[Z_τ(k+1)]
+///   M_k  = L_0 − lower + Σ_{i<k} (L_{i+1} − C_i)
+///   upper = E[ max_k (Z_k − M_k) ]
+/// ```
+///
+/// The final observation offers no continuation, so there
+/// `Z = Σ cf / N + max(h, 0) / N`. Cost is `n_outer · K · n_inner`
+/// inner paths.
+pub fn andersen_broadie<M, P>(
+    model: &mut M,
+    product: &P,
+    rule: &ExerciseRule,
+    lower: f64,
+    outer: &DatedPaths<M::State>,
+    settings: AndersenBroadie,
+    day_counter: &dyn DayCounters,
+) -> Result<LsmEstimate>
+where
+    M: SimulationModel,
+    P: LsmProduct<M::State>,
+{
+    let n_dates = outer.observation_dates.len();
+    let last = n_dates - 1;
+    let times: Vec<f64> = outer
+        .observation_dates
+        .iter()
+        .map(|d| day_counter.year_fraction(outer.valuation_date, *d))
+        .collect::<Result<_>>()?;
+    let max_dt = settings.max_step_days as f64 / 365.0;
+
+    let n_outer = settings.n_outer.min(outer.n_paths());
+    let mut samples = Vec::with_capacity(n_outer);
+    for path in outer.paths.iter().take(n_outer) {
+        let mut accrued = 0.0;
+        let mut martingale = 0.0;
+        let mut prev_continuation = None::<f64>;
+        let mut best = f64::NEG_INFINITY;
+        for k in 0..n_dates {
+            let s = &path[k];
+            let n = product.numeraire(k, s);
+            accrued += product.cashflow(k, s) / n;
+            let h = product.exercise_value(k, s);
+            let stop = if k == last {
+                accrued + h.max(0.0) / n
+            } else {
+                accrued + h / n
+            };
+            let continuation = if k == last {
+                0.0
+            } else {
+                let inner = simulate_from(model, s, &times[k..], settings.n_inner, max_dt);
+                let total: f64 = inner
+                    .iter()
+                    .map(|p| policy_payoff_shifted(product, rule, p, k))
+                    .sum();
+                accrued + total / settings.n_inner as f64
+            };
+            let exercises = k == last || rule.exercise(product, k, s);
+            let value = if exercises { stop } else { continuation };
+            martingale += match prev_continuation {
+                None => value - lower,
+                Some(c) => value - c,
+            };
+            prev_continuation = Some(continuation);
+            best = best.max(stop - martingale);
+        }
+        samples.push(best);
+    }
+    Ok(mean_and_error(&samples))
+}
+
+/// Policy payoff on an inner path whose element `i` is observation
+/// `offset + 1 + i`.
+fn policy_payoff_shifted<S, P: LsmProduct<S>>(
+    product: &P,
+    rule: &ExerciseRule,
+    inner: &[S],
+    offset: usize,
+) -> f64 {
+    let mut total = 0.0;
+    for (i, s) in inner.iter().enumerate() {
+        let k = offset + 1 + i;
+        let n = product.numeraire(k, s);
+        total += product.cashflow(k, s) / n;
+        if rule.exercise(product, k, s) {
+            return total + product.exercise_value(k, s) / n;
+        }
+    }
+    total
+}
+
+/// Simulate `n_paths` continuations of `start`, which sits at
+/// `times[0]`, capturing states at `times[1..]`. Steps are capped at
+/// `max_dt` year-fractions.
+fn simulate_from<M: SimulationModel>(
+    model: &mut M,
+    start: &M::State,
+    times: &[f64],
+    n_paths: usize,
+    max_dt: f64,
+) -> Vec<Vec<M::State>> {
+    (0..n_paths)
+        .map(|_| {
+            let mut state = start.clone();
+            let mut out = Vec::with_capacity(times.len() - 1);
+            for w in times.windows(2) {
+                let n_steps = ((w[1] - w[0]) / max_dt).ceil().max(1.0) as usize;
+                let dt = (w[1] - w[0]) / n_steps as f64;
+                for i in 0..n_steps {
+                    state = model.step(&state, w[0] + (i as f64 + 0.5) * dt, dt);
+                }
+                out.push(state.clone());
+            }
+            out
+        })
+        .collect()
+}
+
+fn mean_and_error(samples: &[f64]) -> LsmEstimate {
+    let n = samples.len() as f64;
+    let mean = samples.iter().sum::<f64>() / n;
+    let var = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0).max(1.0);
+    LsmEstimate {
+        value: mean,
+        std_error: (var / n).sqrt(),
+    }
+}
+
+/// Least-squares fit with its diagnostics: `(β, R², residual RMSE)`;
+/// `β` is empty if the system is singular.
+fn least_squares(rows: &[Vec<f64>], y: &[f64]) -> (Vec<f64>, f64, f64) {
+    let Some(beta) = linalg::least_squares(rows, y) else {
+        return (Vec::new(), 0.0, 0.0);
+    };
+    let n = rows.len();
+    let mean_y = y.iter().sum::<f64>() / n as f64;
+    let mut ss_res = 0.0;
+    let mut ss_tot = 0.0;
+    for (row, &yi) in rows.iter().zip(y.iter()) {
+        let fitted: f64 = row.iter().zip(beta.iter()).map(|(x, b)| x * b).sum();
+        ss_res += (yi - fitted).powi(2);
+        ss_tot += (yi - mean_y).powi(2);
+    }
+    let r_squared = if ss_tot > 0.0 {
+        1.0 - ss_res / ss_tot
+    } else {
+        1.0
+    };
+    (beta, r_squared, (ss_res / n as f64).sqrt())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::common::black_scholes::bs_put_forward;
+    use crate::models::common::simulation::{GeometricBrownianMotion, simulate_at_dates};
+    use crate::time::daycounters::actual365fixed::Actual365Fixed;
+    use chrono::{Days, NaiveDate};
+
+    /// Put on a GBM spot, bank-account numeraire `e^{rt}`.
+    struct BermudanPut {
+        strike: f64,
+        rate: f64,
+        times: Vec<f64>,
+    }
+
+    impl LsmProduct<f64> for BermudanPut {
+        fn basis(&self, _k: usize, s: &f64) -> Vec<f64> {
+            let x = s / self.strike;
+            vec![1.0, x, x * x]
+        }
+        fn exercise_value(&self, _k: usize, s: &f64) -> f64 {
+            self.strike - s
+        }
+        fn numeraire(&self, k: usize, _s: &f64) -> f64 {
+            (self.rate * self.times[k]).exp()
+        }
+    }
+
+    fn setup(n_dates: usize) -> (NaiveDate, Vec<NaiveDate>, BermudanPut) {
+        let val = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let dc = Actual365Fixed::default();
+        let dates: Vec<NaiveDate> = (1..=n_dates)
+            .map(|k| val + Days::new((365 * k / n_dates) as u64))
+            .collect();
+        let times = dates
+            .iter()
+            .map(|d| dc.year_fraction(val, *d).unwrap())
+            .collect();
+        let put = BermudanPut {
+            strike: 40.0,
+            rate: 0.06,
+            times,
+        };
+        (val, dates, put)
+    }
+
+    /// With a single exercise date the product is European: the lower
+    /// bound matches Black–Scholes and the dual bound collapses onto it.
+    #[test]
+    fn single_exercise_date_is_european() -> Result<()> {
+        let (val, dates, put) = setup(1);
+        let dc = Actual365Fixed::default();
+        let mut gbm = GeometricBrownianMotion::new(36.0, 0.06, 0.2, 11);
+        let training = simulate_at_dates(&mut gbm, val, &dates, 2_000, 30, &dc);
+        let pricing = simulate_at_dates(&mut gbm, val, &dates, 50_000, 30, &dc);
+        let result = LsmEngine::new()
+            .with_upper_bound(AndersenBroadie {
+                n_outer: 500,
+                n_inner: 10,
+                max_step_days: 30,
+            })
+            .price(&mut gbm, &put, &training, &pricing, &dc)?;
+        assert!(result.diagnostics.is_empty());
+        let t = put.times[0];
+        let df = (-0.06 * t).exp();
+        let bs = bs_put_forward(36.0 / df, 40.0, 0.2, t, df);
+        let lb = result.lower_bound;
+        assert!((lb.value - bs).abs() < 4.0 * lb.std_error, "{lb:?} vs {bs}");
+        let ub = result.upper_bound.unwrap();
+        assert!((ub.value - lb.value).abs() < 1e-12);
+        Ok(())
+    }
+
+    /// Longstaff–Schwartz (2001) Table 1, first row: S = 36, K = 40,
+    /// r = 6 %, σ = 20 %, T = 1, 50 exercise dates per year. Finite
+    /// differences give 4.478; the European value is 3.844.
+    #[test]
+    fn american_put_matches_longstaff_schwartz_table() -> Result<()> {
+        let (val, dates, put) = setup(50);
+        let dc = Actual365Fixed::default();
+        let mut gbm = GeometricBrownianMotion::new(36.0, 0.06, 0.2, 2001);
+        let training = simulate_at_dates(&mut gbm, val, &dates, 20_000, 7, &dc);
+        let pricing = simulate_at_dates(&mut gbm, val, &dates, 20_000, 7, &dc);
+        let result = LsmEngine::new().price(&mut gbm, &put, &training, &pricing, &dc)?;
+        let lb = result.lower_bound;
+        assert!(
+            (lb.value - 4.478).abs() < 4.0 * lb.std_error + 0.02,
+            "LSM {:.4} ± {:.4}",
+            lb.value,
+            lb.std_error
+        );
+        assert_eq!(result.diagnostics.len(), 49);
+        assert!(result.diagnostics.iter().all(|d| d.n_itm > 0));
+        assert!(result.diagnostics[40].r_squared > 0.0);
+        Ok(())
+    }
+
+    /// The dual bound sits above the primal bound, and the gap is small
+    /// for a good exercise rule.
+    #[test]
+    fn andersen_broadie_brackets_bermudan_price() -> Result<()> {
+        let (val, dates, put) = setup(10);
+        let dc = Actual365Fixed::default();
+        let mut gbm = GeometricBrownianMotion::new(36.0, 0.06, 0.2, 7);
+        let training = simulate_at_dates(&mut gbm, val, &dates, 20_000, 7, &dc);
+        let pricing = simulate_at_dates(&mut gbm, val, &dates, 20_000, 7, &dc);
+        let result = LsmEngine::new()
+            .with_upper_bound(AndersenBroadie {
+                n_outer: 300,
+                n_inner: 300,
+                max_step_days: 7,
+            })
+            .price(&mut gbm, &put, &training, &pricing, &dc)?;
+        let lb = result.lower_bound;
+        let ub = result.upper_bound.unwrap();
+        assert!(ub.value > lb.value - 2.0 * ub.std_error, "{lb:?} {ub:?}");
+        assert!(ub.value - lb.value < 0.05, "{lb:?} {ub:?}");
+        Ok(())
+    }
+
+    /// Pure cash-flow stream with nothing to exercise: the price is the
+    /// sum of deflated expected flows.
+    #[test]
+    fn running_cashflows_are_accumulated() {
+        struct Coupons;
+        impl LsmProduct<f64> for Coupons {
+            fn basis(&self, _k: usize, s: &f64) -> Vec<f64> {
+                vec![1.0, *s]
+            }
+            fn exercise_value(&self, _k: usize, _s: &f64) -> f64 {
+                0.0
+            }
+            fn cashflow(&self, k: usize, _s: &f64) -> f64 {
+                k as f64 + 1.0
+            }
+            fn numeraire(&self, _k: usize, _s: &f64) -> f64 {
+                2.0
+            }
+        }
+        let (val, dates, _) = setup(4);
+        let dc = Actual365Fixed::default();
+        let mut gbm = GeometricBrownianMotion::new(36.0, 0.0, 0.2, 3);
+        let paths = simulate_at_dates(&mut gbm, val, &dates, 100, 30, &dc);
+        let (rule, diagnostics) = fit_exercise_rule(&Coupons, &paths);
+        assert!(diagnostics.iter().all(|d| d.n_itm == 0));
+        let lb = lower_bound(&Coupons, &rule, &paths);
+        assert!((lb.value - 5.0).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,223 @@
+//! Static-arbitrage checks and repair for implied-vol smiles, shared by
+//! the FX and IR vol surfaces.
+//!
+//! Two violations are reported, each at the strike and expiry where it
+//! shows:
+//!
+//! ```text
+//!   butterfly   ∂²C/∂K² < 0      (undiscounted calls, call-spread convexity)
+//!   calendar    w(T₂) < w(T₁)    for T₁ < T₂, total variance w = σ²·T
+//! ```
+//!
+//! Calls are priced from the vols in whatever [`VolQuoteType`] the surface
+//! uses, so the same checks cover Black and Bachelier smiles. Butterflies
+//! are tested on consecutive strike triples through the chord excess
+//! `C(K₂) − chord(K₁, K₃)(K₂)`; the reported size is the negative density
+//! `−2·(s₂₃ − s₁₂) / (K₃ − K₁)` from the two call-spread slopes.
+//!
+//! The repair lowers each call onto the greatest convex minorant of the
+//! quoted calls — the smallest change that restores convexity, which never
+//! drops a price below intrinsic since intrinsic is itself convex — and
+//! raises each vol to the earlier pillar's total variance where the
+//! calendar is violated.
+
+use crate::error::{Error, Result};
+use crate::models::common::vol_conversion::{VolQuoteType, implied_vol, option_price};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// Chord excess, relative to the call price, tolerated as rounding.
+const BUTTERFLY_TOLERANCE: f64 = 1.0e-10;
+
+/// Total-variance decrease, relative to the earlier variance, tolerated as
+/// rounding.
+const CALENDAR_TOLERANCE: f64 = 1.0e-12;
+
+#[derive(Deserialize, Serialize, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ArbitrageKind {
+    /// Negative risk-neutral density.
+    Butterfly,
+    /// Total variance falling with expiry.
+    Calendar,
+}
+
+/// One static-arbitrage violation.
+#[derive(Deserialize, Serialize, Copy, Clone, Debug, PartialEq)]
+pub struct ArbitrageViolation {
+    pub kind: ArbitrageKind,
+    pub expiry: NaiveDate,
+    pub strike: f64,
+    /// Negative density for butterflies; total-variance decrease for
+    /// calendars. Always positive.
+    pub size: f64,
+}
+
+/// All violations found on a surface.
+#[derive(Deserialize, Serialize, Clone, Debug, Default, PartialEq)]
+pub struct ArbitrageReport {
+    pub violations: Vec<ArbitrageViolation>,
+}
+
+impl ArbitrageReport {
+    pub fn is_arbitrage_free(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    pub fn count(&self, kind: ArbitrageKind) -> usize {
+        self.violations.iter().filter(|v| v.kind == kind).count()
+    }
+}
+
+/// Undiscounted call prices at `(strike, vol)` nodes.
+fn call_prices(
+    nodes: &[(f64, f64)],
+    forward: f64,
+    t: f64,
+    quote: VolQuoteType,
+) -> Result<Vec<f64>> {
+    nodes
+        .iter()
+        .map(|&(k, sigma)| {
+            option_price(sigma, quote, forward, k, t, true).ok_or_else(|| {
+                Error::InvalidData(format!(
+                    "strike {k} cannot be quoted as {quote:?} at forward {forward}"
+                ))
+            })
+        })
+        .collect()
+}
+
+/// Butterfly violations on a smile given as `(strike, vol)` nodes sorted
+/// by strike.
+pub fn butterfly_violations(
+    expiry: NaiveDate,
+    nodes: &[(f64, f64)],
+    forward: f64,
+    t: f64,
+    quote: VolQuoteType,
+) -> Result<Vec<ArbitrageViolation>> {
+    let prices = call_prices(nodes, forward, t, quote)?;
+    let mut violations = Vec::new();
+    for j in 1..nodes.len().saturating_sub(1) {
+        let (k0, k1, k2) = (nodes[j - 1].0, nodes[j].0, nodes[j + 1].0);
+        let (c0, c1, c2) = (prices[j - 1], prices[j], prices[j + 1]);
+        let chord = c0 + (c2 - c0) * (k1 - k0) / (k2 - k0);
+        if c1 - chord > BUTTERFLY_TOLERANCE * c1.abs().max(f64::MIN_POSITIVE) {
+            let density = 2.0 * ((c2 - c1) / (k2 - k1) - (c1 - c0) / (k1 - k0)) / (k2 - k0);
+            violations.push(ArbitrageViolation {
+                kind: ArbitrageKind::Butterfly,
+                expiry,
+                strike: k1,
+                size: -density,
+            });
+        }
+    }
+    Ok(violations)
+}
+
+/// Calendar violation at `strike` when the later total variance
+/// `w_later` falls below the earlier `w_earlier`.
+pub fn calendar_violation(
+    expiry: NaiveDate,
+    strike: f64,
+    w_earlier: f64,
+    w_later: f64,
+) -> Option<ArbitrageViolation> {
+    (w_earlier - w_later > CALENDAR_TOLERANCE * w_earlier).then_some(ArbitrageViolation {
+        kind: ArbitrageKind::Calendar,
+        expiry,
+        strike,
+        size: w_earlier - w_later,
+    })
+}
+
+/// Vols of `nodes` after lowering their calls onto the greatest convex
+/// minorant; nodes already on it keep their vol.
+pub fn convexify(
+    nodes: &[(f64, f64)],
+    forward: f64,
+    t: f64,
+    quote: VolQuoteType,
+) -> Result<Vec<(f64, f64)>> {
+    let prices = call_prices(nodes, forward, t, quote)?;
+    // Lower hull by Andrew's monotone chain over strike-sorted points.
+    let mut hull: Vec<(f64, f64)> = Vec::with_capacity(nodes.len());
+    for (&(k, _), &c) in nodes.iter().zip(prices.iter()) {
+        while hull.len() >= 2 {
+            let (ka, ca) = hull[hull.len() - 2];
+            let (kb, cb) = hull[hull.len() - 1];
+            if (cb - ca) * (k - ka) >= (c - ca) * (kb - ka) {
+                hull.pop();
+            } else {
+                break;
+            }
+        }
+        hull.push((k, c));
+    }
+    nodes
+        .iter()
+        .zip(prices.iter())
+        .map(|(&(k, sigma), &c)| {
+            let i = hull.partition_point(|h| h.0 < k);
+            if hull[i].0 == k {
+                return Ok((k, sigma));
+            }
+            let ((ka, ca), (kb, cb)) = (hull[i - 1], hull[i]);
+            let minorant = ca + (cb - ca) * (k - ka) / (kb - ka);
+            if c - minorant <= BUTTERFLY_TOLERANCE * c.abs().max(f64::MIN_POSITIVE) {
+                return Ok((k, sigma));
+            }
+            let repaired = implied_vol(minorant, quote, forward, k, t, true).ok_or_else(|| {
+                Error::InvalidData(format!(
+                    "no {quote:?} vol reprices the convex call {minorant} at strike {k}"
+                ))
+            })?;
+            Ok((k, repaired))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ArbitrageKind, butterfly_violations, calendar_violation, convexify};
+    use crate::error::Result;
+    use crate::models::common::vol_conversion::VolQuoteType;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn convexify_removes_a_vol_spike_and_keeps_clean_nodes() -> Result<()> {
+        let expiry = NaiveDate::from_ymd_opt(2027, 4, 21).unwrap();
+        let (f, t) = (1.10, 1.0);
+        let mut nodes = vec![
+            (0.95, 0.11),
+            (1.00, 0.10),
+            (1.05, 0.095),
+            (1.10, 0.09),
+            (1.15, 0.092),
+            (1.20, 0.097),
+        ];
+        let quote = VolQuoteType::Lognormal;
+        assert!(butterfly_violations(expiry, &nodes, f, t, quote)?.is_empty());
+
+        nodes[3].1 = 0.16;
+        let violations = butterfly_violations(expiry, &nodes, f, t, quote)?;
+        assert!(!violations.is_empty());
+        assert!(
+            violations
+                .iter()
+                .all(|v| v.kind == ArbitrageKind::Butterfly)
+        );
+        assert!(violations.iter().any(|v| v.strike == 1.10 && v.size > 0.0));
+
+        let repaired = convexify(&nodes, f, t, quote)?;
+        assert!(butterfly_violations(expiry, &repaired, f, t, quote)?.is_empty());
+        assert!(repaired[3].1 < 0.16);
+        // Nodes on the hull are untouched.
+        assert_eq!(repaired[0], nodes[0]);
+        assert_eq!(repaired[5], nodes[5]);
+
+        assert!(calendar_violation(expiry, 1.1, 0.01, 0.009).is_some());
+        assert!(calendar_violation(expiry, 1.1, 0.01, 0.011).is_none());
+        Ok(())
+    }
+}
@@ -0,0 +1,173 @@
+//! Conversion between the three ways rate-option vols are quoted:
+//! normal (Bachelier), lognormal (Black) and shifted-lognormal (Black on
+//! `F + s`, `K + s`).
+//!
+//! Conversions are price-equivalent: the source vol is turned into the
+//! undiscounted out-of-the-money option price and the target vol is
+//! implied back from it, so they are exact up to the implied-vol solver
+//! tolerance. Lognormal quotes need `F, K > 0`; shifted quotes need
+//! `F + s, K + s > 0` — outside that domain the conversion returns
+//! `None`.
+//!
+//! ```text
+//!   Normal              C = Bach(F, K, σ_N² T)
+//!   Lognormal           C = Black(F, K, σ_B, T)
+//!   ShiftedLognormal s  C = Black(F + s, K + s, σ_SLN, T)
+//! ```
+
+use crate::models::common::bachelier::{bachelier_call, bachelier_implied_vol, bachelier_put};
+use crate::models::common::black_scholes::{bs_call_forward, bs_implied_vol_tol, bs_put_forward};
+
+/// Vol quoting convention.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum VolQuoteType {
+    Normal,
+    Lognormal,
+    ShiftedLognormal { shift: f64 },
+}
+
+impl VolQuoteType {
+    /// Displacement applied to forward and strike (zero unless shifted).
+    pub fn shift(&self) -> f64 {
+        match self {
+            VolQuoteType::ShiftedLognormal { shift } => *shift,
+            _ => 0.0,
+        }
+    }
+
+    /// Whether a `(forward, strike)` pair can be quoted in this
+    /// convention.
+    pub fn admits(&self, forward: f64, strike: f64) -> bool {
+        match self {
+            VolQuoteType::Normal => true,
+            _ => forward + self.shift() > 0.0 && strike + self.shift() > 0.0,
+        }
+    }
+}
+
+/// Undiscounted option price implied by `vol` quoted as `quote`.
+/// `None` if the pair is outside the quote's domain.
+pub fn option_price(
+    vol: f64,
+    quote: VolQuoteType,
+    forward: f64,
+    strike: f64,
+    t: f64,
+    is_call: bool,
+) -> Option<f64> {
+    if !quote.admits(forward, strike) {
+        return None;
+    }
+    let price = match quote {
+        VolQuoteType::Normal => {
+            let v = vol * vol * t;
+            if is_call {
+                bachelier_call(forward, strike, v)
+            } else {
+                bachelier_put(forward, strike, v)
+            }
+        }
+        _ => {
+            let s = quote.shift();
+            if is_call {
+                bs_call_forward(forward + s, strike + s, vol, t, 1.0)
+            } else {
+                bs_put_forward(forward + s, strike + s, vol, t, 1.0)
+            }
+        }
+    };
+    Some(price)
+}
+
+/// Vol in convention `quote` reproducing the undiscounted `price`.
+pub fn implied_vol(
+    price: f64,
+    quote: VolQuoteType,
+    forward: f64,
+    strike: f64,
+    t: f64,
+    is_call: bool,
+) -> Option<f64> {
+    if !quote.admits(forward, strike) {
+        return None;
+    }
+    match quote {
+        VolQuoteType::Normal => bachelier_implied_vol(price, forward, strike, t, is_call),
+        _ => {
+            let s = quote.shift();
+            bs_implied_vol_tol(price, forward + s, strike + s, t, 1.0, is_call, 1.0e-14)
+        }
+    }
+}
+
+/// Convert `vol` from convention `from` to convention `to` at
+/// `(forward, strike, t)`. Prices the out-of-the-money side for
+/// numerical stability.
+pub fn convert_vol(
+    vol: f64,
+    from: VolQuoteType,
+    to: VolQuoteType,
+    forward: f64,
+    strike: f64,
+    t: f64,
+) -> Option<f64> {
+    if from == to {
+        return Some(vol);
+    }
+    let is_call = strike >= forward;
+    let price = option_price(vol, from, forward, strike, t, is_call)?;
+    implied_vol(price, to, forward, strike, t, is_call)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Normal → lognormal → shifted → normal is the identity.
+    #[test]
+    fn round_trip_through_all_conventions() {
+        let (f, t) = (0.03, 2.0);
+        let shifted = VolQuoteType::ShiftedLognormal { shift: 0.02 };
+        for &k in &[0.015, 0.03, 0.05] {
+            let normal = 0.0090;
+            let black = convert_vol(
+                normal,
+                VolQuoteType::Normal,
+                VolQuoteType::Lognormal,
+                f,
+                k,
+                t,
+            )
+            .unwrap();
+            let sln = convert_vol(black, VolQuoteType::Lognormal, shifted, f, k, t).unwrap();
+            let back = convert_vol(sln, shifted, VolQuoteType::Normal, f, k, t).unwrap();
+            assert!((back - normal).abs() < 1e-8, "k={k}: {back}");
+            // ATM rule of thumb σ_N ≈ σ_B · F, to O(σ_B² T).
+            if k == f {
+                assert!((black * f / normal - 1.0).abs() < 1e-2);
+                assert!((sln * (f + 0.02) / normal - 1.0).abs() < 1e-2);
+            }
+        }
+    }
+
+    /// Negative rates: only normal and sufficiently shifted quotes exist.
+    #[test]
+    fn negative_rates_need_a_shift() {
+        let (f, k, t) = (-0.002, 0.001, 1.0);
+        assert!(
+            convert_vol(
+                0.006,
+                VolQuoteType::Normal,
+                VolQuoteType::Lognormal,
+                f,
+                k,
+                t
+            )
+            .is_none()
+        );
+        let shifted = VolQuoteType::ShiftedLognormal { shift: 0.01 };
+        let sln = convert_vol(0.006, VolQuoteType::Normal, shifted, f, k, t).unwrap();
+        let back = convert_vol(sln, shifted, VolQuoteType::Normal, f, k, t).unwrap();
+        assert!((back - 0.006).abs() < 1e-8);
+    }
+}
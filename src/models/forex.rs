@@ -2,7 +2,8 @@
 //! Libor-market-model variants of cross-currency Heston), the
 //! time-dependent SABR stack (van der Stoep et al., 2015) including
 //! effective-parameter mappings, calibration and the particle-method
-//! SLV compensator, plus the Dupire local-vol surface that feeds it.
+//! SLV compensator, plus the Dupire local-vol surface that feeds it, and
+//! the Vanna–Volga desk pricer for first-generation exotics.
 //!
 //! Parallels `crate::derivatives::forex` and `crate::markets::forex`.
 
@@ -27,3 +28,4 @@ pub mod sabr_effective;
 pub mod sabr_slv;
 pub mod sabr_time_dependent;
 pub mod sabr_time_dependent_calibrator;
+pub mod vanna_volga;
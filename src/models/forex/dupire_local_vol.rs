@@ -32,7 +32,9 @@
 //! or zero denominators — which indicate calendar / butterfly
 //! arbitrage in the input — are clamped to a small positive floor
 //! rather than producing a NaN; the resulting `σ²_LV` is capped
-//! symmetrically.
+//! symmetrically. Sampling the grid from a surface passed through
+//! [`crate::markets::forex::quotes::volsurface::FXVolSurface::repair_arbitrage`]
+//! avoids them at the surface's check strikes.
 
 use crate::models::common::black_scholes::bs_call_forward;
 
@@ -1346,28 +1346,30 @@ mod test {
         let val = NaiveDate::from_ymd_opt(VALUATION.0, VALUATION.1, VALUATION.2).unwrap();
         let fx_pillars: Vec<FXDeltaVolPillar> = pillars()
             .into_iter()
-            .map(|pi| FXDeltaVolPillar {
-                expiry: pi.expiry,
-                forward: pi.forward,
-                quotes: vec![
-                    FXVolQuote::Atm(pi.atm),
-                    FXVolQuote::Put {
-                        delta: 0.25,
-                        vol: pi.p25,
-                    },
-                    FXVolQuote::Call {
-                        delta: 0.25,
-                        vol: pi.c25,
-                    },
-                    FXVolQuote::Put {
-                        delta: 0.10,
-                        vol: pi.p10,
-                    },
-                    FXVolQuote::Call {
-                        delta: 0.10,
-                        vol: pi.c10,
-                    },
-                ],
+            .map(|pi| {
+                FXDeltaVolPillar::new(
+                    pi.expiry.into(),
+                    pi.forward,
+                    vec![
+                        FXVolQuote::Atm(pi.atm),
+                        FXVolQuote::Put {
+                            delta: 0.25,
+                            vol: pi.p25,
+                        },
+                        FXVolQuote::Call {
+                            delta: 0.25,
+                            vol: pi.c25,
+                        },
+                        FXVolQuote::Put {
+                            delta: 0.10,
+                            vol: pi.p10,
+                        },
+                        FXVolQuote::Call {
+                            delta: 0.10,
+                            vol: pi.c10,
+                        },
+                    ],
+                )
             })
             .collect();
         FXVolSurface::new(val, fx_pillars).expect("EURUSD surface builds")
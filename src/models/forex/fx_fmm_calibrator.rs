@@ -26,7 +26,7 @@
 //! [`crate::models::common::calibration::Calibration`] pattern.
 
 use crate::error::Result;
-use crate::math::optimize::{Minimum, NelderMeadOptions, nelder_mead};
+use crate::math::optimize::{Minimum, NelderMeadOptions, inv_softplus, nelder_mead, softplus};
 use crate::models::common::black_scholes::bs_implied_vol;
 use crate::models::common::calibration::{Calibration, CalibrationReport};
 use crate::models::common::cos_pricer::CosPricer;
@@ -134,15 +134,6 @@ pub fn discount_factor(params: &FxFmmParams, expiry: f64) -> f64 {
     p
 }
 
-fn softplus(x: f64) -> f64 {
-    if x > 35.0 { x } else { (1.0 + x.exp()).ln() }
-}
-
-fn inv_softplus(y: f64) -> f64 {
-    assert!(y > 0.0);
-    if y > 35.0 { y } else { (y.exp() - 1.0).ln() }
-}
-
 fn reify_params(base: &FxFmmParams, kappa_floor: f64, x: &[f64]) -> FxFmmParams {
     let mut out = base.clone();
     out.heston.kappa = kappa_floor + softplus(x[0]);
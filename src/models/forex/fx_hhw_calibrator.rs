@@ -22,7 +22,7 @@
 //! brittle projection steps; `tanh` parameterisation bounds correlation.
 
 use crate::error::Result;
-use crate::math::optimize::{Minimum, NelderMeadOptions, nelder_mead};
+use crate::math::optimize::{Minimum, NelderMeadOptions, inv_softplus, nelder_mead, softplus};
 use crate::models::common::black_scholes::bs_implied_vol;
 use crate::models::common::calibration::{Calibration, CalibrationReport};
 use crate::models::common::cos_pricer::CosPricer;
@@ -193,17 +193,6 @@ fn reify_params_bounded(
     out
 }
 
-fn softplus(x: f64) -> f64 {
-    // log(1 + exp(x)), numerically stable for large x.
-    if x > 35.0 { x } else { (1.0 + x.exp()).ln() }
-}
-
-fn inv_softplus(y: f64) -> f64 {
-    // Inverse of softplus: y = log(1+e^x)  ⇒  x = log(e^y − 1).
-    assert!(y > 0.0);
-    if y > 35.0 { y } else { (y.exp() - 1.0).ln() }
-}
-
 fn reify_params(base: &FxHhwParams, kappa_floor: f64, x: &[f64]) -> FxHhwParams {
     let mut out = *base;
     out.heston.kappa = kappa_floor + softplus(x[0]);
@@ -341,16 +330,6 @@ mod tests {
         }
     }
 
-    /// Softplus round-trip: `softplus(inv_softplus(y)) = y` for `y > 0`.
-    #[test]
-    fn softplus_inverse_roundtrip() {
-        for &y in &[0.001_f64, 0.01, 0.1, 1.0, 10.0, 100.0] {
-            let x = inv_softplus(y);
-            let y_back = softplus(x);
-            assert!((y_back - y).abs() < 1e-10, "y={}: back {}", y, y_back);
-        }
-    }
-
     /// Synthetic round-trip: generate market vols from a known param
     /// set, calibrate from a nearby start, and verify recovery.
     /// Allows 0.5% vol tolerance — Nelder-Mead on a 5-D non-convex
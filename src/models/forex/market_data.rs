@@ -115,10 +115,10 @@ mod tests {
     fn toy_surface() -> (FXVolSurface, NaiveDate) {
         let val = NaiveDate::from_ymd_opt(2026, 4, 22).unwrap();
         let exp = NaiveDate::from_ymd_opt(2027, 4, 22).unwrap();
-        let pillar = FXDeltaVolPillar {
-            expiry: exp,
-            forward: 1.1865,
-            quotes: vec![
+        let pillar = FXDeltaVolPillar::new(
+            exp.into(),
+            1.1865,
+            vec![
                 FXVolQuote::Atm(0.0663),
                 FXVolQuote::Put {
                     delta: 0.25,
@@ -137,7 +137,7 @@ mod tests {
                     vol: 0.082775,
                 },
             ],
-        };
+        );
         let surface = FXVolSurface::new(val, vec![pillar]).expect("surface builds");
         (surface, val)
     }
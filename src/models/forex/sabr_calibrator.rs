@@ -15,7 +15,7 @@
 //! no COS / MC in the inner loop, so calibration is microseconds.
 
 use crate::error::Result;
-use crate::math::optimize::{Minimum, NelderMeadOptions, nelder_mead};
+use crate::math::optimize::{Minimum, NelderMeadOptions, inv_softplus, nelder_mead, softplus};
 use crate::models::common::calibration::{Calibration, CalibrationReport};
 use crate::models::forex::market_data::MarketSmileStrip;
 use crate::models::forex::sabr::{SabrParams, hagan_implied_vol};
@@ -83,15 +83,6 @@ pub fn calibrate(
     }
 }
 
-fn softplus(x: f64) -> f64 {
-    if x > 35.0 { x } else { (1.0 + x.exp()).ln() }
-}
-
-fn inv_softplus(y: f64) -> f64 {
-    assert!(y > 0.0);
-    if y > 35.0 { y } else { (y.exp() - 1.0).ln() }
-}
-
 fn reify(beta: f64, x: &[f64]) -> Option<SabrParams> {
     let alpha = softplus(x[0]);
     let rho = x[1].tanh();
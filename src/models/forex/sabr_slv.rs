@@ -100,7 +100,24 @@ impl TimeDependentSabrSlvSimulator {
     pub fn simulate(&mut self, t_end: f64, n_steps: usize, n_paths: usize) -> Vec<SabrState> {
         assert!(n_steps > 0 && n_paths > 0 && t_end > 0.0);
         let dt = t_end / n_steps as f64;
-        let sqrt_dt = dt.sqrt();
+        let times: Vec<f64> = (1..=n_steps).map(|i| i as f64 * dt).collect();
+        self.simulate_on_grid(&times, n_paths, |_, _| {})
+    }
+
+    /// Simulate `n_paths` paths through the increasing model times
+    /// `times`, passing each step's index and population to `observe`
+    /// before returning the terminal states. Path-dependent payoffs
+    /// (barriers) read the population here, as the particle method has
+    /// no per-path [`SimulationModel`] step.
+    ///
+    /// [`SimulationModel`]: crate::models::common::simulation::SimulationModel
+    pub fn simulate_on_grid(
+        &mut self,
+        times: &[f64],
+        n_paths: usize,
+        mut observe: impl FnMut(usize, &[SabrState]),
+    ) -> Vec<SabrState> {
+        assert!(n_paths > 0 && !times.is_empty() && times[0] > 0.0);
 
         // Initial population.
         let alpha0 = self.params.alpha.at(0.0);
@@ -112,7 +129,10 @@ impl TimeDependentSabrSlvSimulator {
             .collect();
 
         let mut t = 0.0_f64;
-        for _ in 0..n_steps {
+        for (step, &t_next) in times.iter().enumerate() {
+            assert!(t_next > t, "simulation times must increase");
+            let dt = t_next - t;
+            let sqrt_dt = dt.sqrt();
             let t_mid = t + 0.5 * dt;
             let pm = self.params.at(t_mid);
             let sqrt_1mr2 = (1.0 - pm.rho * pm.rho).sqrt();
@@ -157,7 +177,8 @@ impl TimeDependentSabrSlvSimulator {
                     vol: new_vol,
                 };
             }
-            t += dt;
+            t = t_next;
+            observe(step, &states);
         }
         states
     }
@@ -0,0 +1,210 @@
+//! **Vanna–Volga** pricing of first-generation FX exotics: the flat-vol
+//! Black–Scholes value of the exotic plus the smile cost of hedging its
+//! vega, vanna and volga with the three pillar instruments — the 25Δ put,
+//! the ATM (delta-neutral straddle) strike and the 25Δ call.
+//!
+//! ```text
+//!     V_VV = V_BS(σ_atm) + p · Σᵢ xᵢ · (Cᵢ(σᵢ) − Cᵢ(σ_atm))
+//!     Σᵢ xᵢ · (vega, vanna, volga)ᵢ = (vega, vanna, volga)_exotic     at σ_atm
+//! ```
+//!
+//! The pillar vols are read off the [`FxMarketContext`] surface at the
+//! exotic's expiry, with strikes in the pair's delta convention, so they
+//! carry the ATM, `RR = σ_c − σ_p` and `BF = (σ_c + σ_p)/2 − σ_atm` the
+//! smile reprices. The weight `p` is one under
+//! [`VannaVolgaWeighting::Full`] and the exotic's risk-neutral survival
+//! probability under [`VannaVolgaWeighting::SurvivalProbability`], which
+//! fades the smile cost as a knock-out becomes likely.
+//!
+//! Vega, vanna and volga of the exotic and of the instruments are taken by
+//! the same central differences in spot and vol, so the hedge ratios are
+//! consistent whatever formula prices the exotic.
+
+use crate::derivatives::forex::basic::FXUnderlying;
+use crate::error::{Error, Result};
+use crate::markets::forex::market_context::{FxMarketContext, FxMarketShift};
+use crate::markets::termstructures::yieldcurve::InterpolationMethodEnum;
+use crate::math::linalg::least_squares;
+use crate::models::common::black_scholes::{bs_call_forward, bs_put_forward};
+use crate::time::daycounters::DayCounters;
+use crate::time::daycounters::actual365fixed::Actual365Fixed;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// Relative spot bump for vanna.
+const SPOT_BUMP: f64 = 1.0e-3;
+
+/// Absolute vol bump for vega, vanna and volga.
+const VOL_BUMP: f64 = 1.0e-3;
+
+/// Scaling of the smile cost.
+#[derive(Deserialize, Serialize, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum VannaVolgaWeighting {
+    /// The whole smile cost.
+    Full,
+    /// The smile cost times the exotic's survival probability.
+    SurvivalProbability,
+}
+
+/// An exotic Vanna–Volga can price from its flat-vol value.
+pub trait VannaVolgaTarget {
+    fn expiry(&self) -> NaiveDate;
+
+    /// Holder value in the quote currency under `shift`, with every vol
+    /// flat at `sigma`.
+    fn flat_vol_value(
+        &self,
+        market: &FxMarketContext,
+        shift: &FxMarketShift,
+        sigma: f64,
+    ) -> Result<f64>;
+
+    /// Risk-neutral probability of reaching expiry without a knock event
+    /// under flat `sigma`; one for trades without barriers.
+    fn survival_probability(&self, _market: &FxMarketContext, _sigma: f64) -> Result<f64> {
+        Ok(1.0)
+    }
+}
+
+/// Breakdown of a Vanna–Volga price, in the quote currency.
+#[derive(Deserialize, Serialize, Copy, Clone, Debug, PartialEq)]
+pub struct VannaVolgaPrice {
+    /// Flat-vol value at the ATM vol.
+    pub black_scholes: f64,
+    /// Market less flat-vol cost of the hedge.
+    pub smile_cost: f64,
+    /// Weight `p` on the smile cost.
+    pub weight: f64,
+    /// Hedge amounts of the 25Δ put, the ATM call and the 25Δ call.
+    pub hedge: [f64; 3],
+    pub value: f64,
+}
+
+/// Vanna–Volga engine.
+#[derive(Deserialize, Serialize, Copy, Clone, Debug, PartialEq)]
+pub struct VannaVolga {
+    pub weighting: VannaVolgaWeighting,
+    /// Delta of the wing instruments.
+    pub delta: f64,
+}
+
+impl VannaVolga {
+    /// Engine hedging with the 25Δ wings.
+    pub fn new(weighting: VannaVolgaWeighting) -> Self {
+        Self {
+            weighting,
+            delta: 0.25,
+        }
+    }
+
+    pub fn price(
+        &self,
+        target: &impl VannaVolgaTarget,
+        asset: &FXUnderlying,
+        market: &FxMarketContext,
+    ) -> Result<VannaVolgaPrice> {
+        let expiry = target.expiry();
+        let calendar = asset.calendar();
+        let unshifted = FxMarketShift::default();
+        let forward = market
+            .forward_under(
+                expiry,
+                &calendar,
+                asset.forward_points_converter(),
+                &unshifted,
+            )?
+            .ok_or_else(|| {
+                Error::InvalidData(format!("expiry {expiry} outside the forward points range"))
+            })?;
+        let discount = market.discount_d_under(
+            expiry,
+            &InterpolationMethodEnum::StepFunctionForward,
+            &unshifted,
+        )?;
+        let t = Actual365Fixed::default().year_fraction(market.valuation_date, expiry)?;
+        let pillars = market.vol_surface.delta_strikes(
+            expiry,
+            forward,
+            forward * discount / market.spot,
+            asset.delta_convention(t),
+            self.delta,
+        )?;
+        let sigma_atm = pillars[1].1;
+
+        let h = SPOT_BUMP * market.spot;
+        let exotic = |ds: f64, sigma: f64| {
+            let shift = FxMarketShift {
+                spot: ds,
+                ..Default::default()
+            };
+            target.flat_vol_value(market, &shift, sigma)
+        };
+        let exotic_greeks = vega_vanna_volga(&exotic, h, sigma_atm)?;
+
+        // Put, ATM call, call; the forward moves one for one with spot as
+        // it does under a spot shift.
+        let instrument = |i: usize, ds: f64, sigma: f64| -> Result<f64> {
+            let (k, f) = (pillars[i].0, forward + ds);
+            Ok(match i {
+                0 => bs_put_forward(f, k, sigma, t, discount),
+                _ => bs_call_forward(f, k, sigma, t, discount),
+            })
+        };
+        let mut columns = Vec::with_capacity(3);
+        let mut smile_costs = [0.0; 3];
+        for (i, &(_, sigma_market)) in pillars.iter().enumerate() {
+            columns.push(vega_vanna_volga(
+                &|ds, sigma| instrument(i, ds, sigma),
+                h,
+                sigma_atm,
+            )?);
+            smile_costs[i] = instrument(i, 0.0, sigma_market)? - instrument(i, 0.0, sigma_atm)?;
+        }
+        let rows: Vec<Vec<f64>> = (0..3)
+            .map(|g| columns.iter().map(|c| c[g]).collect())
+            .collect();
+        let x = least_squares(&rows, &exotic_greeks).ok_or_else(|| {
+            Error::InvalidData(format!(
+                "Vanna–Volga hedge at {expiry} is singular: pillars {pillars:?}"
+            ))
+        })?;
+        let hedge = [x[0], x[1], x[2]];
+        let smile_cost: f64 = hedge.iter().zip(smile_costs).map(|(x, c)| x * c).sum();
+        let weight = match self.weighting {
+            VannaVolgaWeighting::Full => 1.0,
+            VannaVolgaWeighting::SurvivalProbability => {
+                target.survival_probability(market, sigma_atm)?
+            }
+        };
+        let black_scholes = exotic(0.0, sigma_atm)?;
+        Ok(VannaVolgaPrice {
+            black_scholes,
+            smile_cost,
+            weight,
+            hedge,
+            value: black_scholes + weight * smile_cost,
+        })
+    }
+}
+
+/// `[∂V/∂σ, ∂²V/∂S∂σ, ∂²V/∂σ²]` of `value(ds, σ)` by central differences.
+fn vega_vanna_volga(
+    value: &dyn Fn(f64, f64) -> Result<f64>,
+    h: f64,
+    sigma: f64,
+) -> Result<[f64; 3]> {
+    let v = VOL_BUMP;
+    let (up, mid, down) = (
+        value(0.0, sigma + v)?,
+        value(0.0, sigma)?,
+        value(0.0, sigma - v)?,
+    );
+    let vanna = (value(h, sigma + v)? - value(h, sigma - v)? - value(-h, sigma + v)?
+        + value(-h, sigma - v)?)
+        / (4.0 * h * v);
+    Ok([
+        (up - down) / (2.0 * v),
+        vanna,
+        (up - 2.0 * mid + down) / (v * v),
+    ])
+}
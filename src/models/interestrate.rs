@@ -1,6 +1,19 @@
-//! Interest-rate models — Hull-White today, LMM and short-rate variants
-//! as they land. Parallels `crate::derivatives::interestrate` and
+//! Interest-rate models — one-factor Hull-White, two-factor G2++, the
+//! Cheyette quasi-Gaussian local-vol model and the generalised forward
+//! market model (with caplet / swaption analytics), normal and
+//! shifted-lognormal SABR smile sections, CMS convexity by static
+//! replication, plus the swaption-cube bridge their calibrators consume.
+//! Parallels `crate::derivatives::interestrate` and
 //! `crate::markets::interestrate`.
 
+pub mod cheyette;
+pub mod cheyette_calibrator;
+pub mod cms;
 pub mod fmm;
+pub mod fmm_analytics;
+pub mod fmm_calibrator;
+pub mod g2pp;
+pub mod g2pp_calibrator;
 pub mod hull_white;
+pub mod market_data;
+pub mod sabr;
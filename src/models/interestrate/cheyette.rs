@@ -0,0 +1,572 @@
+//! One-factor quasi-Gaussian (Cheyette) short-rate model with a
+//! displaced-diffusion local volatility.
+//!
+//! ```text
+//!     r(t)  = f^M(0, t) + x(t)
+//!     dx(t) = ( y(t) − κ x(t) ) dt + σ_r(t, x) dW(t),     x(0) = 0
+//!     dy(t) = ( σ_r(t, x)² − 2κ y(t) ) dt,                y(0) = 0
+//!     σ_r(t, x) = λ(t) · max(1 + β(t) x, 0)
+//! ```
+//!
+//! Follows Andersen & Piterbarg (2010) ch. 13. The model is Markovian in
+//! the pair `(x, y)` and bonds are reconstructed in closed form from the
+//! state (AP eq. 13.4):
+//!
+//! ```text
+//!     P(t, T) = P^M(0,T) / P^M(0,t) · exp{ −G(t,T) x − ½ G(t,T)² y }
+//!     G(t, T) = (1 − e^{−κ(T−t)}) / κ
+//! ```
+//!
+//! With `β ≡ 0` the local vol is deterministic, `y` collapses to a
+//! deterministic function of time and the model is one-factor
+//! Hull–White (linear Gaussian). `β > 0` adds a rate-level skew: vol
+//! rises as rates rise, interpolating towards shifted-lognormal
+//! behaviour. As in [`super::g2pp`], the fit to the initial curve is
+//! implicit — every curve-dependent method takes an
+//! [`InitialDiscountCurve`].
+//!
+//! `λ(t)` and `β(t)` are piecewise constant on an expiry grid,
+//! flat-extrapolated beyond the last node; this is the layout
+//! [`super::cheyette_calibrator`] bootstraps against a strip of swaption
+//! smiles.
+//!
+//! European swaptions are priced with the AP §13.1.7 swap-rate
+//! approximation: freezing `y` at its `x = 0` path `ȳ(t)` and linearising
+//! `S(x) ≈ S₀ + S'·x` turns the swap-rate SDE into a displaced diffusion
+//!
+//! ```text
+//!     dS ≈ λ(t) β(t) · ( S + S'/β − S₀ ) dW^A
+//! ```
+//!
+//! with the time-dependent skew replaced by Piterbarg's variance-weighted
+//! average `β̄` and the variance of `x(T₀)` taken as the mean-reverted
+//! `ȳ(T₀)`.
+//!
+//! # Papers
+//!
+//! * **Cheyette, O. (1992)** — *Term Structure Dynamics and Mortgage
+//!   Valuation*, Journal of Fixed Income 1(4): 28–41. Markovian
+//!   representation of HJM with separable volatility.
+//! * **Andersen, L., Piterbarg, V. (2010)** — *Interest Rate Modeling,
+//!   Vol. II: Term Structure Models*, Atlantic Financial Press. Ch. 13:
+//!   quasi-Gaussian models, local-vol specification, swaption
+//!   approximation.
+//! * **Piterbarg, V. (2005)** — *Time to Smile*, Risk May 2005: 71–75.
+//!   Parameter averaging for time-dependent skew.
+
+use crate::models::common::bachelier::{bachelier_call, bachelier_put};
+use crate::models::common::black_scholes::{bs_call_forward, bs_put_forward};
+use crate::models::common::simulation::SimulationModel;
+use crate::models::interestrate::fmm::InitialDiscountCurve;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use rand_distr::StandardNormal;
+
+/// Sub-steps per unit time used by [`Cheyette::effective_skew`] to
+/// integrate the Piterbarg weights. The integrand is piecewise smooth
+/// with kinks only at grid nodes, so a modest midpoint rule is exact to
+/// well below calibration noise.
+const SKEW_AVERAGING_STEPS_PER_YEAR: f64 = 200.0;
+
+/// Below this `|β̄|` the displaced-diffusion price is replaced by its
+/// Bachelier limit to avoid the `S'/β̄` blow-up.
+const GAUSSIAN_SKEW_THRESHOLD: f64 = 1.0e-6;
+
+/// Cheyette parameters: mean reversion `κ` plus piecewise-constant
+/// local-vol level `λ(t)` and skew `β(t)`.
+///
+/// `lambdas[i]`, `betas[i]` apply on `(times[i−1], times[i]]` (with
+/// `times[−1] = 0`) and beyond `times.last()`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Cheyette {
+    /// Mean-reversion speed `κ ≥ 0`.
+    pub kappa: f64,
+    /// Right end-points of the piecewise-constant buckets, strictly
+    /// increasing.
+    pub times: Vec<f64>,
+    /// Vol level `λ_i ≥ 0` (normal-vol units: `0.01` ≈ 100bp / y at `x = 0`).
+    pub lambdas: Vec<f64>,
+    /// Skew `β_i ≥ 0`. `0` is Gaussian; larger values steepen the smile
+    /// towards higher strikes.
+    pub betas: Vec<f64>,
+}
+
+impl Cheyette {
+    pub fn new(kappa: f64, times: Vec<f64>, lambdas: Vec<f64>, betas: Vec<f64>) -> Self {
+        assert!(kappa >= 0.0, "Cheyette: κ must be non-negative");
+        assert!(!times.is_empty(), "Cheyette: need at least one bucket");
+        assert_eq!(
+            times.len(),
+            lambdas.len(),
+            "times / lambdas length mismatch"
+        );
+        assert_eq!(times.len(), betas.len(), "times / betas length mismatch");
+        assert!(times[0] > 0.0, "Cheyette: bucket times must be positive");
+        assert!(
+            times.windows(2).all(|w| w[1] > w[0]),
+            "Cheyette: bucket times must be strictly increasing"
+        );
+        assert!(lambdas.iter().all(|&l| l >= 0.0), "Cheyette: λ must be ≥ 0");
+        assert!(betas.iter().all(|&b| b >= 0.0), "Cheyette: β must be ≥ 0");
+        Self {
+            kappa,
+            times,
+            lambdas,
+            betas,
+        }
+    }
+
+    /// Time-homogeneous parameter set — a single bucket.
+    pub fn constant(kappa: f64, lambda: f64, beta: f64) -> Self {
+        Self::new(kappa, vec![1.0], vec![lambda], vec![beta])
+    }
+
+    fn bucket(&self, t: f64) -> usize {
+        self.times
+            .iter()
+            .position(|&ti| t <= ti)
+            .unwrap_or(self.times.len() - 1)
+    }
+
+    /// `λ(t)`.
+    pub fn lambda_at(&self, t: f64) -> f64 {
+        self.lambdas[self.bucket(t)]
+    }
+
+    /// `β(t)`.
+    pub fn beta_at(&self, t: f64) -> f64 {
+        self.betas[self.bucket(t)]
+    }
+
+    /// Local volatility `σ_r(t, x) = λ(t) · max(1 + β(t) x, 0)`.
+    pub fn local_vol(&self, t: f64, x: f64) -> f64 {
+        let i = self.bucket(t);
+        self.lambdas[i] * (1.0 + self.betas[i] * x).max(0.0)
+    }
+
+    /// Duration factor `G(t, T) = (1 − e^{−κ(T−t)}) / κ`, with the
+    /// `κ → 0` limit `T − t`.
+    pub fn big_g(&self, t: f64, big_t: f64) -> f64 {
+        let tau = big_t - t;
+        if self.kappa.abs() < 1.0e-12 {
+            return tau;
+        }
+        (1.0 - (-self.kappa * tau).exp()) / self.kappa
+    }
+
+    /// Zero-coupon bond `P(t, T)` from the Markov state — AP eq. (13.4).
+    /// Reproduces `P^M(0, T)` at `t = 0, x = y = 0`.
+    pub fn discount_factor<C: InitialDiscountCurve>(
+        &self,
+        t: f64,
+        big_t: f64,
+        x: f64,
+        y: f64,
+        curve: &C,
+    ) -> f64 {
+        let g = self.big_g(t, big_t);
+        curve.p0_fwd(t, big_t) * (-g * x - 0.5 * g * g * y).exp()
+    }
+
+    /// Short rate `r(t) = f^M(0, t) + x(t)`. The market instantaneous
+    /// forward is a central difference of `−ln P(0, ·)` with a one-day
+    /// bump.
+    pub fn short_rate<C: InitialDiscountCurve>(
+        &self,
+        state: &CheyetteState,
+        t: f64,
+        curve: &C,
+    ) -> f64 {
+        let h = 1.0 / 365.0;
+        let lo = (t - h).max(0.0);
+        let hi = t + h;
+        let f0t = -(curve.p0(hi).ln() - curve.p0(lo).ln()) / (hi - lo);
+        f0t + state.x
+    }
+
+    /// Money-market account `B(t) = exp ∫₀ᵗ r(u) du = exp(∫₀ᵗ x) / P^M(0, t)`.
+    pub fn bank_account<C: InitialDiscountCurve>(
+        &self,
+        state: &CheyetteState,
+        t: f64,
+        curve: &C,
+    ) -> f64 {
+        state.integral.exp() / curve.p0(t)
+    }
+
+    /// `ȳ(t)` — the `y` path along `x ≡ 0`,
+    /// `∫₀ᵗ e^{−2κ(t−u)} λ(u)² du`, integrated exactly bucket by bucket.
+    pub fn y_bar(&self, t: f64) -> f64 {
+        let mut y = 0.0_f64;
+        let mut prev = 0.0_f64;
+        for (i, &ti) in self.times.iter().enumerate() {
+            let end = if i == self.times.len() - 1 {
+                t
+            } else {
+                ti.min(t)
+            };
+            if end <= prev {
+                break;
+            }
+            y = self.y_bar_step(y, self.lambdas[i], end - prev);
+            prev = end;
+        }
+        y
+    }
+
+    fn y_bar_step(&self, y: f64, lambda: f64, h: f64) -> f64 {
+        let l2 = lambda * lambda;
+        if self.kappa.abs() < 1.0e-12 {
+            return y + l2 * h;
+        }
+        let decay = (-2.0 * self.kappa * h).exp();
+        y * decay + l2 * (1.0 - decay) / (2.0 * self.kappa)
+    }
+
+    /// Integrated squared vol level `∫₀ᵀ λ(u)² du`.
+    pub fn integrated_lambda_sq(&self, big_t: f64) -> f64 {
+        let mut acc = 0.0_f64;
+        let mut prev = 0.0_f64;
+        for (i, &ti) in self.times.iter().enumerate() {
+            let end = if i == self.times.len() - 1 {
+                big_t
+            } else {
+                ti.min(big_t)
+            };
+            if end <= prev {
+                break;
+            }
+            acc += self.lambdas[i] * self.lambdas[i] * (end - prev);
+            prev = end;
+        }
+        acc
+    }
+
+    /// Piterbarg's effective skew on `[0, T]`:
+    ///
+    /// ```text
+    ///     β̄ = ∫₀ᵀ β(t) w(t) dt,   w(t) ∝ λ(t)² ∫₀ᵗ λ(u)² du
+    /// ```
+    ///
+    /// Returns `β(T)` when the weights vanish (`λ ≡ 0`).
+    pub fn effective_skew(&self, big_t: f64) -> f64 {
+        let n = ((big_t * SKEW_AVERAGING_STEPS_PER_YEAR).ceil() as usize).max(20);
+        let h = big_t / n as f64;
+        let mut v = 0.0_f64;
+        let mut num = 0.0_f64;
+        let mut den = 0.0_f64;
+        for k in 0..n {
+            let t_mid = (k as f64 + 0.5) * h;
+            let l2 = self.lambda_at(t_mid).powi(2);
+            let w = l2 * (v + 0.5 * l2 * h);
+            num += self.beta_at(t_mid) * w;
+            den += w;
+            v += l2 * h;
+        }
+        if den > 0.0 {
+            num / den
+        } else {
+            self.beta_at(big_t)
+        }
+    }
+
+    /// Swap rate `S(t, x, y) = (P(t,T₀) − P(t,T_n)) / Σ τ_i P(t,T_i)` for
+    /// the given fixed-leg schedule; the start `T₀ = T₁ − τ₁`.
+    pub fn swap_rate<C: InitialDiscountCurve>(
+        &self,
+        t: f64,
+        x: f64,
+        y: f64,
+        payment_times: &[f64],
+        accruals: &[f64],
+        curve: &C,
+    ) -> f64 {
+        let annuity: f64 = payment_times
+            .iter()
+            .zip(accruals.iter())
+            .map(|(&ti, &tau)| tau * self.discount_factor(t, ti, x, y, curve))
+            .sum();
+        let start = payment_times[0] - accruals[0];
+        let p_start = self.discount_factor(t, start, x, y, curve);
+        let p_end = self.discount_factor(t, *payment_times.last().unwrap(), x, y, curve);
+        (p_start - p_end) / annuity
+    }
+
+    /// European swaption per unit notional, exercisable at `expiry` into
+    /// a swap paying `fixed_rate · accruals[i]` at `payment_times[i]`,
+    /// under the displaced-diffusion swap-rate approximation (see module
+    /// docs). `is_payer = true` prices the right to pay fixed.
+    pub fn swaption<C: InitialDiscountCurve>(
+        &self,
+        expiry: f64,
+        payment_times: &[f64],
+        accruals: &[f64],
+        fixed_rate: f64,
+        is_payer: bool,
+        curve: &C,
+    ) -> f64 {
+        assert!(expiry > 0.0, "swaption expiry must be positive");
+        assert_eq!(
+            payment_times.len(),
+            accruals.len(),
+            "payment_times and accruals must have equal length"
+        );
+        assert!(!payment_times.is_empty(), "need at least one payment");
+
+        let annuity: f64 = payment_times
+            .iter()
+            .zip(accruals.iter())
+            .map(|(&ti, &tau)| tau * curve.p0(ti))
+            .sum();
+        let start = payment_times[0] - accruals[0];
+        let s0 = (curve.p0(start) - curve.p0(*payment_times.last().unwrap())) / annuity;
+
+        // dS/dx at (T₀, x = 0, ȳ(T₀)) by central difference.
+        let y = self.y_bar(expiry);
+        let h = 1.0e-5;
+        let s_up = self.swap_rate(expiry, h, y, payment_times, accruals, curve);
+        let s_dn = self.swap_rate(expiry, -h, y, payment_times, accruals, curve);
+        let ds_dx = (s_up - s_dn) / (2.0 * h);
+
+        // Var[x(T₀)] under the frozen-y dynamics is the mean-reverted
+        // `∫₀ᵀ e^{−2κ(T−u)} λ(u)² du = ȳ(T₀)`.
+        let var_x = y;
+        let beta = self.effective_skew(expiry);
+
+        if beta < GAUSSIAN_SKEW_THRESHOLD {
+            let variance = ds_dx * ds_dx * var_x;
+            return annuity
+                * if is_payer {
+                    bachelier_call(s0, fixed_rate, variance)
+                } else {
+                    bachelier_put(s0, fixed_rate, variance)
+                };
+        }
+        let shift = ds_dx / beta - s0;
+        let forward = s0 + shift;
+        let strike = fixed_rate + shift;
+        if strike <= 0.0 {
+            // The shifted rate is strictly positive, so the option is
+            // always in the money.
+            return if is_payer {
+                annuity * (forward - strike)
+            } else {
+                0.0
+            };
+        }
+        let sigma = beta * (var_x / expiry).sqrt();
+        if is_payer {
+            bs_call_forward(forward, strike, sigma, expiry, annuity)
+        } else {
+            bs_put_forward(forward, strike, sigma, expiry, annuity)
+        }
+    }
+}
+
+/// Per-path Cheyette state: the Markov pair `(x, y)` and the running
+/// integral `∫₀ᵗ x(u) du` needed for the bank account.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct CheyetteState {
+    pub x: f64,
+    pub y: f64,
+    pub integral: f64,
+}
+
+/// Euler simulator for the Cheyette state. `x` takes a plain Euler
+/// step; `y` — a deterministic ODE given the path — uses the exact
+/// solution for frozen `σ_r` over the step, which keeps it non-negative
+/// for any `dt`. The `x` integral uses the trapezoid rule.
+pub struct CheyetteSimulator {
+    pub model: Cheyette,
+    rng: ChaCha20Rng,
+}
+
+impl CheyetteSimulator {
+    pub fn new(model: Cheyette, seed: u64) -> Self {
+        Self {
+            model,
+            rng: ChaCha20Rng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl SimulationModel for CheyetteSimulator {
+    type State = CheyetteState;
+
+    fn initial_state(&self) -> Self::State {
+        CheyetteState::default()
+    }
+
+    fn step(&mut self, state: &Self::State, t: f64, dt: f64) -> Self::State {
+        let m = &self.model;
+        let vol = m.local_vol(t, state.x);
+        let z: f64 = self.rng.sample(StandardNormal);
+        let x = state.x + (state.y - m.kappa * state.x) * dt + vol * dt.sqrt() * z;
+        let y = m.y_bar_step(state.y, vol, dt);
+        let integral = state.integral + 0.5 * (state.x + x) * dt;
+        CheyetteState { x, y, integral }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::common::bachelier::bachelier_implied_vol;
+    use crate::models::common::simulation::simulate_at_dates;
+    use crate::models::interestrate::fmm::FlatCurve;
+    use crate::time::daycounters::DayCounters;
+    use crate::time::daycounters::actual365fixed::Actual365Fixed;
+    use chrono::NaiveDate;
+
+    /// Upward-sloping curve `P(0, T) = exp(−(2% + 0.2%·T)·T)`.
+    struct SlopedCurve;
+    impl InitialDiscountCurve for SlopedCurve {
+        fn p0(&self, big_t: f64) -> f64 {
+            (-(0.02 + 0.002 * big_t) * big_t).exp()
+        }
+    }
+
+    fn skewed() -> Cheyette {
+        Cheyette::new(0.05, vec![1.0, 3.0], vec![0.010, 0.008], vec![4.0, 8.0])
+    }
+
+    /// `P(0, T)` from the state reproduces the market curve exactly.
+    #[test]
+    fn discount_factor_reproduces_curve_at_time_zero() {
+        let m = skewed();
+        let curve = SlopedCurve;
+        for &t in &[0.5, 2.0, 10.0, 30.0] {
+            assert!((m.discount_factor(0.0, t, 0.0, 0.0, &curve) - curve.p0(t)).abs() < 1e-15);
+        }
+    }
+
+    /// Piecewise lookup, flat extrapolation and the local-vol floor.
+    #[test]
+    fn piecewise_parameters_and_local_vol() {
+        let m = skewed();
+        assert_eq!(m.lambda_at(0.5), 0.010);
+        assert_eq!(m.lambda_at(1.0), 0.010);
+        assert_eq!(m.lambda_at(2.0), 0.008);
+        assert_eq!(m.beta_at(10.0), 8.0);
+        assert!((m.local_vol(0.5, 0.01) - 0.010 * 1.04).abs() < 1e-15);
+        assert_eq!(m.local_vol(0.5, -1.0), 0.0);
+    }
+
+    /// `ȳ` matches the HW closed form `λ²(1 − e^{−2κt})/(2κ)` for a
+    /// constant vol, and the skew average of a constant `β` is `β`.
+    #[test]
+    fn y_bar_and_effective_skew_for_constant_parameters() {
+        let m = Cheyette::constant(0.05, 0.01, 3.0);
+        let t = 4.0_f64;
+        let expected = 0.01_f64.powi(2) * (1.0 - (-2.0 * 0.05 * t).exp()) / (2.0 * 0.05);
+        assert!((m.y_bar(t) - expected).abs() < 1e-16);
+        assert!((m.effective_skew(t) - 3.0).abs() < 1e-12);
+        let piecewise = skewed().effective_skew(5.0);
+        assert!(piecewise > 4.0 && piecewise < 8.0);
+    }
+
+    /// Payer − receiver = forward-starting payer swap value.
+    #[test]
+    fn swaption_parity() {
+        let curve = SlopedCurve;
+        let times = [3.0, 4.0, 5.0, 6.0, 7.0];
+        let taus = [1.0; 5];
+        let annuity: f64 = times.iter().map(|&t| curve.p0(t)).sum();
+        for m in [skewed(), Cheyette::constant(0.05, 0.01, 0.0)] {
+            for &k in &[0.02, 0.035, 0.05] {
+                let pay = m.swaption(2.0, &times, &taus, k, true, &curve);
+                let rec = m.swaption(2.0, &times, &taus, k, false, &curve);
+                let swap = curve.p0(2.0) - curve.p0(7.0) - k * annuity;
+                assert!(
+                    (pay - rec - swap).abs() < 1e-12,
+                    "{} vs {}",
+                    pay - rec,
+                    swap
+                );
+            }
+        }
+    }
+
+    /// Positive skew produces a normal-vol smile increasing in strike.
+    #[test]
+    fn skew_tilts_normal_vol_smile_upwards() {
+        let m = Cheyette::constant(0.05, 0.01, 10.0);
+        let curve = FlatCurve { rate: 0.03 };
+        let times: Vec<f64> = (1..=5).map(|i| 2.0 + i as f64).collect();
+        let taus = [1.0; 5];
+        let annuity: f64 = times.iter().map(|&t| curve.p0(t)).sum();
+        let s0 = (curve.p0(2.0) - curve.p0(7.0)) / annuity;
+        let vols: Vec<f64> = [-0.01, 0.0, 0.01]
+            .iter()
+            .map(|&d| {
+                let p = m.swaption(2.0, &times, &taus, s0 + d, true, &curve);
+                bachelier_implied_vol(p / annuity, s0, s0 + d, 2.0, true).unwrap()
+            })
+            .collect();
+        assert!(vols[0] < vols[1] && vols[1] < vols[2], "{:?}", vols);
+    }
+
+    /// Monte Carlo under Q reprices the initial curve and agrees with the
+    /// swaption approximation across a skewed smile. The mean reversion is
+    /// strong enough that dropping it from the swap-rate variance would
+    /// miss by several basis points of normal vol.
+    #[test]
+    fn mc_reprices_curve_and_swaption_smile() {
+        let m = Cheyette::constant(0.15, 0.01, 8.0);
+        let curve = FlatCurve { rate: 0.03 };
+        let val = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let expiry = NaiveDate::from_ymd_opt(2027, 1, 1).unwrap();
+        let dc = Actual365Fixed::default();
+        let t_exp = dc.year_fraction(val, expiry).unwrap();
+
+        let mut sim = CheyetteSimulator::new(m.clone(), 2024);
+        let paths = simulate_at_dates(&mut sim, val, &[expiry], 20_000, 7, &dc);
+        let states = paths.states_at(expiry).unwrap();
+
+        let deflators: Vec<f64> = states
+            .iter()
+            .map(|s| 1.0 / m.bank_account(s, t_exp, &curve))
+            .collect();
+        let p_mc = deflators.iter().sum::<f64>() / deflators.len() as f64;
+        assert!(
+            (p_mc / curve.p0(t_exp) - 1.0).abs() < 2e-3,
+            "MC P(0,T) {} vs {}",
+            p_mc,
+            curve.p0(t_exp)
+        );
+
+        let times: Vec<f64> = (1..=5).map(|i| t_exp + i as f64).collect();
+        let taus = [1.0; 5];
+        let annuity: f64 = times.iter().map(|&t| curve.p0(t)).sum();
+        let s0 = (curve.p0(t_exp) - curve.p0(t_exp + 5.0)) / annuity;
+        for &offset in &[-0.01, 0.0, 0.01] {
+            let k = s0 + offset;
+            let payoffs: Vec<f64> = states
+                .iter()
+                .zip(deflators.iter())
+                .map(|(s, d)| {
+                    let bond: f64 = times
+                        .iter()
+                        .enumerate()
+                        .map(|(i, &ti)| {
+                            let c = if i == times.len() - 1 { 1.0 + k } else { k };
+                            c * m.discount_factor(t_exp, ti, s.x, s.y, &curve)
+                        })
+                        .sum();
+                    d * (1.0 - bond).max(0.0)
+                })
+                .collect();
+            let mc = payoffs.iter().sum::<f64>() / payoffs.len() as f64;
+            let approx = m.swaption(t_exp, &times, &taus, k, true, &curve);
+            let iv_mc = bachelier_implied_vol(mc / annuity, s0, k, t_exp, true).unwrap();
+            let iv_approx = bachelier_implied_vol(approx / annuity, s0, k, t_exp, true).unwrap();
+            assert!(
+                (iv_mc - iv_approx).abs() < 2.0e-4,
+                "offset {}: MC vol {:.2}bp vs approx {:.2}bp",
+                offset,
+                iv_mc * 1e4,
+                iv_approx * 1e4
+            );
+        }
+    }
+}
@@ -0,0 +1,538 @@
+//! CMS convexity by static replication over a swaption smile, with the
+//! annuity mapping of a terminal swap-rate (TSR) model.
+//!
+//! A CMS coupon pays the swap rate `S` fixing at `T₀` on a date `T_p`
+//! that is not the swap's natural (annuity) numéraire. With `A` the
+//! annuity of the underlying swap and `α(S)` the **annuity mapping**
+//! `P(T₀, T_p)/A(T₀)` expressed as a function of the terminal swap rate,
+//!
+//! ```text
+//!   E^{T_p}[g(S)] = E^A[α(S) · g(S)] / α₀,    α₀ = P(0, T_p) / A(0) = E^A[α(S)]
+//! ```
+//!
+//! and `E^A` is the measure under which the smile prices swaptions. Any
+//! twice-differentiable `α · g` is replicated with payer / receiver
+//! swaptions; for the CMS caplet `g = (S − K)⁺`,
+//!
+//! ```text
+//!   E^A[α(S)(S − K)⁺] = α(K) · C(K) + ∫_K^∞ [α''(k)(k − K) + 2α'(k)] · C(k) dk
+//!   E^A[α(S)(K − S)⁺] = α(K) · P(K) + ∫_{−∞}^K [α''(k)(K − k) − 2α'(k)] · P(k) dk
+//! ```
+//!
+//! and the CMS rate follows from parity at `K = S₀`. The mapping comes
+//! from a one-factor Gaussian model with mean reversion `κ`, in which
+//! every discount ratio moves with a single state `x`:
+//!
+//! ```text
+//!   P(T₀, t)/P(T₀, T₀) = P(0, t)/P(0, T₀) · exp(−G(t) x),   G(t) = (1 − e^{−κ(t − T₀)})/κ
+//! ```
+//!
+//! [`CmsModel::StaticReplication`] uses that mapping as it stands (shifted
+//! by a constant so `E^A[α] = α₀`); [`CmsModel::LinearTsr`] linearises it
+//! at the forward, `α(S) = α₀ + a(S − S₀)`, which reduces the convexity
+//! adjustment to the smile's variance: `E^{T_p}[S] = S₀ + a · Var^A(S)/α₀`.
+//! Larger `κ` damps long-end moves relative to the short end, steepening
+//! the mapping and raising the adjustment.
+//!
+//! CMS spread options treat the two CMS rates as jointly normal under the
+//! payment measure, each with its convexity-adjusted mean and the normal
+//! vol implied from its own replicated at-the-money CMS caplet, so the
+//! spread is Bachelier with variance `(σ₁² + σ₂² − 2ρσ₁σ₂) T₀`.
+//!
+//! # Papers
+//!
+//! * **Hagan, P. (2003)** — *Convexity Conundrums: Pricing CMS Swaps, Caps,
+//!   and Floors*, Wilmott Magazine, 38–44. Static replication of CMS
+//!   products.
+//! * **Andersen, L., Piterbarg, V. (2010)** — *Interest Rate Modeling*,
+//!   Vol. III, §16.3–16.6. Linear and Gaussian terminal swap-rate models,
+//!   and the Gaussian treatment of CMS spread options.
+
+use roots::{SimpleConvergency, find_root_brent};
+
+use crate::error::{Error, Result};
+use crate::math::interpolation::interpolate_flat;
+use crate::models::common::bachelier::{bachelier_call, bachelier_implied_vol, bachelier_put};
+use crate::models::interestrate::fmm::InitialDiscountCurve;
+use crate::models::interestrate::market_data::SwaptionCube;
+use crate::models::interestrate::sabr::SabrSmileSection;
+
+/// Finite-difference step, in rate units, for derivatives of `α`.
+const MAPPING_STEP: f64 = 5.0e-4;
+/// Simpson intervals per replication integral.
+const REPLICATION_INTERVALS: usize = 400;
+/// Replication integrals run this many ATM standard deviations past the
+/// strike and the forward.
+const REPLICATION_WIDTH: f64 = 10.0;
+
+/// Swaption smile at one expiry and tenor, priced per unit annuity.
+pub trait SwaptionSmile {
+    fn forward(&self) -> f64;
+
+    /// Year fraction to expiry.
+    fn expiry(&self) -> f64;
+
+    /// Undiscounted payer (`is_call`) or receiver price per unit annuity.
+    fn price(&self, strike: f64, is_call: bool) -> f64;
+}
+
+impl SwaptionSmile for SabrSmileSection {
+    fn forward(&self) -> f64 {
+        self.forward
+    }
+
+    fn expiry(&self) -> f64 {
+        self.t
+    }
+
+    fn price(&self, strike: f64, is_call: bool) -> f64 {
+        SabrSmileSection::price(self, strike, is_call)
+    }
+}
+
+/// Smile given as normal vols at absolute strikes, linear between them
+/// and flat outside.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NormalSmile {
+    pub forward: f64,
+    pub expiry: f64,
+    /// `(strike, normal vol)` sorted by strike.
+    pub nodes: Vec<(f64, f64)>,
+}
+
+impl NormalSmile {
+    pub fn normal_vol(&self, strike: f64) -> f64 {
+        interpolate_flat(&self.nodes, strike)
+    }
+
+    /// Same smile with every vol moved by `shift`.
+    pub fn shifted(mut self, shift: f64) -> Self {
+        for node in &mut self.nodes {
+            node.1 += shift;
+        }
+        self
+    }
+}
+
+impl SwaptionSmile for NormalSmile {
+    fn forward(&self) -> f64 {
+        self.forward
+    }
+
+    fn expiry(&self) -> f64 {
+        self.expiry
+    }
+
+    fn price(&self, strike: f64, is_call: bool) -> f64 {
+        let sigma = self.normal_vol(strike);
+        let variance = sigma * sigma * self.expiry;
+        if is_call {
+            bachelier_call(self.forward, strike, variance)
+        } else {
+            bachelier_put(self.forward, strike, variance)
+        }
+    }
+}
+
+/// Annuity mapping used to move from the annuity to the payment measure.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum CmsModel {
+    /// Gaussian mapping linearised at the forward swap rate.
+    LinearTsr { mean_reversion: f64 },
+    /// Full Gaussian mapping replicated across the smile.
+    StaticReplication { mean_reversion: f64 },
+}
+
+impl CmsModel {
+    fn mean_reversion(&self) -> f64 {
+        match self {
+            CmsModel::LinearTsr { mean_reversion }
+            | CmsModel::StaticReplication { mean_reversion } => *mean_reversion,
+        }
+    }
+}
+
+/// Swaption cube and mapping choice for pricing CMS products off an
+/// [`IrMarketContext`](crate::markets::interestrate::market_context::IrMarketContext).
+#[derive(Clone, Debug, PartialEq)]
+pub struct CmsMarket {
+    pub swaptions: SwaptionCube,
+    pub model: CmsModel,
+}
+
+/// One CMS fixing: times are year fractions from valuation, each paired
+/// with its discount factor.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CmsCoupon {
+    /// Fixing time `T₀`.
+    pub expiry: f64,
+    /// Start of the underlying swap.
+    pub start: (f64, f64),
+    /// Fixed-leg payments of the underlying swap, in time order.
+    pub fixed_leg: Vec<(f64, f64)>,
+    /// CMS payment `T_p`.
+    pub payment: (f64, f64),
+}
+
+impl CmsCoupon {
+    /// Coupon on the `tenor`-year swap starting at `expiry`, with
+    /// `fixed_frequency` payments a year, paid at `payment`.
+    pub fn from_curve<C: InitialDiscountCurve>(
+        curve: &C,
+        expiry: f64,
+        tenor: f64,
+        fixed_frequency: u32,
+        payment: f64,
+    ) -> Self {
+        let n = ((tenor * fixed_frequency as f64).round() as usize).max(1);
+        let fixed_leg = (1..=n)
+            .map(|i| {
+                let t = expiry + tenor * i as f64 / n as f64;
+                (t, curve.p0(t))
+            })
+            .collect();
+        Self {
+            expiry,
+            start: (expiry, curve.p0(expiry)),
+            fixed_leg,
+            payment: (payment, curve.p0(payment)),
+        }
+    }
+
+    /// Annuity `A(0) = Σ τ_i P(0, T_i)`.
+    pub fn annuity(&self) -> f64 {
+        let mut previous = self.start.0;
+        self.fixed_leg
+            .iter()
+            .map(|&(t, df)| {
+                let tau = t - previous;
+                previous = t;
+                tau * df
+            })
+            .sum()
+    }
+
+    /// Forward swap rate `S₀ = (P(0, T₀) − P(0, T_n)) / A(0)`.
+    pub fn forward_swap_rate(&self) -> f64 {
+        let end = self.fixed_leg.last().map_or(self.start.1, |p| p.1);
+        (self.start.1 - end) / self.annuity()
+    }
+
+    /// `(S, α)` in the Gaussian model at state `x`.
+    fn gaussian_state(&self, kappa: f64, x: f64) -> (f64, f64) {
+        let (t0, p0) = self.start;
+        let ratio = |t: f64, df: f64| {
+            let g = if kappa.abs() < 1.0e-8 {
+                t - t0
+            } else {
+                (1.0 - (-kappa * (t - t0)).exp()) / kappa
+            };
+            df / p0 * (-g * x).exp()
+        };
+        let mut previous = t0;
+        let mut annuity = 0.0;
+        for &(t, df) in &self.fixed_leg {
+            annuity += (t - previous) * ratio(t, df);
+            previous = t;
+        }
+        let end = self.fixed_leg.last().map_or(1.0, |&(t, df)| ratio(t, df));
+        let (tp, dfp) = self.payment;
+        ((1.0 - end) / annuity, ratio(tp, dfp) / annuity)
+    }
+
+    /// Gaussian annuity mapping at swap rate `s`.
+    fn gaussian_mapping(&self, kappa: f64, s: f64) -> Result<f64> {
+        let bound = 0.5;
+        let mut residual = |x: f64| self.gaussian_state(kappa, x).0 - s;
+        let x = if residual(-bound) >= 0.0 {
+            -bound
+        } else if residual(bound) <= 0.0 {
+            bound
+        } else {
+            let mut convergency = SimpleConvergency {
+                eps: 1e-14_f64,
+                max_iter: 200,
+            };
+            find_root_brent(-bound, bound, &mut residual, &mut convergency).map_err(|e| {
+                Error::InvalidData(format!("no Gaussian state reproduces swap rate {s}: {e:?}"))
+            })?
+        };
+        Ok(self.gaussian_state(kappa, x).1)
+    }
+
+    /// Annuity mapping under `model`, normalised so `E^A[α(S)] = α₀`.
+    fn mapping(
+        &self,
+        smile: &dyn SwaptionSmile,
+        model: CmsModel,
+    ) -> Result<impl Fn(f64) -> Result<f64> + '_> {
+        let kappa = model.mean_reversion();
+        let s0 = self.forward_swap_rate();
+        let alpha0 = self.payment.1 / self.annuity();
+        let (slope, offset) = match model {
+            CmsModel::LinearTsr { .. } => {
+                let (s_up, a_up) = self.gaussian_state(kappa, 1.0e-4);
+                let (s_down, a_down) = self.gaussian_state(kappa, -1.0e-4);
+                ((a_up - a_down) / (s_up - s_down), 0.0)
+            }
+            CmsModel::StaticReplication { .. } => {
+                let exact = |s: f64| self.gaussian_mapping(kappa, s);
+                let (lower, upper) = replication_bounds(smile, s0);
+                let mean = exact(s0)?
+                    + simpson(
+                        |k| Ok(second_derivative(&exact, k)? * smile.price(k, false)),
+                        lower,
+                        s0,
+                    )?
+                    + simpson(
+                        |k| Ok(second_derivative(&exact, k)? * smile.price(k, true)),
+                        s0,
+                        upper,
+                    )?;
+                (0.0, alpha0 - mean)
+            }
+        };
+        let linear = matches!(model, CmsModel::LinearTsr { .. });
+        Ok(move |s: f64| {
+            if linear {
+                Ok(alpha0 + slope * (s - s0))
+            } else {
+                Ok(self.gaussian_mapping(kappa, s)? + offset)
+            }
+        })
+    }
+
+    /// Undiscounted CMS caplet (`is_call`) or floorlet, `E^{T_p}[(S − K)⁺]`
+    /// or `E^{T_p}[(K − S)⁺]`.
+    pub fn option_price(
+        &self,
+        smile: &dyn SwaptionSmile,
+        model: CmsModel,
+        strike: f64,
+        is_call: bool,
+    ) -> Result<f64> {
+        let alpha = self.mapping(smile, model)?;
+        Ok(self.replicate(smile, &alpha, strike, is_call)? * self.annuity() / self.payment.1)
+    }
+
+    fn replicate(
+        &self,
+        smile: &dyn SwaptionSmile,
+        alpha: &dyn Fn(f64) -> Result<f64>,
+        strike: f64,
+        is_call: bool,
+    ) -> Result<f64> {
+        let (lower, upper) = replication_bounds(smile, strike);
+        let at_strike = alpha(strike)? * smile.price(strike, is_call);
+        Ok(if is_call {
+            at_strike
+                + simpson(
+                    |k| {
+                        Ok((second_derivative(alpha, k)? * (k - strike)
+                            + 2.0 * first_derivative(alpha, k)?)
+                            * smile.price(k, true))
+                    },
+                    strike,
+                    upper,
+                )?
+        } else {
+            at_strike
+                + simpson(
+                    |k| {
+                        Ok((second_derivative(alpha, k)? * (strike - k)
+                            - 2.0 * first_derivative(alpha, k)?)
+                            * smile.price(k, false))
+                    },
+                    lower,
+                    strike,
+                )?
+        })
+    }
+
+    /// Convexity-adjusted CMS rate `E^{T_p}[S(T₀)]`.
+    pub fn rate(&self, smile: &dyn SwaptionSmile, model: CmsModel) -> Result<f64> {
+        let alpha = self.mapping(smile, model)?;
+        let s0 = self.forward_swap_rate();
+        let scale = self.annuity() / self.payment.1;
+        Ok(s0
+            + (self.replicate(smile, &alpha, s0, true)?
+                - self.replicate(smile, &alpha, s0, false)?)
+                * scale)
+    }
+
+    /// `E^{T_p}[S(T₀)] − S₀`.
+    pub fn convexity_adjustment(&self, smile: &dyn SwaptionSmile, model: CmsModel) -> Result<f64> {
+        Ok(self.rate(smile, model)? - self.forward_swap_rate())
+    }
+
+    /// CMS rate and the normal vol of its payment-measure distribution,
+    /// implied from the at-the-money CMS caplet.
+    fn marginal(&self, smile: &dyn SwaptionSmile, model: CmsModel) -> Result<(f64, f64)> {
+        let mean = self.rate(smile, model)?;
+        let atm = self.option_price(smile, model, mean, true)?;
+        let sigma = bachelier_implied_vol(atm, mean, mean, self.expiry, true).ok_or_else(|| {
+            Error::InvalidData(format!(
+                "no normal vol reproduces the CMS caplet at {} fixing in {}y",
+                mean, self.expiry
+            ))
+        })?;
+        Ok((mean, sigma))
+    }
+}
+
+/// Undiscounted option on `S_long − S_short` struck at `strike`, paid at
+/// the common payment date, with CMS rates jointly normal at
+/// `correlation`.
+pub fn cms_spread_option(
+    long: (&CmsCoupon, &dyn SwaptionSmile),
+    short: (&CmsCoupon, &dyn SwaptionSmile),
+    model: CmsModel,
+    correlation: f64,
+    strike: f64,
+    is_call: bool,
+) -> Result<f64> {
+    let t = long.0.expiry;
+    if (short.0.expiry - t).abs() > 1.0e-10
+        || (short.0.payment.0 - long.0.payment.0).abs() > 1.0e-10
+    {
+        return Err(Error::InvalidData(
+            "CMS spread legs must share fixing and payment".to_string(),
+        ));
+    }
+    if t <= 0.0 {
+        let spread = long.0.forward_swap_rate() - short.0.forward_swap_rate();
+        let sign = if is_call { 1.0 } else { -1.0 };
+        return Ok((sign * (spread - strike)).max(0.0));
+    }
+    let (m1, s1) = long.0.marginal(long.1, model)?;
+    let (m2, s2) = short.0.marginal(short.1, model)?;
+    let variance = (s1 * s1 + s2 * s2 - 2.0 * correlation * s1 * s2).max(0.0) * t;
+    Ok(if is_call {
+        bachelier_call(m1 - m2, strike, variance)
+    } else {
+        bachelier_put(m1 - m2, strike, variance)
+    })
+}
+
+/// Integration range around `strike` and the smile's forward.
+fn replication_bounds(smile: &dyn SwaptionSmile, strike: f64) -> (f64, f64) {
+    let forward = smile.forward();
+    // ATM straddle ≈ σ√T · √(2/π); the call alone is half of it.
+    let deviation = smile.price(forward, true) * (2.0 * std::f64::consts::PI).sqrt();
+    (
+        strike.min(forward) - REPLICATION_WIDTH * deviation,
+        strike.max(forward) + REPLICATION_WIDTH * deviation,
+    )
+}
+
+fn first_derivative(f: &dyn Fn(f64) -> Result<f64>, x: f64) -> Result<f64> {
+    Ok((f(x + MAPPING_STEP)? - f(x - MAPPING_STEP)?) / (2.0 * MAPPING_STEP))
+}
+
+fn second_derivative(f: &dyn Fn(f64) -> Result<f64>, x: f64) -> Result<f64> {
+    Ok((f(x + MAPPING_STEP)? - 2.0 * f(x)? + f(x - MAPPING_STEP)?) / (MAPPING_STEP * MAPPING_STEP))
+}
+
+fn simpson(f: impl Fn(f64) -> Result<f64>, a: f64, b: f64) -> Result<f64> {
+    if b <= a {
+        return Ok(0.0);
+    }
+    let n = REPLICATION_INTERVALS;
+    let h = (b - a) / n as f64;
+    let mut inner = 0.0;
+    for i in 1..n {
+        let weight = if i % 2 == 1 { 4.0 } else { 2.0 };
+        inner += weight * f(a + i as f64 * h)?;
+    }
+    Ok((f(a)? + inner + f(b)?) * h / 3.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::interestrate::fmm::FlatCurve;
+
+    fn flat_smile(coupon: &CmsCoupon, vol: f64) -> NormalSmile {
+        NormalSmile {
+            forward: coupon.forward_swap_rate(),
+            expiry: coupon.expiry,
+            nodes: vec![(0.0, vol)],
+        }
+    }
+
+    /// On a flat normal smile the linear TSR adjustment is the closed
+    /// form `a σ² T / α₀`; replication of the full mapping lands close to
+    /// it; the adjustment is positive, shrinks with payment delay and
+    /// grows with mean reversion.
+    #[test]
+    fn convexity_adjustment_on_flat_smile() -> Result<()> {
+        let curve = FlatCurve { rate: 0.03 };
+        let coupon = CmsCoupon::from_curve(&curve, 5.0, 10.0, 1, 5.25);
+        let smile = flat_smile(&coupon, 0.01);
+        let kappa = 0.01;
+        let linear = CmsModel::LinearTsr {
+            mean_reversion: kappa,
+        };
+        let (s_up, a_up) = coupon.gaussian_state(kappa, 1.0e-4);
+        let (s_down, a_down) = coupon.gaussian_state(kappa, -1.0e-4);
+        let slope = (a_up - a_down) / (s_up - s_down);
+        let alpha0 = coupon.payment.1 / coupon.annuity();
+        let closed_form = slope * 0.01 * 0.01 * 5.0 / alpha0;
+        let adjustment = coupon.convexity_adjustment(&smile, linear)?;
+        assert!(adjustment > 0.0);
+        assert!(
+            (adjustment - closed_form).abs() < 1e-3 * closed_form,
+            "{adjustment} vs {closed_form}"
+        );
+
+        let replicated = coupon.convexity_adjustment(
+            &smile,
+            CmsModel::StaticReplication {
+                mean_reversion: kappa,
+            },
+        )?;
+        assert!((replicated / adjustment - 1.0).abs() < 0.05, "{replicated}");
+
+        let in_arrears = CmsCoupon::from_curve(&curve, 5.0, 10.0, 1, 5.0);
+        assert!(in_arrears.convexity_adjustment(&smile, linear)? > adjustment);
+        let reverting = coupon.convexity_adjustment(
+            &smile,
+            CmsModel::LinearTsr {
+                mean_reversion: 0.1,
+            },
+        )?;
+        assert!(reverting > adjustment);
+        Ok(())
+    }
+
+    /// Caplet − floorlet parity holds at any strike, and a perfectly
+    /// correlated spread option on the same rate is worth its intrinsic.
+    #[test]
+    fn cms_caplet_parity_and_spread_limits() -> Result<()> {
+        let curve = FlatCurve { rate: 0.025 };
+        let ten = CmsCoupon::from_curve(&curve, 2.0, 10.0, 1, 2.25);
+        let two = CmsCoupon::from_curve(&curve, 2.0, 2.0, 1, 2.25);
+        let smile = NormalSmile {
+            forward: ten.forward_swap_rate(),
+            expiry: 2.0,
+            nodes: vec![(0.0, 0.011), (0.025, 0.0095), (0.05, 0.0105)],
+        };
+        let model = CmsModel::StaticReplication {
+            mean_reversion: 0.02,
+        };
+        let rate = ten.rate(&smile, model)?;
+        for strike in [0.01, 0.025, 0.04] {
+            let call = ten.option_price(&smile, model, strike, true)?;
+            let put = ten.option_price(&smile, model, strike, false)?;
+            assert!((call - put - (rate - strike)).abs() < 1e-6, "{strike}");
+        }
+
+        let same = cms_spread_option((&ten, &smile), (&ten, &smile), model, 1.0, -0.001, true)?;
+        assert!((same - 0.001).abs() < 1e-6);
+        let two_smile = flat_smile(&two, 0.012);
+        let low = cms_spread_option((&ten, &smile), (&two, &two_smile), model, 0.9, 0.0, true)?;
+        let high = cms_spread_option((&ten, &smile), (&two, &two_smile), model, 0.3, 0.0, true)?;
+        assert!(0.0 < low && low < high);
+        Ok(())
+    }
+}
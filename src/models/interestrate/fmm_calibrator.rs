@@ -0,0 +1,560 @@
+//! FMM calibrator — fits the per-rate vol schedule and the rate
+//! correlation to a stripped caplet surface plus a swaption cube, with
+//! the tenor, decay, displacement and initial curve held fixed.
+//!
+//! Follows the classic two-layer LMM recipe (Brigo & Mercurio §7.4,
+//! Rebonato 2002 ch. 8), carried over to the FMM:
+//!
+//! 1. **Vol shape** — `σ_j(t) = k_j · g(T_j − T_{i−1})` on each tenor
+//!    period `(T_{i−1}, T_i]`, with the time-homogeneous "abcd" hump
+//!    `g(τ) = (a + bτ)e^{−cτ} + d`. The result is stored as a
+//!    [`VolSchedule::PiecewiseConstant`].
+//! 2. **Caplets exactly** — for a given shape, each `k_j` is solved in
+//!    closed form so the ATM caplet on `R_j` reprices the surface vol
+//!    under the surface's [`CapStyle`] (backward-looking caplets carry the
+//!    `γ_j` decay variance, see [`super::fmm_analytics`]).
+//! 3. **Swaptions best-fit** — `(a, b, c)` and the two-parameter
+//!    correlation `ρ_{i,j} = ρ_∞ + (1 − ρ_∞) e^{−θ|T_i − T_j|}` are fitted
+//!    by Nelder-Mead to the cube's normal vols priced with the Rebonato
+//!    approximation. `d ≡ 1` fixes the shape's scale, which the `k_j`
+//!    absorb.
+//!
+//! | Parameter | Reparameterisation      | Domain      |
+//! |-----------|-------------------------|-------------|
+//! | `a`       | `log(1 + eˣ) − 1`       | `(−1, ∞)`   |
+//! | `b`       | identity                | `ℝ`         |
+//! | `c`       | `log(1 + eˣ)`           | `(0, ∞)`    |
+//! | `ρ_∞`     | `1 / (1 + e^{−x})`      | `(0, 1)`    |
+//! | `θ`       | `log(1 + eˣ)`           | `(0, ∞)`    |
+//!
+//! Every swaption quote's expiry and final payment must be FMM tenor
+//! dates; quotes that are not are rejected with an error rather than
+//! silently dropped. Rates with no caplet information (the first rate
+//! under forward-looking caplets, whose fixing is today) keep the seed
+//! level `initial.sigma_at(j, 0)`.
+//!
+//! # Papers
+//!
+//! * **Brigo, D., Mercurio, F. (2006)** — *Interest Rate Models — Theory
+//!   and Practice*, 2nd ed., Springer. §6.3.1 (abcd vol), §7.4 (joint
+//!   cap / swaption calibration).
+//! * **Rebonato, R. (2002)** — *Modern Pricing of Interest-Rate
+//!   Derivatives: The LIBOR Market Model and Beyond*, Princeton UP. Ch. 7
+//!   (two-parameter correlation), ch. 8 (caplet-exact calibration).
+
+use crate::derivatives::interestrate::basic::{CapStyle, caplet_total_variance};
+use crate::error::{Error, Result};
+use crate::math::optimize::{Minimum, NelderMeadOptions, inv_softplus, nelder_mead, softplus};
+use crate::models::common::bachelier::{bachelier_call, bachelier_implied_vol};
+use crate::models::common::calibration::{Calibration, CalibrationReport};
+use crate::models::interestrate::fmm::{Fmm, FmmTenor, InitialDiscountCurve, VolSchedule};
+use crate::models::interestrate::fmm_analytics::{
+    caplet, caplet_variance, rate_option, swaption, tenor_index,
+};
+use crate::models::interestrate::market_data::{IrVolMarket, SwaptionCube};
+use chrono::{Duration, NaiveDate};
+
+/// Time-homogeneous abcd vol shape `g(τ) = (a + bτ)e^{−cτ} + d` in the
+/// time-to-maturity `τ`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct AbcdShape {
+    pub a: f64,
+    pub b: f64,
+    pub c: f64,
+    pub d: f64,
+}
+
+impl AbcdShape {
+    /// The flat shape `g ≡ 1`.
+    pub fn flat() -> Self {
+        Self {
+            a: 0.0,
+            b: 0.0,
+            c: 1.0,
+            d: 1.0,
+        }
+    }
+
+    pub fn eval(&self, tau: f64) -> f64 {
+        (self.a + self.b * tau) * (-self.c * tau).exp() + self.d
+    }
+}
+
+/// Two-parameter correlation `ρ_{i,j} = ρ_∞ + (1 − ρ_∞) e^{−θ|T_i − T_j|}`
+/// on the rate end-dates. Positive-definite for `ρ_∞ ∈ [0, 1)`, `θ > 0`.
+pub fn exponential_correlation(tenor: &FmmTenor, long_corr: f64, decay: f64) -> Vec<Vec<f64>> {
+    let m = tenor.m();
+    (1..=m)
+        .map(|i| {
+            (1..=m)
+                .map(|j| {
+                    let dist = (tenor.dates[i] - tenor.dates[j]).abs();
+                    long_corr + (1.0 - long_corr) * (-decay * dist).exp()
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// ATM caplet target on rate `R_j`.
+#[derive(Copy, Clone, Debug)]
+struct CapletTarget {
+    j: usize,
+    /// Market normal vol read off the surface.
+    market_vol: f64,
+    /// Market total-variance factor `V / σ²` under the surface style.
+    vol_time: f64,
+    /// Model integrated variance `∫ σ_j² γ_j²` reproducing the premium.
+    model_variance: f64,
+}
+
+/// Joint cap + swaption calibrator for [`Fmm`]. `initial` supplies the
+/// tenor, decay and optional displacement (all held fixed) plus the seed
+/// level for rates without caplet quotes; `shape`, `long_correlation`
+/// and `correlation_decay` seed the Nelder-Mead stage.
+pub struct FmmCalibrator<C: InitialDiscountCurve> {
+    pub initial: Fmm,
+    pub curve: C,
+    pub shape: AbcdShape,
+    pub long_correlation: f64,
+    pub correlation_decay: f64,
+}
+
+impl<C: InitialDiscountCurve> FmmCalibrator<C> {
+    /// Caplet targets from the surface: one ATM caplet per rate with a
+    /// positive vol time.
+    fn caplet_targets(&self, market: &IrVolMarket) -> Result<Vec<CapletTarget>> {
+        let tenor = &self.initial.tenor;
+        let valuation = market.cap_surface.valuation_date;
+        let mut targets = Vec::new();
+        for j in 1..=tenor.m() {
+            let (ts, te) = (tenor.dates[j - 1], tenor.dates[j]);
+            let vol_time = caplet_total_variance(market.cap_style, 1.0, ts, te);
+            if vol_time <= 1.0e-10 {
+                continue;
+            }
+            let r0 = tenor.initial_rates[j - 1];
+            let market_vol = market
+                .cap_surface
+                .caplet_volatility(year_fraction_to_date(valuation, ts), r0)?;
+            let premium = bachelier_call(r0, r0, market_vol * market_vol * vol_time);
+            let model_variance =
+                invert_rate_variance(&self.initial, j, premium).ok_or_else(|| {
+                    Error::InvalidData(format!(
+                        "FMM calibration: cannot match caplet vol {market_vol} on rate {j}"
+                    ))
+                })?;
+            targets.push(CapletTarget {
+                j,
+                market_vol,
+                vol_time,
+                model_variance,
+            });
+        }
+        Ok(targets)
+    }
+
+    /// Assemble the model for a given shape and correlation, scaling each
+    /// rate's schedule so its caplet target is hit exactly.
+    fn build(
+        &self,
+        shape: &AbcdShape,
+        long_corr: f64,
+        decay: f64,
+        style: CapStyle,
+        targets: &[CapletTarget],
+    ) -> Fmm {
+        let tenor = &self.initial.tenor;
+        let m = tenor.m();
+        let unit: Vec<Vec<(f64, f64)>> = (1..=m)
+            .map(|j| {
+                (1..=j)
+                    .map(|i| {
+                        (
+                            tenor.dates[i - 1],
+                            shape.eval(tenor.dates[j] - tenor.dates[i - 1]),
+                        )
+                    })
+                    .collect()
+            })
+            .collect();
+        let correlation = exponential_correlation(tenor, long_corr, decay);
+        let mut model = Fmm {
+            correlation,
+            vol_schedule: Some(VolSchedule::PiecewiseConstant(unit.clone())),
+            ..self.initial.clone()
+        };
+
+        let scales: Vec<f64> = (1..=m)
+            .map(|j| match targets.iter().find(|t| t.j == j) {
+                Some(t) => {
+                    let unit_var = caplet_variance(&model, j, style);
+                    (t.model_variance / unit_var).sqrt()
+                }
+                None => self.initial.sigma_at(j, 0.0) / unit[j - 1][0].1,
+            })
+            .collect();
+        let schedule: Vec<Vec<(f64, f64)>> = unit
+            .iter()
+            .zip(scales.iter())
+            .map(|(knots, &k)| knots.iter().map(|&(t, g)| (t, k * g)).collect())
+            .collect();
+        model.sigmas = schedule
+            .iter()
+            .map(|knots| knots.last().unwrap().1)
+            .collect();
+        model.vol_schedule = Some(VolSchedule::PiecewiseConstant(schedule));
+        model
+    }
+
+    fn swaption_errors(&self, model: &Fmm, cube: &SwaptionCube) -> Option<Vec<f64>> {
+        cube.quotes
+            .iter()
+            .map(|q| {
+                let times = cube.fixed_leg_times(q);
+                let accruals = cube.fixed_leg_accruals(q);
+                let strike = cube.strike(q, &self.curve);
+                let premium = swaption(
+                    model,
+                    q.expiry,
+                    &times,
+                    &accruals,
+                    strike,
+                    true,
+                    &self.curve,
+                );
+                cube.implied_normal_vol(q, &self.curve, premium)
+                    .map(|v| v - q.normal_vol)
+            })
+            .collect()
+    }
+
+    fn caplet_errors(&self, model: &Fmm, style: CapStyle, targets: &[CapletTarget]) -> Vec<f64> {
+        let tenor = &model.tenor;
+        targets
+            .iter()
+            .map(|t| {
+                let r0 = tenor.initial_rates[t.j - 1];
+                let annuity = tenor.tau(t.j) * self.curve.p0(tenor.dates[t.j]);
+                let premium = caplet(model, t.j, r0, style, true, &self.curve) / annuity;
+                bachelier_implied_vol(premium, r0, r0, t.vol_time, true)
+                    .map_or(f64::NAN, |v| v - t.market_vol)
+            })
+            .collect()
+    }
+}
+
+impl<C: InitialDiscountCurve> Calibration for FmmCalibrator<C> {
+    type Market = IrVolMarket;
+    type Params = Fmm;
+
+    fn calibrate(
+        &self,
+        market: &Self::Market,
+        options: NelderMeadOptions,
+    ) -> Result<CalibrationReport<Self::Params>> {
+        let style = market.cap_style;
+        let cube = &market.swaptions;
+        for q in &cube.quotes {
+            let end = q.expiry + q.tenor;
+            match (
+                tenor_index(&self.initial, q.expiry),
+                tenor_index(&self.initial, end),
+            ) {
+                (Some(a), Some(b)) if b > a => {}
+                _ => {
+                    return Err(Error::InvalidData(format!(
+                        "FMM calibration: swaption {}y × {}y does not align with the FMM tenor",
+                        q.expiry, q.tenor
+                    )));
+                }
+            }
+        }
+        let targets = self.caplet_targets(market)?;
+        if targets.is_empty() && cube.quotes.is_empty() {
+            return Err(Error::InvalidData(
+                "FMM calibration needs at least one caplet or swaption quote".to_string(),
+            ));
+        }
+
+        let (shape, long_corr, decay, optimiser) = if cube.quotes.is_empty() {
+            (
+                self.shape,
+                self.long_correlation,
+                self.correlation_decay,
+                None,
+            )
+        } else {
+            let x0 = vec![
+                inv_softplus(self.shape.a + 1.0),
+                self.shape.b,
+                inv_softplus(self.shape.c),
+                logit(self.long_correlation.clamp(1e-6, 1.0 - 1e-6)),
+                inv_softplus(self.correlation_decay),
+            ];
+            let objective = |x: &[f64]| -> f64 {
+                let (shape, long_corr, decay) = reify(x);
+                let model = self.build(&shape, long_corr, decay, style, &targets);
+                match self.swaption_errors(&model, cube) {
+                    Some(errs) if errs.iter().all(|e| e.is_finite()) => {
+                        errs.iter().map(|e| e * e).sum()
+                    }
+                    _ => 1.0e6,
+                }
+            };
+            let minimum: Minimum = nelder_mead(objective, &x0, options);
+            let (shape, long_corr, decay) = reify(&minimum.x);
+            (shape, long_corr, decay, Some(minimum))
+        };
+
+        let params = self.build(&shape, long_corr, decay, style, &targets);
+        let mut errors = self.caplet_errors(&params, style, &targets);
+        errors.extend(self.swaption_errors(&params, cube).ok_or_else(|| {
+            Error::InvalidData("FMM calibration: swaption premium out of range".to_string())
+        })?);
+        let rmse = (errors.iter().map(|e| e * e).sum::<f64>() / errors.len() as f64).sqrt();
+        Ok(CalibrationReport {
+            params,
+            rmse,
+            optimiser,
+        })
+    }
+}
+
+/// Calendar date `round(365 · t)` days after `valuation` — the inverse of
+/// the Act/365 vol-time convention the cap surface is stripped under.
+fn year_fraction_to_date(valuation: NaiveDate, t: f64) -> NaiveDate {
+    valuation + Duration::days((t * 365.0).round() as i64)
+}
+
+/// Integrated variance `V` such that the undiscounted ATM option on
+/// `R_j` under `model`'s dynamics equals `premium`. The price is
+/// increasing in `V`, so bracket by doubling and bisect.
+fn invert_rate_variance(model: &Fmm, j: usize, premium: f64) -> Option<f64> {
+    let strike = model.tenor.initial_rates[j - 1];
+    let price = |v: f64| rate_option(model, j, strike, v, true);
+    if premium <= 0.0 {
+        return Some(0.0);
+    }
+    let mut hi = 1.0e-4;
+    while price(hi) < premium {
+        hi *= 2.0;
+        if hi > 1.0e4 {
+            return None;
+        }
+    }
+    let mut lo = 0.0_f64;
+    for _ in 0..200 {
+        let mid = 0.5 * (lo + hi);
+        if price(mid) < premium {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+        if hi - lo < 1.0e-16 * hi.max(1e-300) {
+            break;
+        }
+    }
+    Some(0.5 * (lo + hi))
+}
+
+fn logit(p: f64) -> f64 {
+    (p / (1.0 - p)).ln()
+}
+
+fn reify(x: &[f64]) -> (AbcdShape, f64, f64) {
+    let shape = AbcdShape {
+        a: softplus(x[0]) - 1.0,
+        b: x[1],
+        c: softplus(x[2]),
+        d: 1.0,
+    };
+    let long_corr = 1.0 / (1.0 + (-x[3]).exp());
+    (shape, long_corr, softplus(x[4]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::markets::interestrate::volsurface::{CapletVolPillar, IRNormalVolSurface};
+    use crate::models::interestrate::fmm::{FlatCurve, LinearDecay};
+    use crate::models::interestrate::market_data::SwaptionQuote;
+
+    fn valuation() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 1, 1).unwrap()
+    }
+
+    /// Annual FMM out to 10y on a flat curve.
+    fn annual_fmm(curve: &FlatCurve) -> Fmm {
+        let m = 10;
+        let dates: Vec<f64> = (0..=m).map(|k| k as f64).collect();
+        let rates: Vec<f64> = (1..=m)
+            .map(|j| curve.p0_fwd(dates[j - 1], dates[j]).recip() - 1.0)
+            .collect();
+        let tenor = FmmTenor::new(dates, rates);
+        let corr = exponential_correlation(&tenor, 0.5, 0.2);
+        Fmm::new(tenor, vec![0.01; m], corr, LinearDecay)
+    }
+
+    /// Surface with one ATM-only pillar per FMM period start, vols
+    /// taken from `vols_bp[j − 1]`.
+    fn surface(model: &Fmm, vols_bp: &[f64]) -> IRNormalVolSurface {
+        let mut s = IRNormalVolSurface::new(valuation());
+        s.pillars = (1..=model.tenor.m())
+            .map(|j| CapletVolPillar {
+                expiry: year_fraction_to_date(valuation(), model.tenor.dates[j - 1]),
+                nodes: vec![(model.tenor.initial_rates[j - 1], vols_bp[j - 1] * 1e-4)],
+            })
+            .collect();
+        s
+    }
+
+    fn cube_from(model: &Fmm, curve: &FlatCurve) -> SwaptionCube {
+        let mut quotes = Vec::new();
+        for &(e, t) in &[
+            (1.0, 2.0),
+            (1.0, 5.0),
+            (2.0, 3.0),
+            (2.0, 8.0),
+            (3.0, 5.0),
+            (5.0, 5.0),
+        ] {
+            quotes.push(SwaptionQuote {
+                expiry: e,
+                tenor: t,
+                strike_offset: 0.0,
+                normal_vol: 0.0,
+            });
+        }
+        let mut cube = SwaptionCube::new(1, quotes);
+        for i in 0..cube.quotes.len() {
+            let q = cube.quotes[i];
+            let times = cube.fixed_leg_times(&q);
+            let taus = cube.fixed_leg_accruals(&q);
+            let k = cube.strike(&q, curve);
+            let p = swaption(model, q.expiry, &times, &taus, k, true, curve);
+            cube.quotes[i].normal_vol = cube.implied_normal_vol(&q, curve, p).unwrap();
+        }
+        cube
+    }
+
+    /// Caplets are repriced exactly for any shape, both cap styles.
+    #[test]
+    fn caplets_are_repriced_exactly() {
+        let curve = FlatCurve { rate: 0.03 };
+        let model = annual_fmm(&curve);
+        let vols: Vec<f64> = (0..10).map(|k| 80.0 + 3.0 * k as f64).collect();
+        for style in [CapStyle::ForwardLooking, CapStyle::BackwardCompounded] {
+            let market = IrVolMarket {
+                cap_surface: surface(&model, &vols),
+                cap_style: style,
+                swaptions: SwaptionCube::new(1, vec![]),
+            };
+            let calibrator = FmmCalibrator {
+                initial: model.clone(),
+                curve,
+                shape: AbcdShape {
+                    a: 0.2,
+                    b: 0.3,
+                    c: 0.8,
+                    d: 1.0,
+                },
+                long_correlation: 0.5,
+                correlation_decay: 0.2,
+            };
+            let report = calibrator
+                .calibrate(&market, NelderMeadOptions::default())
+                .unwrap();
+            assert!(report.optimiser.is_none());
+            assert!(report.rmse < 1e-9, "{style:?} rmse {}", report.rmse);
+        }
+    }
+
+    /// Round-trip: caps and swaptions generated from a known shape and
+    /// correlation are fitted jointly, with caplets exact and swaptions
+    /// to sub-bp accuracy.
+    #[test]
+    fn joint_calibration_round_trips_synthetic_market() {
+        let curve = FlatCurve { rate: 0.03 };
+        let base = annual_fmm(&curve);
+        let truth_shape = AbcdShape {
+            a: -0.3,
+            b: 0.6,
+            c: 0.9,
+            d: 1.0,
+        };
+        let vols: Vec<f64> = (0..10).map(|k| 90.0 - 2.0 * k as f64).collect();
+        let market_caps = surface(&base, &vols);
+        let style = CapStyle::BackwardCompounded;
+        let truth_calibrator = FmmCalibrator {
+            initial: base.clone(),
+            curve,
+            shape: truth_shape,
+            long_correlation: 0.4,
+            correlation_decay: 0.3,
+        };
+        let seed_market = IrVolMarket {
+            cap_surface: market_caps.clone(),
+            cap_style: style,
+            swaptions: SwaptionCube::new(1, vec![]),
+        };
+        let truth = truth_calibrator
+            .calibrate(&seed_market, NelderMeadOptions::default())
+            .unwrap()
+            .params;
+        let market = IrVolMarket {
+            cap_surface: market_caps,
+            cap_style: style,
+            swaptions: cube_from(&truth, &curve),
+        };
+
+        let calibrator = FmmCalibrator {
+            initial: base,
+            curve,
+            shape: AbcdShape::flat(),
+            long_correlation: 0.7,
+            correlation_decay: 0.1,
+        };
+        let opts = NelderMeadOptions {
+            max_iter: 3000,
+            ftol: 1e-16,
+            xtol: 1e-10,
+            step_frac: 0.2,
+        };
+        let report = calibrator.calibrate(&market, opts).unwrap();
+        assert!(report.optimiser.is_some());
+        assert!(report.rmse < 5e-6, "rmse {:.4} bp", report.rmse * 1e4);
+    }
+
+    /// Swaptions off the FMM grid are rejected.
+    #[test]
+    fn misaligned_swaption_errors() {
+        let curve = FlatCurve { rate: 0.03 };
+        let model = annual_fmm(&curve);
+        let market = IrVolMarket {
+            cap_surface: surface(&model, &[80.0; 10]),
+            cap_style: CapStyle::ForwardLooking,
+            swaptions: SwaptionCube::new(
+                1,
+                vec![SwaptionQuote {
+                    expiry: 1.5,
+                    tenor: 2.0,
+                    strike_offset: 0.0,
+                    normal_vol: 0.008,
+                }],
+            ),
+        };
+        let calibrator = FmmCalibrator {
+            initial: model,
+            curve,
+            shape: AbcdShape::flat(),
+            long_correlation: 0.5,
+            correlation_decay: 0.2,
+        };
+        assert!(
+            calibrator
+                .calibrate(&market, NelderMeadOptions::default())
+                .is_err()
+        );
+    }
+}
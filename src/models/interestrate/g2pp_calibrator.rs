@@ -0,0 +1,206 @@
+//! G2++ calibrator — fits `(a, σ, b, η, ρ)` to a swaption cube of
+//! Bachelier normal vols, with the initial curve held fixed.
+//!
+//! Mirrors the structure of [`crate::models::forex::sabr_calibrator`]:
+//! Nelder-Mead on an unconstrained reparameterisation, objective is the
+//! sum of squared normal-vol errors.
+//!
+//! | Parameter | Reparameterisation | Domain      |
+//! |-----------|--------------------|-------------|
+//! | `a`, `b`  | `log(1 + eˣ)`      | `(0, ∞)`    |
+//! | `σ`, `η`  | `log(1 + eˣ)`      | `(0, ∞)`    |
+//! | `ρ`       | `tanh(x)`          | `(−1, 1)`   |
+//!
+//! Model prices come from [`G2pp::swaption`] (BM eq. 4.31) and are
+//! converted back to normal vols via
+//! [`SwaptionCube::implied_normal_vol`]. The G2++ likelihood surface has
+//! a factor-swap symmetry `(a, σ) ↔ (b, η)`; the fit is unique only up
+//! to that relabelling, so start from `a > b` to land on the
+//! conventional "fast factor first" branch.
+
+use crate::error::{Error, Result};
+use crate::math::optimize::{NelderMeadOptions, inv_softplus, nelder_mead, softplus};
+use crate::models::common::calibration::{Calibration, CalibrationReport};
+use crate::models::interestrate::fmm::InitialDiscountCurve;
+use crate::models::interestrate::g2pp::G2pp;
+use crate::models::interestrate::market_data::{SwaptionCube, SwaptionQuote};
+
+/// G2++ model payer-swaption premium for one cube quote, per unit
+/// notional.
+pub fn model_premium<C: InitialDiscountCurve>(
+    params: &G2pp,
+    cube: &SwaptionCube,
+    quote: &SwaptionQuote,
+    curve: &C,
+) -> f64 {
+    let times = cube.fixed_leg_times(quote);
+    let accruals = cube.fixed_leg_accruals(quote);
+    let strike = cube.strike(quote, curve);
+    params.swaption(quote.expiry, &times, &accruals, strike, true, curve)
+}
+
+/// Model normal vols on every quote of `cube`. `None` where the model
+/// premium falls outside the Bachelier range.
+pub fn model_normal_vols<C: InitialDiscountCurve>(
+    params: &G2pp,
+    cube: &SwaptionCube,
+    curve: &C,
+) -> Vec<Option<f64>> {
+    cube.quotes
+        .iter()
+        .map(|q| {
+            let premium = model_premium(params, cube, q, curve);
+            cube.implied_normal_vol(q, curve, premium)
+        })
+        .collect()
+}
+
+/// Swaption-cube calibrator for [`G2pp`]. The initial discount curve is
+/// part of the calibrator (not the market bundle) so one curve can be
+/// reused across several cubes.
+pub struct G2ppSwaptionCalibrator<C: InitialDiscountCurve> {
+    /// Seed parameters — fed as the Nelder-Mead starting point.
+    pub initial: G2pp,
+    pub curve: C,
+}
+
+impl<C: InitialDiscountCurve> Calibration for G2ppSwaptionCalibrator<C> {
+    type Market = SwaptionCube;
+    type Params = G2pp;
+
+    fn calibrate(
+        &self,
+        market: &Self::Market,
+        options: NelderMeadOptions,
+    ) -> Result<CalibrationReport<Self::Params>> {
+        if market.quotes.is_empty() {
+            return Err(Error::InvalidData(
+                "G2++ calibration needs at least one swaption quote".to_string(),
+            ));
+        }
+        let init = self.initial;
+        let x0 = vec![
+            inv_softplus(init.a.max(1e-6)),
+            inv_softplus(init.sigma.max(1e-8)),
+            inv_softplus(init.b.max(1e-6)),
+            inv_softplus(init.eta.max(1e-8)),
+            init.rho.clamp(-0.999, 0.999).atanh(),
+        ];
+
+        let objective = |x: &[f64]| -> f64 {
+            let trial = match reify(x) {
+                Some(p) => p,
+                None => return 1.0e6,
+            };
+            let mut ssr = 0.0_f64;
+            for (q, model_vol) in
+                market
+                    .quotes
+                    .iter()
+                    .zip(model_normal_vols(&trial, market, &self.curve))
+            {
+                match model_vol {
+                    Some(v) => ssr += (v - q.normal_vol).powi(2),
+                    None => return 1.0e6,
+                }
+            }
+            ssr
+        };
+
+        let minimum = nelder_mead(objective, &x0, options);
+        let params = reify(&minimum.x).unwrap_or(init);
+        let rmse = (minimum.f / market.quotes.len() as f64).sqrt();
+        Ok(CalibrationReport {
+            params,
+            rmse,
+            optimiser: Some(minimum),
+        })
+    }
+}
+
+fn reify(x: &[f64]) -> Option<G2pp> {
+    let a = softplus(x[0]);
+    let sigma = softplus(x[1]);
+    let b = softplus(x[2]);
+    let eta = softplus(x[3]);
+    let rho = x[4].tanh();
+    if ![a, sigma, b, eta].iter().all(|v| v.is_finite() && *v > 0.0) {
+        return None;
+    }
+    if rho <= -1.0 || rho >= 1.0 {
+        return None;
+    }
+    Some(G2pp::new(a, sigma, b, eta, rho))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::interestrate::fmm::FlatCurve;
+
+    fn synthetic_cube(truth: &G2pp, curve: &FlatCurve) -> SwaptionCube {
+        let mut quotes = Vec::new();
+        for &expiry in &[1.0, 2.0, 5.0] {
+            for &tenor in &[2.0, 5.0, 10.0] {
+                quotes.push(SwaptionQuote {
+                    expiry,
+                    tenor,
+                    strike_offset: 0.0,
+                    normal_vol: 0.0,
+                });
+            }
+        }
+        let mut cube = SwaptionCube::new(1, quotes);
+        let vols = model_normal_vols(truth, &cube, curve);
+        for (q, v) in cube.quotes.iter_mut().zip(vols) {
+            q.normal_vol = v.unwrap();
+        }
+        cube
+    }
+
+    /// Round-trip: build an ATM cube from known G2++ parameters, start
+    /// from a perturbed seed, and recover the cube to sub-bp accuracy.
+    #[test]
+    fn calibrator_round_trips_synthetic_cube() {
+        let curve = FlatCurve { rate: 0.03 };
+        let truth = G2pp::new(0.60, 0.015, 0.05, 0.009, -0.75);
+        let cube = synthetic_cube(&truth, &curve);
+
+        let calibrator = G2ppSwaptionCalibrator {
+            initial: G2pp::new(0.40, 0.010, 0.08, 0.007, -0.50),
+            curve,
+        };
+        let opts = NelderMeadOptions {
+            max_iter: 1500,
+            ftol: 1.0e-14,
+            xtol: 1.0e-8,
+            step_frac: 0.20,
+        };
+        let report = calibrator.calibrate(&cube, opts).unwrap();
+        assert!(
+            report.rmse < 5.0e-5,
+            "rmse {} (= {:.2} bp)",
+            report.rmse,
+            report.rmse * 1e4
+        );
+        let fit = model_normal_vols(&report.params, &cube, &curve);
+        for (q, v) in cube.quotes.iter().zip(fit) {
+            assert!((v.unwrap() - q.normal_vol).abs() < 1.5e-4);
+        }
+    }
+
+    /// Empty cube is rejected rather than producing a NaN rmse.
+    #[test]
+    fn empty_cube_errors() {
+        let calibrator = G2ppSwaptionCalibrator {
+            initial: G2pp::new(0.5, 0.01, 0.05, 0.008, -0.7),
+            curve: FlatCurve { rate: 0.03 },
+        };
+        let cube = SwaptionCube::new(1, vec![]);
+        assert!(
+            calibrator
+                .calibrate(&cube, NelderMeadOptions::default())
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,259 @@
+//! **Markets → models** bridge for IR calibration — the swaption quote
+//! grid every short-rate / market-model calibrator in
+//! `crate::models::interestrate` consumes.
+//!
+//! The IR analogue of [`crate::models::forex::market_data`]: quotes are
+//! expressed in year-fractions from the valuation date so the models
+//! never touch calendars, and every curve-dependent quantity (annuity,
+//! forward swap rate, Bachelier premium) is computed off an
+//! [`InitialDiscountCurve`] at calibration time.
+//!
+//! ```text
+//!     SwaptionCube { quotes: [SwaptionQuote { expiry, tenor, strike_offset, normal_vol }] }
+//!        │
+//!        │  .fixed_leg_times(q)  .annuity(q, curve)  .forward_swap_rate(q, curve)
+//!        ▼
+//!     market premium = A(0) · Bachelier(S₀, S₀ + offset, σ_N² · T)
+//! ```
+//!
+//! Swaptions are physically-settled payer swaptions on a spot-starting-at-
+//! expiry swap with a regular fixed leg of `fixed_frequency` payments per
+//! year. Strikes are quoted as offsets to the ATM forward swap rate — the
+//! usual cube layout (ATM, ±25bp, ±50bp, …).
+//!
+//! Calibrators that fit caps and swaptions jointly take an [`IrVolMarket`]:
+//! the stripped [`IRNormalVolSurface`] (date-based, as built by the
+//! markets layer) next to a [`SwaptionCube`].
+
+use crate::derivatives::interestrate::basic::CapStyle;
+use crate::markets::interestrate::volsurface::IRNormalVolSurface;
+use crate::math::interpolation::interpolate_flat;
+use crate::models::common::bachelier::{bachelier_call, bachelier_implied_vol};
+use crate::models::interestrate::cms::NormalSmile;
+use crate::models::interestrate::fmm::InitialDiscountCurve;
+
+/// One point of the swaption cube: normal vol for an `expiry × tenor`
+/// swaption struck at `ATM + strike_offset`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SwaptionQuote {
+    /// Option expiry `T₀` as a year-fraction from valuation.
+    pub expiry: f64,
+    /// Underlying swap tenor in years (`T_n − T₀`).
+    pub tenor: f64,
+    /// Strike offset to the ATM forward swap rate, in decimals
+    /// (`0.0025` = ATM + 25bp).
+    pub strike_offset: f64,
+    /// Market Bachelier (normal) vol, in decimals (`0.0090` = 90bp / y).
+    pub normal_vol: f64,
+}
+
+/// Grid of swaption quotes sharing a fixed-leg frequency.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SwaptionCube {
+    /// Fixed-leg payments per year (`1` = annual, `2` = semi-annual).
+    pub fixed_frequency: u32,
+    pub quotes: Vec<SwaptionQuote>,
+}
+
+impl SwaptionCube {
+    pub fn new(fixed_frequency: u32, quotes: Vec<SwaptionQuote>) -> Self {
+        assert!(fixed_frequency > 0, "fixed_frequency must be positive");
+        for q in &quotes {
+            assert!(q.expiry > 0.0, "swaption expiry must be positive");
+            assert!(q.tenor > 0.0, "swaption tenor must be positive");
+            assert!(q.normal_vol >= 0.0, "normal vol must be non-negative");
+        }
+        Self {
+            fixed_frequency,
+            quotes,
+        }
+    }
+
+    /// Fixed-leg payment times `T₁ < … < T_n` for `quote`. The number of
+    /// periods is `round(tenor · frequency)`, each of length
+    /// `1 / frequency` (the last period absorbs any rounding residual so
+    /// that `T_n = T₀ + tenor` exactly).
+    pub fn fixed_leg_times(&self, quote: &SwaptionQuote) -> Vec<f64> {
+        let step = 1.0 / self.fixed_frequency as f64;
+        let n = ((quote.tenor * self.fixed_frequency as f64).round() as usize).max(1);
+        let mut times: Vec<f64> = (1..=n).map(|i| quote.expiry + i as f64 * step).collect();
+        *times.last_mut().unwrap() = quote.expiry + quote.tenor;
+        times
+    }
+
+    /// Fixed-leg accrual fractions `τ_i = T_i − T_{i−1}` matching
+    /// [`Self::fixed_leg_times`].
+    pub fn fixed_leg_accruals(&self, quote: &SwaptionQuote) -> Vec<f64> {
+        let times = self.fixed_leg_times(quote);
+        let mut prev = quote.expiry;
+        times
+            .iter()
+            .map(|&t| {
+                let tau = t - prev;
+                prev = t;
+                tau
+            })
+            .collect()
+    }
+
+    /// Annuity `A(0) = Σ τ_i P(0, T_i)`.
+    pub fn annuity<C: InitialDiscountCurve>(&self, quote: &SwaptionQuote, curve: &C) -> f64 {
+        self.fixed_leg_times(quote)
+            .iter()
+            .zip(self.fixed_leg_accruals(quote).iter())
+            .map(|(&t, &tau)| tau * curve.p0(t))
+            .sum()
+    }
+
+    /// Forward swap rate `S₀ = (P(0, T₀) − P(0, T_n)) / A(0)`.
+    pub fn forward_swap_rate<C: InitialDiscountCurve>(
+        &self,
+        quote: &SwaptionQuote,
+        curve: &C,
+    ) -> f64 {
+        let p_start = curve.p0(quote.expiry);
+        let p_end = curve.p0(quote.expiry + quote.tenor);
+        (p_start - p_end) / self.annuity(quote, curve)
+    }
+
+    /// Absolute fixed rate `K = S₀ + strike_offset`.
+    pub fn strike<C: InitialDiscountCurve>(&self, quote: &SwaptionQuote, curve: &C) -> f64 {
+        self.forward_swap_rate(quote, curve) + quote.strike_offset
+    }
+
+    /// Market payer-swaption premium per unit notional:
+    /// `A(0) · Bachelier(S₀, K, σ_N² · T₀)`.
+    pub fn market_premium<C: InitialDiscountCurve>(&self, quote: &SwaptionQuote, curve: &C) -> f64 {
+        let annuity = self.annuity(quote, curve);
+        let forward = self.forward_swap_rate(quote, curve);
+        let variance = quote.normal_vol * quote.normal_vol * quote.expiry;
+        annuity * bachelier_call(forward, forward + quote.strike_offset, variance)
+    }
+
+    /// Invert a model payer-swaption premium (per unit notional) back to
+    /// the Bachelier normal vol for `quote`. `None` if the premium is
+    /// outside the achievable Bachelier range.
+    pub fn implied_normal_vol<C: InitialDiscountCurve>(
+        &self,
+        quote: &SwaptionQuote,
+        curve: &C,
+        premium: f64,
+    ) -> Option<f64> {
+        let annuity = self.annuity(quote, curve);
+        let forward = self.forward_swap_rate(quote, curve);
+        bachelier_implied_vol(
+            premium / annuity,
+            forward,
+            forward + quote.strike_offset,
+            quote.expiry,
+            true,
+        )
+    }
+
+    /// Smile of the `expiry × tenor` swaption struck around `forward`.
+    /// Each quoted strike offset's vol is interpolated linearly in tenor,
+    /// then in expiry, flat outside the grid. `None` for an empty cube.
+    pub fn normal_smile(&self, expiry: f64, tenor: f64, forward: f64) -> Option<NormalSmile> {
+        let sorted = |mut xs: Vec<f64>| {
+            xs.sort_by(f64::total_cmp);
+            xs.dedup();
+            xs
+        };
+        let offsets = sorted(self.quotes.iter().map(|q| q.strike_offset).collect());
+        if offsets.is_empty() {
+            return None;
+        }
+        let nodes = offsets
+            .iter()
+            .map(|&offset| {
+                let at_offset = || {
+                    self.quotes
+                        .iter()
+                        .filter(move |q| q.strike_offset == offset)
+                };
+                let by_expiry: Vec<(f64, f64)> = sorted(at_offset().map(|q| q.expiry).collect())
+                    .into_iter()
+                    .map(|e| {
+                        let mut by_tenor: Vec<(f64, f64)> = at_offset()
+                            .filter(|q| q.expiry == e)
+                            .map(|q| (q.tenor, q.normal_vol))
+                            .collect();
+                        by_tenor.sort_by(|a, b| a.0.total_cmp(&b.0));
+                        (e, interpolate_flat(&by_tenor, tenor))
+                    })
+                    .collect();
+                (forward + offset, interpolate_flat(&by_expiry, expiry))
+            })
+            .collect();
+        Some(NormalSmile {
+            forward,
+            expiry,
+            nodes,
+        })
+    }
+}
+
+/// Cap and swaption vol bundle for joint calibrations. Caplet vols are
+/// read off `cap_surface` at `valuation_date + round(365 · T)` for a
+/// model year-fraction `T` (Act/365, the surface's vol-time convention).
+#[derive(Clone, Debug)]
+pub struct IrVolMarket {
+    pub cap_surface: IRNormalVolSurface,
+    /// Caplet convention the surface was stripped under.
+    pub cap_style: CapStyle,
+    pub swaptions: SwaptionCube,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::interestrate::fmm::FlatCurve;
+
+    fn cube() -> SwaptionCube {
+        SwaptionCube::new(
+            1,
+            vec![SwaptionQuote {
+                expiry: 1.0,
+                tenor: 5.0,
+                strike_offset: 0.0,
+                normal_vol: 0.0090,
+            }],
+        )
+    }
+
+    /// Annual 1y×5y: payments at 2..6, each τ = 1.
+    #[test]
+    fn fixed_leg_layout() {
+        let c = cube();
+        let q = c.quotes[0];
+        assert_eq!(c.fixed_leg_times(&q), vec![2.0, 3.0, 4.0, 5.0, 6.0]);
+        assert!(
+            c.fixed_leg_accruals(&q)
+                .iter()
+                .all(|&t| (t - 1.0).abs() < 1e-15)
+        );
+    }
+
+    /// Flat continuously-compounded curve ⇒ annual par rate `e^r − 1`.
+    #[test]
+    fn forward_swap_rate_on_flat_curve() {
+        let c = cube();
+        let q = c.quotes[0];
+        let curve = FlatCurve { rate: 0.03 };
+        let s = c.forward_swap_rate(&q, &curve);
+        assert!((s - (0.03_f64.exp() - 1.0)).abs() < 1e-12);
+    }
+
+    /// Premium → implied vol recovers the quoted normal vol.
+    #[test]
+    fn premium_implied_vol_round_trip() {
+        let mut c = cube();
+        c.quotes[0].strike_offset = 0.0050;
+        let q = c.quotes[0];
+        let curve = FlatCurve { rate: 0.03 };
+        let premium = c.market_premium(&q, &curve);
+        assert!(premium > 0.0);
+        let iv = c.implied_normal_vol(&q, &curve, premium).unwrap();
+        assert!((iv - q.normal_vol).abs() < 1e-10);
+    }
+}
@@ -0,0 +1,440 @@
+//! SABR smile sections for rates: **normal SABR** (`β = 0`, Bachelier
+//! vol expansion, negative rates allowed) and **shifted-lognormal SABR**
+//! (Hagan's Black-vol expansion on `F + s`, `K + s`).
+//!
+//! A [`SabrSmileSection`] is one caplet (or swaption) expiry: forward,
+//! year fraction, fitted [`SabrParams`] and the strike range inside
+//! which the SABR expansion is trusted. Outside that range the section
+//! switches to exponential price tails,
+//!
+//! ```text
+//!   K > K_R :  C(K) = C(K_R) · exp(−m_R (K − K_R)),   m_R = −C'(K_R) / C(K_R)
+//!   K < K_L :  P(K) = P(K_L) · exp( m_L (K − K_L)),   m_L =  P'(K_L) / P(K_L)
+//! ```
+//!
+//! which match price and slope at the cutoff and are strictly convex, so
+//! the implied density stays positive and integrable in the wings. The
+//! cutoffs start at the outermost fitted strikes and are pulled towards
+//! the forward wherever the SABR expansion itself shows negative density
+//! (the well-known low-strike failure of Hagan's formula).
+//!
+//! [`fit_smile_section`] fits `(α, ρ, ν)` with `β` (and the shift) held
+//! fixed to `(strike, normal vol)` nodes such as the pillars of a
+//! stripped [`IRNormalVolSurface`]; it mirrors
+//! [`crate::models::forex::sabr_calibrator`].
+//!
+//! | Parameter | Reparameterisation | Domain      |
+//! |-----------|--------------------|-------------|
+//! | `α`       | `log(1 + eˣ)`      | `(0, ∞)`    |
+//! | `ρ`       | `tanh(x)`          | `(−1, 1)`   |
+//! | `ν`       | `log(1 + eˣ)`      | `(0, ∞)`    |
+//!
+//! [`IRNormalVolSurface`]: crate::markets::interestrate::volsurface::IRNormalVolSurface
+//!
+//! # Papers
+//!
+//! * **Hagan, P., Kumar, D., Lesniewski, A., Woodward, D. (2002)** —
+//!   *Managing Smile Risk*, Wilmott Magazine, 84–108. Normal-vol
+//!   expansion (B.70) and Black-vol expansion (B.69a).
+//! * **Benaim, S., Dodgson, M., Kainth, D. (2008)** — *An arbitrage-free
+//!   method for smile extrapolation*, RBS working paper. Price-tail
+//!   wing extrapolation.
+
+use crate::error::{Error, Result};
+use crate::math::optimize::{NelderMeadOptions, inv_softplus, nelder_mead, softplus};
+use crate::models::common::vol_conversion::{VolQuoteType, convert_vol, implied_vol, option_price};
+use crate::models::forex::sabr::{SabrParams, hagan_implied_vol};
+
+/// Number of grid points used when scanning for negative density
+/// between the forward and each fitted edge.
+const DENSITY_SCAN_POINTS: usize = 100;
+
+/// Which SABR expansion a section uses.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum RatesSabrKind {
+    /// `β = 0` SABR, quoted in normal vol.
+    Normal,
+    /// SABR on `F + shift`, quoted in shifted-lognormal vol.
+    ShiftedLognormal { shift: f64 },
+}
+
+impl RatesSabrKind {
+    /// Vol convention the expansion produces natively.
+    pub fn quote_type(&self) -> VolQuoteType {
+        match self {
+            RatesSabrKind::Normal => VolQuoteType::Normal,
+            RatesSabrKind::ShiftedLognormal { shift } => {
+                VolQuoteType::ShiftedLognormal { shift: *shift }
+            }
+        }
+    }
+}
+
+/// Hagan normal-vol expansion for `β = 0`:
+///
+/// ```text
+///   ζ     = (ν / α) · (F − K)
+///   σ_N  ≈ α · ζ / x(ζ) · [1 + (2 − 3ρ²) ν² T / 24]
+/// ```
+///
+/// with `x(ζ)` as in [`hagan_implied_vol`]. `params.beta` is ignored.
+pub fn normal_sabr_vol(params: &SabrParams, forward: f64, strike: f64, t: f64) -> f64 {
+    let SabrParams { alpha, rho, nu, .. } = *params;
+    let zeta = nu / alpha * (forward - strike);
+    let zeta_over_x = if zeta.abs() < 1.0e-8 {
+        1.0 - 0.5 * rho * zeta + (1.0 / 12.0) * (3.0 * rho * rho - 2.0) * zeta * zeta
+    } else {
+        let num = (1.0 - 2.0 * rho * zeta + zeta * zeta).sqrt() + zeta - rho;
+        zeta / (num / (1.0 - rho)).ln()
+    };
+    alpha * zeta_over_x * (1.0 + (2.0 - 3.0 * rho * rho) * nu * nu * t / 24.0)
+}
+
+/// SABR smile at a single expiry with arbitrage-aware wings.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SabrSmileSection {
+    pub kind: RatesSabrKind,
+    pub params: SabrParams,
+    pub forward: f64,
+    /// Year fraction to expiry.
+    pub t: f64,
+    /// Below this strike the put-price exponential tail is used.
+    pub left_cutoff: f64,
+    /// Above this strike the call-price exponential tail is used.
+    pub right_cutoff: f64,
+}
+
+impl SabrSmileSection {
+    /// Section with cutoffs placed at `[k_min, k_max]`, then pulled
+    /// inwards past any strike where the SABR density is negative.
+    pub fn new(
+        kind: RatesSabrKind,
+        params: SabrParams,
+        forward: f64,
+        t: f64,
+        k_min: f64,
+        k_max: f64,
+    ) -> Self {
+        let mut section = Self {
+            kind,
+            params,
+            forward,
+            t,
+            left_cutoff: f64::NEG_INFINITY,
+            right_cutoff: f64::INFINITY,
+        };
+        let lowest = match kind {
+            RatesSabrKind::Normal => k_min,
+            // Keep the scan strictly inside the shifted domain.
+            RatesSabrKind::ShiftedLognormal { shift } => k_min.max(-shift + 1.0e-4),
+        };
+        section.left_cutoff = section.scan_cutoff(lowest.min(forward));
+        section.right_cutoff = section.scan_cutoff(k_max.max(forward));
+        section
+    }
+
+    /// Walk from the forward out to `edge`; return the last strike before
+    /// the raw SABR density turns negative or the tail slope degenerates.
+    fn scan_cutoff(&self, edge: f64) -> f64 {
+        let step = (edge - self.forward) / DENSITY_SCAN_POINTS as f64;
+        if step == 0.0 {
+            return edge;
+        }
+        let h = step.abs() * 0.25;
+        let mut last_good = self.forward;
+        for i in 1..=DENSITY_SCAN_POINTS {
+            let k = self.forward + step * i as f64;
+            let density = self.raw_price(k + h, true) - 2.0 * self.raw_price(k, true)
+                + self.raw_price(k - h, true);
+            if !density.is_finite() || density < 0.0 || !self.tail_rate(k).is_finite() {
+                return last_good;
+            }
+            last_good = k;
+        }
+        edge
+    }
+
+    /// Native-convention SABR vol with no wing treatment.
+    pub fn sabr_vol(&self, strike: f64) -> f64 {
+        match self.kind {
+            RatesSabrKind::Normal => normal_sabr_vol(&self.params, self.forward, strike, self.t),
+            RatesSabrKind::ShiftedLognormal { shift } => {
+                hagan_implied_vol(&self.params, self.forward + shift, strike + shift, self.t)
+            }
+        }
+    }
+
+    fn raw_price(&self, strike: f64, is_call: bool) -> f64 {
+        let vol = self.sabr_vol(strike);
+        option_price(
+            vol,
+            self.kind.quote_type(),
+            self.forward,
+            strike,
+            self.t,
+            is_call,
+        )
+        .unwrap_or(f64::NAN)
+    }
+
+    /// Exponential decay rate of the OTM price at `strike` (call to the
+    /// right of the forward, put to the left). Positive when finite.
+    fn tail_rate(&self, strike: f64) -> f64 {
+        let is_call = strike >= self.forward;
+        let h = 1.0e-6;
+        let p = self.raw_price(strike, is_call);
+        let slope =
+            (self.raw_price(strike + h, is_call) - self.raw_price(strike - h, is_call)) / (2.0 * h);
+        let rate = if is_call { -slope / p } else { slope / p };
+        if p > 0.0 && rate > 0.0 {
+            rate
+        } else {
+            f64::NAN
+        }
+    }
+
+    /// Undiscounted option price, SABR inside the cutoffs and
+    /// exponential tails outside.
+    pub fn price(&self, strike: f64, is_call: bool) -> f64 {
+        let parity = self.forward - strike;
+        if strike > self.right_cutoff {
+            let k = self.right_cutoff;
+            let call = self.raw_price(k, true) * (-self.tail_rate(k) * (strike - k)).exp();
+            if is_call { call } else { call - parity }
+        } else if strike < self.left_cutoff {
+            let k = self.left_cutoff;
+            let put = self.raw_price(k, false) * (self.tail_rate(k) * (strike - k)).exp();
+            if is_call { put + parity } else { put }
+        } else {
+            self.raw_price(strike, is_call)
+        }
+    }
+
+    /// Normal vol at `strike`, wings included. `None` only if the price
+    /// falls outside the Bachelier range.
+    pub fn normal_vol(&self, strike: f64) -> Option<f64> {
+        self.vol(strike, VolQuoteType::Normal)
+    }
+
+    /// Vol at `strike` in any quoting convention.
+    pub fn vol(&self, strike: f64, quote: VolQuoteType) -> Option<f64> {
+        if strike >= self.left_cutoff && strike <= self.right_cutoff {
+            return convert_vol(
+                self.sabr_vol(strike),
+                self.kind.quote_type(),
+                quote,
+                self.forward,
+                strike,
+                self.t,
+            );
+        }
+        let is_call = strike >= self.forward;
+        implied_vol(
+            self.price(strike, is_call),
+            quote,
+            self.forward,
+            strike,
+            self.t,
+            is_call,
+        )
+    }
+}
+
+/// Fit a [`SabrSmileSection`] to `(strike, normal vol)` nodes. `beta`
+/// is held fixed (ignored for [`RatesSabrKind::Normal`]). Returns the
+/// section and the RMSE in normal-vol units.
+pub fn fit_smile_section(
+    kind: RatesSabrKind,
+    beta: f64,
+    forward: f64,
+    t: f64,
+    nodes: &[(f64, f64)],
+    options: NelderMeadOptions,
+) -> Result<(SabrSmileSection, f64)> {
+    if nodes.is_empty() || t <= 0.0 {
+        return Err(Error::InvalidData(
+            "SABR smile fit needs at least one node and a positive expiry".to_string(),
+        ));
+    }
+    let quote = kind.quote_type();
+    let beta = match kind {
+        RatesSabrKind::Normal => 0.0,
+        RatesSabrKind::ShiftedLognormal { .. } => beta,
+    };
+    let targets: Vec<(f64, f64)> = nodes
+        .iter()
+        .map(|&(k, sigma)| {
+            convert_vol(sigma, VolQuoteType::Normal, quote, forward, k, t)
+                .map(|v| (k, v))
+                .ok_or_else(|| {
+                    Error::InvalidData(format!(
+                        "strike {k} cannot be quoted as {quote:?} at forward {forward}"
+                    ))
+                })
+        })
+        .collect::<Result<_>>()?;
+
+    // Seed α from the node closest to the forward.
+    let atm = targets
+        .iter()
+        .min_by(|a, b| {
+            (a.0 - forward)
+                .abs()
+                .partial_cmp(&(b.0 - forward).abs())
+                .unwrap()
+        })
+        .unwrap()
+        .1;
+    let alpha0 = match kind {
+        RatesSabrKind::Normal => atm,
+        RatesSabrKind::ShiftedLognormal { shift } => atm * (forward + shift).powf(1.0 - beta),
+    };
+    let x0 = vec![inv_softplus(alpha0), 0.0, inv_softplus(0.3)];
+
+    let params_of = |x: &[f64]| SabrParams {
+        alpha: softplus(x[0]),
+        beta,
+        rho: x[1].tanh().clamp(-0.9999, 0.9999),
+        nu: softplus(x[2]),
+    };
+    let objective = |x: &[f64]| -> f64 {
+        let p = params_of(x);
+        if p.alpha <= 0.0 || !p.alpha.is_finite() || !p.nu.is_finite() {
+            return 1.0e6;
+        }
+        let section = SabrSmileSection {
+            kind,
+            params: p,
+            forward,
+            t,
+            left_cutoff: f64::NEG_INFINITY,
+            right_cutoff: f64::INFINITY,
+        };
+        let mut ssr = 0.0;
+        for &(k, v) in &targets {
+            let model = section.sabr_vol(k);
+            if !model.is_finite() {
+                return 1.0e6;
+            }
+            ssr += (model - v).powi(2);
+        }
+        ssr
+    };
+    let minimum = nelder_mead(objective, &x0, options);
+    let params = params_of(&minimum.x);
+
+    let k_min = nodes.iter().map(|n| n.0).fold(f64::INFINITY, f64::min);
+    let k_max = nodes.iter().map(|n| n.0).fold(f64::NEG_INFINITY, f64::max);
+    let section = SabrSmileSection::new(kind, params, forward, t, k_min, k_max);
+
+    let mut ssr = 0.0;
+    for &(k, sigma) in nodes {
+        let model = section.normal_vol(k).ok_or_else(|| {
+            Error::InvalidData(format!("fitted SABR smile has no normal vol at strike {k}"))
+        })?;
+        ssr += (model - sigma).powi(2);
+    }
+    Ok((section, (ssr / nodes.len() as f64).sqrt()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::common::bachelier::bachelier_implied_vol;
+
+    fn opts() -> NelderMeadOptions {
+        NelderMeadOptions {
+            max_iter: 2_000,
+            ftol: 1.0e-18,
+            xtol: 1.0e-12,
+            step_frac: 0.2,
+        }
+    }
+
+    /// At-the-money the normal expansion reduces to
+    /// `α·[1 + (2 − 3ρ²)ν²T/24]`, and it is smooth through `K = F`.
+    #[test]
+    fn normal_sabr_atm_limit() {
+        let p = SabrParams::new(0.008, 0.0, -0.2, 0.4);
+        let atm = normal_sabr_vol(&p, 0.01, 0.01, 2.0);
+        let expected = 0.008 * (1.0 + (2.0 - 3.0 * 0.04) * 0.16 * 2.0 / 24.0);
+        assert!((atm - expected).abs() < 1e-15);
+        let near = normal_sabr_vol(&p, 0.01, 0.01 + 1e-7, 2.0);
+        assert!((near - atm).abs() < 1e-8);
+    }
+
+    /// Round trip for both kinds: nodes generated by a known SABR are
+    /// recovered to sub-0.01bp, including at a negative forward for the
+    /// normal kind.
+    #[test]
+    fn fit_recovers_generating_parameters() {
+        let strikes = [-0.01, -0.005, 0.0, 0.005, 0.01, 0.02];
+        let cases = [
+            (
+                RatesSabrKind::Normal,
+                SabrParams::new(0.007, 0.0, -0.3, 0.45),
+                -0.002,
+            ),
+            (
+                RatesSabrKind::ShiftedLognormal { shift: 0.03 },
+                SabrParams::new(0.05, 0.5, 0.2, 0.35),
+                0.004,
+            ),
+        ];
+        for (kind, truth, forward) in cases {
+            let t = 3.0;
+            let generator = SabrSmileSection {
+                kind,
+                params: truth,
+                forward,
+                t,
+                left_cutoff: f64::NEG_INFINITY,
+                right_cutoff: f64::INFINITY,
+            };
+            let nodes: Vec<(f64, f64)> = strikes
+                .iter()
+                .map(|&k| (k, generator.normal_vol(k).unwrap()))
+                .collect();
+            let (section, rmse) =
+                fit_smile_section(kind, truth.beta, forward, t, &nodes, opts()).unwrap();
+            assert!(rmse < 1.0e-6, "{kind:?}: rmse {rmse}");
+            assert!((section.params.rho - truth.rho).abs() < 1e-2, "{section:?}");
+            assert!((section.params.nu - truth.nu).abs() < 1e-2, "{section:?}");
+        }
+    }
+
+    /// Extreme vol-of-vol at a long expiry makes Hagan's formula produce
+    /// negative density at low strikes. The section pulls its left cutoff
+    /// in, and the extrapolated smile has positive butterflies everywhere.
+    #[test]
+    fn wings_restore_positive_density() {
+        let kind = RatesSabrKind::ShiftedLognormal { shift: 0.01 };
+        let params = SabrParams::new(0.02, 0.3, -0.6, 0.9);
+        let (forward, t) = (0.01, 20.0);
+        let naive = SabrSmileSection {
+            kind,
+            params,
+            forward,
+            t,
+            left_cutoff: f64::NEG_INFINITY,
+            right_cutoff: f64::INFINITY,
+        };
+        let butterfly = |s: &SabrSmileSection, k: f64| {
+            let h = 2.5e-4;
+            s.price(k + h, true) - 2.0 * s.price(k, true) + s.price(k - h, true)
+        };
+        let grid: Vec<f64> = (0..140).map(|i| -0.0095 + 0.0005 * i as f64).collect();
+        assert!(grid.iter().any(|&k| butterfly(&naive, k) < 0.0));
+
+        let section = SabrSmileSection::new(kind, params, forward, t, -0.0095, 0.06);
+        assert!(section.left_cutoff > -0.0095);
+        for &k in &grid {
+            assert!(butterfly(&section, k) >= -1e-12, "k={k}");
+        }
+        // Wing prices stay consistent with the normal vols reported.
+        let k = -0.009;
+        let v = section.normal_vol(k).unwrap();
+        let p = section.price(k, false);
+        assert!((bachelier_implied_vol(p, forward, k, t, false).unwrap() - v).abs() < 1e-10);
+    }
+}
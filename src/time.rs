@@ -4,3 +4,4 @@ pub mod daycounters;
 pub mod frequency;
 pub mod imm;
 pub mod period;
+pub mod volclock;
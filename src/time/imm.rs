@@ -1,4 +1,4 @@
-use chrono::{Datelike, NaiveDate, Weekday};
+use chrono::{Datelike, Months, NaiveDate, Weekday};
 use std::str::FromStr;
 use strum::IntoEnumIterator;
 use strum_macros::{Display, EnumIter, EnumString};
@@ -146,6 +146,44 @@ impl IMM {
         result
     }
 
+    /// Next CDS roll date (20 March, June, September or December)
+    /// strictly after `date`.
+    pub fn next_cds_date(&self, date: NaiveDate) -> NaiveDate {
+        let mut year = date.year();
+        let mut month = date.month().div_ceil(3) * 3;
+        if date.month() == month && date.day() >= 20 {
+            month += 3;
+        }
+        if month > 12 {
+            month -= 12;
+            year += 1;
+        }
+        NaiveDate::from_ymd_opt(year, month, 20).unwrap()
+    }
+
+    /// Latest CDS roll date on or before `date`.
+    pub fn previous_cds_date(&self, date: NaiveDate) -> NaiveDate {
+        self.next_cds_date(date) - Months::new(3)
+    }
+
+    /// Standard CDS maturity `tenor_months` after `trade_date` under the
+    /// semi-annual roll (ISDA, December 2015): contracts traded from 20
+    /// March (20 September) mature on the 20 June (20 December) following
+    /// the tenor.
+    pub fn cds_maturity(&self, trade_date: NaiveDate, tenor_months: u32) -> NaiveDate {
+        let year = trade_date.year();
+        let march = NaiveDate::from_ymd_opt(year, 3, 20).unwrap();
+        let september = NaiveDate::from_ymd_opt(year, 9, 20).unwrap();
+        let roll = if trade_date < march {
+            NaiveDate::from_ymd_opt(year - 1, 9, 20).unwrap()
+        } else if trade_date < september {
+            march
+        } else {
+            september
+        };
+        roll + Months::new(3 + tenor_months)
+    }
+
     fn nth_weekday(&self, nth: i32, day_of_week: Weekday, m: u32, y: i32) -> Option<NaiveDate> {
         if !(0..=6).contains(&nth) {
             None
@@ -248,4 +286,17 @@ mod tests {
             NaiveDate::from_ymd_opt(2025, 9, 17)
         );
     }
+
+    #[test]
+    fn test_cds_dates() {
+        let d = |y, m, dd| NaiveDate::from_ymd_opt(y, m, dd).unwrap();
+        assert_eq!(IMM.next_cds_date(d(2026, 3, 19)), d(2026, 3, 20));
+        assert_eq!(IMM.next_cds_date(d(2026, 3, 20)), d(2026, 6, 20));
+        assert_eq!(IMM.next_cds_date(d(2026, 12, 25)), d(2027, 3, 20));
+        assert_eq!(IMM.previous_cds_date(d(2026, 3, 20)), d(2026, 3, 20));
+        assert_eq!(IMM.previous_cds_date(d(2026, 2, 1)), d(2025, 12, 20));
+        assert_eq!(IMM.cds_maturity(d(2026, 3, 19), 60), d(2030, 12, 20));
+        assert_eq!(IMM.cds_maturity(d(2026, 3, 20), 60), d(2031, 6, 20));
+        assert_eq!(IMM.cds_maturity(d(2026, 10, 1), 12), d(2027, 12, 20));
+    }
 }
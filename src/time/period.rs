@@ -41,6 +41,24 @@ pub enum Period {
 }
 
 impl Period {
+    /// Whole months in a month or year period; day-based and FX swap
+    /// tenors are an error.
+    pub fn months(&self) -> Result<u32> {
+        match self {
+            Period::Months(m) => Ok(*m),
+            Period::Years(y) => Ok(12 * y),
+            _ => Err(Error::InvalidData(format!(
+                "period {:?} is not in months or years",
+                self
+            ))),
+        }
+    }
+
+    /// [`Period::months`] in years.
+    pub fn years(&self) -> Result<f64> {
+        Ok(self.months()? as f64 / 12.0)
+    }
+
     /// Returns the **far-leg** settlement date for this tenor using the standard T+2
     /// spot convention.
     ///
@@ -302,6 +320,15 @@ mod tests {
         assert_eq!(2 * Period::Days(1), Period::Days(2));
     }
 
+    #[test]
+    fn test_months_and_years() -> Result<()> {
+        assert_eq!(Period::Years(2).months()?, 24);
+        assert_eq!(Period::Months(3).years()?, 0.25);
+        assert!(Period::Weeks(1).months().is_err());
+        assert!(Period::SPOT.years().is_err());
+        Ok(())
+    }
+
     /// Verify TN (Tom-Next) settlement dates for GBPUSD (joint US+UK calendar, T+2 spot).
     ///
     /// For a standard T+2 pair on 2023-10-16 (Monday):
@@ -0,0 +1,128 @@
+//! Vol-time clock: business-time weighting of calendar days for variance
+//! accrual.
+//!
+//! Each calendar day carries a weight — `weekend_weight` on weekends,
+//! `holiday_weight` on the calendar's other holidays, one otherwise — plus
+//! the weights of any events (FOMC, NFP, ECB, ...) falling on it. Vol time
+//! between two dates sums the weights of the days after the first, up to
+//! and including the second:
+//!
+//! ```text
+//!     τ(d₁, d₂) = Σ_{d₁ < d ≤ d₂} w(d) / 365
+//!     w(d)      = base(d) + Σ_{events e on d} weight(e)
+//! ```
+//!
+//! With every weight at one and no events the clock is Act/365.
+
+use crate::error::{Error, Result};
+use crate::time::calendars::Calendar;
+use chrono::{Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+/// A dated variance event. `weight` is in days of ordinary variance and is
+/// added to the weight of the day it falls on.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub struct VolEvent {
+    pub name: String,
+    pub date: NaiveDate,
+    pub weight: f64,
+}
+
+/// Per-day variance weights driven by a holiday calendar and an event list.
+#[derive(Deserialize, Serialize, Debug)]
+pub struct VolClock {
+    calendar: Box<dyn Calendar>,
+    weekend_weight: f64,
+    holiday_weight: f64,
+    events: Vec<VolEvent>,
+}
+
+impl VolClock {
+    /// Clock weighting the weekends and holidays of `calendar`, e.g.
+    /// `Box::new(pair.calendar())` for an FX pair's joint calendar.
+    pub fn new(
+        calendar: Box<dyn Calendar>,
+        weekend_weight: f64,
+        holiday_weight: f64,
+        events: Vec<VolEvent>,
+    ) -> Result<Self> {
+        if weekend_weight < 0.0 || holiday_weight < 0.0 {
+            return Err(Error::InvalidData(format!(
+                "vol-clock weights must be non-negative, got weekend {weekend_weight} and holiday {holiday_weight}"
+            )));
+        }
+        if let Some(event) = events.iter().find(|e| e.weight < 0.0) {
+            return Err(Error::InvalidData(format!(
+                "event {} on {} has negative weight {}",
+                event.name, event.date, event.weight
+            )));
+        }
+        Ok(Self {
+            calendar,
+            weekend_weight,
+            holiday_weight,
+            events,
+        })
+    }
+
+    /// Weight of a single day, events included.
+    pub fn day_weight(&self, date: NaiveDate) -> f64 {
+        let base = if self.calendar.is_weekend(date) {
+            self.weekend_weight
+        } else if self.calendar.is_holiday(date) {
+            self.holiday_weight
+        } else {
+            1.0
+        };
+        base + self
+            .events
+            .iter()
+            .filter(|e| e.date == date)
+            .map(|e| e.weight)
+            .sum::<f64>()
+    }
+
+    /// Vol time from `start` to `end` in weighted years; negative when
+    /// `end` is before `start`.
+    pub fn vol_time(&self, start: NaiveDate, end: NaiveDate) -> f64 {
+        if end < start {
+            return -self.vol_time(end, start);
+        }
+        (1..=(end - start).num_days())
+            .map(|n| self.day_weight(start + Duration::days(n)))
+            .sum::<f64>()
+            / 365.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{VolClock, VolEvent};
+    use crate::error::Result;
+    use crate::time::calendars::{Calendar, UnitedStates, WeekendsOnly};
+    use chrono::NaiveDate;
+
+    #[test]
+    fn weights_weekends_holidays_and_events() -> Result<()> {
+        // Friday 2026-07-03 is the observed US Independence Day.
+        let (thu, mon) = (
+            NaiveDate::from_ymd_opt(2026, 7, 2).unwrap(),
+            NaiveDate::from_ymd_opt(2026, 7, 6).unwrap(),
+        );
+        let flat = VolClock::new(Box::new(WeekendsOnly), 1.0, 1.0, vec![])?;
+        assert!((flat.vol_time(thu, mon) - 4.0 / 365.0).abs() < 1e-15);
+
+        let us = UnitedStates::default();
+        assert!(us.is_holiday(NaiveDate::from_ymd_opt(2026, 7, 3).unwrap()));
+        let nfp = VolEvent {
+            name: "NFP".to_string(),
+            date: mon,
+            weight: 2.0,
+        };
+        let clock = VolClock::new(Box::new(us), 0.1, 0.3, vec![nfp])?;
+        // Fri holiday 0.3, Sat and Sun 0.1 each, Mon 1 + 2.
+        assert!((clock.vol_time(thu, mon) - 3.5 / 365.0).abs() < 1e-15);
+        assert_eq!(clock.vol_time(mon, thu), -clock.vol_time(thu, mon));
+        Ok(())
+    }
+}